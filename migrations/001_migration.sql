@@ -104,7 +104,8 @@ CREATE TABLE IF NOT EXISTS search_request (
   payment_type payment_type,
   domain_category_code TEXT NOT NULL,
   search_type product_search_type NOT NULL,
-  fulfillment_type fulfillment_type
+  fulfillment_type fulfillment_type,
+  include_seller_scorecard BOOLEAN DEFAULT false NOT NULL
 );
 
 CREATE TYPE ondc_network_participant_type AS ENUM (
@@ -145,6 +146,7 @@ CREATE TABLE IF NOT EXISTS ondc_buyer_order_req (
     request_payload JSONB NOT NULL,
     created_on TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
 );
+CREATE INDEX IF NOT EXISTS ondc_buyer_order_req_transaction_idx ON ondc_buyer_order_req (transaction_id, created_on);
 
 
 CREATE TYPE commerce_data_type AS ENUM (
@@ -436,6 +438,12 @@ CREATE TYPE domain_category AS ENUM (
 );
 
 
+CREATE TYPE approval_status_type AS ENUM (
+  'pending',
+  'approved',
+  'rejected'
+);
+
 CREATE TABLE IF NOT EXISTS commerce_data(
   id uuid PRIMARY KEY,
   urn TEXT NOT NULL,
@@ -470,10 +478,20 @@ CREATE TABLE IF NOT EXISTS commerce_data(
   country_code country_code NOT NULL,
   billing JSONB,
   bpp_terms JSONB,
-  cancellation_terms JSONB
+  cancellation_terms JSONB,
+  last_status_polled_on timestamptz,
+  status_poll_count INT NOT NULL DEFAULT 0,
+  last_callback_on TIMESTAMPTZ,
+  version INT NOT NULL DEFAULT 1,
+  quote_issues JSONB,
+  buyer_terms JSONB,
+  approval_status approval_status_type,
+  approval_levels INT[],
+  approval_requested_on TIMESTAMPTZ
 );
 
 ALTER TABLE commerce_data ADD CONSTRAINT commerce_data_uq UNIQUE (external_urn);
+CREATE INDEX IF NOT EXISTS commerce_data_status_poll_idx ON commerce_data (record_status, last_status_polled_on);
 
 CREATE TABLE IF NOT EXISTS commerce_data_line(
   id uuid PRIMARY KEY,
@@ -484,6 +502,7 @@ CREATE TABLE IF NOT EXISTS commerce_data_line(
   item_code TEXT,
   qty DECIMAL(20, 2) NOT NULL,
   tax_rate DECIMAL(5, 2) NOT NULL DEFAULT 0.0,
+  cess_rate DECIMAL(5, 2),
   location_ids JSONB,
   fulfillment_ids JSONB,
   mrp DECIMAL(20, 3) NOT NULL DEFAULT 0.0,
@@ -491,12 +510,16 @@ CREATE TABLE IF NOT EXISTS commerce_data_line(
   refunded_tax_value DECIMAL(20, 3),
   refunded_discount_amount DECIMAL(20, 2),
   refunded_gross_total DECIMAL(20, 2),
+  cancelled_qty DECIMAL(20, 2),
+  invoiced_qty DECIMAL(20, 2),
   unit_price DECIMAL(20, 3) NOT NULL DEFAULT 0.0,
   gross_total DECIMAL(20, 3) NOT NULL DEFAULT 0.0,
   available_qty DECIMAL(20, 2),
   discount_amount DECIMAL(20, 2) NOT NULL DEFAULT 0.0,
   item_req TEXT,
-  packaging_req TEXT
+  packaging_req TEXT,
+  seller_item_req TEXT,
+  seller_packaging_req TEXT
 );
 
 ALTER TABLE commerce_data_line ADD CONSTRAINT commerce_data_fk FOREIGN KEY ("commerce_data_id") REFERENCES commerce_data ("id") ON DELETE CASCADE;
@@ -552,6 +575,7 @@ CREATE TABLE IF NOT EXISTS commerce_fulfillment_data(
   fulfillment_status commerce_fulfillment_status_type DEFAULT 'pending'::commerce_fulfillment_status_type NOT NULL,
   inco_terms inco_term_type,
   place_of_delivery TEXT,
+  seller_inco_terms inco_term_type,
   vectors JSONB,
   remark TEXT,
   provider_name TEXT,
@@ -562,8 +586,8 @@ CREATE TABLE IF NOT EXISTS commerce_fulfillment_data(
   servicable_status fulfillment_servicability_status,
   pickup_data JSONB NOT NULL,
   drop_off_data JSONB,
-  created_on TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
-
+  created_on TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  last_callback_on TIMESTAMPTZ
 );
 
 ALTER TABLE commerce_fulfillment_data ADD CONSTRAINT commerce_fulfillment_fk FOREIGN KEY ("commerce_data_id") REFERENCES commerce_data ("id") ON DELETE CASCADE;
@@ -617,15 +641,397 @@ ALTER TABLE commerce_payment_data ADD CONSTRAINT commerce_payment_fk FOREIGN KEY
 
 
 
-CREATE TABLE IF NOT EXISTS  buyer_order_status_history(
+CREATE TYPE return_status_type AS ENUM (
+  'initiated',
+  'approved',
+  'picked',
+  'delivered',
+  'rejected'
+);
+
+CREATE TABLE IF NOT EXISTS commerce_return_data(
   id uuid PRIMARY KEY,
-  order_id TEXT NOT NULL,
-  seller_id TEXT NOT NULL,
+  commerce_data_id uuid NOT NULL,
+  return_id TEXT NOT NULL,
+  item_id TEXT NOT NULL,
+  qty DECIMAL(20, 2) NOT NULL,
+  reason_id TEXT NOT NULL,
+  reason_desc TEXT,
+  images JSONB NOT NULL,
   fulfillment_id TEXT,
+  return_status return_status_type NOT NULL,
+  refund_amount DECIMAL(20, 3),
+  created_on TIMESTAMPTZ NOT NULL,
+  created_by uuid NOT NULL,
+  updated_on TIMESTAMPTZ
+);
+ALTER TABLE commerce_return_data ADD CONSTRAINT commerce_return_fk FOREIGN KEY ("commerce_data_id") REFERENCES commerce_data ("id") ON DELETE CASCADE;
+ALTER TABLE commerce_return_data ADD CONSTRAINT commerce_return_data_uq UNIQUE (commerce_data_id, return_id);
+
+CREATE TABLE IF NOT EXISTS commerce_grn(
+  id uuid PRIMARY KEY,
+  commerce_data_id uuid NOT NULL,
+  grn_no TEXT NOT NULL,
+  fulfillment_id TEXT NOT NULL,
+  received_on TIMESTAMPTZ NOT NULL,
+  remark TEXT,
+  created_on TIMESTAMPTZ NOT NULL,
+  created_by uuid NOT NULL
+);
+
+ALTER TABLE commerce_grn ADD CONSTRAINT commerce_grn_fk FOREIGN KEY ("commerce_data_id") REFERENCES commerce_data ("id") ON DELETE CASCADE;
+ALTER TABLE commerce_grn ADD CONSTRAINT commerce_grn_uq UNIQUE (commerce_data_id, grn_no);
+
+CREATE TABLE IF NOT EXISTS commerce_grn_line(
+  id uuid PRIMARY KEY,
+  grn_id uuid NOT NULL,
+  commerce_data_line_id uuid NOT NULL,
+  received_qty DECIMAL(20, 2) NOT NULL,
+  accepted_qty DECIMAL(20, 2) NOT NULL,
+  rejected_qty DECIMAL(20, 2) NOT NULL,
+  remark TEXT,
+  images JSONB NOT NULL
+);
+
+ALTER TABLE commerce_grn_line ADD CONSTRAINT commerce_grn_line_fk FOREIGN KEY ("grn_id") REFERENCES commerce_grn ("id") ON DELETE CASCADE;
+ALTER TABLE commerce_grn_line ADD CONSTRAINT commerce_grn_data_line_fk FOREIGN KEY ("commerce_data_line_id") REFERENCES commerce_data_line ("id") ON DELETE CASCADE;
+ALTER TABLE commerce_grn_line ADD CONSTRAINT commerce_grn_line_uq UNIQUE (grn_id, commerce_data_line_id);
+
+CREATE TYPE status_entity_type AS ENUM (
+  'order',
+  'fulfillment',
+  'payment',
+  'quote',
+  'return'
+);
+
+CREATE TYPE status_actor_type AS ENUM (
+  'buyer',
+  'seller',
+  'system'
+);
+
+CREATE TABLE IF NOT EXISTS  buyer_order_status_history(
+  id uuid PRIMARY KEY,
+  transaction_id uuid NOT NULL,
+  entity_type status_entity_type NOT NULL,
+  entity_id TEXT,
+  previous_status TEXT,
   status TEXT NOT NULL,
-  created_on TEXT NOT NULL
+  actor_type status_actor_type NOT NULL,
+  actor_id TEXT NOT NULL,
+  action_type TEXT NOT NULL,
+  message_id uuid,
+  is_valid BOOLEAN NOT NULL DEFAULT true,
+  detail JSONB,
+  created_on TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS buyer_order_status_history_idx ON buyer_order_status_history (transaction_id, created_on);
+
+CREATE TABLE IF NOT EXISTS order_approval_policy(
+  id uuid PRIMARY KEY,
+  business_id uuid NOT NULL,
+  name TEXT NOT NULL,
+  approval_level INT NOT NULL,
+  min_amount DECIMAL(20, 3) NOT NULL,
+  domain_category_code domain_category,
+  seller_id TEXT,
+  approver_ids uuid[] NOT NULL DEFAULT '{}',
+  is_active BOOLEAN NOT NULL DEFAULT true,
+  created_on TIMESTAMPTZ NOT NULL,
+  created_by uuid NOT NULL,
+  updated_on TIMESTAMPTZ,
+  updated_by uuid
+);
+CREATE INDEX IF NOT EXISTS order_approval_policy_business_idx ON order_approval_policy (business_id, is_active);
+
+CREATE TABLE IF NOT EXISTS buyer_order_approval(
+  id uuid PRIMARY KEY,
+  transaction_id uuid NOT NULL,
+  approval_level INT NOT NULL,
+  approval_status approval_status_type NOT NULL,
+  approval_requested_on TIMESTAMPTZ NOT NULL,
+  remark TEXT,
+  created_on TIMESTAMPTZ NOT NULL,
+  created_by uuid NOT NULL
+);
+ALTER TABLE buyer_order_approval ADD CONSTRAINT buyer_order_approval_uq UNIQUE (transaction_id, approval_requested_on, approval_level);
+
+CREATE TYPE budget_exceed_action AS ENUM (
+  'reject',
+  'approval'
+);
+
+CREATE TABLE IF NOT EXISTS buyer_budget(
+  id uuid PRIMARY KEY,
+  business_id uuid NOT NULL,
+  name TEXT NOT NULL,
+  user_id uuid,
+  domain_category_code domain_category,
+  amount DECIMAL(20, 3) NOT NULL,
+  period_start TIMESTAMPTZ NOT NULL,
+  period_end TIMESTAMPTZ NOT NULL,
+  exceed_action budget_exceed_action NOT NULL,
+  approval_level INT NOT NULL DEFAULT 1,
+  is_active BOOLEAN NOT NULL DEFAULT true,
+  created_on TIMESTAMPTZ NOT NULL,
+  created_by uuid NOT NULL,
+  updated_on TIMESTAMPTZ,
+  updated_by uuid
+);
+CREATE INDEX IF NOT EXISTS buyer_budget_business_idx ON buyer_budget (business_id, is_active);
+CREATE INDEX IF NOT EXISTS commerce_data_buyer_idx ON commerce_data (buyer_id, created_on);
+
+CREATE TYPE order_export_format AS ENUM (
+  'csv',
+  'xlsx'
 );
 
+CREATE TYPE order_export_status AS ENUM (
+  'processing',
+  'completed',
+  'failed',
+  'expired'
+);
+
+CREATE TABLE IF NOT EXISTS order_export_job(
+  id uuid PRIMARY KEY,
+  business_id uuid NOT NULL,
+  format order_export_format NOT NULL,
+  status order_export_status NOT NULL,
+  order_count BIGINT NOT NULL,
+  file_name TEXT NOT NULL,
+  content BYTEA,
+  error TEXT,
+  created_on TIMESTAMPTZ NOT NULL,
+  created_by uuid NOT NULL,
+  completed_on TIMESTAMPTZ
+);
+CREATE INDEX IF NOT EXISTS order_export_job_business_idx ON order_export_job (business_id, created_by);
+
+CREATE TYPE standing_order_frequency AS ENUM (
+  'daily',
+  'weekly',
+  'monthly'
+);
+
+CREATE TYPE standing_order_status AS ENUM (
+  'active',
+  'paused'
+);
+
+CREATE TYPE standing_order_event_type AS ENUM (
+  'created',
+  'updated',
+  'paused',
+  'resumed'
+);
+
+CREATE TYPE standing_order_run_status AS ENUM (
+  'select_sent',
+  'init_sent',
+  'confirm_sent',
+  'confirmed',
+  'awaiting_buyer',
+  'failed'
+);
+
+CREATE TABLE IF NOT EXISTS standing_order(
+  id uuid PRIMARY KEY,
+  business_id uuid NOT NULL,
+  name TEXT NOT NULL,
+  bpp_id TEXT NOT NULL,
+  provider_id TEXT NOT NULL,
+  domain_category_code domain_category NOT NULL,
+  payment_types payment_type[] NOT NULL,
+  order_type commerce_data_type NOT NULL,
+  ttl TEXT NOT NULL,
+  items JSONB NOT NULL,
+  fulfillments JSONB NOT NULL,
+  frequency standing_order_frequency NOT NULL,
+  interval_count INT NOT NULL,
+  next_run_on TIMESTAMPTZ NOT NULL,
+  end_on TIMESTAMPTZ,
+  auto_confirm JSONB,
+  status standing_order_status NOT NULL,
+  created_on TIMESTAMPTZ NOT NULL,
+  created_by uuid NOT NULL,
+  updated_on TIMESTAMPTZ,
+  updated_by uuid
+);
+CREATE INDEX IF NOT EXISTS standing_order_business_idx ON standing_order (business_id, created_by);
+CREATE INDEX IF NOT EXISTS standing_order_due_idx ON standing_order (status, next_run_on);
+
+CREATE TABLE IF NOT EXISTS standing_order_event(
+  id uuid PRIMARY KEY,
+  standing_order_id uuid NOT NULL REFERENCES standing_order(id) ON DELETE CASCADE,
+  event_type standing_order_event_type NOT NULL,
+  detail JSONB NOT NULL,
+  created_on TIMESTAMPTZ NOT NULL,
+  created_by uuid NOT NULL
+);
+CREATE INDEX IF NOT EXISTS standing_order_event_idx ON standing_order_event (standing_order_id, created_on);
+
+CREATE TABLE IF NOT EXISTS standing_order_run(
+  id uuid PRIMARY KEY,
+  standing_order_id uuid NOT NULL REFERENCES standing_order(id) ON DELETE CASCADE,
+  transaction_id uuid NOT NULL,
+  scheduled_on TIMESTAMPTZ NOT NULL,
+  status standing_order_run_status NOT NULL,
+  quote_total DECIMAL(20, 3),
+  message TEXT,
+  created_on TIMESTAMPTZ NOT NULL,
+  updated_on TIMESTAMPTZ
+);
+ALTER TABLE standing_order_run ADD CONSTRAINT standing_order_run_uq UNIQUE (transaction_id);
+CREATE INDEX IF NOT EXISTS standing_order_run_idx ON standing_order_run (standing_order_id, scheduled_on);
+
+CREATE TABLE IF NOT EXISTS buyer_delivery_location(
+  id uuid PRIMARY KEY,
+  business_id uuid NOT NULL,
+  name TEXT NOT NULL,
+  location JSONB NOT NULL,
+  contact_name TEXT,
+  contact_email TEXT,
+  opening_hours JSONB NOT NULL,
+  is_default BOOLEAN NOT NULL DEFAULT false,
+  created_on TIMESTAMPTZ NOT NULL,
+  created_by uuid NOT NULL,
+  updated_on TIMESTAMPTZ,
+  updated_by uuid
+);
+CREATE INDEX IF NOT EXISTS buyer_delivery_location_idx ON buyer_delivery_location (business_id);
+CREATE UNIQUE INDEX IF NOT EXISTS buyer_delivery_location_default_uq ON buyer_delivery_location (business_id) WHERE is_default;
+
+CREATE TABLE IF NOT EXISTS buyer_billing_profile(
+  id uuid PRIMARY KEY,
+  business_id uuid NOT NULL,
+  name TEXT NOT NULL,
+  billing JSONB NOT NULL,
+  country_code country_code NOT NULL,
+  is_default BOOLEAN NOT NULL DEFAULT false,
+  created_on TIMESTAMPTZ NOT NULL,
+  created_by uuid NOT NULL,
+  updated_on TIMESTAMPTZ,
+  updated_by uuid
+);
+CREATE INDEX IF NOT EXISTS buyer_billing_profile_idx ON buyer_billing_profile (business_id);
+CREATE UNIQUE INDEX IF NOT EXISTS buyer_billing_profile_default_uq ON buyer_billing_profile (business_id) WHERE is_default;
+
+CREATE TABLE IF NOT EXISTS buyer_terms_template(
+  id uuid PRIMARY KEY,
+  business_id uuid NOT NULL,
+  name TEXT NOT NULL,
+  domain_category_code domain_category,
+  terms JSONB NOT NULL,
+  is_default BOOLEAN NOT NULL DEFAULT false,
+  created_on TIMESTAMPTZ NOT NULL,
+  created_by uuid NOT NULL,
+  updated_on TIMESTAMPTZ,
+  updated_by uuid
+);
+CREATE INDEX IF NOT EXISTS buyer_terms_template_idx ON buyer_terms_template (business_id);
+CREATE UNIQUE INDEX IF NOT EXISTS buyer_terms_template_default_uq ON buyer_terms_template (business_id, domain_category_code) NULLS NOT DISTINCT WHERE is_default;
+
+CREATE TABLE IF NOT EXISTS cart_item(
+  id uuid PRIMARY KEY,
+  business_id uuid NOT NULL,
+  user_id uuid NOT NULL,
+  domain_category_code domain_category NOT NULL,
+  bpp_id TEXT NOT NULL,
+  provider_id TEXT NOT NULL,
+  item_id TEXT NOT NULL,
+  item_name TEXT NOT NULL,
+  location_id TEXT NOT NULL,
+  qty INT NOT NULL,
+  unit_price DECIMAL(20, 3) NOT NULL,
+  currency_code currency_code_type NOT NULL,
+  buyer_term JSONB,
+  import_detail JSONB,
+  created_on TIMESTAMPTZ NOT NULL,
+  updated_on TIMESTAMPTZ
+);
+ALTER TABLE cart_item ADD CONSTRAINT cart_item_uq UNIQUE (business_id, user_id, bpp_id, provider_id, item_id);
+
+CREATE TABLE IF NOT EXISTS cart_checkout(
+  id uuid PRIMARY KEY,
+  business_id uuid NOT NULL,
+  user_id uuid NOT NULL,
+  created_on TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX IF NOT EXISTS cart_checkout_idx ON cart_checkout (business_id, user_id, created_on);
+
+CREATE TABLE IF NOT EXISTS cart_checkout_order(
+  checkout_id uuid NOT NULL REFERENCES cart_checkout(id) ON DELETE CASCADE,
+  transaction_id uuid NOT NULL,
+  bpp_id TEXT NOT NULL,
+  provider_id TEXT NOT NULL,
+  items JSONB NOT NULL,
+  error TEXT,
+  PRIMARY KEY (checkout_id, transaction_id)
+);
+
+CREATE TABLE IF NOT EXISTS commerce_import_line(
+  transaction_id uuid NOT NULL,
+  item_id TEXT NOT NULL,
+  hs_code TEXT NOT NULL,
+  country_of_origin country_code NOT NULL,
+  duty_rate DECIMAL(5, 2),
+  PRIMARY KEY (transaction_id, item_id)
+);
+
+CREATE TABLE IF NOT EXISTS commerce_import_cost(
+  transaction_id uuid PRIMARY KEY,
+  freight_amount DECIMAL(20, 3) NOT NULL,
+  insurance_amount DECIMAL(20, 3) NOT NULL,
+  created_on TIMESTAMPTZ NOT NULL,
+  created_by uuid NOT NULL
+);
+
+CREATE TYPE customs_document_type AS ENUM (
+  'commercial_invoice',
+  'packing_list',
+  'bill_of_lading',
+  'airway_bill',
+  'bill_of_entry',
+  'certificate_of_origin',
+  'insurance_certificate'
+);
+
+CREATE TABLE IF NOT EXISTS commerce_customs_document(
+  id uuid PRIMARY KEY,
+  transaction_id uuid NOT NULL,
+  document_type customs_document_type NOT NULL,
+  document_no TEXT NOT NULL,
+  url TEXT,
+  issued_on DATE,
+  remark TEXT,
+  created_on TIMESTAMPTZ NOT NULL,
+  created_by uuid NOT NULL,
+  updated_on TIMESTAMPTZ,
+  updated_by uuid
+);
+CREATE INDEX IF NOT EXISTS commerce_customs_document_idx ON commerce_customs_document (transaction_id);
+
+CREATE TYPE commerce_document_type AS ENUM (
+  'invoice',
+  'proforma_invoice'
+);
+
+CREATE TABLE IF NOT EXISTS commerce_document_file(
+  id uuid PRIMARY KEY,
+  transaction_id uuid NOT NULL,
+  document_type commerce_document_type NOT NULL,
+  version INTEGER NOT NULL,
+  source_url TEXT NOT NULL,
+  storage_key TEXT NOT NULL,
+  checksum TEXT NOT NULL,
+  content_type TEXT NOT NULL,
+  size_bytes BIGINT NOT NULL,
+  created_on TIMESTAMPTZ NOT NULL
+);
+ALTER TABLE commerce_document_file ADD CONSTRAINT commerce_document_file_uq UNIQUE (transaction_id, document_type, version);
+
 CREATE TABLE IF NOT EXISTS ondc_seller_info (
     id SERIAL NOT NULL PRIMARY KEY,
     seller_subscriber_id TEXT NOT NULL,
@@ -635,6 +1041,30 @@ CREATE TABLE IF NOT EXISTS ondc_seller_info (
 );
 ALTER TABLE ondc_seller_info ADD CONSTRAINT ondc_seller_info_constraint UNIQUE (seller_subscriber_id, provider_id);
 
+CREATE TABLE IF NOT EXISTS seller_scorecard (
+    bpp_id TEXT NOT NULL,
+    provider_id TEXT NOT NULL,
+    quote_response_count INT NOT NULL DEFAULT 0,
+    quote_response_secs BIGINT NOT NULL DEFAULT 0,
+    quote_rejected_count INT NOT NULL DEFAULT 0,
+    order_accepted_count INT NOT NULL DEFAULT 0,
+    order_rejected_count INT NOT NULL DEFAULT 0,
+    seller_cancelled_count INT NOT NULL DEFAULT 0,
+    delivered_count INT NOT NULL DEFAULT 0,
+    delivery_promised_count INT NOT NULL DEFAULT 0,
+    on_time_delivery_count INT NOT NULL DEFAULT 0,
+    created_on TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_on TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (bpp_id, provider_id)
+);
+
+CREATE TABLE IF NOT EXISTS seller_scorecard_event (
+    transaction_id uuid NOT NULL,
+    event_key TEXT NOT NULL,
+    created_on TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (transaction_id, event_key)
+);
+
 CREATE TABLE IF NOT EXISTS ondc_seller_location_info(
     id SERIAL NOT NULL PRIMARY KEY,
     seller_subscriber_id TEXT NOT NULL,
@@ -666,6 +1096,7 @@ CREATE TABLE IF NOT EXISTS ondc_seller_product_info (
     item_code TEXT NOT NULL,
     item_name TEXT NOT NULL,
     tax_rate DECIMAL(5, 2) NOT NULL,
+    cess_rate DECIMAL(5, 2),
     images JSONB NOT NULL,
     mrp DECIMAL(20, 3) NOT NULL DEFAULT 0.0,
     unit_price_with_tax DECIMAL(20, 3) NOT NULL DEFAULT 0.0,
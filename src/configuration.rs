@@ -1,8 +1,16 @@
 use crate::{
-    chat_client::ChatClient, domain::EmailObject, elastic_search_client::ElasticSearchClient,
-    email_client::SmtpEmailClient, kafka_client::KafkaClient, payment_client::PaymentClient,
-    redis::RedisClient, user_client::UserClient, websocket_client::WebSocketClient,
+    chat_client::ChatClient,
+    document_client::{DocumentClient, DocumentStorageType},
+    domain::EmailObject,
+    elastic_search_client::ElasticSearchClient,
+    email_client::SmtpEmailClient,
+    kafka_client::KafkaClient,
+    payment_client::PaymentClient,
+    redis::RedisClient,
+    user_client::UserClient,
+    websocket_client::WebSocketClient,
 };
+use anyhow::anyhow;
 use config::{self, ConfigError, Environment};
 use secrecy::{ExposeSecret, SecretString};
 use serde::Deserialize;
@@ -63,6 +71,12 @@ pub struct Config {
     pub elastic_search: ElasticSearchConfig,
     pub payment: PaymentConfig,
     pub secret: SecretConfig,
+    #[serde(default)]
+    pub status_poll: StatusPollConfig,
+    #[serde(default)]
+    pub standing_order: StandingOrderConfig,
+    #[serde(default)]
+    pub document_store: DocumentStoreConfig,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -357,3 +371,101 @@ impl ElasticSearchConfig {
         ElasticSearchClient::new(self.url)
     }
 }
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct StatusPollConfig {
+    pub interval_seconds: u64,
+    pub min_order_age_seconds: u64,
+    pub max_backoff_seconds: u64,
+    pub per_seller_limit: i64,
+    pub batch_size: i64,
+}
+
+impl Default for StatusPollConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: 300,
+            min_order_age_seconds: 900,
+            max_backoff_seconds: 21600,
+            per_seller_limit: 20,
+            batch_size: 200,
+        }
+    }
+}
+
+impl StatusPollConfig {
+    pub fn interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.interval_seconds)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct StandingOrderConfig {
+    pub interval_seconds: u64,
+    pub batch_size: i64,
+}
+
+impl Default for StandingOrderConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: 60,
+            batch_size: 50,
+        }
+    }
+}
+
+impl StandingOrderConfig {
+    pub fn interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.interval_seconds)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct DocumentStoreConfig {
+    pub storage_type: DocumentStorageType,
+    pub base_path: Option<String>,
+    pub base_url: Option<String>,
+    pub token: Option<SecretString>,
+    pub timeout_milliseconds: u64,
+    pub max_size_bytes: usize,
+}
+
+impl Default for DocumentStoreConfig {
+    fn default() -> Self {
+        Self {
+            storage_type: DocumentStorageType::Local,
+            base_path: Some("documents".to_owned()),
+            base_url: None,
+            token: None,
+            timeout_milliseconds: 60000,
+            max_size_bytes: 20971520,
+        }
+    }
+}
+
+impl DocumentStoreConfig {
+    pub fn client(self) -> Result<DocumentClient, anyhow::Error> {
+        let timeout = std::time::Duration::from_millis(self.timeout_milliseconds);
+        let client = match self.storage_type {
+            DocumentStorageType::Local => DocumentClient::new_local(
+                self.base_path.ok_or_else(|| {
+                    anyhow!("DOCUMENT_STORE__BASE_PATH is required for local storage")
+                })?,
+                timeout,
+                self.max_size_bytes,
+            ),
+            DocumentStorageType::Object => DocumentClient::new_object(
+                self.base_url.ok_or_else(|| {
+                    anyhow!("DOCUMENT_STORE__BASE_URL is required for object storage")
+                })?,
+                self.token,
+                timeout,
+                self.max_size_bytes,
+            ),
+        };
+        Ok(client)
+    }
+}
@@ -1,6 +1,16 @@
 pub const ONDC_TTL: &str = "PT30S";
 // pub const TEST_DB: &str = "ondc_b2b_buyer";
 pub const DUMMY_DOMAIN: &str = "abc.co";
+pub const ORDER_ANALYTICS_ITEM_LIMIT: i64 = 50;
+pub const ORDER_LIST_MAX_LIMIT: i32 = 500;
+pub const ORDER_EXPORT_PAGE_SIZE: i32 = 100;
+pub const ORDER_EXPORT_SYNC_LIMIT: i64 = 1000;
+pub const ORDER_EXPORT_STALE_MINUTES: i64 = 30;
+pub const ORDER_EXPORT_RETENTION_HOURS: i64 = 24;
+pub const ORDER_EXPORT_CLEANUP_INTERVAL_SECONDS: u64 = 600;
+pub const BULK_ORDER_MAX_ROWS: usize = 1000;
+pub const BULK_ORDER_MAX_BYTES: usize = 1 << 20;
+pub const BULK_ORDER_MAX_SELECTS: usize = 25;
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -0,0 +1,299 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use blake2::{Blake2b512, Digest};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::{header, redirect, Client, Url};
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentStorageType {
+    Local,
+    Object,
+}
+
+/// Local files are kept under a base path, object storage is any S3-compatible bucket endpoint
+/// (or a local stand-in) accepting plain PUT and GET of `{base_url}/{key}`.
+#[derive(Debug)]
+enum DocumentStorage {
+    Local {
+        base_path: PathBuf,
+    },
+    Object {
+        base_url: String,
+        authorization_token: Option<SecretString>,
+    },
+}
+
+#[derive(Debug)]
+pub struct DocumentClient {
+    http_client: Client,
+    download_client: Client,
+    storage: DocumentStorage,
+    max_size_bytes: usize,
+}
+
+#[derive(Debug)]
+pub struct DownloadedDocument {
+    pub content: Vec<u8>,
+    pub content_type: String,
+}
+
+/// BLAKE2b-512 of the document content, hex encoded.
+pub fn get_document_checksum(content: &[u8]) -> String {
+    hex::encode(Blake2b512::digest(content))
+}
+
+fn is_public_ipv4(ip: &Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || a == 0
+        || a >= 240
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 192 && b == 0 && c == 0)
+        || (a == 198 && (18..20).contains(&b)))
+}
+
+/// Only globally routable addresses, so seller document urls cannot reach internal services.
+pub fn is_public_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => {
+            if let Some(ipv4) = ip.to_ipv4_mapped() {
+                return is_public_ipv4(&ipv4);
+            }
+            let segments = ip.segments();
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || (segments[0] == 0x64 && segments[1] == 0xff9b)
+                || (segments[0] & 0xfe00) == 0xfc00
+                || (segments[0] & 0xffc0) == 0xfe80
+                || (segments[0] == 0x2001 && segments[1] == 0x0db8))
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> =
+                tokio::task::spawn_blocking(move || (host.as_str(), 0).to_socket_addrs())
+                    .await??
+                    .collect();
+            if addrs.is_empty() || !addrs.iter().all(|addr| is_public_ip(&addr.ip())) {
+                return Err(anyhow!("Document host does not resolve to a public address").into());
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+fn get_download_client(timeout: std::time::Duration) -> Client {
+    Client::builder()
+        .timeout(timeout)
+        .https_only(true)
+        .no_proxy()
+        .redirect(redirect::Policy::none())
+        .dns_resolver(Arc::new(PublicResolver))
+        .build()
+        .unwrap()
+}
+
+fn get_local_path(base_path: &Path, key: &str) -> Result<PathBuf, anyhow::Error> {
+    let key_path = Path::new(key);
+    if !key_path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return Err(anyhow!("Invalid document key {}", key));
+    }
+    Ok(base_path.join(key_path))
+}
+
+impl DocumentClient {
+    #[tracing::instrument]
+    pub fn new_local(
+        base_path: String,
+        timeout: std::time::Duration,
+        max_size_bytes: usize,
+    ) -> Self {
+        tracing::info!("Using local document storage at {}.", base_path);
+        let http_client = Client::builder().timeout(timeout).build().unwrap();
+        Self {
+            http_client,
+            download_client: get_download_client(timeout),
+            storage: DocumentStorage::Local {
+                base_path: PathBuf::from(base_path),
+            },
+            max_size_bytes,
+        }
+    }
+
+    #[tracing::instrument]
+    pub fn new_object(
+        base_url: String,
+        authorization_token: Option<SecretString>,
+        timeout: std::time::Duration,
+        max_size_bytes: usize,
+    ) -> Self {
+        tracing::info!("Using object document storage at {}.", base_url);
+        let http_client = Client::builder().timeout(timeout).build().unwrap();
+        Self {
+            http_client,
+            download_client: get_download_client(timeout),
+            storage: DocumentStorage::Object {
+                base_url: base_url.trim_end_matches('/').to_string(),
+                authorization_token,
+            },
+            max_size_bytes,
+        }
+    }
+
+    /// Fetches a document shared by the seller over https from a public address without following
+    /// redirects, refusing anything larger than the configured limit.
+    #[tracing::instrument(skip(self))]
+    pub async fn download(&self, url: &str) -> Result<DownloadedDocument, anyhow::Error> {
+        let parsed_url = Url::parse(url).context("Invalid document url")?;
+        if parsed_url.scheme() != "https" {
+            return Err(anyhow!("Document url {} is not an https url", url));
+        }
+        let host = parsed_url
+            .host_str()
+            .ok_or_else(|| anyhow!("Document url {} has no host", url))?;
+        let ip = host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .ok();
+        if ip.is_some_and(|ip| !is_public_ip(&ip)) {
+            return Err(anyhow!("Document url {} is not a public address", url));
+        }
+        let mut response = self
+            .download_client
+            .get(parsed_url)
+            .send()
+            .await
+            .context("Failed to download document")?
+            .error_for_status()
+            .context("Document download was not successful")?;
+        if response.status().is_redirection() {
+            return Err(anyhow!("Document url {} redirects", url));
+        }
+        if response
+            .content_length()
+            .is_some_and(|length| length as usize > self.max_size_bytes)
+        {
+            return Err(anyhow!("Document {} is larger than allowed", url));
+        }
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let mut content = vec![];
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .context("Failed to read downloaded document")?
+        {
+            if content.len() + chunk.len() > self.max_size_bytes {
+                return Err(anyhow!("Document {} is larger than allowed", url));
+            }
+            content.extend_from_slice(&chunk);
+        }
+        Ok(DownloadedDocument {
+            content,
+            content_type,
+        })
+    }
+
+    #[tracing::instrument(skip(self, content))]
+    pub async fn put(
+        &self,
+        key: &str,
+        content: Vec<u8>,
+        content_type: &str,
+    ) -> Result<(), anyhow::Error> {
+        match &self.storage {
+            DocumentStorage::Local { base_path } => {
+                let path = get_local_path(base_path, key)?;
+                tokio::task::spawn_blocking(move || {
+                    if let Some(parent) = path.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    std::fs::write(path, content)
+                })
+                .await?
+                .context("Failed to write document to local storage")?;
+            }
+            DocumentStorage::Object {
+                base_url,
+                authorization_token,
+            } => {
+                let mut request = self
+                    .http_client
+                    .put(format!("{}/{}", base_url, key))
+                    .header(header::CONTENT_TYPE, content_type)
+                    .body(content);
+                if let Some(token) = authorization_token {
+                    request = request.bearer_auth(token.expose_secret());
+                }
+                request
+                    .send()
+                    .await
+                    .context("Failed to upload document to object storage")?
+                    .error_for_status()
+                    .context("Object storage rejected the document")?;
+            }
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get(&self, key: &str) -> Result<Vec<u8>, anyhow::Error> {
+        match &self.storage {
+            DocumentStorage::Local { base_path } => {
+                let path = get_local_path(base_path, key)?;
+                Ok(tokio::task::spawn_blocking(move || std::fs::read(path))
+                    .await?
+                    .context("Failed to read document from local storage")?)
+            }
+            DocumentStorage::Object {
+                base_url,
+                authorization_token,
+            } => {
+                let mut request = self.http_client.get(format!("{}/{}", base_url, key));
+                if let Some(token) = authorization_token {
+                    request = request.bearer_auth(token.expose_secret());
+                }
+                let content = request
+                    .send()
+                    .await
+                    .context("Failed to fetch document from object storage")?
+                    .error_for_status()
+                    .context("Object storage did not return the document")?
+                    .bytes()
+                    .await
+                    .context("Failed to read document from object storage")?;
+                Ok(content.to_vec())
+            }
+        }
+    }
+}
@@ -2,6 +2,7 @@ pub mod chat_client;
 pub mod commands;
 pub mod configuration;
 pub mod constants;
+pub mod document_client;
 pub mod domain;
 pub mod elastic_search_client;
 pub mod email_client;
@@ -14,6 +15,7 @@ pub mod openapi;
 pub mod payment_client;
 pub mod redis;
 pub mod routes;
+mod scheduler;
 pub mod schemas;
 pub mod session_state;
 pub mod startup;
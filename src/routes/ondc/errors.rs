@@ -1,4 +1,5 @@
 use crate::routes::ondc::{ONDCBuyerErrorCode, ONDCResponse, ONDCResponseErrorBody, ONDErrorType};
+use crate::routes::order::errors::{OrderTransitionError, QuoteValidationError};
 use crate::utils::error_chain_fmt;
 use actix_web::http::StatusCode;
 use actix_web::{HttpResponse, ResponseError};
@@ -45,6 +46,11 @@ pub enum ONDCBuyerError {
     },
     #[error("Invalid Signature")]
     InvalidSignatureError { path: Option<String> },
+    #[error("Invalid Quote")]
+    InvalidQuoteError {
+        path: Option<String>,
+        message: String,
+    },
 }
 
 impl std::fmt::Debug for ONDCBuyerError {
@@ -61,6 +67,7 @@ impl ResponseError for ONDCBuyerError {
             ONDCBuyerError::BuyerResponseSequenceError { .. } => StatusCode::BAD_REQUEST,
             ONDCBuyerError::InvalidResponseError { .. } => StatusCode::BAD_REQUEST,
             ONDCBuyerError::InvalidSignatureError { .. } => StatusCode::BAD_REQUEST,
+            ONDCBuyerError::InvalidQuoteError { .. } => StatusCode::BAD_REQUEST,
         }
     }
 
@@ -98,6 +105,12 @@ impl ResponseError for ONDCBuyerError {
                 path,
                 ONDErrorType::JsonSchemaError,
             ),
+            ONDCBuyerError::InvalidQuoteError { path, message } => (
+                message.as_str(),
+                ONDCBuyerErrorCode::InvalidResponseCode,
+                path,
+                ONDErrorType::DomainError,
+            ),
         };
 
         let error_obj: ONDCResponseErrorBody<ONDCBuyerErrorCode> = ONDCResponseErrorBody {
@@ -109,3 +122,30 @@ impl ResponseError for ONDCBuyerError {
         HttpResponse::build(status_code).json(ONDCResponse::error_response(None, error_obj))
     }
 }
+
+impl From<OrderTransitionError> for ONDCBuyerError {
+    fn from(err: OrderTransitionError) -> ONDCBuyerError {
+        match err {
+            OrderTransitionError::InvalidTransition(message) => {
+                tracing::warn!("Rejected seller callback: {}", message);
+                ONDCBuyerError::BuyerResponseSequenceError {
+                    path: Some("message.order.state".to_string()),
+                }
+            }
+        }
+    }
+}
+
+impl From<QuoteValidationError> for ONDCBuyerError {
+    fn from(err: QuoteValidationError) -> ONDCBuyerError {
+        match err {
+            QuoteValidationError::InvalidQuote(message) => {
+                tracing::warn!("Rejected seller quote: {}", message);
+                ONDCBuyerError::InvalidQuoteError {
+                    path: Some("message.order.quote".to_string()),
+                    message,
+                }
+            }
+        }
+    }
+}
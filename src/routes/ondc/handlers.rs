@@ -1,8 +1,11 @@
 use actix_web::web;
 use anyhow::Context;
+use bigdecimal::BigDecimal;
 use rdkafka::producer::FutureRecord;
 use rdkafka::util::Timeout;
 use sqlx::PgPool;
+use std::collections::HashMap;
+use std::str::FromStr;
 
 use super::errors::ONDCBuyerError;
 use super::schemas::{
@@ -20,14 +23,27 @@ use super::{
 };
 use crate::chat_client::ChatClient;
 use crate::constants::ONDC_TTL;
+use crate::document_client::DocumentClient;
 use crate::kafka_client::{KafkaClient, KafkaGroupName};
 use crate::routes::ondc::{ONDCActionType, ONDCBuyerErrorCode, ONDCResponse};
+use crate::routes::order::documents::spawn_order_document_ingestion;
+use crate::routes::order::quote_validation::{
+    validate_order_quote, validate_quote, validate_quote_issues,
+};
+use crate::routes::order::schemas::{CommerceDocument, CommerceStatusType};
+use crate::routes::order::scorecard::SellerScorecardEvent;
+use crate::routes::order::standing_order::{spawn_standing_order_callback, StandingOrderCallback};
+use crate::routes::order::state_machine::{
+    get_quote_event, save_status_transitions, validate_order_transition, StatusActor,
+};
 use crate::routes::order::utils::{
-    fetch_order_by_id, initialize_order_on_cancel, initialize_order_on_confirm,
-    initialize_order_on_init, initialize_order_on_select, initialize_order_on_status,
-    initialize_order_on_update, send_rfq_accept_chat, send_rfq_cancel_chat,
+    extend_order_approval_requirement, fetch_approval_policies, fetch_commerce_status_for_update,
+    fetch_order_by_id, fetch_order_callback_on_for_update, get_order_approval_levels,
+    initialize_order_on_cancel, initialize_order_on_confirm, initialize_order_on_init,
+    initialize_order_on_select, initialize_order_on_status, initialize_order_on_update,
+    is_stale_callback, save_seller_scorecard_events, send_rfq_accept_chat, send_rfq_cancel_chat,
     send_rfq_confirmed_chat, send_rfq_init_chat, send_rfq_reject_chat, send_rfq_status_chat,
-    send_rfq_update_chat,
+    send_rfq_update_chat, update_order_approval_requirement, update_order_quote_issues,
 };
 
 use crate::user_client::UserClient;
@@ -106,12 +122,29 @@ pub async fn on_select(
         serde_json::from_value::<ONDCSelectRequest>(ondc_select_model.request_payload).unwrap();
 
     let is_rfq = ondc_select_req.context.ttl != ONDC_TTL;
+    let standing_order_callback = StandingOrderCallback::Select {
+        quote_total: match body.error {
+            None => BigDecimal::from_str(&body.message.order.quote.price.value).ok(),
+            Some(_) => None,
+        },
+    };
+    let standing_order_error = body.error.as_ref().map(|error| error.message.clone());
+    let scorecard_event = SellerScorecardEvent::QuoteResponded {
+        message_id: body.context.message_id,
+        response_secs: (body.context.timestamp - ondc_select_req.context.timestamp).num_seconds(),
+        is_rejected: body.error.is_some(),
+    };
     let mut transaction = pool
         .begin()
         .await
         .context("Failed to acquire a Postgres connection from the pool")
         .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
     if body.error.is_none() {
+        let previous_status =
+            fetch_commerce_status_for_update(&mut transaction, body.context.transaction_id)
+                .await
+                .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+        validate_order_transition(previous_status.as_ref(), &CommerceStatusType::QuoteAccepted)?;
         let task_1 = user_client.get_user_account(None, Some(ondc_select_model.user_id));
         // .await
         // .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
@@ -160,6 +193,7 @@ pub async fn on_select(
         // .await
         // .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?
         // .ok_or(ONDCBuyerError::BuyerInternalServerError { path: None })?;
+        let task_7 = fetch_approval_policies(&pool, ondc_select_model.business_id);
         let (
             user_res,
             product_map_res,
@@ -167,7 +201,8 @@ pub async fn on_select(
             seller_location_map_res,
             setting_res,
             business_res,
-        ) = tokio::join!(task_1, task_2, task_3, task_4, task_5, task_6);
+            approval_policy_res,
+        ) = tokio::join!(task_1, task_2, task_3, task_4, task_5, task_6, task_7);
 
         let user = match user_res {
             Ok(user) => user,
@@ -206,6 +241,14 @@ pub async fn on_select(
                 return Err(ONDCBuyerError::BuyerInternalServerError { path: None });
             }
         };
+        let approval_policies = approval_policy_res
+            .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+        let tax_rates: HashMap<&str, &BigDecimal> = product_map
+            .values()
+            .map(|product| (product.item_id.as_str(), &product.tax_rate))
+            .collect();
+        let quote_issues = validate_quote(&body.message.order.quote, &tax_rates);
+        validate_quote_issues(&quote_issues)?;
 
         initialize_order_on_select(
             &mut transaction,
@@ -218,6 +261,26 @@ pub async fn on_select(
             &seller_info_map,
             &seller_location_map,
             &setting,
+            previous_status.as_ref(),
+        )
+        .await
+        .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+        update_order_quote_issues(&mut transaction, body.context.transaction_id, &quote_issues)
+            .await
+            .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+        let quote_value = BigDecimal::from_str(&body.message.order.quote.price.value)
+            .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+        let approval_levels = get_order_approval_levels(
+            &approval_policies,
+            &quote_value,
+            &body.context.domain.get_category_domain(),
+            &body.message.order.provider.id,
+        );
+        update_order_approval_requirement(
+            &mut transaction,
+            body.context.transaction_id,
+            &approval_levels,
+            body.context.timestamp,
         )
         .await
         .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
@@ -227,6 +290,19 @@ pub async fn on_select(
                 .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?
         }
     } else if let Some(error) = body.error {
+        save_status_transitions(
+            &mut transaction,
+            body.context.transaction_id,
+            &[get_quote_event(
+                &CommerceStatusType::QuoteRejected,
+                Some(serde_json::json!({ "code": error.code, "message": error.message })),
+            )],
+            &StatusActor::seller(body.context.bpp_id.as_deref().unwrap_or("")),
+            ONDCActionType::OnSelect,
+            body.context.message_id,
+        )
+        .await
+        .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
         send_rfq_reject_chat(
             &chat_client,
             &error.message,
@@ -236,6 +312,19 @@ pub async fn on_select(
         .await
         .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?
     }
+    save_seller_scorecard_events(
+        &mut transaction,
+        body.context.transaction_id,
+        body.context
+            .bpp_id
+            .as_deref()
+            .or(ondc_select_req.context.bpp_id.as_deref())
+            .unwrap_or(""),
+        &ondc_select_req.message.order.provider.id,
+        vec![scorecard_event],
+    )
+    .await
+    .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
 
     transaction
         .commit()
@@ -250,6 +339,14 @@ pub async fn on_select(
             None,
         )
         .await;
+    spawn_standing_order_callback(
+        pool.clone(),
+        user_client.clone(),
+        websocket_srv.clone(),
+        body.context.transaction_id,
+        standing_order_callback,
+        standing_order_error,
+    );
     Ok(web::Json(ONDCResponse::successful_response(None)))
 }
 
@@ -259,6 +356,7 @@ pub async fn on_init(
     body: ONDCOnInitRequest,
     websocket_srv: web::Data<WebSocketClient>,
     chat_client: web::Data<ChatClient>,
+    user_client: web::Data<UserClient>,
 ) -> Result<web::Json<ONDCResponse<ONDCBuyerErrorCode>>, ONDCBuyerError> {
     let task_1 = fetch_ondc_order_request(
         &pool,
@@ -278,6 +376,33 @@ pub async fn on_init(
         order_request_model_opt.ok_or(ONDCBuyerError::BuyerResponseSequenceError { path: None })?;
     let commerce_data =
         commerce_data_opt.ok_or(ONDCBuyerError::BuyerResponseSequenceError { path: None })?;
+    validate_order_transition(
+        Some(&commerce_data.record_status),
+        &CommerceStatusType::Initialized,
+    )?;
+    let quote_issues = match body.error {
+        None => Some(validate_order_quote(
+            &body.message.order.quote,
+            &commerce_data.items,
+        )?),
+        Some(_) => None,
+    };
+    let approval_levels = match body.error {
+        None => {
+            let approval_policies = fetch_approval_policies(&pool, order_request_model.business_id)
+                .await
+                .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+            let quote_value = BigDecimal::from_str(&body.message.order.quote.price.value)
+                .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+            get_order_approval_levels(
+                &approval_policies,
+                &quote_value,
+                &commerce_data.domain_category_code,
+                &body.message.order.provider.id,
+            )
+        }
+        Some(_) => vec![],
+    };
     // .await
     // .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?
     // .ok_or(ONDCBuyerError::BuyerResponseSequenceError { path: None })?;
@@ -307,9 +432,27 @@ pub async fn on_init(
         .context("Failed to acquire a Postgres connection from the pool")
         .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
 
-    initialize_order_on_init(&mut transaction, &body, order_request_model.business_id)
-        .await
-        .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    initialize_order_on_init(
+        &mut transaction,
+        &body,
+        &commerce_data,
+        order_request_model.business_id,
+    )
+    .await
+    .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    if let Some(quote_issues) = &quote_issues {
+        update_order_quote_issues(&mut transaction, body.context.transaction_id, quote_issues)
+            .await
+            .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    }
+    extend_order_approval_requirement(
+        &mut transaction,
+        body.context.transaction_id,
+        &approval_levels,
+        body.context.timestamp,
+    )
+    .await
+    .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
 
     if commerce_data.record_type.is_purchase_order() {
         send_rfq_init_chat(&chat_client, body.context.transaction_id, &commerce_data)
@@ -326,6 +469,19 @@ pub async fn on_init(
         .await
         .context("Failed to commit SQL transaction to store an order")
         .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    spawn_standing_order_callback(
+        pool.clone(),
+        user_client.clone(),
+        websocket_srv.clone(),
+        body.context.transaction_id,
+        StandingOrderCallback::Init {
+            quote_total: match body.error {
+                None => BigDecimal::from_str(&body.message.order.quote.price.value).ok(),
+                Some(_) => None,
+            },
+        },
+        body.error.as_ref().map(|error| error.message.clone()),
+    );
 
     Ok(web::Json(ONDCResponse::successful_response(None)))
 }
@@ -336,7 +492,26 @@ pub async fn on_confirm(
     body: ONDCOnConfirmRequest,
     websocket_srv: web::Data<WebSocketClient>,
     chat_client: web::Data<ChatClient>,
+    user_client: web::Data<UserClient>,
 ) -> Result<web::Json<ONDCResponse<ONDCBuyerErrorCode>>, ONDCBuyerError> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")
+        .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    let last_callback_on =
+        fetch_order_callback_on_for_update(&mut transaction, body.context.transaction_id)
+            .await
+            .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?
+            .ok_or(ONDCBuyerError::BuyerResponseSequenceError { path: None })?;
+    if is_stale_callback(last_callback_on, body.context.timestamp) {
+        tracing::warn!(
+            "Ignoring stale on_confirm for {} with timestamp {}",
+            body.context.transaction_id,
+            body.context.timestamp
+        );
+        return Ok(web::Json(ONDCResponse::successful_response(None)));
+    }
     let task1 = fetch_ondc_order_request(
         &pool,
         body.context.transaction_id,
@@ -352,6 +527,21 @@ pub async fn on_confirm(
     let order = res2
         .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?
         .ok_or(ONDCBuyerError::BuyerResponseSequenceError { path: None })?;
+    validate_order_transition(
+        Some(&order.record_status),
+        &body
+            .message
+            .order
+            .state
+            .get_commerce_status(&order.record_type, None),
+    )?;
+    let quote_issues = match body.error {
+        None => Some(validate_order_quote(
+            &body.message.order.quote,
+            &order.items,
+        )?),
+        Some(_) => None,
+    };
 
     let payment_links: Vec<&str> = body
         .message
@@ -372,11 +562,6 @@ pub async fn on_confirm(
     };
     let ws_json = serde_json::to_value(ws_obj).unwrap();
     let ws_params_obj = get_ondc_order_param_from_req(&order_request_model);
-    let mut transaction = pool
-        .begin()
-        .await
-        .context("Failed to acquire a Postgres connection from the pool")
-        .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
     initialize_order_on_confirm(
         &mut transaction,
         &body,
@@ -385,6 +570,22 @@ pub async fn on_confirm(
     )
     .await
     .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    if let Some(quote_issues) = &quote_issues {
+        update_order_quote_issues(&mut transaction, body.context.transaction_id, quote_issues)
+            .await
+            .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    }
+    save_seller_scorecard_events(
+        &mut transaction,
+        body.context.transaction_id,
+        &order.bpp.id,
+        &order.seller.id,
+        vec![SellerScorecardEvent::OrderConfirmed {
+            is_rejected: body.error.is_some(),
+        }],
+    )
+    .await
+    .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
 
     if order.record_type.is_purchase_order() {
         send_rfq_confirmed_chat(&chat_client, body.context.transaction_id, &order)
@@ -405,6 +606,14 @@ pub async fn on_confirm(
         .await
         .context("Failed to commit SQL transaction to store an order")
         .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    spawn_standing_order_callback(
+        pool.clone(),
+        user_client.clone(),
+        websocket_srv.clone(),
+        body.context.transaction_id,
+        StandingOrderCallback::Confirm,
+        body.error.as_ref().map(|error| error.message.clone()),
+    );
     Ok(web::Json(ONDCResponse::successful_response(None)))
 }
 
@@ -414,7 +623,26 @@ pub async fn on_status(
     body: ONDCOnStatusRequest,
     websocket_srv: web::Data<WebSocketClient>,
     chat_client: web::Data<ChatClient>,
+    document_client: web::Data<DocumentClient>,
 ) -> Result<web::Json<ONDCResponse<ONDCBuyerErrorCode>>, ONDCBuyerError> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")
+        .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    let last_callback_on =
+        fetch_order_callback_on_for_update(&mut transaction, body.context.transaction_id)
+            .await
+            .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?
+            .ok_or(ONDCBuyerError::BuyerResponseSequenceError { path: None })?;
+    if is_stale_callback(last_callback_on, body.context.timestamp) {
+        tracing::warn!(
+            "Ignoring stale on_status for {} with timestamp {}",
+            body.context.transaction_id,
+            body.context.timestamp
+        );
+        return Ok(web::Json(ONDCResponse::successful_response(None)));
+    }
     let task1 = fetch_ondc_order_request(
         &pool,
         body.context.transaction_id,
@@ -429,11 +657,13 @@ pub async fn on_status(
     let order = res2
         .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?
         .ok_or(ONDCBuyerError::BuyerResponseSequenceError { path: None })?;
-    let mut transaction = pool
-        .begin()
-        .await
-        .context("Failed to acquire a Postgres connection from the pool")
-        .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    validate_order_transition(
+        Some(&order.record_status),
+        &body.message.order.state.get_commerce_status(
+            &order.record_type,
+            Some(body.message.order.documents.is_some()),
+        ),
+    )?;
 
     if order.record_type.is_purchase_order() {
         let proforma_present = body.message.order.documents.is_some();
@@ -444,7 +674,7 @@ pub async fn on_status(
                 .order
                 .state
                 .get_commerce_status(&order.record_type, Some(proforma_present)),
-            &body.message.order.fulfillments,
+            &body.message.order.get_forward_fulfillments(),
             body.error.as_ref().map(|e| e.message.clone()),
             &order,
         )
@@ -484,6 +714,23 @@ pub async fn on_status(
         .await
         .context("Failed to commit SQL transaction to store an order")
         .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    let documents = body
+        .message
+        .order
+        .documents
+        .iter()
+        .flatten()
+        .map(|document| CommerceDocument {
+            r#type: document.label.get_document_type(),
+            url: document.url.clone(),
+        })
+        .collect();
+    spawn_order_document_ingestion(
+        pool.clone(),
+        document_client.clone(),
+        body.context.transaction_id,
+        documents,
+    );
     Ok(web::Json(ONDCResponse::successful_response(None)))
 }
 
@@ -494,6 +741,24 @@ pub async fn on_cancel(
     websocket_srv: web::Data<WebSocketClient>,
     chat_client: web::Data<ChatClient>,
 ) -> Result<web::Json<ONDCResponse<ONDCBuyerErrorCode>>, ONDCBuyerError> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")
+        .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    let last_callback_on =
+        fetch_order_callback_on_for_update(&mut transaction, body.context.transaction_id)
+            .await
+            .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?
+            .ok_or(ONDCBuyerError::BuyerResponseSequenceError { path: None })?;
+    if is_stale_callback(last_callback_on, body.context.timestamp) {
+        tracing::warn!(
+            "Ignoring stale on_cancel for {} with timestamp {}",
+            body.context.transaction_id,
+            body.context.timestamp
+        );
+        return Ok(web::Json(ONDCResponse::successful_response(None)));
+    }
     let task1 = fetch_ondc_order_request(
         &pool,
         body.context.transaction_id,
@@ -508,16 +773,19 @@ pub async fn on_cancel(
     let order = res2
         .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?
         .ok_or(ONDCBuyerError::BuyerResponseSequenceError { path: None })?;
+    validate_order_transition(
+        Some(&order.record_status),
+        &body
+            .message
+            .order
+            .state
+            .get_commerce_status(&order.record_type, None),
+    )?;
     if order.record_type.is_purchase_order() {
         send_rfq_cancel_chat(&chat_client, body.context.transaction_id, &order)
             .await
             .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
     }
-    let mut transaction = pool
-        .begin()
-        .await
-        .context("Failed to acquire a Postgres connection from the pool")
-        .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
     let updated_by = order_request_model.as_ref().map_or_else(
         || "seller".to_string(),
         |model| model.business_id.to_string(),
@@ -525,6 +793,17 @@ pub async fn on_cancel(
     initialize_order_on_cancel(&mut transaction, &body, &order, &updated_by)
         .await
         .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    if order_request_model.is_none() && body.error.is_none() {
+        save_seller_scorecard_events(
+            &mut transaction,
+            body.context.transaction_id,
+            &order.bpp.id,
+            &order.seller.id,
+            vec![SellerScorecardEvent::SellerCancelled],
+        )
+        .await
+        .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    }
     if let Some(order_request_model) = order_request_model {
         let ws_obj = WSCancel {
             transaction_id: body.context.transaction_id,
@@ -561,6 +840,24 @@ pub async fn on_update(
     websocket_srv: web::Data<WebSocketClient>,
     chat_client: web::Data<ChatClient>,
 ) -> Result<web::Json<ONDCResponse<ONDCBuyerErrorCode>>, ONDCBuyerError> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")
+        .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
+    let last_callback_on =
+        fetch_order_callback_on_for_update(&mut transaction, body.context.transaction_id)
+            .await
+            .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?
+            .ok_or(ONDCBuyerError::BuyerResponseSequenceError { path: None })?;
+    if is_stale_callback(last_callback_on, body.context.timestamp) {
+        tracing::warn!(
+            "Ignoring stale on_update for {} with timestamp {}",
+            body.context.transaction_id,
+            body.context.timestamp
+        );
+        return Ok(web::Json(ONDCResponse::successful_response(None)));
+    }
     let task1 = fetch_ondc_order_request(
         &pool,
         body.context.transaction_id,
@@ -575,16 +872,19 @@ pub async fn on_update(
     let order = res2
         .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?
         .ok_or(ONDCBuyerError::BuyerResponseSequenceError { path: None })?;
+    let next_status = body
+        .message
+        .order
+        .state
+        .get_commerce_status(&order.record_type, None);
+    if next_status != order.record_status {
+        validate_order_transition(Some(&order.record_status), &next_status)?;
+    }
     if order.record_type.is_purchase_order() {
         send_rfq_update_chat(&chat_client, body.context.transaction_id, &order)
             .await
             .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
     }
-    let mut transaction = pool
-        .begin()
-        .await
-        .context("Failed to acquire a Postgres connection from the pool")
-        .map_err(|_| ONDCBuyerError::BuyerInternalServerError { path: None })?;
     let updated_by = order_request_model.as_ref().map_or_else(
         || "seller".to_string(),
         |model| model.business_id.to_string(),
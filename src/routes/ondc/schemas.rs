@@ -24,7 +24,7 @@ use crate::routes::order::schemas::{
     CancellationFeeType, CommerceBPPTerms, CommerceStatusType, DocumentType,
     FulfillmentCategoryType, FulfillmentStatusType, IncoTermType, Payment, PaymentCollectedBy,
     PaymentSettlementCounterparty, PaymentSettlementPhase, PaymentSettlementType, PaymentStatus,
-    ServiceableType, SettlementBasis,
+    ReturnStatusType, ServiceableType, SettlementBasis,
 };
 use crate::routes::product::schemas::{FulfillmentType, PaymentType};
 use crate::schemas::{CurrencyType, FeeType};
@@ -422,6 +422,8 @@ pub enum ONDCTagType {
     BppTerms,
     #[serde(rename = "COMM_CHANNEL")]
     CommChannel,
+    CancelRequest,
+    ReturnRequest,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -458,6 +460,7 @@ pub enum ONDCTagItemCode {
     Nonveg,
     TimeToShip,
     TaxRate,
+    CessRate,
     Cancellable,
     Brand,
     PackSize,
@@ -483,6 +486,14 @@ pub enum ONDCTagItemCode {
     DelayInterest,
     AcceptBppTerms,
     ChatUrl,
+    ReasonId,
+    InitiatedBy,
+    Id,
+    ItemId,
+    ItemQuantity,
+    ReasonDesc,
+    Images,
+    TaxNumber,
 }
 
 impl std::fmt::Display for ONDCTagItemCode {
@@ -661,6 +672,52 @@ impl TagTrait for ONDCTag {
 }
 
 impl ONDCTag {
+    pub fn get_cancel_request_tag(reason_id: &str, initiated_by: &str) -> ONDCTag {
+        ONDCTag {
+            descriptor: ONDCTagDescriptor {
+                code: ONDCTagType::CancelRequest,
+            },
+            list: vec![
+                ONDCTagItem::set_tag_item(ONDCTagItemCode::ReasonId, reason_id),
+                ONDCTagItem::set_tag_item(ONDCTagItemCode::InitiatedBy, initiated_by),
+            ],
+        }
+    }
+
+    pub fn get_return_request_tag(
+        return_id: &str,
+        item_id: &str,
+        item_quantity: i32,
+        reason_id: &str,
+        reason_desc: Option<&str>,
+        images: &[String],
+    ) -> ONDCTag {
+        let mut list = vec![
+            ONDCTagItem::set_tag_item(ONDCTagItemCode::Id, return_id),
+            ONDCTagItem::set_tag_item(ONDCTagItemCode::ItemId, item_id),
+            ONDCTagItem::set_tag_item(ONDCTagItemCode::ItemQuantity, &item_quantity.to_string()),
+            ONDCTagItem::set_tag_item(ONDCTagItemCode::ReasonId, reason_id),
+        ];
+        if let Some(reason_desc) = reason_desc {
+            list.push(ONDCTagItem::set_tag_item(
+                ONDCTagItemCode::ReasonDesc,
+                reason_desc,
+            ));
+        }
+        if !images.is_empty() {
+            list.push(ONDCTagItem::set_tag_item(
+                ONDCTagItemCode::Images,
+                &images.join(","),
+            ));
+        }
+        ONDCTag {
+            descriptor: ONDCTagDescriptor {
+                code: ONDCTagType::ReturnRequest,
+            },
+            list,
+        }
+    }
+
     pub fn get_buyer_fee_tag(finder_fee_type: ONDCFeeType, finder_fee_amount: &str) -> ONDCTag {
         ONDCTag {
             descriptor: ONDCTagDescriptor {
@@ -738,7 +795,12 @@ impl ONDCTag {
                     ONDCTagItemCode::DelayInterest,
                     &commerce_bpp_term.delay_interest,
                 ),
-            ],
+            ]
+            .into_iter()
+            .chain(commerce_bpp_term.tax_number.as_deref().map(|tax_number| {
+                ONDCTagItem::set_tag_item(ONDCTagItemCode::TaxNumber, tax_number)
+            }))
+            .collect(),
         }
     }
     pub fn get_bap_agreement_to_bpp_terms_tag(agree: &str) -> ONDCTag {
@@ -1704,6 +1766,7 @@ pub struct BulkSellerProductInfo<'a> {
     pub item_ids: Vec<&'a str>,
     pub item_names: Vec<&'a str>,
     pub tax_rates: Vec<BigDecimal>,
+    pub cess_rates: Vec<Option<BigDecimal>>,
     pub mrps: Vec<BigDecimal>,
     pub unit_price_with_taxes: Vec<BigDecimal>,
     pub unit_price_without_taxes: Vec<BigDecimal>,
@@ -1730,6 +1793,7 @@ pub struct ONDCSellerProductInfo {
     pub seller_subscriber_id: String,
     pub provider_id: String,
     pub tax_rate: BigDecimal,
+    pub cess_rate: Option<BigDecimal>,
     pub mrp: BigDecimal,
     pub unit_price_with_tax: BigDecimal,
     pub unit_price_without_tax: BigDecimal,
@@ -1893,6 +1957,7 @@ pub struct ONDCOnInitFulfillment {
     pub tracking: bool,
     pub r#type: ONDCFulfillmentType,
     pub stops: Vec<ONDCOrderFulfillmentEnd>,
+    pub tags: Option<Vec<ONDCTag>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -2488,10 +2553,60 @@ pub struct ONDCOnStatusOrder {
     pub created_at: DateTime<Utc>,
     #[serde(serialize_with = "serialize_timestamp_without_nanos")]
     pub updated_at: DateTime<Utc>,
-    pub fulfillments: Vec<ONDCOnConfirmFulfillment>,
+    pub fulfillments: Vec<ONDCOnStatusFulfillment>,
     pub documents: Option<Vec<ONDCDocument>>,
 }
 
+impl ONDCOnStatusOrder {
+    pub fn get_forward_fulfillments(&self) -> Vec<&ONDCOnConfirmFulfillment> {
+        self.fulfillments
+            .iter()
+            .filter_map(|fulfillment| match fulfillment {
+                ONDCOnStatusFulfillment::Forward(fulfillment) => Some(fulfillment),
+                ONDCOnStatusFulfillment::Return(_) => None,
+            })
+            .collect()
+    }
+
+    pub fn get_return_fulfillments(&self) -> Vec<&ONDCOnUpdateFulfillment> {
+        self.fulfillments
+            .iter()
+            .filter_map(|fulfillment| match fulfillment {
+                ONDCOnStatusFulfillment::Forward(_) => None,
+                ONDCOnStatusFulfillment::Return(fulfillment) => Some(fulfillment),
+            })
+            .collect()
+    }
+}
+
+/// `on_status` carries the return fulfillments alongside the delivery ones, they do not share a shape.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ONDCOnStatusFulfillment {
+    Forward(ONDCOnConfirmFulfillment),
+    Return(ONDCOnUpdateFulfillment),
+}
+
+/// The variant is picked from the fulfillment `type`, so a malformed delivery fulfillment fails to parse
+/// instead of being read as a return.
+impl<'de> Deserialize<'de> for ONDCOnStatusFulfillment {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        match value.get("type").and_then(Value::as_str) {
+            Some("Return") | Some("Cancel") => serde_json::from_value(value)
+                .map(ONDCOnStatusFulfillment::Return)
+                .map_err(serde::de::Error::custom),
+            Some(_) => serde_json::from_value(value)
+                .map(ONDCOnStatusFulfillment::Forward)
+                .map_err(serde::de::Error::custom),
+            None => Err(serde::de::Error::missing_field("type")),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ONDCOnStatusMessage {
     pub order: ONDCOnStatusOrder,
@@ -2614,6 +2729,19 @@ pub struct ONDCUpdateItem {
     pub quantity: ONDCQuantitySelect,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ONDCUpdateFulfillmentType {
+    Cancel,
+    Return,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ONDCUpdateFulfillment {
+    pub r#type: ONDCUpdateFulfillmentType,
+    pub tags: Vec<ONDCTag>,
+}
+
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ONDCUpdateOrder {
     pub id: String,
@@ -2621,6 +2749,98 @@ pub struct ONDCUpdateOrder {
     pub provider: ONDCUpdateProvider,
     pub payments: Vec<ONDCOnConfirmPayment>,
     pub items: Vec<ONDCUpdateItem>,
+    pub fulfillments: Option<Vec<ONDCUpdateFulfillment>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub enum ONDCOnUpdateFulfillmentType {
+    Delivery,
+    #[serde(rename = "Self-Pickup")]
+    SelfPickup,
+    Cancel,
+    Return,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub enum ONDCReturnStateType {
+    #[serde(rename = "Return_Initiated")]
+    Initiated,
+    #[serde(rename = "Return_Approved")]
+    Approved,
+    #[serde(rename = "Return_Picked")]
+    Picked,
+    #[serde(rename = "Return_Delivered")]
+    Delivered,
+    #[serde(rename = "Return_Rejected")]
+    Rejected,
+    #[serde(other)]
+    Other,
+}
+
+impl ONDCReturnStateType {
+    pub fn get_return_status(&self) -> Option<ReturnStatusType> {
+        match self {
+            ONDCReturnStateType::Initiated => Some(ReturnStatusType::Initiated),
+            ONDCReturnStateType::Approved => Some(ReturnStatusType::Approved),
+            ONDCReturnStateType::Picked => Some(ReturnStatusType::Picked),
+            ONDCReturnStateType::Delivered => Some(ReturnStatusType::Delivered),
+            ONDCReturnStateType::Rejected => Some(ReturnStatusType::Rejected),
+            ONDCReturnStateType::Other => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ONDCReturnStateDescriptor {
+    pub code: ONDCReturnStateType,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ONDCReturnState {
+    pub descriptor: ONDCReturnStateDescriptor,
+}
+
+/// Fulfillment as echoed back in `on_update`/`on_status`, only the return fulfillments are read.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ONDCOnUpdateFulfillment {
+    pub id: String,
+    pub r#type: ONDCOnUpdateFulfillmentType,
+    pub state: Option<ONDCReturnState>,
+    pub tags: Option<Vec<ONDCTag>>,
+}
+
+impl ONDCOnUpdateFulfillment {
+    pub fn get_return_request_tag(&self) -> Option<&ONDCTag> {
+        if self.r#type != ONDCOnUpdateFulfillmentType::Return {
+            return None;
+        }
+        self.tags
+            .as_ref()?
+            .iter()
+            .find(|tag| tag.descriptor.code == ONDCTagType::ReturnRequest)
+    }
+
+    /// Returns the buyer return id and the reported return status of a return fulfillment.
+    pub fn get_return_status(&self) -> Option<(&str, ReturnStatusType)> {
+        let return_id = self
+            .get_return_request_tag()?
+            .get_tag_value(&ONDCTagItemCode::Id.to_string())?;
+        let status = self.state.as_ref()?.descriptor.code.get_return_status()?;
+        Some((return_id, status))
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ONDCOnUpdateOrder {
+    pub id: String,
+    pub state: ONDCOrderStatus,
+    pub provider: ONDCUpdateProvider,
+    pub payments: Vec<ONDCOnConfirmPayment>,
+    pub items: Vec<ONDCUpdateItem>,
+    pub fulfillments: Option<Vec<ONDCOnUpdateFulfillment>>,
+    pub quote: Option<ONDCQuote>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -2629,7 +2849,7 @@ pub enum ONDCOrderUpdateTarget {
     Payment,
     #[serde(rename = "fulfillment")]
     Fulfillment,
-    #[serde(rename = "iulfillment")]
+    #[serde(rename = "item")]
     Item,
 }
 #[derive(Debug, Serialize, Deserialize)]
@@ -2646,7 +2866,7 @@ pub struct ONDCUpdateRequest {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ONDCOnUpdateMessage {
-    pub order: ONDCUpdateOrder,
+    pub order: ONDCOnUpdateOrder,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -85,6 +85,7 @@ mod tests {
             fulfillment_locations: None,
             city_code: "std:080".to_string(),
             update_cache: false,
+            include_seller_scorecard: false,
         };
 
         // seach by item
@@ -3,8 +3,9 @@ use super::{
     ONDCCancelRequest, ONDCConfirmMessage, ONDCConfirmOrder, ONDCConfirmProvider, ONDCContext,
     ONDCContextCity, ONDCContextCountry, ONDCContextLocation, ONDCCredential, ONDCCredentialType,
     ONDCDomain, ONDCFeeType, ONDCSearchStop, ONDCSellePriceSlab, ONDCStatusMessage,
-    ONDCStatusRequest, ONDCTag, ONDCUpdateItem, ONDCUpdateMessage, ONDCUpdateOrder,
-    ONDCUpdateProvider, ONDCUpdateRequest, ONDCVersion, OndcUrl,
+    ONDCStatusRequest, ONDCTag, ONDCUpdateFulfillment, ONDCUpdateFulfillmentType, ONDCUpdateItem,
+    ONDCUpdateMessage, ONDCUpdateOrder, ONDCUpdateProvider, ONDCUpdateRequest, ONDCVersion,
+    OndcUrl,
 };
 
 use crate::chat_client::ChatData;
@@ -51,12 +52,15 @@ use crate::routes::order::errors::{
 };
 use crate::routes::order::schemas::{
     BuyerTerms, CancellationFeeType, Commerce, CommerceBilling, CommerceCancellationFee,
-    CommerceCancellationTerm, CommerceFulfillment, CommerceItem, CommercePayment, DropOffData,
-    OrderCancelRequest, OrderConfirmRequest, OrderDeliveyTerm, OrderInitBilling, OrderInitRequest,
+    CommerceCancellationTerm, CommerceFulfillment, CommerceItem, CommercePayment,
+    CommerceStatusType, DropOffData, FulfillmentStatusType, OrderCancelRequest,
+    OrderConfirmRequest, OrderDeliveyTerm, OrderInitBilling, OrderInitRequest,
     OrderSelectFulfillment, OrderSelectItem, OrderSelectRequest, OrderStatusRequest, OrderType,
-    OrderUpdateRequest, PaymentCollectedBy, PickUpData, SelectFulfillmentLocation, SettlementBasis,
-    TradeType, UpdateOrderPaymentRequest,
+    OrderUpdateRequest, PaymentCollectedBy, PickUpData, ReturnStatusType,
+    SelectFulfillmentLocation, SellerScorecard, SettlementBasis, TradeType, UpdateOrderItemRequest,
+    UpdateOrderPaymentRequest, UpdateOrderReturnRequest,
 };
+use crate::routes::order::utils::fetch_seller_scorecards;
 use crate::routes::product::schemas::{
     CategoryDomain, FulfillmentType, PaymentType, ProductFulFillmentLocations,
     ProductSearchRequest, ProductSearchType, SearchRequestModel, UnitizedProductQty,
@@ -459,7 +463,7 @@ pub async fn get_product_search_params(
 ) -> Result<Option<SearchRequestModel>, anyhow::Error> {
     let row = sqlx::query_as!(
         SearchRequestModel,
-        r#"SELECT transaction_id, user_id, business_id, device_id, update_cache
+        r#"SELECT transaction_id, user_id, business_id, device_id, update_cache, include_seller_scorecard
         FROM search_request
         WHERE transaction_id = $1 AND message_id = $2 ORDER BY created_on DESC
         "#,
@@ -747,6 +751,12 @@ pub fn get_product_from_on_search_request(
                     &ONDCTagItemCode::TaxRate.to_string(),
                 )
                 .unwrap_or("0.00");
+                let cess_rate = get_search_tag_item_value(
+                    &item.tags,
+                    &ONDCTagType::G2,
+                    &ONDCTagItemCode::CessRate.to_string(),
+                )
+                .and_then(|cess_rate| BigDecimal::from_str(cess_rate).ok());
                 let payment_obj = item
                     .payment_ids
                     .iter()
@@ -793,7 +803,7 @@ pub fn get_product_from_on_search_request(
                     location_ids: item.location_ids.iter().map(|s| s.to_owned()).collect(),
                     categories,
                     tax_rate: tax,
-
+                    cess_rate,
                     quantity: get_ws_quantity_from_ondc_quantity(&item.quantity),
                     payment_types: payment_obj, // payment_types: todo!(),
                     price_slabs,
@@ -808,6 +818,7 @@ pub fn get_product_from_on_search_request(
                     &provider_obj.rating,
                     &provider_obj.descriptor,
                 ),
+                seller_scorecard: None,
             };
             provider_list.push(provider)
         }
@@ -828,6 +839,32 @@ pub fn get_product_from_on_search_request(
     Ok(None)
 }
 
+/// Adds the scorecard of every provider in the search result that has one.
+#[tracing::instrument(name = "attach seller scorecards", skip(pool, search_data))]
+async fn attach_seller_scorecards(
+    pool: &PgPool,
+    search_data: &mut WSSearchData,
+) -> Result<(), anyhow::Error> {
+    let provider_ids: Vec<String> = search_data
+        .providers
+        .iter()
+        .map(|provider| provider.provider_detail.id.clone())
+        .collect();
+    let mut scorecard_map: HashMap<String, SellerScorecard> = fetch_seller_scorecards(
+        pool,
+        Some(&search_data.bpp.subscriber_id),
+        Some(&provider_ids),
+    )
+    .await?
+    .into_iter()
+    .map(|scorecard| (scorecard.provider_id.clone(), scorecard))
+    .collect();
+    for provider in search_data.providers.iter_mut() {
+        provider.seller_scorecard = scorecard_map.remove(&provider.provider_detail.id);
+    }
+    Ok(())
+}
+
 #[tracing::instrument(name = "get search ws body", skip())]
 pub fn get_search_ws_body(
     message_id: Uuid,
@@ -1157,6 +1194,7 @@ pub fn create_bulk_seller_product_info_objs<'a>(
     let mut item_names: Vec<&str> = vec![];
     let mut item_ids: Vec<&str> = vec![];
     let mut tax_rates: Vec<BigDecimal> = vec![];
+    let mut cess_rates: Vec<Option<BigDecimal>> = vec![];
     let mut image_objs: Vec<Value> = vec![];
     let mut mrps: Vec<BigDecimal> = vec![];
     let mut unit_price_with_taxes: Vec<BigDecimal> = vec![];
@@ -1172,6 +1210,7 @@ pub fn create_bulk_seller_product_info_objs<'a>(
             item_codes.push(item.code.as_deref());
             item_names.push(&item.name);
             tax_rates.push(item.tax_rate.clone());
+            cess_rates.push(item.cess_rate.clone());
             mrps.push(item.price.maximum_value.clone());
             unit_price_with_taxes.push(item.price.price_with_tax.clone());
             unit_price_without_taxes.push(item.price.price_without_tax.clone());
@@ -1198,6 +1237,7 @@ pub fn create_bulk_seller_product_info_objs<'a>(
         item_ids,
         item_names,
         tax_rates,
+        cess_rates,
         image_objs,
         mrps,
         unit_price_with_taxes,
@@ -1230,7 +1270,8 @@ pub async fn save_ondc_seller_product_info<'a>(
             mrp,
             currency_code,
             price_slab,
-            country_code
+            country_code,
+            cess_rate
         )
         SELECT *
         FROM UNNEST(
@@ -1246,12 +1287,14 @@ pub async fn save_ondc_seller_product_info<'a>(
             $10::decimal[],
             $11::currency_code_type[],
             $12::jsonb[],
-            $13::country_code[]
+            $13::country_code[],
+            $14::decimal[]
         )
         ON CONFLICT (seller_subscriber_id, country_code, provider_id, item_id) 
         DO UPDATE SET 
             item_name = EXCLUDED.item_name,
             tax_rate = EXCLUDED.tax_rate,
+            cess_rate = EXCLUDED.cess_rate,
             images = EXCLUDED.images,
             unit_price_with_tax = EXCLUDED.unit_price_with_tax,
             unit_price_without_tax = EXCLUDED.unit_price_with_tax,
@@ -1271,6 +1314,7 @@ pub async fn save_ondc_seller_product_info<'a>(
         &product_data.currency_codes[..] as &[&CurrencyType],
         &product_data.price_slabs[..] as &[Option<Value>],
         &product_data.country_codes[..] as &[&CountryCode],
+        &product_data.cess_rates[..] as &[Option<BigDecimal>],
     )
     .execute(pool)
     .await
@@ -1293,7 +1337,7 @@ pub async fn fetch_ondc_seller_product_info(
     let row: Vec<ONDCSellerProductInfo> = sqlx::query_as!(
         ONDCSellerProductInfo,
         r#"SELECT item_name, currency_code  as "currency_code: CurrencyType", item_id, item_code, seller_subscriber_id,
-        price_slab as "price_slab?: Json<Vec<ONDCSellePriceSlab>>", provider_id, tax_rate, 
+        price_slab as "price_slab?: Json<Vec<ONDCSellePriceSlab>>", provider_id, tax_rate, cess_rate,
         unit_price_with_tax,unit_price_without_tax, mrp, images from ondc_seller_product_info where 
         provider_id  = $1 AND seller_subscriber_id=$2 AND item_id::text = ANY($3) AND country_code =$4"#,
         provider_id,
@@ -1580,7 +1624,7 @@ fn get_get_ondc_init_fulfillment(
 #[tracing::instrument(name = "get ondc init message body", skip())]
 fn get_ondc_init_message(
     business_account: &BusinessAccount,
-    init_request: &OrderInitRequest,
+    billing: &OrderInitBilling,
     order: &Commerce,
 ) -> Result<ONDCInitMessage, InitOrderError> {
     let location_ids = order.get_ondc_location_ids();
@@ -1593,7 +1637,7 @@ fn get_ondc_init_message(
                     .map(|e| ONDCLocationId { id: e.to_string() })
                     .collect(),
             },
-            billing: get_ondc_billing_from_init_billing(&init_request.billing),
+            billing: get_ondc_billing_from_init_billing(billing),
             add_ons: None,
             payments: get_ondc_payment_from_order(&order.payments),
             items: get_ondc_items_from_order(&order.items),
@@ -1610,6 +1654,7 @@ pub fn get_ondc_init_payload(
     business_account: &BusinessAccount,
     order: &Commerce,
     init_request: &OrderInitRequest,
+    billing: &OrderInitBilling,
 ) -> Result<ONDCInitRequest, InitOrderError> {
     let context = get_ondc_context_from_order(
         init_request.transaction_id,
@@ -1617,7 +1662,7 @@ pub fn get_ondc_init_payload(
         order,
         ONDCActionType::Init,
     )?;
-    let message = get_ondc_init_message(business_account, init_request, order)?;
+    let message = get_ondc_init_message(business_account, billing, order)?;
     Ok(ONDCInitRequest { context, message })
 }
 
@@ -2295,8 +2340,212 @@ fn get_ondc_update_message_for_payment(
             },
             payments: get_ondc_confirm_request_payment(order, bap_detail),
             items: get_ondc_update_items(order),
+            fulfillments: None,
+        },
+    }
+}
+
+fn validate_item_cancel_request(
+    order: &Commerce,
+    body: &UpdateOrderItemRequest,
+) -> Result<(), OrderUpdateError> {
+    if !matches!(
+        order.record_status,
+        CommerceStatusType::Created | CommerceStatusType::Accepted | CommerceStatusType::InProgress
+    ) {
+        return Err(OrderUpdateError::ValidationError(format!(
+            "Items cannot be cancelled for an order in {} state",
+            order.record_status
+        )));
+    }
+    if body.items.is_empty() {
+        return Err(OrderUpdateError::ValidationError(
+            "At least one item is required for cancellation".to_string(),
+        ));
+    }
+    let item_map: HashMap<&str, &CommerceItem> = order
+        .items
+        .iter()
+        .map(|item| (item.item_id.as_str(), item))
+        .collect();
+    for cancel_item in &body.items {
+        let item = item_map.get(cancel_item.item_id.as_str()).ok_or_else(|| {
+            OrderUpdateError::ValidationError(format!(
+                "{} is not part of the order",
+                cancel_item.item_id
+            ))
+        })?;
+        let remaining_qty = &item.qty
+            - item
+                .cancelled_qty
+                .clone()
+                .unwrap_or_else(|| BigDecimal::from(0));
+        if cancel_item.qty <= 0 || BigDecimal::from(cancel_item.qty) > remaining_qty {
+            return Err(OrderUpdateError::ValidationError(format!(
+                "Cancel quantity for {} should be between 1 and {}",
+                cancel_item.item_id, remaining_qty
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn get_ondc_update_message_for_item(
+    order: &Commerce,
+    body: &UpdateOrderItemRequest,
+    bap_detail: &RegisteredNetworkParticipant,
+) -> Result<ONDCUpdateMessage, OrderUpdateError> {
+    validate_item_cancel_request(order, body)?;
+    let items = body
+        .items
+        .iter()
+        .map(|item| ONDCUpdateItem {
+            id: item.item_id.clone(),
+            quantity: ONDCQuantitySelect {
+                selected: ONDCQuantityCountInt { count: item.qty },
+            },
+        })
+        .collect();
+    Ok(ONDCUpdateMessage {
+        update_target: body.target_type.get_ondc_type(),
+        order: ONDCUpdateOrder {
+            id: order.urn.clone(),
+            state: order.record_status.get_ondc_order_status(),
+            provider: ONDCUpdateProvider {
+                id: order.seller.id.clone(),
+            },
+            payments: get_ondc_confirm_request_payment(order, bap_detail),
+            items,
+            fulfillments: Some(vec![ONDCUpdateFulfillment {
+                r#type: ONDCUpdateFulfillmentType::Cancel,
+                tags: vec![ONDCTag::get_cancel_request_tag(
+                    &body.reason_id,
+                    &bap_detail.subscriber_id,
+                )],
+            }]),
         },
+    })
+}
+
+fn validate_item_return_request(
+    order: &Commerce,
+    body: &UpdateOrderReturnRequest,
+) -> Result<(), OrderUpdateError> {
+    if !matches!(
+        order.record_status,
+        CommerceStatusType::InProgress | CommerceStatusType::Completed
+    ) {
+        return Err(OrderUpdateError::ValidationError(format!(
+            "Items cannot be returned for an order in {} state",
+            order.record_status
+        )));
+    }
+    if body.returns.is_empty() {
+        return Err(OrderUpdateError::ValidationError(
+            "At least one item is required for return".to_string(),
+        ));
     }
+    let item_map: HashMap<&str, &CommerceItem> = order
+        .items
+        .iter()
+        .map(|item| (item.item_id.as_str(), item))
+        .collect();
+    let mut seen_item_ids = HashSet::new();
+    for return_item in &body.returns {
+        if !seen_item_ids.insert(return_item.item_id.as_str()) {
+            return Err(OrderUpdateError::ValidationError(format!(
+                "{} is repeated in the return request",
+                return_item.item_id
+            )));
+        }
+        let item = item_map.get(return_item.item_id.as_str()).ok_or_else(|| {
+            OrderUpdateError::ValidationError(format!(
+                "{} is not part of the order",
+                return_item.item_id
+            ))
+        })?;
+        let is_delivered = order.fulfillments.iter().any(|fulfillment| {
+            item.fulfillment_ids.contains(&fulfillment.fulfillment_id)
+                && fulfillment.fulfillment_status == FulfillmentStatusType::OrderDelivered
+        });
+        if !is_delivered {
+            return Err(OrderUpdateError::ValidationError(format!(
+                "{} is not delivered yet",
+                return_item.item_id
+            )));
+        }
+        let returned_qty = order
+            .returns
+            .iter()
+            .filter(|order_return| {
+                order_return.item_id == return_item.item_id
+                    && order_return.return_status != ReturnStatusType::Rejected
+            })
+            .fold(BigDecimal::from(0), |total, order_return| {
+                total + &order_return.qty
+            });
+        let returnable_qty = &item.qty
+            - item
+                .cancelled_qty
+                .clone()
+                .unwrap_or_else(|| BigDecimal::from(0))
+            - returned_qty;
+        if return_item.qty <= 0 || BigDecimal::from(return_item.qty) > returnable_qty {
+            return Err(OrderUpdateError::ValidationError(format!(
+                "Return quantity for {} should be between 1 and {}",
+                return_item.item_id, returnable_qty
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn get_ondc_update_message_for_return(
+    order: &Commerce,
+    body: &UpdateOrderReturnRequest,
+    bap_detail: &RegisteredNetworkParticipant,
+) -> Result<ONDCUpdateMessage, OrderUpdateError> {
+    validate_item_return_request(order, body)?;
+    let items = body
+        .returns
+        .iter()
+        .map(|return_item| ONDCUpdateItem {
+            id: return_item.item_id.clone(),
+            quantity: ONDCQuantitySelect {
+                selected: ONDCQuantityCountInt {
+                    count: return_item.qty,
+                },
+            },
+        })
+        .collect();
+    let fulfillments = body
+        .returns
+        .iter()
+        .map(|return_item| ONDCUpdateFulfillment {
+            r#type: ONDCUpdateFulfillmentType::Return,
+            tags: vec![ONDCTag::get_return_request_tag(
+                &body.get_return_id(&return_item.item_id),
+                &return_item.item_id,
+                return_item.qty,
+                &return_item.reason_id,
+                return_item.reason_desc.as_deref(),
+                &return_item.images,
+            )],
+        })
+        .collect();
+    Ok(ONDCUpdateMessage {
+        update_target: body.target_type.get_ondc_type(),
+        order: ONDCUpdateOrder {
+            id: order.urn.clone(),
+            state: order.record_status.get_ondc_order_status(),
+            provider: ONDCUpdateProvider {
+                id: order.seller.id.clone(),
+            },
+            payments: get_ondc_confirm_request_payment(order, bap_detail),
+            items,
+            fulfillments: Some(fulfillments),
+        },
+    })
 }
 
 #[tracing::instrument(name = "get ondc update payload", skip())]
@@ -2316,9 +2565,12 @@ pub fn get_ondc_update_payload(
         OrderUpdateRequest::UpdatePayment(body) => {
             get_ondc_update_message_for_payment(order, body, bap_detail)
         }
-        OrderUpdateRequest::UpdateItem(_) => Err(OrderUpdateError::NotImplemented(
-            "Item Updation not implemented".to_string(),
-        ))?,
+        OrderUpdateRequest::UpdateItem(body) => {
+            get_ondc_update_message_for_item(order, body, bap_detail)?
+        }
+        OrderUpdateRequest::UpdateReturn(body) => {
+            get_ondc_update_message_for_return(order, body, bap_detail)?
+        }
         OrderUpdateRequest::UpdateFulfillment(_) => Err(OrderUpdateError::NotImplemented(
             "Fulfillment Updation not implemented".to_string(),
         ))?,
@@ -2336,7 +2588,7 @@ pub async fn process_on_search(
     let product_objs: Option<WSSearchData> =
         get_product_from_on_search_request(&body).map_err(|op| anyhow!("error:{}", op))?;
 
-    if let Some(product_objs) = product_objs {
+    if let Some(mut product_objs) = product_objs {
         if !product_objs.providers.is_empty() {
             let _ = save_ondc_seller_info(pool, &product_objs)
                 .await
@@ -2350,6 +2602,9 @@ pub async fn process_on_search(
             let task2 = save_ondc_seller_location_info(pool, &product_objs);
 
             tokio::try_join!(task1, task2)?;
+            if extracted_search_obj.include_seller_scorecard {
+                attach_seller_scorecards(pool, &mut product_objs).await?;
+            }
             if !extracted_search_obj.update_cache {
                 let ws_params = get_websocket_params_from_search_req(extracted_search_obj);
                 let ws_body = get_search_ws_body(
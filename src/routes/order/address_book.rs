@@ -0,0 +1,79 @@
+use chrono::NaiveTime;
+use uuid::Uuid;
+
+use super::schemas::{
+    BillingProfile, DeliveryLocation, DeliveryLocationOpeningHours, OrderInitBilling,
+    OrderSelectFulfillment, OrderSelectSavedFulfillment,
+};
+
+fn parse_opening_time(time: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(time, "%H:%M")
+        .map_err(|_| format!("{} is not a valid HH:MM time", time))
+}
+
+pub fn validate_opening_hours(
+    opening_hours: &[DeliveryLocationOpeningHours],
+) -> Result<(), String> {
+    for hours in opening_hours {
+        if hours.days.is_empty() {
+            return Err("Opening hours must have at least one day".to_string());
+        }
+        let open_time = parse_opening_time(&hours.open_time)?;
+        let close_time = parse_opening_time(&hours.close_time)?;
+        if open_time >= close_time {
+            return Err(format!(
+                "Open time {} must be before close time {}",
+                hours.open_time, hours.close_time
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Maps the saved fulfillments of a select onto fulfillments with the location of their delivery location.
+pub fn get_saved_select_fulfillments(
+    saved_fulfillments: &[OrderSelectSavedFulfillment],
+    delivery_locations: &[DeliveryLocation],
+) -> Result<Vec<OrderSelectFulfillment>, String> {
+    saved_fulfillments
+        .iter()
+        .map(|fulfillment| {
+            let delivery_location = match fulfillment.delivery_location_id {
+                Some(id) => delivery_locations
+                    .iter()
+                    .find(|location| location.id == id)
+                    .ok_or_else(|| format!("Delivery location {} is not found", id))?,
+                None => delivery_locations
+                    .iter()
+                    .find(|location| location.is_default)
+                    .ok_or_else(|| {
+                        "Business does not have a default delivery location".to_string()
+                    })?,
+            };
+            Ok(OrderSelectFulfillment {
+                id: fulfillment.id.clone(),
+                r#type: fulfillment.r#type.clone(),
+                location: delivery_location.location.clone(),
+                delivery_terms: fulfillment.delivery_terms.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Billing of the given billing profile, the default billing profile when no id is given.
+pub fn get_profile_billing(
+    billing_profile_id: Option<Uuid>,
+    billing_profiles: &[BillingProfile],
+) -> Result<OrderInitBilling, String> {
+    let billing_profile = match billing_profile_id {
+        Some(id) => billing_profiles
+            .iter()
+            .find(|profile| profile.id == id)
+            .ok_or_else(|| format!("Billing profile {} is not found", id))?,
+        None => billing_profiles
+            .iter()
+            .find(|profile| profile.is_default)
+            .ok_or_else(|| "Business does not have a default billing profile".to_string())?,
+    };
+    Ok(billing_profile.billing.clone())
+}
@@ -0,0 +1,274 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use uuid::Uuid;
+
+use super::schemas::{
+    BulkOrderRowError, BulkOrderUploadRequest, OrderItemImportDetail, OrderSelectItem,
+    OrderSelectRequest,
+};
+use crate::constants::{BULK_ORDER_MAX_BYTES, BULK_ORDER_MAX_ROWS, BULK_ORDER_MAX_SELECTS};
+use crate::schemas::CountryCode;
+
+pub const BULK_ORDER_COLUMNS: [&str; 6] = [
+    "bpp_id",
+    "provider_id",
+    "item_id",
+    "qty",
+    "location_id",
+    "delivery_location_id",
+];
+
+/// Optional columns, required for the items of a foreign seller.
+pub const BULK_ORDER_IMPORT_COLUMNS: [&str; 3] = ["hs_code", "country_of_origin", "duty_rate"];
+
+#[derive(Debug, PartialEq)]
+pub struct BulkOrderRow {
+    pub row_no: usize,
+    pub bpp_id: String,
+    pub provider_id: String,
+    pub item_id: String,
+    pub qty: i32,
+    pub location_id: String,
+    pub delivery_location_id: String,
+    pub import_detail: Option<OrderItemImportDetail>,
+}
+
+/// Splits CSV content into records, quoted fields may contain commas, escaped quotes and line breaks.
+/// Parsing stops with an error once there are more than `max_records` records.
+pub fn parse_csv(content: &str, max_records: usize) -> Result<Vec<Vec<String>>, String> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut is_quoted = false;
+    let mut chars = content.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(char) = chars.next() {
+        match (is_quoted, char) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            (true, '"') => is_quoted = false,
+            (true, _) => field.push(char),
+            (false, '"') if field.is_empty() => is_quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') | (false, '\r') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+                if records.len() > max_records {
+                    return Err(format!("CSV cannot have more than {} rows", max_records));
+                }
+            }
+            (false, _) => field.push(char),
+        }
+    }
+    if is_quoted {
+        return Err("CSV has an unterminated quoted field".to_string());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+        if records.len() > max_records {
+            return Err(format!("CSV cannot have more than {} rows", max_records));
+        }
+    }
+    Ok(records)
+}
+
+/// Maps the CSV records onto bulk order rows, the first record holds the column names.
+/// Row numbers are the record numbers in the file, the column row being row 1.
+pub fn get_bulk_order_rows(
+    content: &str,
+    delivery_location_ids: &HashSet<&str>,
+) -> Result<(Vec<BulkOrderRow>, Vec<BulkOrderRowError>), String> {
+    if content.len() > BULK_ORDER_MAX_BYTES {
+        return Err(format!(
+            "CSV cannot be larger than {} bytes",
+            BULK_ORDER_MAX_BYTES
+        ));
+    }
+    let mut records = parse_csv(content, BULK_ORDER_MAX_ROWS + 1)?.into_iter();
+    let columns: Vec<String> = records
+        .next()
+        .ok_or_else(|| "CSV is empty".to_string())?
+        .iter()
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+    let mut column_idx = [0; BULK_ORDER_COLUMNS.len()];
+    for (idx, name) in BULK_ORDER_COLUMNS.iter().enumerate() {
+        column_idx[idx] = columns
+            .iter()
+            .position(|column| column == name)
+            .ok_or_else(|| format!("CSV column {} is missing", name))?;
+    }
+    let import_column_idx: Vec<Option<usize>> = BULK_ORDER_IMPORT_COLUMNS
+        .iter()
+        .map(|name| columns.iter().position(|column| column == name))
+        .collect();
+
+    let mut rows = vec![];
+    let mut errors = vec![];
+    let mut item_keys = HashSet::new();
+    for (idx, record) in records.enumerate() {
+        let row_no = idx + 2;
+        if record.iter().all(|value| value.trim().is_empty()) {
+            continue;
+        }
+        let value = |idx: usize| {
+            record
+                .get(column_idx[idx])
+                .map(|value| value.trim().to_string())
+                .unwrap_or_default()
+        };
+        let (bpp_id, provider_id, item_id, qty, location_id, delivery_location_id) =
+            (value(0), value(1), value(2), value(3), value(4), value(5));
+        let import_value = |idx: usize| {
+            import_column_idx[idx]
+                .and_then(|idx| record.get(idx))
+                .map(|value| value.trim().to_string())
+                .unwrap_or_default()
+        };
+        let (hs_code, country_of_origin, duty_rate) =
+            (import_value(0), import_value(1), import_value(2));
+        let mut push_error = |message: String| errors.push(BulkOrderRowError { row_no, message });
+
+        if let Some((name, _)) = [
+            ("bpp_id", &bpp_id),
+            ("provider_id", &provider_id),
+            ("item_id", &item_id),
+            ("location_id", &location_id),
+        ]
+        .into_iter()
+        .find(|(_, value)| value.is_empty())
+        {
+            push_error(format!("{} is required", name));
+            continue;
+        }
+        let Some(qty) = qty.parse::<i32>().ok().filter(|qty| *qty > 0) else {
+            push_error(format!("qty {} must be a positive whole number", qty));
+            continue;
+        };
+        if !delivery_location_ids.contains(delivery_location_id.as_str()) {
+            push_error(format!(
+                "delivery_location_id {} is not in the delivery locations",
+                delivery_location_id
+            ));
+            continue;
+        }
+        if !item_keys.insert((bpp_id.clone(), provider_id.clone(), item_id.clone())) {
+            push_error(format!(
+                "Item {} is repeated for provider {}",
+                item_id, provider_id
+            ));
+            continue;
+        }
+        let import_detail = if hs_code.is_empty() && country_of_origin.is_empty() {
+            None
+        } else {
+            let Ok(country_code) = serde_json::from_value::<CountryCode>(
+                serde_json::Value::String(country_of_origin.to_uppercase()),
+            ) else {
+                push_error(format!(
+                    "country_of_origin {} is not a valid country code",
+                    country_of_origin
+                ));
+                continue;
+            };
+            let duty_rate = match duty_rate.as_str() {
+                "" => None,
+                rate => match BigDecimal::from_str(rate) {
+                    Ok(rate) => Some(rate),
+                    Err(_) => {
+                        push_error(format!("duty_rate {} must be a number", rate));
+                        continue;
+                    }
+                },
+            };
+            Some(OrderItemImportDetail {
+                hs_code,
+                country_of_origin: country_code,
+                duty_rate,
+            })
+        };
+        rows.push(BulkOrderRow {
+            row_no,
+            bpp_id,
+            provider_id,
+            item_id,
+            qty,
+            location_id,
+            delivery_location_id,
+            import_detail,
+        });
+    }
+    Ok((rows, errors))
+}
+
+/// Groups the rows into one select per BPP and provider, in the order they first appear.
+/// The delivery locations used by the rows of a group become the fulfillments of its select.
+pub fn get_bulk_order_selects(
+    rows: &[BulkOrderRow],
+    request: &BulkOrderUploadRequest,
+) -> Result<Vec<(Vec<usize>, OrderSelectRequest)>, String> {
+    let mut group_idx: HashMap<(&str, &str), usize> = HashMap::new();
+    let mut groups: Vec<Vec<&BulkOrderRow>> = vec![];
+    for row in rows {
+        let idx = *group_idx
+            .entry((row.bpp_id.as_str(), row.provider_id.as_str()))
+            .or_insert_with(|| {
+                groups.push(vec![]);
+                groups.len() - 1
+            });
+        groups[idx].push(row);
+    }
+    // The selects are sent one after another within the upload request.
+    if groups.len() > BULK_ORDER_MAX_SELECTS {
+        return Err(format!(
+            "CSV has {} sellers, an upload can order from at most {} sellers",
+            groups.len(),
+            BULK_ORDER_MAX_SELECTS
+        ));
+    }
+
+    Ok(groups
+        .into_iter()
+        .map(|group| {
+            let delivery_location_ids: HashSet<&str> = group
+                .iter()
+                .map(|row| row.delivery_location_id.as_str())
+                .collect();
+            let select_request = OrderSelectRequest {
+                transaction_id: Uuid::new_v4(),
+                message_id: Uuid::new_v4(),
+                domain_category_code: request.domain_category_code.clone(),
+                payment_types: request.payment_types.clone(),
+                provider_id: group[0].provider_id.clone(),
+                items: group
+                    .iter()
+                    .map(|row| OrderSelectItem {
+                        item_id: row.item_id.clone(),
+                        location_ids: vec![row.location_id.clone()],
+                        qty: row.qty,
+                        buyer_term: None,
+                        fulfillment_ids: vec![row.delivery_location_id.clone()],
+                        import_detail: row.import_detail.clone(),
+                    })
+                    .collect(),
+                ttl: request.ttl.clone(),
+                fulfillments: request
+                    .delivery_locations
+                    .iter()
+                    .filter(|location| delivery_location_ids.contains(location.id.as_str()))
+                    .cloned()
+                    .collect(),
+                order_type: request.order_type.clone(),
+                bpp_id: group[0].bpp_id.clone(),
+                saved_fulfillments: vec![],
+                buyer_terms_template_id: None,
+            };
+            (group.iter().map(|row| row.row_no).collect(), select_request)
+        })
+        .collect())
+}
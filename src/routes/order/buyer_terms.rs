@@ -0,0 +1,144 @@
+use bigdecimal::BigDecimal;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::routes::product::schemas::CategoryDomain;
+
+use super::schemas::{
+    BuyerTermsDeviation, BuyerTermsDeviationType, BuyerTermsTemplate, Commerce, CommerceBuyerTerms,
+    OrderDeliveyTerm, OrderSelectRequest,
+};
+
+/// Template of the given id, else the default template of the domain, else the default template for every domain.
+pub fn get_select_buyer_terms_template<'a>(
+    templates: &'a [BuyerTermsTemplate],
+    template_id: Option<Uuid>,
+    domain_category_code: &CategoryDomain,
+) -> Result<Option<&'a BuyerTermsTemplate>, String> {
+    if let Some(id) = template_id {
+        let template = templates
+            .iter()
+            .find(|template| template.id == id)
+            .ok_or_else(|| format!("Buyer terms template {} is not found", id))?;
+        if template
+            .domain_category_code
+            .as_ref()
+            .is_some_and(|domain| domain != domain_category_code)
+        {
+            return Err(format!(
+                "Buyer terms template {} is not for domain {}",
+                template.name, domain_category_code
+            ));
+        }
+        return Ok(Some(template));
+    }
+    let default_template = |domain: Option<&CategoryDomain>| {
+        templates.iter().find(|template| {
+            template.is_default && template.domain_category_code.as_ref() == domain
+        })
+    };
+    Ok(default_template(Some(domain_category_code)).or_else(|| default_template(None)))
+}
+
+pub fn apply_buyer_terms(
+    select_request: &mut OrderSelectRequest,
+    buyer_terms: &CommerceBuyerTerms,
+) {
+    let item_buyer_terms = buyer_terms.terms.get_item_buyer_terms();
+    for item in select_request.items.iter_mut() {
+        if item.buyer_term.is_none() {
+            item.buyer_term = Some(item_buyer_terms.clone());
+        }
+    }
+    if let Some(inco_terms) = &buyer_terms.terms.inco_terms {
+        for fulfillment in select_request.fulfillments.iter_mut() {
+            if fulfillment.delivery_terms.is_none() {
+                fulfillment.delivery_terms = Some(OrderDeliveyTerm {
+                    inco_terms: inco_terms.clone(),
+                    place_of_delivery: fulfillment.location.city.name.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// Where the seller's response departs from the buyer terms template of the order.
+pub fn get_buyer_terms_deviations(order: &Commerce) -> Vec<BuyerTermsDeviation> {
+    let Some(buyer_terms) = &order.buyer_terms else {
+        return vec![];
+    };
+    let spec = &buyer_terms.terms;
+    let mut deviations = vec![];
+
+    let item_buyer_terms = spec.get_item_buyer_terms();
+    for item in &order.items {
+        let is_matching = item.seller_terms.as_ref().is_some_and(|terms| {
+            terms.item_req == item_buyer_terms.item_req
+                && terms.packaging_req == item_buyer_terms.packaging_req
+        });
+        if !is_matching {
+            deviations.push(BuyerTermsDeviation {
+                r#type: BuyerTermsDeviationType::ItemTerms,
+                ref_id: Some(item.item_id.clone()),
+                expected: format!(
+                    "{} / {}",
+                    item_buyer_terms.item_req, item_buyer_terms.packaging_req
+                ),
+                actual: item
+                    .seller_terms
+                    .as_ref()
+                    .map(|terms| format!("{} / {}", terms.item_req, terms.packaging_req)),
+            });
+        }
+    }
+
+    // The seller confirms the delivery terms in on_init, which also carries the seller terms.
+    if let (Some(inco_terms), Some(_)) = (&spec.inco_terms, &order.bpp_terms) {
+        for fulfillment in &order.fulfillments {
+            let actual = fulfillment.seller_inco_terms.as_ref();
+            if actual != Some(inco_terms) {
+                deviations.push(BuyerTermsDeviation {
+                    r#type: BuyerTermsDeviationType::IncoTerms,
+                    ref_id: Some(fulfillment.fulfillment_id.clone()),
+                    expected: inco_terms.to_string(),
+                    actual: actual.map(|inco_terms| inco_terms.to_string()),
+                });
+            }
+        }
+    }
+
+    if !spec.payment_types.is_empty() {
+        for payment in &order.payments {
+            if !spec.payment_types.contains(&payment.payment_type) {
+                deviations.push(BuyerTermsDeviation {
+                    r#type: BuyerTermsDeviationType::PaymentType,
+                    ref_id: None,
+                    expected: spec
+                        .payment_types
+                        .iter()
+                        .map(|payment_type| payment_type.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    actual: Some(payment.payment_type.to_string()),
+                });
+            }
+        }
+    }
+
+    if let (Some(max_delay_interest), Some(bpp_terms)) =
+        (&spec.max_delay_interest, &order.bpp_terms)
+    {
+        let is_accepted = BigDecimal::from_str(bpp_terms.delay_interest.trim())
+            .is_ok_and(|delay_interest| &delay_interest <= max_delay_interest);
+        if !is_accepted {
+            deviations.push(BuyerTermsDeviation {
+                r#type: BuyerTermsDeviationType::DelayInterest,
+                ref_id: None,
+                expected: max_delay_interest.to_string(),
+                actual: Some(bpp_terms.delay_interest.clone()),
+            });
+        }
+    }
+
+    deviations
+}
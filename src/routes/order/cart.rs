@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use super::schemas::{CartCheckoutRequest, CartItem, OrderSelectItem, OrderSelectRequest};
+
+pub fn get_cart_selects(
+    items: &[CartItem],
+    request: &CartCheckoutRequest,
+) -> Vec<(Vec<CartItem>, OrderSelectRequest)> {
+    let mut group_idx: HashMap<(String, &str, &str), usize> = HashMap::new();
+    let mut groups: Vec<Vec<&CartItem>> = vec![];
+    for item in items {
+        let idx = *group_idx
+            .entry((
+                item.domain_category_code.to_string(),
+                item.bpp_id.as_str(),
+                item.provider_id.as_str(),
+            ))
+            .or_insert_with(|| {
+                groups.push(vec![]);
+                groups.len() - 1
+            });
+        groups[idx].push(item);
+    }
+
+    groups
+        .into_iter()
+        .map(|group| {
+            let select_request = OrderSelectRequest {
+                transaction_id: Uuid::new_v4(),
+                message_id: Uuid::new_v4(),
+                domain_category_code: group[0].domain_category_code.clone(),
+                payment_types: request.payment_types.clone(),
+                provider_id: group[0].provider_id.clone(),
+                items: group
+                    .iter()
+                    .map(|item| OrderSelectItem {
+                        item_id: item.item_id.clone(),
+                        location_ids: vec![item.location_id.clone()],
+                        qty: item.qty,
+                        buyer_term: item.buyer_term.clone(),
+                        fulfillment_ids: vec![request.fulfillment.id.clone()],
+                        import_detail: item.import_detail.clone(),
+                    })
+                    .collect(),
+                ttl: request.ttl.clone(),
+                fulfillments: vec![request.fulfillment.clone()],
+                order_type: request.order_type.clone(),
+                bpp_id: group[0].bpp_id.clone(),
+                saved_fulfillments: vec![],
+                buyer_terms_template_id: None,
+            };
+            (group.into_iter().cloned().collect(), select_request)
+        })
+        .collect()
+}
@@ -0,0 +1,133 @@
+use actix_web::web;
+use anyhow::{anyhow, Context};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::document_client::{get_document_checksum, DocumentClient};
+
+use super::models::CommerceDocumentFileModel;
+use super::schemas::{CommerceDocument, DocumentType};
+use super::utils::{
+    fetch_commerce_status_for_update, fetch_latest_order_document_file, fetch_order_document_files,
+    save_order_document_file,
+};
+
+// Keyed by content so that concurrent callbacks never overwrite a stored version.
+pub fn get_document_storage_key(
+    transaction_id: Uuid,
+    document_type: &DocumentType,
+    checksum: &str,
+) -> String {
+    format!("orders/{}/{}/{}", transaction_id, document_type, checksum)
+}
+
+/// Version the downloaded content is stored as, `None` when it matches the latest stored version.
+pub fn get_document_version(
+    latest_file: Option<&CommerceDocumentFileModel>,
+    checksum: &str,
+) -> Option<i32> {
+    match latest_file {
+        Some(file) if file.checksum == checksum => None,
+        Some(file) => Some(file.version + 1),
+        None => Some(1),
+    }
+}
+
+/// Sellers often share signed links that change on every callback, so a new link is downloaded
+/// but only stored as a new version when its content differs from the latest version.
+#[tracing::instrument(name = "save order document", skip(pool, document_client))]
+async fn save_order_document(
+    pool: &PgPool,
+    document_client: &DocumentClient,
+    transaction_id: Uuid,
+    document: &CommerceDocument,
+    latest_file: Option<&CommerceDocumentFileModel>,
+) -> Result<(), anyhow::Error> {
+    if latest_file.is_some_and(|file| file.source_url == document.url) {
+        return Ok(());
+    }
+    let downloaded = document_client.download(&document.url).await?;
+    let checksum = get_document_checksum(&downloaded.content);
+    if get_document_version(latest_file, &checksum).is_none() {
+        return Ok(());
+    }
+    let storage_key = get_document_storage_key(transaction_id, &document.r#type, &checksum);
+    let size_bytes = downloaded.content.len() as i64;
+    document_client
+        .put(&storage_key, downloaded.content, &downloaded.content_type)
+        .await?;
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    fetch_commerce_status_for_update(&mut transaction, transaction_id)
+        .await?
+        .ok_or_else(|| anyhow!("{} is not found in database", transaction_id))?;
+    let latest_file =
+        fetch_latest_order_document_file(&mut transaction, transaction_id, &document.r#type)
+            .await?;
+    let Some(version) = get_document_version(latest_file.as_ref(), &checksum) else {
+        return Ok(());
+    };
+    save_order_document_file(
+        &mut transaction,
+        transaction_id,
+        &document.r#type,
+        version,
+        &document.url,
+        &storage_key,
+        &checksum,
+        &downloaded.content_type,
+        size_bytes,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store order document")
+}
+
+async fn save_order_documents(
+    pool: &PgPool,
+    document_client: &DocumentClient,
+    transaction_id: Uuid,
+    documents: &[CommerceDocument],
+) -> Result<(), anyhow::Error> {
+    let files = fetch_order_document_files(pool, transaction_id).await?;
+    for document in documents {
+        let latest_file = files
+            .iter()
+            .filter(|file| file.document_type == document.r#type)
+            .max_by_key(|file| file.version);
+        if let Err(e) =
+            save_order_document(pool, document_client, transaction_id, document, latest_file).await
+        {
+            tracing::error!(
+                "Failed to store {} of {}: {:?}",
+                document.r#type,
+                transaction_id,
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Stores the documents shared in a callback in the background, a failed document is retried on the next callback.
+pub fn spawn_order_document_ingestion(
+    pool: web::Data<PgPool>,
+    document_client: web::Data<DocumentClient>,
+    transaction_id: Uuid,
+    documents: Vec<CommerceDocument>,
+) {
+    if documents.is_empty() {
+        return;
+    }
+    tokio::spawn(async move {
+        if let Err(e) =
+            save_order_documents(&pool, &document_client, transaction_id, &documents).await
+        {
+            tracing::error!("Failed to store documents of {}: {:?}", transaction_id, e);
+        }
+    });
+}
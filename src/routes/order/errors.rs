@@ -166,3 +166,37 @@ impl From<OrderUpdateError> for GenericError {
         }
     }
 }
+
+#[derive(thiserror::Error)]
+pub enum QuoteValidationError {
+    #[error("{0}")]
+    InvalidQuote(String),
+}
+
+impl std::fmt::Debug for QuoteValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        error_chain_fmt(self, f)
+    }
+}
+
+#[derive(thiserror::Error)]
+pub enum OrderTransitionError {
+    #[error("{0}")]
+    InvalidTransition(String),
+}
+
+impl std::fmt::Debug for OrderTransitionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        error_chain_fmt(self, f)
+    }
+}
+
+impl From<OrderTransitionError> for GenericError {
+    fn from(err: OrderTransitionError) -> GenericError {
+        match err {
+            OrderTransitionError::InvalidTransition(message) => {
+                GenericError::ValidationError(message)
+            }
+        }
+    }
+}
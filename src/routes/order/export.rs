@@ -0,0 +1,255 @@
+use bigdecimal::{BigDecimal, ToPrimitive};
+use rust_xlsxwriter::Workbook;
+
+use super::schemas::{Commerce, CommerceItem, CommerceTaxSplit, SupplyType};
+
+pub const ORDER_EXPORT_HEADER_COLUMNS: [&str; 23] = [
+    "Order No",
+    "Transaction Id",
+    "Status",
+    "Created On",
+    "Domain",
+    "Seller Id",
+    "Seller Name",
+    "BPP Id",
+    "City",
+    "Currency",
+    "Billing Name",
+    "Buyer GSTIN",
+    "Seller GSTIN",
+    "Supply Type",
+    "Place Of Supply",
+    "CGST",
+    "SGST",
+    "IGST",
+    "Cess",
+    "Refund Total",
+    "Grand Total",
+    "Payment Status",
+    "Fulfillment Status",
+];
+
+pub const ORDER_EXPORT_LINE_COLUMNS: [&str; 19] = [
+    "Order No",
+    "Item Id",
+    "Item Code",
+    "Item Name",
+    "Qty",
+    "Cancelled Qty",
+    "Unit Price",
+    "Discount",
+    "Gross Total",
+    "Tax Rate",
+    "Tax Value",
+    "CGST Rate",
+    "CGST",
+    "SGST Rate",
+    "SGST",
+    "IGST Rate",
+    "IGST",
+    "Cess Rate",
+    "Cess",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderExportCell {
+    Text(String),
+    Amount(BigDecimal),
+}
+
+impl OrderExportCell {
+    fn from_option(value: Option<String>) -> Self {
+        OrderExportCell::Text(value.unwrap_or_default())
+    }
+
+    fn get_csv_field(&self) -> String {
+        match self {
+            OrderExportCell::Text(value) => {
+                // Spreadsheets evaluate text starting with these characters as a formula.
+                let value = if value.starts_with(['=', '+', '-', '@']) {
+                    format!("'{}", value)
+                } else {
+                    value.to_owned()
+                };
+                if value.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", value.replace('"', "\"\""))
+                } else {
+                    value
+                }
+            }
+            OrderExportCell::Amount(value) => value.to_string(),
+        }
+    }
+}
+
+fn join_unique(values: impl Iterator<Item = String>) -> String {
+    let mut unique: Vec<String> = vec![];
+    for value in values {
+        if !unique.contains(&value) {
+            unique.push(value);
+        }
+    }
+    unique.join("|")
+}
+
+pub fn get_order_header_row(order: &Commerce) -> Vec<OrderExportCell> {
+    let tax_summary = order.tax_summary.as_ref();
+    let tax_total = tax_summary.map(|summary| &summary.total);
+    let tax_amount = |value: fn(&CommerceTaxSplit) -> &BigDecimal| {
+        OrderExportCell::Amount(tax_total.map(value).cloned().unwrap_or_default())
+    };
+    let refund_total: BigDecimal = order
+        .items
+        .iter()
+        .filter_map(|item| item.refunded_gross_total.as_ref())
+        .sum();
+    vec![
+        OrderExportCell::Text(order.urn.clone()),
+        OrderExportCell::Text(order.external_urn.to_string()),
+        OrderExportCell::Text(order.record_status.to_string()),
+        OrderExportCell::Text(order.created_on.to_rfc3339()),
+        OrderExportCell::Text(order.domain_category_code.to_string()),
+        OrderExportCell::Text(order.seller.id.clone()),
+        OrderExportCell::from_option(order.seller.name.clone()),
+        OrderExportCell::Text(order.bpp.id.clone()),
+        OrderExportCell::Text(order.city_code.clone()),
+        OrderExportCell::from_option(order.currency_type.as_ref().map(|c| c.to_string())),
+        OrderExportCell::from_option(order.billing.as_ref().map(|b| b.name.clone())),
+        OrderExportCell::from_option(order.billing.as_ref().map(|b| b.tax_id.clone())),
+        OrderExportCell::from_option(tax_summary.and_then(|s| s.seller_gstin.clone())),
+        OrderExportCell::from_option(tax_summary.map(|s| match s.supply_type {
+            SupplyType::IntraState => "intra_state".to_string(),
+            SupplyType::InterState => "inter_state".to_string(),
+        })),
+        OrderExportCell::from_option(tax_summary.and_then(|s| s.place_of_supply.clone())),
+        tax_amount(|split| &split.cgst_value),
+        tax_amount(|split| &split.sgst_value),
+        tax_amount(|split| &split.igst_value),
+        tax_amount(|split| &split.cess_value),
+        OrderExportCell::Amount(refund_total),
+        OrderExportCell::Amount(order.grand_total.clone().unwrap_or_default()),
+        OrderExportCell::Text(join_unique(order.payments.iter().filter_map(|payment| {
+            payment
+                .payment_status
+                .as_ref()
+                .map(|status| status.to_string())
+        }))),
+        OrderExportCell::Text(join_unique(
+            order
+                .fulfillments
+                .iter()
+                .map(|fulfillment| fulfillment.fulfillment_status.to_string()),
+        )),
+    ]
+}
+
+pub fn get_order_line_row(order: &Commerce, item: &CommerceItem) -> Vec<OrderExportCell> {
+    let tax_split = item.tax_split.clone().unwrap_or_default();
+    vec![
+        OrderExportCell::Text(order.urn.clone()),
+        OrderExportCell::Text(item.item_id.clone()),
+        OrderExportCell::from_option(item.item_code.clone()),
+        OrderExportCell::Text(item.item_name.clone()),
+        OrderExportCell::Amount(item.qty.clone()),
+        OrderExportCell::Amount(item.cancelled_qty.clone().unwrap_or_default()),
+        OrderExportCell::Amount(item.unit_price.clone()),
+        OrderExportCell::Amount(item.discount_amount.clone()),
+        OrderExportCell::Amount(item.gross_total.clone()),
+        OrderExportCell::Amount(item.tax_rate.clone()),
+        OrderExportCell::Amount(item.tax_value.clone()),
+        OrderExportCell::Amount(tax_split.cgst_rate),
+        OrderExportCell::Amount(tax_split.cgst_value),
+        OrderExportCell::Amount(tax_split.sgst_rate),
+        OrderExportCell::Amount(tax_split.sgst_value),
+        OrderExportCell::Amount(tax_split.igst_rate),
+        OrderExportCell::Amount(tax_split.igst_value),
+        OrderExportCell::Amount(tax_split.cess_rate),
+        OrderExportCell::Amount(tax_split.cess_value),
+    ]
+}
+
+pub fn get_csv_record(cells: &[OrderExportCell]) -> String {
+    let mut record = cells
+        .iter()
+        .map(OrderExportCell::get_csv_field)
+        .collect::<Vec<String>>()
+        .join(",");
+    record.push_str("\r\n");
+    record
+}
+
+/// CSV has a single table, so every line row repeats the order columns.
+/// Orders without lines are still written with empty line columns.
+pub fn get_order_csv(orders: &[Commerce], include_column_row: bool) -> String {
+    let mut csv = String::new();
+    if include_column_row {
+        let columns: Vec<OrderExportCell> = ORDER_EXPORT_HEADER_COLUMNS
+            .iter()
+            .chain(ORDER_EXPORT_LINE_COLUMNS.iter().skip(1))
+            .map(|column| OrderExportCell::Text(column.to_string()))
+            .collect();
+        csv.push_str(&get_csv_record(&columns));
+    }
+    for order in orders {
+        let header_row = get_order_header_row(order);
+        if order.items.is_empty() {
+            csv.push_str(&get_csv_record(&header_row));
+        }
+        for item in &order.items {
+            let mut row = header_row.clone();
+            row.extend(get_order_line_row(order, item).into_iter().skip(1));
+            csv.push_str(&get_csv_record(&row));
+        }
+    }
+    csv
+}
+
+fn write_xlsx_sheet(
+    workbook: &mut Workbook,
+    name: &str,
+    columns: &[&str],
+    rows: impl Iterator<Item = Vec<OrderExportCell>>,
+) -> Result<(), anyhow::Error> {
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name(name)?;
+    for (col, column) in columns.iter().enumerate() {
+        worksheet.write_string(0, col as u16, *column)?;
+    }
+    for (row_idx, row) in rows.enumerate() {
+        let row_no = row_idx as u32 + 1;
+        for (col, cell) in row.iter().enumerate() {
+            match cell {
+                OrderExportCell::Text(value) => {
+                    worksheet.write_string(row_no, col as u16, value)?;
+                }
+                OrderExportCell::Amount(value) => {
+                    worksheet.write_number(row_no, col as u16, value.to_f64().unwrap_or(0.0))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Workbook with one `Orders` sheet for the order headers and one `Lines` sheet for the items.
+pub fn get_order_xlsx(orders: &[Commerce]) -> Result<Vec<u8>, anyhow::Error> {
+    let mut workbook = Workbook::new();
+    write_xlsx_sheet(
+        &mut workbook,
+        "Orders",
+        &ORDER_EXPORT_HEADER_COLUMNS,
+        orders.iter().map(get_order_header_row),
+    )?;
+    write_xlsx_sheet(
+        &mut workbook,
+        "Lines",
+        &ORDER_EXPORT_LINE_COLUMNS,
+        orders.iter().flat_map(|order| {
+            order
+                .items
+                .iter()
+                .map(move |item| get_order_line_row(order, item))
+        }),
+    )?;
+    Ok(workbook.save_to_buffer()?)
+}
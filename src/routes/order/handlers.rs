@@ -1,35 +1,87 @@
 use std::collections::HashSet;
+use uuid::Uuid;
 
 use crate::chat_client::ChatClient;
-use actix_web::web;
+use actix_web::http::header;
+use actix_web::{web, HttpResponse};
+use futures::stream;
+use futures::StreamExt;
 use utoipa::TupleUnit;
 // use anyhow::Context;
 use crate::configuration::ONDCConfig;
+use crate::constants::{ORDER_EXPORT_SYNC_LIMIT, ORDER_LIST_MAX_LIMIT};
+use crate::document_client::{get_document_checksum, DocumentClient};
 use crate::errors::GenericError;
 use crate::routes::ondc::utils::{
-    fetch_ondc_seller_info, get_lookup_data_from_db, get_ondc_cancel_payload,
-    get_ondc_seller_location_info_mapping, get_ondc_seller_product_info_mapping,
-    get_ondc_status_payload, get_ondc_update_payload,
+    fetch_ondc_seller_info, fetch_ondc_seller_location_info, fetch_ondc_seller_product_info,
+    get_lookup_data_from_db, get_ondc_cancel_payload, get_ondc_seller_location_info_mapping,
+    get_ondc_seller_product_info_mapping, get_ondc_status_payload, get_ondc_update_payload,
 };
 use crate::routes::ondc::utils::{
     get_ondc_confirm_payload, get_ondc_init_payload, get_ondc_select_payload, send_ondc_payload,
 };
 use crate::routes::ondc::{ONDCActionType, ONDCDomain};
 use crate::user_client::{AllowedPermission, BusinessAccount, PermissionType, UserAccount};
-use crate::user_client::{SettingKey, UserClient};
+use crate::user_client::{SettingData, SettingKey, UserClient};
 use crate::utils::{create_authorization_header, get_np_detail};
 
-use crate::schemas::{GenericResponse, ONDCNetworkType, RequestMetaData};
-use sqlx::PgPool;
+use crate::schemas::{CountryCode, GenericResponse, ONDCNetworkType, RequestMetaData};
+use anyhow::Context;
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use sqlx::{PgPool, Postgres, Transaction};
 
+use super::address_book::{
+    get_profile_billing, get_saved_select_fulfillments, validate_opening_hours,
+};
+use super::bulk::{get_bulk_order_rows, get_bulk_order_selects};
+use super::buyer_terms::{apply_buyer_terms, get_select_buyer_terms_template};
+use super::cart::get_cart_selects;
+use super::export::get_order_csv;
+use super::import_trade::{is_import_order, validate_import_init};
 use super::schemas::{
-    Commerce, CommerceList, OrderCancelRequest, OrderConfirmRequest, OrderInitRequest,
-    OrderListFilter, OrderListRequest, OrderReadRequest, OrderSelectRequest, OrderStatusRequest,
-    OrderType, OrderUpdateRequest,
+    AddressBookDeleteRequest, ApprovalStatusType, BillingProfile, BillingProfileSaveRequest,
+    BudgetExceedAction, BulkOrderSelect, BulkOrderUploadRequest, BulkOrderUploadResponse,
+    BuyerBudget, BuyerBudgetReportRequest, BuyerBudgetSaveRequest, BuyerTermsTemplate,
+    BuyerTermsTemplateDeleteRequest, BuyerTermsTemplateSaveRequest, CartCheckout,
+    CartCheckoutFetchRequest, CartCheckoutOrder, CartCheckoutRequest, CartItem, CartItemAddRequest,
+    CartItemRemoveRequest, CartItemUpdateRequest, Commerce, CommerceBuyerTerms,
+    CommerceGoodsReceipt, CommerceList, CommerceListPage, CommerceStatusType, CustomsDocument,
+    CustomsDocumentDeleteRequest, CustomsDocumentSaveRequest, DeliveryLocation,
+    DeliveryLocationSaveRequest, GoodsReceiptCreateRequest, GoodsReceiptMatchReport,
+    OrderAnalytics, OrderAnalyticsRequest, OrderApprovalPolicy, OrderApprovalPolicySaveRequest,
+    OrderApprovalRequest, OrderCancelRequest, OrderCancellationPreview, OrderConfirmRequest,
+    OrderDocumentDownloadRequest, OrderDocumentFile, OrderExportFormat, OrderExportJob,
+    OrderExportJobRequest, OrderExportRequest, OrderExportStatus, OrderInitRequest,
+    OrderListFilter, OrderListRequest, OrderReadRequest, OrderReorderRequest, OrderReorderResponse,
+    OrderSelectRequest, OrderStatusRequest, OrderTimelineEvent, OrderType, OrderUpdateRequest,
+    SellerScorecard, SellerScorecardRequest, StandingOrder, StandingOrderHistory,
+    StandingOrderHistoryRequest, StandingOrderSaveRequest, StandingOrderStatus,
+    StandingOrderStatusRequest,
 };
+use super::standing_order::get_upcoming_run_on;
+use super::state_machine::validate_order_action;
+use super::tax::is_valid_gstin;
 use super::utils::{
-    fetch_order_by_id, get_chat_links, get_order_list, initialize_order_select,
-    save_ondc_order_request, send_rfq_request_chat, validate_select_request,
+    claim_cart_items, create_order_export_job, delete_billing_profile, delete_buyer_terms_template,
+    delete_cart_items, delete_customs_document, delete_delivery_location, delete_order_returns,
+    fail_cart_checkout_order, fetch_approval_policies, fetch_billing_profiles, fetch_buyer_budgets,
+    fetch_buyer_terms_templates, fetch_cart_checkout, fetch_cart_items,
+    fetch_commerce_status_for_update, fetch_delivery_locations, fetch_goods_receipts,
+    fetch_order_by_id, fetch_order_document_file, fetch_order_document_files,
+    fetch_order_export_file, fetch_order_export_job, fetch_order_export_page,
+    fetch_order_list_count, fetch_order_timeline, fetch_seller_scorecards, fetch_standing_order,
+    fetch_standing_order_history, fetch_standing_orders, get_cancellation_preview, get_chat_links,
+    get_exceeded_budgets, get_goods_receipt_match_report, get_order_analytics,
+    get_order_export_file, get_order_list, get_order_list_page, get_received_qty_map,
+    get_reorder_select_request, initialize_order_select, is_order_level_approver,
+    lock_buyer_budgets, request_order_approval, revert_order_request, run_order_export_job,
+    save_approval_policy, save_billing_profile, save_buyer_budget, save_buyer_terms_template,
+    save_cart_checkout, save_cart_item, save_customs_document, save_delivery_location,
+    save_goods_receipt, save_ondc_order_request, save_order_approval, save_order_import_costs,
+    save_order_import_lines, save_order_returns, save_standing_order, send_rfq_request_chat,
+    update_cart_item, update_order_version, update_standing_order_status,
+    validate_bulk_order_catalog, validate_select_request,
 };
 
 #[utoipa::path(
@@ -61,13 +113,72 @@ pub async fn order_select(
     chat_client: web::Data<ChatClient>,
     user_client: web::Data<UserClient>,
 ) -> Result<web::Json<GenericResponse<()>>, GenericError> {
-    let task1 = get_np_detail(
+    let mut body = body;
+    if !body.saved_fulfillments.is_empty() {
+        let delivery_locations = fetch_delivery_locations(&pool, business_account.id)
+            .await
+            .map_err(|e| {
+                GenericError::DatabaseError("Failed to fetch delivery locations".to_string(), e)
+            })?;
+        let fulfillments =
+            get_saved_select_fulfillments(&body.saved_fulfillments, &delivery_locations)
+                .map_err(GenericError::ValidationError)?;
+        body.fulfillments.extend(fulfillments);
+    }
+    send_order_select(
+        &body,
         &pool,
-        &business_account.subscriber_id,
-        &ONDCNetworkType::Bap,
-    );
+        &user_account,
+        &business_account,
+        &meta_data,
+        &chat_client,
+        &user_client,
+    )
+    .await?;
+    Ok(web::Json(GenericResponse::success(
+        "Successfully send select request",
+        Some(()),
+    )))
+}
+
+pub async fn send_order_select(
+    body: &OrderSelectRequest,
+    pool: &PgPool,
+    user_account: &UserAccount,
+    business_account: &BusinessAccount,
+    meta_data: &RequestMetaData,
+    chat_client: &ChatClient,
+    user_client: &UserClient,
+) -> Result<(), GenericError> {
+    let buyer_terms = if body.order_type == OrderType::PurchaseOrder {
+        let templates = fetch_buyer_terms_templates(pool, business_account.id)
+            .await
+            .map_err(|e| {
+                GenericError::DatabaseError("Failed to fetch buyer terms templates".to_string(), e)
+            })?;
+        get_select_buyer_terms_template(
+            &templates,
+            body.buyer_terms_template_id,
+            &body.domain_category_code,
+        )
+        .map_err(GenericError::ValidationError)?
+        .map(|template| CommerceBuyerTerms {
+            template_id: template.id,
+            template_name: template.name.clone(),
+            terms: template.terms.clone(),
+        })
+    } else {
+        None
+    };
+    let mut select_request = body.clone();
+    if let Some(buyer_terms) = &buyer_terms {
+        apply_buyer_terms(&mut select_request, buyer_terms);
+    }
+    let body = &select_request;
+
+    let task1 = get_np_detail(pool, &business_account.subscriber_id, &ONDCNetworkType::Bap);
     let ondc_domain = ONDCDomain::get_ondc_domain(&body.domain_category_code);
-    let task2 = get_lookup_data_from_db(&pool, &body.bpp_id, &ONDCNetworkType::Bpp, &ondc_domain);
+    let task2 = get_lookup_data_from_db(pool, &body.bpp_id, &ONDCNetworkType::Bpp, &ondc_domain);
 
     let location_id_list: Vec<String> = body
         .items
@@ -78,12 +189,12 @@ pub async fn order_select(
         .collect();
 
     let task3 = get_ondc_seller_location_info_mapping(
-        &pool,
+        pool,
         &body.bpp_id,
         &body.provider_id,
         &location_id_list,
     );
-    let task4 = fetch_ondc_seller_info(&pool, &body.bpp_id, &body.provider_id);
+    let task4 = fetch_ondc_seller_info(pool, &body.bpp_id, &body.provider_id);
     let task5 = user_client.fetch_setting(
         user_account.id,
         business_account.id,
@@ -137,15 +248,15 @@ pub async fn order_select(
         }
     };
 
-    validate_select_request(&body, &business_account, &seller_location_info_mapping)
+    validate_select_request(body, business_account, &seller_location_info_mapping)
         .map_err(|e| GenericError::ValidationError(e.to_string()))?;
 
     let chat_data = if body.order_type == OrderType::PurchaseOrder {
         Some(
             get_chat_links(
-                &chat_client,
+                chat_client,
                 body.transaction_id,
-                &business_account,
+                business_account,
                 &seller_info,
             )
             .await?,
@@ -155,9 +266,9 @@ pub async fn order_select(
     };
 
     let ondc_select_payload = get_ondc_select_payload(
-        &user_account,
-        &business_account,
-        &body,
+        user_account,
+        business_account,
+        body,
         &bap_detail,
         &bpp_detail,
         &seller_location_info_mapping,
@@ -170,10 +281,10 @@ pub async fn order_select(
     let header = create_authorization_header(&ondc_select_payload_str, &bap_detail, None, None)?;
     let select_json_obj = serde_json::to_value(&ondc_select_payload)?;
     let task_5 = save_ondc_order_request(
-        &pool,
-        &user_account,
-        &business_account,
-        &meta_data,
+        pool,
+        user_account,
+        business_account,
+        meta_data,
         &select_json_obj,
         body.transaction_id,
         body.message_id,
@@ -185,8 +296,9 @@ pub async fn order_select(
         &header,
         ONDCActionType::Select,
     );
+    let task_7 = save_order_import_lines(pool, body);
     // futures::future::join(task_4, task_5).await.1?;
-    match tokio::try_join!(task_5, task_6) {
+    match tokio::try_join!(task_5, task_6, task_7) {
         Ok(_) => (),
         Err(e) => {
             return Err(GenericError::DatabaseError(e.to_string(), e));
@@ -200,7 +312,7 @@ pub async fn order_select(
             .map(|item| item.item_id.as_str())
             .collect();
         let seller_product_map = match get_ondc_seller_product_info_mapping(
-            &pool,
+            pool,
             &bpp_detail.subscriber_id,
             &body.provider_id,
             &item_code_list,
@@ -214,11 +326,11 @@ pub async fn order_select(
         // .map_err(|e| return Err(GenericError::DatabaseError(e.to_string(), e)))?;
 
         let task_7 = initialize_order_select(
-            &pool,
-            &chat_client,
-            &user_account,
-            &business_account,
-            &body,
+            pool,
+            chat_client,
+            user_account,
+            business_account,
+            body,
             &bap_detail,
             &bpp_detail,
             &seller_location_info_mapping,
@@ -226,10 +338,11 @@ pub async fn order_select(
             &seller_product_map,
             &chat_data,
             &setting_data,
+            buyer_terms.as_ref(),
         );
 
         let task_8 =
-            send_rfq_request_chat(&chat_client, &body, &business_account, &seller_product_map);
+            send_rfq_request_chat(chat_client, body, business_account, &seller_product_map);
 
         match tokio::try_join!(task_7, task_8) {
             Ok(_) => (),
@@ -239,9 +352,160 @@ pub async fn order_select(
         };
     }
 
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/bulk/upload",
+    tag = "Order",
+    description="This API creates the ONDC select requests of a CSV order upload, one per provider and at most 25 providers per upload. Every row is validated against the cached seller catalog and nothing is sent when a row is invalid.",
+    summary= "Order Bulk Upload Request",
+    request_body(content = BulkOrderUploadRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order Bulk Upload Response", body= GenericResponse<BulkOrderUploadResponse>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(name = "order bulk upload", skip(pool, body), fields())]
+pub async fn order_bulk_upload(
+    body: BulkOrderUploadRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+    meta_data: RequestMetaData,
+    chat_client: web::Data<ChatClient>,
+    user_client: web::Data<UserClient>,
+) -> Result<web::Json<GenericResponse<BulkOrderUploadResponse>>, GenericError> {
+    let delivery_location_ids: HashSet<&str> = body
+        .delivery_locations
+        .iter()
+        .map(|location| location.id.as_str())
+        .collect();
+    if delivery_location_ids.len() != body.delivery_locations.len() {
+        return Err(GenericError::ValidationError(
+            "Delivery location ids must be unique".to_string(),
+        ));
+    }
+    let (rows, mut row_errors) = get_bulk_order_rows(&body.csv, &delivery_location_ids)
+        .map_err(GenericError::ValidationError)?;
+    if rows.is_empty() && row_errors.is_empty() {
+        return Err(GenericError::ValidationError(
+            "CSV does not have any order rows".to_string(),
+        ));
+    }
+    let selects = get_bulk_order_selects(&rows, &body).map_err(GenericError::ValidationError)?;
+    let catalog_errors = validate_bulk_order_catalog(&pool, &rows, &body.domain_category_code)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to validate bulk order rows".to_string(), e)
+        })?;
+    row_errors.extend(catalog_errors);
+    if !row_errors.is_empty() {
+        row_errors.sort_by_key(|error| error.row_no);
+        return Ok(web::Json(GenericResponse::success(
+            "Bulk order has invalid rows, no select request is sent",
+            Some(BulkOrderUploadResponse {
+                row_errors,
+                orders: vec![],
+            }),
+        )));
+    }
+
+    let mut orders = vec![];
+    for (row_nos, select_request) in selects {
+        let error = send_order_select(
+            &select_request,
+            &pool,
+            &user_account,
+            &business_account,
+            &meta_data,
+            &chat_client,
+            &user_client,
+        )
+        .await
+        .err()
+        .map(|e| e.to_string());
+        orders.push(BulkOrderSelect {
+            transaction_id: select_request.transaction_id,
+            bpp_id: select_request.bpp_id,
+            provider_id: select_request.provider_id,
+            row_nos,
+            error,
+        });
+    }
     Ok(web::Json(GenericResponse::success(
-        "Successfully send select request",
-        Some(()),
+        "Successfully send bulk order select requests",
+        Some(BulkOrderUploadResponse { row_errors, orders }),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/reorder",
+    tag = "Order",
+    description="This API sends a new ONDC select built from the lines, fulfillments and payment types of a previous order, the seller quotes the current prices and availability.",
+    summary= "Order Reorder Request",
+    request_body(content = OrderReorderRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order Reorder Response", body= GenericResponse<OrderReorderResponse>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(name = "order reorder", skip(pool), fields(transaction_id=body.transaction_id.to_string()))]
+pub async fn order_reorder(
+    body: OrderReorderRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+    meta_data: RequestMetaData,
+    chat_client: web::Data<ChatClient>,
+    user_client: web::Data<UserClient>,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<OrderReorderResponse>>, GenericError> {
+    let order = fetch_order_by_id(&pool, body.transaction_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch order".to_string(), e))?
+        .filter(|order| order.buyer_id == business_account.id)
+        .ok_or_else(|| GenericError::ValidationError("Order not found".to_string()))?;
+    if !allowed_permission.validate_commerce_self(
+        order.created_by,
+        order.buyer_id,
+        PermissionType::CreateOrderSelf,
+    ) {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "You do not have sufficent preveliege to reorder the order".to_owned(),
+        ));
+    }
+    let select_request = get_reorder_select_request(&order, &body.items)?;
+    send_order_select(
+        &select_request,
+        &pool,
+        &user_account,
+        &business_account,
+        &meta_data,
+        &chat_client,
+        &user_client,
+    )
+    .await?;
+    Ok(web::Json(GenericResponse::success(
+        "Successfully send reorder select request",
+        Some(OrderReorderResponse {
+            transaction_id: select_request.transaction_id,
+            billing: order.billing,
+        }),
     )))
 }
 
@@ -257,11 +521,13 @@ pub async fn order_select(
         (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
         (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
 	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=409, description= "Order version conflict", body= GenericResponse<TupleUnit>),
 	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
         (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
 	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
     )
 )]
+#[allow(clippy::too_many_arguments)]
 #[tracing::instrument(name = "order init", skip(pool), fields(transaction_id=body.transaction_id.to_string()))]
 pub async fn order_init(
     body: OrderInitRequest,
@@ -271,20 +537,11 @@ pub async fn order_init(
     business_account: BusinessAccount,
     meta_data: RequestMetaData,
     allowed_permission: AllowedPermission,
+    user_client: web::Data<UserClient>,
 ) -> Result<web::Json<GenericResponse<()>>, GenericError> {
-    let task1 = fetch_order_by_id(&pool, body.transaction_id);
-    let task2 = get_np_detail(
-        &pool,
-        &business_account.subscriber_id,
-        &ONDCNetworkType::Bap,
-    );
-
-    let (order, bap_detail) = match tokio::try_join!(task1, task2) {
-        Ok((order_res, bap_detail_res)) => (order_res, bap_detail_res),
-        Err(e) => {
-            return Err(GenericError::DatabaseError(e.to_string(), e));
-        }
-    };
+    let order = fetch_order_by_id(&pool, body.transaction_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
 
     let order = match order {
         Some(order_detail) => order_detail,
@@ -305,6 +562,79 @@ pub async fn order_init(
         ));
     }
 
+    send_order_init(
+        &body,
+        &order,
+        &pool,
+        &user_account,
+        &business_account,
+        &meta_data,
+        &user_client,
+    )
+    .await?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully send init request",
+        Some(()),
+    )))
+}
+
+pub async fn send_order_init(
+    body: &OrderInitRequest,
+    order: &Commerce,
+    pool: &PgPool,
+    user_account: &UserAccount,
+    business_account: &BusinessAccount,
+    meta_data: &RequestMetaData,
+    user_client: &UserClient,
+) -> Result<(), GenericError> {
+    validate_order_action(&order.record_status, &ONDCActionType::Init)?;
+    let billing = match &body.billing {
+        Some(billing) => billing.clone(),
+        None => {
+            let billing_profiles = fetch_billing_profiles(pool, business_account.id)
+                .await
+                .map_err(|e| {
+                    GenericError::DatabaseError("Failed to fetch billing profiles".to_string(), e)
+                })?;
+            get_profile_billing(body.billing_profile_id, &billing_profiles)
+                .map_err(GenericError::ValidationError)?
+        }
+    };
+    if order.country_code == CountryCode::IND && !is_valid_gstin(&billing.tax_id) {
+        let setting_data = user_client
+            .fetch_setting(
+                user_account.id,
+                business_account.id,
+                vec![SettingKey::GstinValidation],
+            )
+            .await
+            .map_err(GenericError::UnexpectedError)?;
+        // The tax summary flags an invalid buyer GSTIN, rejecting the init is opt-in per business.
+        if setting_data
+            .get_setting(SettingKey::GstinValidation)
+            .is_some_and(|value| value == "true")
+        {
+            return Err(GenericError::ValidationError(format!(
+                "{} is not a valid GSTIN",
+                &billing.tax_id
+            )));
+        }
+        tracing::warn!("{} is not a valid GSTIN", &billing.tax_id);
+    }
+    validate_import_init(order, body.import_costs.as_ref())
+        .map_err(GenericError::ValidationError)?;
+    if let Some(import_costs) = &body.import_costs {
+        save_order_import_costs(pool, body.transaction_id, import_costs, user_account.id)
+            .await
+            .map_err(|e| {
+                GenericError::DatabaseError("Failed to save import costs".to_string(), e)
+            })?;
+    }
+
+    let bap_detail = get_np_detail(pool, &business_account.subscriber_id, &ONDCNetworkType::Bap)
+        .await
+        .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
     let bap_detail = match bap_detail {
         Some(bap_detail) => bap_detail,
         None => {
@@ -315,36 +645,61 @@ pub async fn order_init(
         }
     };
 
-    let ondc_init_payload = get_ondc_init_payload(&user_account, &business_account, &order, &body)?;
+    let ondc_init_payload =
+        get_ondc_init_payload(user_account, business_account, order, body, &billing)?;
 
     let ondc_init_payload_str = serde_json::to_string(&ondc_init_payload).map_err(|e| {
         GenericError::SerializationError(format!("Failed to serialize ONDC init payload: {}", e))
     })?;
     let header = create_authorization_header(&ondc_init_payload_str, &bap_detail, None, None)?;
     let init_json_obj = serde_json::to_value(&ondc_init_payload)?;
-    let task_3 = save_ondc_order_request(
-        &pool,
-        &user_account,
-        &business_account,
-        &meta_data,
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    validate_order_version(
+        &mut transaction,
+        order,
+        body.expected_version,
+        &user_account.id.to_string(),
+    )
+    .await?;
+    save_ondc_order_request(
+        &mut *transaction,
+        user_account,
+        business_account,
+        meta_data,
         &init_json_obj,
         body.transaction_id,
         body.message_id,
         ONDCActionType::Init,
-    );
-    let task_4 = send_ondc_payload(
+    )
+    .await
+    .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store order init request")?;
+    if let Err(e) = send_ondc_payload(
         &order.bpp.uri,
         &ondc_init_payload_str,
         &header,
         ONDCActionType::Init,
-    );
-
-    futures::future::join(task_3, task_4).await.1?;
-
-    Ok(web::Json(GenericResponse::success(
-        "Successfully send init request",
-        Some(()),
-    )))
+    )
+    .await
+    {
+        revert_order_request(
+            pool,
+            body.transaction_id,
+            body.message_id,
+            ONDCActionType::Init,
+            body.expected_version,
+        )
+        .await
+        .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
+        return Err(e.into());
+    }
+    Ok(())
 }
 
 #[utoipa::path(
@@ -359,6 +714,7 @@ pub async fn order_init(
         (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
         (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
 	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=409, description= "Order version conflict", body= GenericResponse<TupleUnit>),
 	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
         (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
 	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
@@ -374,19 +730,9 @@ pub async fn order_confirm(
     meta_data: RequestMetaData,
     allowed_permission: AllowedPermission,
 ) -> Result<web::Json<GenericResponse<()>>, GenericError> {
-    let task1 = fetch_order_by_id(&pool, body.transaction_id);
-    let task2 = get_np_detail(
-        &pool,
-        &business_account.subscriber_id,
-        &ONDCNetworkType::Bap,
-    );
-
-    let (order, bap_detail) = match tokio::try_join!(task1, task2) {
-        Ok((order_res, bap_detail_res)) => (order_res, bap_detail_res),
-        Err(e) => {
-            return Err(GenericError::DatabaseError(e.to_string(), e));
-        }
-    };
+    let order = fetch_order_by_id(&pool, body.transaction_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
 
     let order = match order {
         Some(order_detail) => order_detail,
@@ -408,6 +754,45 @@ pub async fn order_confirm(
         ));
     }
 
+    send_order_confirm(
+        &body,
+        &order,
+        &pool,
+        &user_account,
+        &business_account,
+        &meta_data,
+    )
+    .await?;
+    Ok(web::Json(GenericResponse::success(
+        "Successfully send confirm request",
+        Some(()),
+    )))
+}
+
+pub async fn send_order_confirm(
+    body: &OrderConfirmRequest,
+    order: &Commerce,
+    pool: &PgPool,
+    user_account: &UserAccount,
+    business_account: &BusinessAccount,
+    meta_data: &RequestMetaData,
+) -> Result<(), GenericError> {
+    validate_order_action(&order.record_status, &ONDCActionType::Confirm)?;
+    validate_order_approval(order)?;
+    // The budgets stay locked until the confirm is logged, the logged confirm then counts against the
+    // budgets for the next confirm until the seller responds.
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    lock_buyer_budgets(&mut transaction, order.buyer_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to lock budgets".to_string(), e))?;
+    validate_order_budget(pool, &mut transaction, order).await?;
+
+    let bap_detail = get_np_detail(pool, &business_account.subscriber_id, &ONDCNetworkType::Bap)
+        .await
+        .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
     let bap_detail = match bap_detail {
         Some(bap_detail) => bap_detail,
         None => {
@@ -419,34 +804,56 @@ pub async fn order_confirm(
     };
 
     let ondc_confirm_payload =
-        get_ondc_confirm_payload(&user_account, &business_account, &order, &body, &bap_detail)?;
+        get_ondc_confirm_payload(user_account, business_account, order, body, &bap_detail)?;
 
     let ondc_confirm_payload_str = serde_json::to_string(&ondc_confirm_payload).map_err(|e| {
         GenericError::SerializationError(format!("Failed to serialize ONDC init payload: {}", e))
     })?;
     let header = create_authorization_header(&ondc_confirm_payload_str, &bap_detail, None, None)?;
+    validate_order_version(
+        &mut transaction,
+        order,
+        body.expected_version,
+        &user_account.id.to_string(),
+    )
+    .await?;
     let confirm_json_obj = serde_json::to_value(&ondc_confirm_payload)?;
-    let task_3 = save_ondc_order_request(
-        &pool,
-        &user_account,
-        &business_account,
-        &meta_data,
+    save_ondc_order_request(
+        &mut *transaction,
+        user_account,
+        business_account,
+        meta_data,
         &confirm_json_obj,
         body.transaction_id,
         body.message_id,
         ONDCActionType::Confirm,
-    );
-    let task_4 = send_ondc_payload(
+    )
+    .await
+    .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store order confirm request")?;
+    if let Err(e) = send_ondc_payload(
         &order.bpp.uri,
         &ondc_confirm_payload_str,
         &header,
         ONDCActionType::Confirm,
-    );
-    futures::future::join(task_3, task_4).await.1?;
-    Ok(web::Json(GenericResponse::success(
-        "Successfully send confirm request",
-        Some(()),
-    )))
+    )
+    .await
+    {
+        revert_order_request(
+            pool,
+            body.transaction_id,
+            body.message_id,
+            ONDCActionType::Confirm,
+            body.expected_version,
+        )
+        .await
+        .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
+        return Err(e.into());
+    }
+    Ok(())
 }
 
 #[utoipa::path(
@@ -516,7 +923,7 @@ pub async fn order_status(
     })?;
     let header = create_authorization_header(&ondc_status_payload_str, &bap_detail, None, None)?;
     let task_3 = save_ondc_order_request(
-        &pool,
+        pool.get_ref(),
         &user_account,
         &business_account,
         &meta_data,
@@ -551,16 +958,19 @@ pub async fn order_status(
         (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
         (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
 	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=409, description= "Order version conflict", body= GenericResponse<TupleUnit>),
 	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
         (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
 	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
     )
 )]
+#[allow(clippy::too_many_arguments)]
 #[tracing::instrument(name = "order cancel", skip(pool), fields(transaction_id=body.transaction_id.to_string()))]
 pub async fn order_cancel(
     body: OrderCancelRequest,
     pool: web::Data<PgPool>,
     ondc_obj: web::Data<ONDCConfig>,
+    user_client: web::Data<UserClient>,
     user_account: UserAccount,
     business_account: BusinessAccount,
     meta_data: RequestMetaData,
@@ -572,9 +982,14 @@ pub async fn order_cancel(
         &business_account.subscriber_id,
         &ONDCNetworkType::Bap,
     );
+    let task3 = user_client.fetch_setting(
+        user_account.id,
+        business_account.id,
+        vec![SettingKey::CancellationFeeAcknowledgement],
+    );
 
-    let (order, bap_detail) = match tokio::try_join!(task1, task2) {
-        Ok((order_res, bap_detail_res)) => (order_res, bap_detail_res),
+    let (order, bap_detail, setting_data) = match tokio::try_join!(task1, task2, task3) {
+        Ok((order_res, bap_detail_res, setting_res)) => (order_res, bap_detail_res, setting_res),
         Err(e) => {
             return Err(GenericError::DatabaseError(e.to_string(), e));
         }
@@ -599,6 +1014,13 @@ pub async fn order_cancel(
         ));
     }
 
+    validate_order_action(&order.record_status, &ONDCActionType::Cancel)?;
+    validate_cancellation_fee_acknowledgement(
+        &order,
+        body.acknowledged_fee.as_ref(),
+        &setting_data,
+    )?;
+
     let bap_detail = match bap_detail {
         Some(bap_detail) => bap_detail,
         None => {
@@ -615,8 +1037,19 @@ pub async fn order_cancel(
         GenericError::SerializationError(format!("Failed to serialize ONDC cancel payload: {}", e))
     })?;
     let header = create_authorization_header(&ondc_cancel_payload_str, &bap_detail, None, None)?;
-    let task_3 = save_ondc_order_request(
-        &pool,
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    validate_order_version(
+        &mut transaction,
+        &order,
+        body.expected_version,
+        &user_account.id.to_string(),
+    )
+    .await?;
+    save_ondc_order_request(
+        &mut *transaction,
         &user_account,
         &business_account,
         &meta_data,
@@ -624,19 +1057,37 @@ pub async fn order_cancel(
         body.transaction_id,
         body.message_id,
         ONDCActionType::Cancel,
-    );
-    let task_4 = send_ondc_payload(
+    )
+    .await
+    .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store order cancel request")?;
+    if let Err(e) = send_ondc_payload(
         &order.bpp.uri,
         &ondc_cancel_payload_str,
         &header,
         ONDCActionType::Cancel,
-    );
-    futures::future::join(task_3, task_4).await.1?;
-
-    Ok(web::Json(GenericResponse::success(
-        "Successfully send cancel request",
-        Some(()),
-    )))
+    )
+    .await
+    {
+        revert_order_request(
+            &pool,
+            body.transaction_id,
+            body.message_id,
+            ONDCActionType::Cancel,
+            body.expected_version,
+        )
+        .await
+        .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
+        return Err(e.into());
+    }
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully send cancel request",
+        Some(()),
+    )))
 }
 
 #[utoipa::path(
@@ -651,6 +1102,7 @@ pub async fn order_cancel(
         (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
         (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
 	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=409, description= "Order version conflict", body= GenericResponse<TupleUnit>),
 	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
         (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
 	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
@@ -700,6 +1152,11 @@ pub async fn order_update(
         ));
     }
 
+    // Returns are raised against delivered orders, which are already in a terminal state.
+    if !matches!(body, OrderUpdateRequest::UpdateReturn(_)) {
+        validate_order_action(&order.record_status, &ONDCActionType::Update)?;
+    }
+
     let bap_detail = match bap_detail {
         Some(bap_detail) => bap_detail,
         None => {
@@ -716,8 +1173,19 @@ pub async fn order_update(
         GenericError::SerializationError(format!("Failed to serialize ONDC update payload: {}", e))
     })?;
     let header = create_authorization_header(&ondc_update_payload_str, &bap_detail, None, None)?;
-    let task_3 = save_ondc_order_request(
-        &pool,
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    validate_order_version(
+        &mut transaction,
+        &order,
+        body.expected_version(),
+        &user_account.id.to_string(),
+    )
+    .await?;
+    save_ondc_order_request(
+        &mut *transaction,
         &user_account,
         &business_account,
         &meta_data,
@@ -725,14 +1193,43 @@ pub async fn order_update(
         body.transaction_id(),
         body.message_id(),
         ONDCActionType::Update,
-    );
-    let task_4 = send_ondc_payload(
+    )
+    .await
+    .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store order update request")?;
+    // The return is stored before sending so that a fast on_update finds it.
+    if let OrderUpdateRequest::UpdateReturn(return_request) = &body {
+        save_order_returns(&pool, &order, return_request, user_account.id)
+            .await
+            .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
+    }
+    if let Err(e) = send_ondc_payload(
         &order.bpp.uri,
         &ondc_update_payload_str,
         &header,
         ONDCActionType::Update,
-    );
-    futures::future::join(task_3, task_4).await.1?;
+    )
+    .await
+    {
+        if let OrderUpdateRequest::UpdateReturn(return_request) = &body {
+            delete_order_returns(&pool, &order, return_request)
+                .await
+                .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
+        }
+        revert_order_request(
+            &pool,
+            body.transaction_id(),
+            body.message_id(),
+            ONDCActionType::Update,
+            body.expected_version(),
+        )
+        .await
+        .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
+        return Err(e.into());
+    }
 
     Ok(web::Json(GenericResponse::success(
         "Successfully send update request",
@@ -807,7 +1304,7 @@ pub async fn order_fetch(
     summary= "Order Fetch Request",
     request_body(content = OrderListRequest, description = "Request Body"),
     responses(
-        (status=200, description= "Order Update Response", body= GenericResponse<TupleUnit>),
+        (status=200, description= "Order List Response", body= GenericResponse<Vec<CommerceList>>),
         (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
         (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
 	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
@@ -832,7 +1329,7 @@ pub async fn order_list(
     } else {
         None
     };
-    let list_filter = OrderListFilter::new(body, user_id, allowed_permission.business_id);
+    let list_filter = OrderListFilter::new(body, user_id, allowed_permission.business_id)?;
     let data = get_order_list(&pool, list_filter).await.map_err(|e| {
         tracing::error!("Database error while fetching order list: {:?}", e);
         GenericError::DatabaseError("Failed to fetch order list".to_string(), e)
@@ -842,3 +1339,2304 @@ pub async fn order_list(
         Some(data),
     )))
 }
+
+#[utoipa::path(
+    post,
+    path = "/order/list/page",
+    tag = "Order",
+    description="This API List a page of the orders of given query along with the total count and the cursor of the next page.",
+    summary= "Order List Page Request",
+    request_body(content = OrderListRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order List Page Response", body= GenericResponse<CommerceListPage>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order list page", skip(pool), fields())]
+pub async fn order_list_page(
+    body: OrderListRequest,
+    pool: web::Data<PgPool>,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<CommerceListPage>>, GenericError> {
+    if !(1..=ORDER_LIST_MAX_LIMIT).contains(&body.limit) || body.offset < 0 {
+        return Err(GenericError::ValidationError(format!(
+            "limit must be between 1 and {} and offset cannot be negative",
+            ORDER_LIST_MAX_LIMIT
+        )));
+    }
+    let user_id = if allowed_permission
+        .permission_list
+        .contains(&PermissionType::ListOrderSelf)
+    {
+        Some(allowed_permission.user_id)
+    } else {
+        None
+    };
+    let list_filter = OrderListFilter::new(body, user_id, allowed_permission.business_id)?;
+    let data = get_order_list_page(&pool, list_filter).await.map_err(|e| {
+        tracing::error!("Database error while fetching order list page: {:?}", e);
+        GenericError::DatabaseError("Failed to fetch order list".to_string(), e)
+    })?;
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched orders",
+        Some(data),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/analytics",
+    tag = "Order",
+    description="This API returns the spend, status and quote analytics of the orders matching the query.",
+    summary= "Order Analytics Request",
+    request_body(content = OrderAnalyticsRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order Analytics Response", body= GenericResponse<OrderAnalytics>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order analytics", skip(pool), fields())]
+pub async fn order_analytics(
+    body: OrderAnalyticsRequest,
+    pool: web::Data<PgPool>,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<OrderAnalytics>>, GenericError> {
+    let user_id = if allowed_permission
+        .permission_list
+        .contains(&PermissionType::ListOrderSelf)
+    {
+        Some(allowed_permission.user_id)
+    } else {
+        None
+    };
+    let filter =
+        OrderListFilter::from_analytics_request(body, user_id, allowed_permission.business_id);
+    let data = get_order_analytics(&pool, filter).await.map_err(|e| {
+        GenericError::DatabaseError("Failed to fetch order analytics".to_string(), e)
+    })?;
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched order analytics",
+        Some(data),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/export",
+    tag = "Order",
+    description="This API exports the orders matching the order list filters as CSV or XLSX. Large exports are processed in the background and return the export job instead of the file.",
+    summary= "Order Export Request",
+    request_body(content = OrderExportRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order Export File", body = Vec<u8>, content_type = "application/octet-stream"),
+        (status=202, description= "Order Export Job Response", body= GenericResponse<OrderExportJob>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order export", skip(pool), fields())]
+pub async fn order_export(
+    body: OrderExportRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<HttpResponse, GenericError> {
+    let user_id = if allowed_permission
+        .permission_list
+        .contains(&PermissionType::ListOrderSelf)
+    {
+        Some(allowed_permission.user_id)
+    } else {
+        None
+    };
+    let filter =
+        OrderListFilter::from_export_request(&body, user_id, allowed_permission.business_id);
+    let order_count = fetch_order_list_count(&pool, &filter)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch order count".to_string(), e))?;
+    let created_on = Utc::now();
+    let file_name = body.format.get_file_name(created_on);
+
+    if order_count > ORDER_EXPORT_SYNC_LIMIT {
+        let job_id = Uuid::new_v4();
+        create_order_export_job(
+            &pool,
+            job_id,
+            allowed_permission.business_id,
+            user_account.id,
+            &body.format,
+            order_count,
+            &file_name,
+            created_on,
+        )
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to create order export job".to_string(), e)
+        })?;
+        tokio::spawn(run_order_export_job(
+            pool.get_ref().clone(),
+            job_id,
+            filter,
+            body.format.clone(),
+        ));
+        let job = OrderExportJob {
+            id: job_id,
+            format: body.format,
+            status: OrderExportStatus::Processing,
+            order_count,
+            file_name,
+            error: None,
+            created_on,
+            completed_on: None,
+        };
+        return Ok(HttpResponse::Accepted().json(GenericResponse::success(
+            "Order export is being processed",
+            Some(job),
+        )));
+    }
+
+    let mut response = HttpResponse::Ok();
+    response
+        .content_type(body.format.get_content_type())
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", file_name),
+        ));
+    match body.format {
+        OrderExportFormat::Csv => {
+            let pool = pool.get_ref().clone();
+            let column_row = stream::once(async {
+                Ok::<web::Bytes, GenericError>(web::Bytes::from(get_order_csv(&[], true)))
+            });
+            let rows = stream::try_unfold(filter, move |mut filter| {
+                let pool = pool.clone();
+                async move {
+                    let page = fetch_order_export_page(&pool, &mut filter)
+                        .await
+                        .map_err(|e| {
+                            GenericError::DatabaseError("Failed to export orders".to_string(), e)
+                        })?;
+                    Ok(
+                        page.map(|orders| {
+                            (web::Bytes::from(get_order_csv(&orders, false)), filter)
+                        }),
+                    )
+                }
+            });
+            Ok(response.streaming(column_row.chain(rows)))
+        }
+        OrderExportFormat::Xlsx => {
+            let content = get_order_export_file(&pool, filter, &body.format)
+                .await
+                .map_err(|e| {
+                    GenericError::DatabaseError("Failed to export orders".to_string(), e)
+                })?;
+            Ok(response.body(content))
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/export/status",
+    tag = "Order",
+    description="This API returns the status of a background order export job.",
+    summary= "Order Export Status Request",
+    request_body(content = OrderExportJobRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order Export Job Response", body= GenericResponse<OrderExportJob>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order export status", skip(pool), fields())]
+pub async fn order_export_status(
+    body: OrderExportJobRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<OrderExportJob>>, GenericError> {
+    let user_id = if allowed_permission
+        .permission_list
+        .contains(&PermissionType::ListOrderSelf)
+    {
+        Some(allowed_permission.user_id)
+    } else {
+        None
+    };
+    let job = fetch_order_export_job(&pool, body.job_id, allowed_permission.business_id, user_id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to fetch order export job".to_string(), e)
+        })?
+        .ok_or_else(|| GenericError::DataNotFound("Order export job not found".to_string()))?;
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched order export job",
+        Some(job),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/export/download",
+    tag = "Order",
+    description="This API downloads the file of a completed background order export job.",
+    summary= "Order Export Download Request",
+    request_body(content = OrderExportJobRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order Export File", body = Vec<u8>, content_type = "application/octet-stream"),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order export download", skip(pool), fields())]
+pub async fn order_export_download(
+    body: OrderExportJobRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<HttpResponse, GenericError> {
+    let user_id = if allowed_permission
+        .permission_list
+        .contains(&PermissionType::ListOrderSelf)
+    {
+        Some(allowed_permission.user_id)
+    } else {
+        None
+    };
+    let file = fetch_order_export_file(&pool, body.job_id, allowed_permission.business_id, user_id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to fetch order export file".to_string(), e)
+        })?
+        .ok_or_else(|| GenericError::DataNotFound("Order export job not found".to_string()))?;
+    let content = match (&file.status, file.content) {
+        (OrderExportStatus::Completed, Some(content)) => content,
+        (OrderExportStatus::Failed, _) => {
+            return Err(GenericError::ValidationError(
+                "Order export failed, please request a new export".to_string(),
+            ))
+        }
+        (OrderExportStatus::Expired, _) => {
+            return Err(GenericError::ValidationError(
+                "Order export has expired, please request a new export".to_string(),
+            ))
+        }
+        _ => {
+            return Err(GenericError::ValidationError(
+                "Order export is still being processed".to_string(),
+            ))
+        }
+    };
+    Ok(HttpResponse::Ok()
+        .content_type(file.format.get_content_type())
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", file.file_name),
+        ))
+        .body(content))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/timeline",
+    tag = "Order",
+    description="This API returns the chronological timeline of buyer requests and status changes of an order.",
+    summary= "Order Timeline Request",
+    request_body(content = OrderReadRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order Timeline Response", body= GenericResponse<Vec<OrderTimelineEvent>>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order timeline", skip(pool), fields(transaction_id = %body.transaction_id))]
+pub async fn order_timeline(
+    body: OrderReadRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<Vec<OrderTimelineEvent>>>, GenericError> {
+    let order = fetch_order_by_id(&pool, body.transaction_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch order".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Order not found".to_string()))?;
+
+    if !allowed_permission.validate_commerce_self(
+        order.created_by,
+        order.buyer_id,
+        PermissionType::ReadOrderSelf,
+    ) {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "You do not have sufficent preveliege to read the order".to_owned(),
+        ));
+    }
+    let timeline = fetch_order_timeline(&pool, body.transaction_id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to fetch order timeline".to_string(), e)
+        })?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched order timeline",
+        Some(timeline),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/cancel/preview",
+    tag = "Order",
+    description="This API evaluates the cancellation terms of an order against its current fulfillment state and returns the cancellation fee and expected refund.",
+    summary= "Order Cancellation Preview Request",
+    request_body(content = OrderReadRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order Cancellation Preview Response", body= GenericResponse<OrderCancellationPreview>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order cancel preview", skip(pool), fields(transaction_id = %body.transaction_id))]
+pub async fn order_cancel_preview(
+    body: OrderReadRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<OrderCancellationPreview>>, GenericError> {
+    let order = fetch_order_by_id(&pool, body.transaction_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch order".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Order not found".to_string()))?;
+
+    if !allowed_permission.validate_commerce_self(
+        order.created_by,
+        order.buyer_id,
+        PermissionType::CancelOrderSelf,
+    ) {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "You do not have sufficent preveliege to cancel the order".to_owned(),
+        ));
+    }
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully computed cancellation preview",
+        Some(get_cancellation_preview(&order)),
+    )))
+}
+
+/// An acknowledged fee must match the current one, and is mandatory when the business asks for it.
+fn validate_cancellation_fee_acknowledgement(
+    order: &Commerce,
+    acknowledged_fee: Option<&BigDecimal>,
+    setting_data: &SettingData,
+) -> Result<(), GenericError> {
+    let preview = get_cancellation_preview(order);
+    let is_required = setting_data
+        .get_setting(SettingKey::CancellationFeeAcknowledgement)
+        .is_some_and(|value| value == "true");
+    match acknowledged_fee {
+        Some(fee) if fee.round(2) != preview.cancellation_fee => {
+            Err(GenericError::Conflict(format!(
+                "Cancellation fee is {}, please review it before cancelling",
+                preview.cancellation_fee
+            )))
+        }
+        None if is_required && preview.cancellation_fee > BigDecimal::from(0) => {
+            Err(GenericError::ValidationError(format!(
+                "Cancellation fee of {} must be acknowledged before cancelling",
+                preview.cancellation_fee
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn validate_expected_order_version(
+    transaction_id: Uuid,
+    version: i32,
+    expected_version: i32,
+) -> Result<(), GenericError> {
+    if version != expected_version {
+        return Err(GenericError::Conflict(format!(
+            "Order {} has been modified by someone else, please refresh and retry",
+            transaction_id
+        )));
+    }
+    Ok(())
+}
+
+/// Bumps the order version in the transaction that logs the request, see `revert_order_request`
+/// for a request that could not be sent.
+async fn validate_order_version(
+    transaction: &mut Transaction<'_, Postgres>,
+    order: &Commerce,
+    expected_version: i32,
+    updated_by: &str,
+) -> Result<(), GenericError> {
+    validate_expected_order_version(order.external_urn, order.version, expected_version)?;
+    let is_updated = update_order_version(
+        transaction,
+        order.external_urn,
+        expected_version,
+        updated_by,
+    )
+    .await
+    .map_err(|e| GenericError::DatabaseError("Failed to update order version".to_string(), e))?;
+    if !is_updated {
+        return Err(GenericError::Conflict(format!(
+            "Order {} has been modified by someone else, please refresh and retry",
+            order.external_urn
+        )));
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/grn/create",
+    tag = "Order",
+    description="This API records a goods receipt note with the received, accepted and rejected quantity of the items of a fulfillment.",
+    summary= "Goods Receipt Create Request",
+    request_body(content = GoodsReceiptCreateRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Goods Receipt Create Response", body= GenericResponse<TupleUnit>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=409, description= "Conflict", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order grn create", skip(pool), fields(transaction_id = %body.transaction_id))]
+pub async fn order_grn_create(
+    body: GoodsReceiptCreateRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<()>>, GenericError> {
+    // The order row stays locked until the receipt is stored, so that concurrent receipts of the
+    // same order are validated against each other.
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    fetch_commerce_status_for_update(&mut transaction, body.transaction_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to lock order".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Order not found".to_string()))?;
+    let order = fetch_order_by_id(&pool, body.transaction_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch order".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Order not found".to_string()))?;
+
+    if !allowed_permission.validate_commerce_self(
+        order.created_by,
+        order.buyer_id,
+        PermissionType::UpdateOrderSelf,
+    ) {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "You do not have sufficent preveliege to update the order".to_owned(),
+        ));
+    }
+    let receipts = fetch_goods_receipts(&mut *transaction, order.id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to fetch goods receipts".to_string(), e)
+        })?;
+    validate_goods_receipt(&order, &receipts, &body)?;
+    save_goods_receipt(&mut transaction, &order, &body, user_account.id)
+        .await
+        .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store goods receipt")?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully recorded goods receipt",
+        Some(()),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/grn/list",
+    tag = "Order",
+    description="This API returns the goods receipt notes recorded against an order.",
+    summary= "Goods Receipt List Request",
+    request_body(content = OrderReadRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Goods Receipt List Response", body= GenericResponse<Vec<CommerceGoodsReceipt>>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order grn list", skip(pool), fields(transaction_id = %body.transaction_id))]
+pub async fn order_grn_list(
+    body: OrderReadRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<Vec<CommerceGoodsReceipt>>>, GenericError> {
+    let order = fetch_order_by_id(&pool, body.transaction_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch order".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Order not found".to_string()))?;
+
+    if !allowed_permission.validate_commerce_self(
+        order.created_by,
+        order.buyer_id,
+        PermissionType::ReadOrderSelf,
+    ) {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "You do not have sufficent preveliege to read the order".to_owned(),
+        ));
+    }
+    let receipts = fetch_goods_receipts(pool.get_ref(), order.id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to fetch goods receipts".to_string(), e)
+        })?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched goods receipts",
+        Some(receipts),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/grn/match",
+    tag = "Order",
+    description="This API compares the ordered, seller invoiced and received quantity of every item of an order.",
+    summary= "Goods Receipt Three Way Match Request",
+    request_body(content = OrderReadRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Goods Receipt Three Way Match Response", body= GenericResponse<GoodsReceiptMatchReport>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order grn match", skip(pool), fields(transaction_id = %body.transaction_id))]
+pub async fn order_grn_match(
+    body: OrderReadRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<GoodsReceiptMatchReport>>, GenericError> {
+    let order = fetch_order_by_id(&pool, body.transaction_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch order".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Order not found".to_string()))?;
+
+    if !allowed_permission.validate_commerce_self(
+        order.created_by,
+        order.buyer_id,
+        PermissionType::ReadOrderSelf,
+    ) {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "You do not have sufficent preveliege to read the order".to_owned(),
+        ));
+    }
+    let receipts = fetch_goods_receipts(pool.get_ref(), order.id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to fetch goods receipts".to_string(), e)
+        })?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully computed three way match",
+        Some(get_goods_receipt_match_report(&order, &receipts)),
+    )))
+}
+
+/// Receipts can be partial, but the total received per item cannot exceed what the seller invoiced.
+fn validate_goods_receipt(
+    order: &Commerce,
+    receipts: &[CommerceGoodsReceipt],
+    body: &GoodsReceiptCreateRequest,
+) -> Result<(), GenericError> {
+    if !matches!(
+        order.record_status,
+        CommerceStatusType::InProgress | CommerceStatusType::Completed
+    ) {
+        return Err(GenericError::ValidationError(format!(
+            "Goods cannot be received for an order in {} state",
+            order.record_status
+        )));
+    }
+    if body.items.is_empty() {
+        return Err(GenericError::ValidationError(
+            "At least one item is required for goods receipt".to_string(),
+        ));
+    }
+    if receipts.iter().any(|receipt| receipt.grn_no == body.grn_no) {
+        return Err(GenericError::Conflict(format!(
+            "{} is already recorded for the order",
+            body.grn_no
+        )));
+    }
+    if !order
+        .fulfillments
+        .iter()
+        .any(|fulfillment| fulfillment.fulfillment_id == body.fulfillment_id)
+    {
+        return Err(GenericError::ValidationError(format!(
+            "{} is not a fulfillment of the order",
+            body.fulfillment_id
+        )));
+    }
+    let received_qty_map = get_received_qty_map(receipts);
+    let mut seen_item_ids = HashSet::new();
+    for receipt_item in &body.items {
+        if !seen_item_ids.insert(receipt_item.item_id.as_str()) {
+            return Err(GenericError::ValidationError(format!(
+                "{} is repeated in the goods receipt",
+                receipt_item.item_id
+            )));
+        }
+        let item = order
+            .items
+            .iter()
+            .find(|item| item.item_id == receipt_item.item_id)
+            .ok_or_else(|| {
+                GenericError::ValidationError(format!(
+                    "{} is not part of the order",
+                    receipt_item.item_id
+                ))
+            })?;
+        if !item.fulfillment_ids.contains(&body.fulfillment_id) {
+            return Err(GenericError::ValidationError(format!(
+                "{} is not part of fulfillment {}",
+                receipt_item.item_id, body.fulfillment_id
+            )));
+        }
+        let zero = BigDecimal::from(0);
+        if receipt_item.received_qty <= zero
+            || receipt_item.accepted_qty < zero
+            || receipt_item.rejected_qty < zero
+            || &receipt_item.accepted_qty + &receipt_item.rejected_qty != receipt_item.received_qty
+        {
+            return Err(GenericError::ValidationError(format!(
+                "Accepted and rejected quantity of {} must add up to the received quantity",
+                receipt_item.item_id
+            )));
+        }
+        let already_received = received_qty_map
+            .get(receipt_item.item_id.as_str())
+            .map(|(received_qty, _, _)| received_qty.clone())
+            .unwrap_or_default();
+        let invoiced_qty = item.invoiced_qty.clone().ok_or_else(|| {
+            GenericError::ValidationError(format!(
+                "{} is not invoiced by the seller yet",
+                receipt_item.item_id
+            ))
+        })?;
+        if already_received + &receipt_item.received_qty > invoiced_qty {
+            return Err(GenericError::ValidationError(format!(
+                "Received quantity of {} exceeds the invoiced quantity {}",
+                receipt_item.item_id, invoiced_qty
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Orders matching an approval policy can only be confirmed once every level has approved.
+fn validate_order_approval(order: &Commerce) -> Result<(), GenericError> {
+    match order.approval.as_ref().map(|approval| &approval.status) {
+        Some(ApprovalStatusType::Pending) => Err(GenericError::ValidationError(format!(
+            "Order {} is pending approval",
+            order.urn
+        ))),
+        Some(ApprovalStatusType::Rejected) => Err(GenericError::ValidationError(format!(
+            "Order {} is rejected by the approver",
+            order.urn
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Rejects the confirm when a budget would be exceeded, or starts an approval round for the budget
+/// approval levels the order has not been approved for yet.
+async fn validate_order_budget(
+    pool: &PgPool,
+    transaction: &mut Transaction<'_, Postgres>,
+    order: &Commerce,
+) -> Result<(), GenericError> {
+    let now = Utc::now();
+    let budgets = fetch_buyer_budgets(
+        &mut **transaction,
+        order.buyer_id,
+        Some(now),
+        Some(order.external_urn),
+    )
+    .await
+    .map_err(|e| GenericError::DatabaseError("Failed to fetch budgets".to_string(), e))?;
+    let order_value = order.grand_total.clone().unwrap_or_default();
+    let exceeded_budgets = get_exceeded_budgets(
+        &budgets,
+        &order_value,
+        order.created_by,
+        &order.domain_category_code,
+        now,
+    );
+    if let Some(budget) = exceeded_budgets
+        .iter()
+        .find(|budget| budget.exceed_action == BudgetExceedAction::Reject)
+    {
+        return Err(GenericError::ValidationError(format!(
+            "Order {} exceeds budget {}, remaining amount is {}",
+            order.urn,
+            budget.name,
+            budget.remaining_amount.round(2)
+        )));
+    }
+    let approved_levels = order
+        .approval
+        .as_ref()
+        .filter(|approval| approval.status == ApprovalStatusType::Approved)
+        .map(|approval| approval.levels.clone())
+        .unwrap_or_default();
+    let mut levels: Vec<i32> = exceeded_budgets
+        .iter()
+        .map(|budget| budget.approval_level)
+        .filter(|level| !approved_levels.contains(level))
+        .collect();
+    if levels.is_empty() {
+        return Ok(());
+    }
+    levels.extend(approved_levels);
+    levels.sort();
+    levels.dedup();
+    request_order_approval(pool, order.external_urn, &levels)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to request order approval".to_string(), e)
+        })?;
+    Err(GenericError::ValidationError(format!(
+        "Order {} exceeds the budget and is sent for approval",
+        order.urn
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/approve",
+    tag = "Order",
+    description="This API approves or rejects the pending approval level of an order, only an approver of an applicable policy of that level can act on it.",
+    summary= "Order Approval Request",
+    request_body(content = OrderApprovalRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order Approval Response", body= GenericResponse<TupleUnit>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=409, description= "Conflict", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order approve", skip(pool), fields(transaction_id = %body.transaction_id))]
+pub async fn order_approve(
+    body: OrderApprovalRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<()>>, GenericError> {
+    let order = fetch_order_by_id(&pool, body.transaction_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch order".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Order not found".to_string()))?;
+    if order.buyer_id != business_account.id {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "You do not have sufficent preveliege to approve the order".to_owned(),
+        ));
+    }
+    let approval = order
+        .approval
+        .as_ref()
+        .filter(|approval| approval.status == ApprovalStatusType::Pending)
+        .ok_or_else(|| {
+            GenericError::ValidationError(format!("Order {} is not pending approval", order.urn))
+        })?;
+    if order.created_by == user_account.id
+        || approval.records.iter().any(|record| {
+            record.approval_requested_on == approval.requested_on
+                && record.created_by == user_account.id
+        })
+    {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "An approval level must be approved by a different user".to_owned(),
+        ));
+    }
+    let level = approval.get_pending_level().ok_or_else(|| {
+        GenericError::ValidationError(format!("Order {} has no pending approval", order.urn))
+    })?;
+    let policies = fetch_approval_policies(&pool, business_account.id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to fetch approval policies".to_string(), e)
+        })?;
+    if !is_order_level_approver(
+        &policies,
+        level,
+        &order.grand_total.clone().unwrap_or_default(),
+        &order.domain_category_code,
+        &order.seller.id,
+        user_account.id,
+    ) {
+        return Err(GenericError::InsufficientPrevilegeError(format!(
+            "You are not an approver of approval level {}",
+            level
+        )));
+    }
+    let status = save_order_approval(
+        &pool,
+        order.external_urn,
+        approval,
+        level,
+        &body.action,
+        body.remark.as_deref(),
+        user_account.id,
+    )
+    .await
+    .map_err(|e| GenericError::DatabaseError("Failed to save order approval".to_string(), e))?
+    .ok_or_else(|| {
+        GenericError::Conflict(format!(
+            "Approval of order {} has been updated by someone else, please refresh and retry",
+            order.urn
+        ))
+    })?;
+
+    Ok(web::Json(GenericResponse::success(
+        &format!("Order approval is {}", status),
+        Some(()),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/approval/policy/save",
+    tag = "Order",
+    description="This API creates or updates an order approval policy of the business.",
+    summary= "Order Approval Policy Save Request",
+    request_body(content = OrderApprovalPolicySaveRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order Approval Policy Save Response", body= GenericResponse<TupleUnit>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order approval policy save", skip(pool))]
+pub async fn order_approval_policy_save(
+    body: OrderApprovalPolicySaveRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<()>>, GenericError> {
+    if body.approval_level < 1 || body.min_amount < BigDecimal::from(0) {
+        return Err(GenericError::ValidationError(
+            "Approval level must be at least 1 and minimum amount cannot be negative".to_string(),
+        ));
+    }
+    if body.approver_ids.is_empty() {
+        return Err(GenericError::ValidationError(
+            "Approval policy must have at least one approver".to_string(),
+        ));
+    }
+    let is_saved = save_approval_policy(&pool, business_account.id, &body, user_account.id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to save approval policy".to_string(), e)
+        })?;
+    if !is_saved {
+        return Err(GenericError::ValidationError(
+            "Approval policy not found".to_string(),
+        ));
+    }
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully saved approval policy",
+        Some(()),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/approval/policy/list",
+    tag = "Order",
+    description="This API returns the order approval policies of the business.",
+    summary= "Order Approval Policy List Request",
+    responses(
+        (status=200, description= "Order Approval Policy List Response", body= GenericResponse<Vec<OrderApprovalPolicy>>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order approval policy list", skip(pool))]
+pub async fn order_approval_policy_list(
+    pool: web::Data<PgPool>,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<Vec<OrderApprovalPolicy>>>, GenericError> {
+    let policies = fetch_approval_policies(&pool, business_account.id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to fetch approval policies".to_string(), e)
+        })?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched approval policies",
+        Some(policies),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/budget/save",
+    tag = "Order",
+    description="This API creates or updates a spend budget of the business, a user or a category domain.",
+    summary= "Order Budget Save Request",
+    request_body(content = BuyerBudgetSaveRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order Budget Save Response", body= GenericResponse<TupleUnit>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order budget save", skip(pool))]
+pub async fn order_budget_save(
+    body: BuyerBudgetSaveRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<()>>, GenericError> {
+    if body.amount < BigDecimal::from(0) || body.period_end <= body.period_start {
+        return Err(GenericError::ValidationError(
+            "Budget amount cannot be negative and period end must be after period start"
+                .to_string(),
+        ));
+    }
+    if body.approval_level.is_some_and(|level| level < 1) {
+        return Err(GenericError::ValidationError(
+            "Approval level must be at least 1".to_string(),
+        ));
+    }
+    let is_saved = save_buyer_budget(&pool, business_account.id, &body, user_account.id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to save budget".to_string(), e))?;
+    if !is_saved {
+        return Err(GenericError::ValidationError(
+            "Budget not found".to_string(),
+        ));
+    }
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully saved budget",
+        Some(()),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/budget/report",
+    tag = "Order",
+    description="This API returns the budgets of the business with the consumed and remaining amount.",
+    summary= "Order Budget Report Request",
+    request_body(content = BuyerBudgetReportRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order Budget Report Response", body= GenericResponse<Vec<BuyerBudget>>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order budget report", skip(pool))]
+pub async fn order_budget_report(
+    body: BuyerBudgetReportRequest,
+    pool: web::Data<PgPool>,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<Vec<BuyerBudget>>>, GenericError> {
+    let budgets = fetch_buyer_budgets(pool.get_ref(), business_account.id, body.at, None)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch budgets".to_string(), e))?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched budgets",
+        Some(budgets),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/seller/scorecard",
+    tag = "Order",
+    description="This API returns the performance scorecard of sellers built from their order callbacks.",
+    summary= "Seller Scorecard Request",
+    request_body(content = SellerScorecardRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Seller Scorecard Response", body= GenericResponse<Vec<SellerScorecard>>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "seller scorecard", skip(pool))]
+pub async fn seller_scorecard(
+    body: SellerScorecardRequest,
+    pool: web::Data<PgPool>,
+) -> Result<web::Json<GenericResponse<Vec<SellerScorecard>>>, GenericError> {
+    let scorecards =
+        fetch_seller_scorecards(&pool, body.bpp_id.as_deref(), body.provider_id.as_deref())
+            .await
+            .map_err(|e| {
+                GenericError::DatabaseError("Failed to fetch seller scorecards".to_string(), e)
+            })?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched seller scorecards",
+        Some(scorecards),
+    )))
+}
+
+fn validate_standing_order(body: &StandingOrderSaveRequest) -> Result<(), GenericError> {
+    if body.name.trim().is_empty() {
+        return Err(GenericError::ValidationError(
+            "Standing order name is required".to_string(),
+        ));
+    }
+    if body.items.is_empty() || body.items.iter().any(|item| item.qty <= 0) {
+        return Err(GenericError::ValidationError(
+            "Standing order needs at least one item and every qty must be positive".to_string(),
+        ));
+    }
+    if body.interval_count < 1 {
+        return Err(GenericError::ValidationError(
+            "Interval count must be at least 1".to_string(),
+        ));
+    }
+    if body.next_run_on <= Utc::now() {
+        return Err(GenericError::ValidationError(
+            "Next run must be in the future".to_string(),
+        ));
+    }
+    if body.end_on.is_some_and(|end_on| end_on < body.next_run_on) {
+        return Err(GenericError::ValidationError(
+            "End date cannot be before the next run".to_string(),
+        ));
+    }
+    if let Some(auto_confirm) = &body.auto_confirm {
+        if auto_confirm.reference_total <= BigDecimal::from(0)
+            || auto_confirm.price_tolerance < BigDecimal::from(0)
+        {
+            return Err(GenericError::ValidationError(
+                "Reference total must be positive and price tolerance cannot be negative"
+                    .to_string(),
+            ));
+        }
+        if body
+            .fulfillments
+            .iter()
+            .any(|fulfillment| fulfillment.location.country.code == CountryCode::IND)
+            && !is_valid_gstin(&auto_confirm.billing.tax_id)
+        {
+            return Err(GenericError::ValidationError(format!(
+                "{} is not a valid GSTIN",
+                &auto_confirm.billing.tax_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/standing/save",
+    tag = "Order",
+    description="This API creates or updates a standing order template, a select is sent for every occurrence of its schedule.",
+    summary= "Standing Order Save Request",
+    request_body(content = StandingOrderSaveRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Standing Order Save Response", body= GenericResponse<StandingOrder>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "standing order save", skip(pool))]
+pub async fn standing_order_save(
+    body: StandingOrderSaveRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<StandingOrder>>, GenericError> {
+    validate_standing_order(&body)?;
+    if let Some(id) = body.id {
+        let standing_order = fetch_standing_order(&pool, id)
+            .await
+            .map_err(|e| {
+                GenericError::DatabaseError("Failed to fetch standing order".to_string(), e)
+            })?
+            .filter(|standing_order| standing_order.business_id == business_account.id)
+            .ok_or_else(|| GenericError::ValidationError("Standing order not found".to_string()))?;
+        if !allowed_permission.validate_commerce_self(
+            standing_order.created_by,
+            standing_order.business_id,
+            PermissionType::CreateOrderSelf,
+        ) {
+            return Err(GenericError::InsufficientPrevilegeError(
+                "You do not have sufficent preveliege to update the standing order".to_owned(),
+            ));
+        }
+    }
+    let standing_order = save_standing_order(&pool, business_account.id, &body, user_account.id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to save standing order".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Standing order not found".to_string()))?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully saved standing order",
+        Some(standing_order),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/standing/list",
+    tag = "Order",
+    description="This API returns the standing order templates of the business.",
+    summary= "Standing Order List Request",
+    responses(
+        (status=200, description= "Standing Order List Response", body= GenericResponse<Vec<StandingOrder>>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "standing order list", skip(pool))]
+pub async fn standing_order_list(
+    pool: web::Data<PgPool>,
+    business_account: BusinessAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<Vec<StandingOrder>>>, GenericError> {
+    let user_id = if allowed_permission
+        .permission_list
+        .contains(&PermissionType::ListOrderSelf)
+    {
+        Some(allowed_permission.user_id)
+    } else {
+        None
+    };
+    let standing_orders = fetch_standing_orders(&pool, business_account.id, user_id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to fetch standing orders".to_string(), e)
+        })?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched standing orders",
+        Some(standing_orders),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/standing/status",
+    tag = "Order",
+    description="This API pauses or resumes a standing order template, a resumed template continues from its next occurrence after now.",
+    summary= "Standing Order Status Request",
+    request_body(content = StandingOrderStatusRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Standing Order Status Response", body= GenericResponse<StandingOrder>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "standing order status", skip(pool))]
+pub async fn standing_order_status(
+    body: StandingOrderStatusRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<StandingOrder>>, GenericError> {
+    let standing_order = fetch_standing_order(&pool, body.id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch standing order".to_string(), e))?
+        .filter(|standing_order| standing_order.business_id == business_account.id)
+        .ok_or_else(|| GenericError::ValidationError("Standing order not found".to_string()))?;
+    if !allowed_permission.validate_commerce_self(
+        standing_order.created_by,
+        standing_order.business_id,
+        PermissionType::CreateOrderSelf,
+    ) {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "You do not have sufficent preveliege to update the standing order".to_owned(),
+        ));
+    }
+    if standing_order.status == body.status {
+        return Err(GenericError::ValidationError(
+            "Standing order already has this status".to_string(),
+        ));
+    }
+    let next_run_on = match body.status {
+        StandingOrderStatus::Active => get_upcoming_run_on(&standing_order, Utc::now()),
+        StandingOrderStatus::Paused => standing_order.next_run_on,
+    };
+    let standing_order = update_standing_order_status(
+        &pool,
+        body.id,
+        business_account.id,
+        &body.status,
+        next_run_on,
+        user_account.id,
+    )
+    .await
+    .map_err(|e| GenericError::DatabaseError("Failed to update standing order".to_string(), e))?
+    .ok_or_else(|| GenericError::ValidationError("Standing order not found".to_string()))?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully updated standing order status",
+        Some(standing_order),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/standing/history",
+    tag = "Order",
+    description="This API returns the audit events and the runs of a standing order template.",
+    summary= "Standing Order History Request",
+    request_body(content = StandingOrderHistoryRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Standing Order History Response", body= GenericResponse<StandingOrderHistory>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "standing order history", skip(pool))]
+pub async fn standing_order_history(
+    body: StandingOrderHistoryRequest,
+    pool: web::Data<PgPool>,
+    business_account: BusinessAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<StandingOrderHistory>>, GenericError> {
+    let standing_order = fetch_standing_order(&pool, body.id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch standing order".to_string(), e))?
+        .filter(|standing_order| standing_order.business_id == business_account.id)
+        .ok_or_else(|| GenericError::ValidationError("Standing order not found".to_string()))?;
+    if !allowed_permission.validate_commerce_self(
+        standing_order.created_by,
+        standing_order.business_id,
+        PermissionType::ListOrderSelf,
+    ) {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "You do not have sufficent preveliege to view the standing order".to_owned(),
+        ));
+    }
+    let history = fetch_standing_order_history(&pool, body.id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to fetch standing order history".to_string(), e)
+        })?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched standing order history",
+        Some(history),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/cart/add",
+    tag = "Order",
+    description="This API adds an item of the cached seller catalog to the cart of the user, the quantity is added when the item is already in the cart.",
+    summary= "Cart Item Add Request",
+    request_body(content = CartItemAddRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Cart Item Add Response", body= GenericResponse<CartItem>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "cart item add", skip(pool))]
+pub async fn cart_item_add(
+    body: CartItemAddRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<CartItem>>, GenericError> {
+    if body.qty <= 0 {
+        return Err(GenericError::ValidationError(
+            "Quantity must be greater than zero".to_string(),
+        ));
+    }
+    let ondc_domain = ONDCDomain::get_ondc_domain(&body.domain_category_code);
+    let location_ids = vec![body.location_id.clone()];
+    let task1 = get_lookup_data_from_db(&pool, &body.bpp_id, &ONDCNetworkType::Bpp, &ondc_domain);
+    let task2 =
+        fetch_ondc_seller_location_info(&pool, &body.bpp_id, &body.provider_id, &location_ids);
+    let (bpp_detail, locations) = tokio::try_join!(task1, task2).map_err(|e| {
+        GenericError::DatabaseError("Failed to fetch seller catalog".to_string(), e)
+    })?;
+    if bpp_detail.is_none() {
+        return Err(GenericError::ValidationError(format!(
+            "{} is not a Valid BPP Id",
+            &body.bpp_id
+        )));
+    }
+    let location = locations.first().ok_or_else(|| {
+        GenericError::ValidationError(format!(
+            "Location {} is not available for provider {}",
+            body.location_id, body.provider_id
+        ))
+    })?;
+    let product = fetch_ondc_seller_product_info(
+        &pool,
+        &body.bpp_id,
+        &body.provider_id,
+        &vec![body.item_id.as_str()],
+        &location.country_code,
+    )
+    .await
+    .map_err(|e| GenericError::DatabaseError("Failed to fetch seller catalog".to_string(), e))?
+    .into_iter()
+    .next()
+    .ok_or_else(|| {
+        GenericError::ValidationError(format!(
+            "Item {} is not in the catalog of provider {}",
+            body.item_id, body.provider_id
+        ))
+    })?;
+    let cart_item = save_cart_item(&pool, business_account.id, user_account.id, &body, &product)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to save cart item".to_string(), e))?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully added cart item",
+        Some(cart_item),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/cart/update",
+    tag = "Order",
+    description="This API updates the quantity and buyer terms of an item in the cart of the user.",
+    summary= "Cart Item Update Request",
+    request_body(content = CartItemUpdateRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Cart Item Update Response", body= GenericResponse<CartItem>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "cart item update", skip(pool))]
+pub async fn cart_item_update(
+    body: CartItemUpdateRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<CartItem>>, GenericError> {
+    if body.qty <= 0 {
+        return Err(GenericError::ValidationError(
+            "Quantity must be greater than zero".to_string(),
+        ));
+    }
+    let cart_item = update_cart_item(&pool, business_account.id, user_account.id, &body)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to update cart item".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Cart item not found".to_string()))?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully updated cart item",
+        Some(cart_item),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/cart/remove",
+    tag = "Order",
+    description="This API removes items from the cart of the user.",
+    summary= "Cart Item Remove Request",
+    request_body(content = CartItemRemoveRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Cart Item Remove Response", body= GenericResponse<TupleUnit>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "cart item remove", skip(pool))]
+pub async fn cart_item_remove(
+    body: CartItemRemoveRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<()>>, GenericError> {
+    delete_cart_items(&pool, business_account.id, user_account.id, &body.ids)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to remove cart items".to_string(), e))?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully removed cart items",
+        Some(()),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/cart/fetch",
+    tag = "Order",
+    description="This API returns the items in the cart of the user.",
+    summary= "Cart Fetch Request",
+    responses(
+        (status=200, description= "Cart Fetch Response", body= GenericResponse<Vec<CartItem>>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "cart fetch", skip(pool))]
+pub async fn cart_fetch(
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<Vec<CartItem>>>, GenericError> {
+    let cart_items = fetch_cart_items(&pool, business_account.id, user_account.id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch cart items".to_string(), e))?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched cart items",
+        Some(cart_items),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/cart/checkout",
+    tag = "Order",
+    description="This API sends one ONDC select per provider in the cart of the user and records them as one checkout, the items of a sent select are removed from the cart.",
+    summary= "Cart Checkout Request",
+    request_body(content = CartCheckoutRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Cart Checkout Response", body= GenericResponse<CartCheckout>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(name = "cart checkout", skip(pool))]
+pub async fn cart_checkout(
+    body: CartCheckoutRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+    meta_data: RequestMetaData,
+    chat_client: web::Data<ChatClient>,
+    user_client: web::Data<UserClient>,
+) -> Result<web::Json<GenericResponse<CartCheckout>>, GenericError> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let cart_items = claim_cart_items(&mut transaction, business_account.id, user_account.id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to claim cart items".to_string(), e))?;
+    if cart_items.is_empty() {
+        return Err(GenericError::ValidationError("Cart is empty".to_string()));
+    }
+
+    let selects = get_cart_selects(&cart_items, &body);
+    let checkout = CartCheckout {
+        id: Uuid::new_v4(),
+        created_on: Utc::now(),
+        orders: selects
+            .iter()
+            .map(|(items, select_request)| CartCheckoutOrder {
+                transaction_id: select_request.transaction_id,
+                bpp_id: select_request.bpp_id.clone(),
+                provider_id: select_request.provider_id.clone(),
+                items: items.clone(),
+                error: None,
+                record_status: None,
+            })
+            .collect(),
+    };
+    save_cart_checkout(
+        &mut transaction,
+        business_account.id,
+        user_account.id,
+        &checkout,
+    )
+    .await
+    .map_err(|e| GenericError::DatabaseError("Failed to save cart checkout".to_string(), e))?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store a cart checkout")?;
+
+    for (items, select_request) in selects {
+        if let Err(e) = send_order_select(
+            &select_request,
+            &pool,
+            &user_account,
+            &business_account,
+            &meta_data,
+            &chat_client,
+            &user_client,
+        )
+        .await
+        {
+            fail_cart_checkout_order(
+                &pool,
+                business_account.id,
+                user_account.id,
+                checkout.id,
+                select_request.transaction_id,
+                &e.to_string(),
+                &items,
+            )
+            .await
+            .map_err(|e| {
+                GenericError::DatabaseError("Failed to save cart checkout error".to_string(), e)
+            })?;
+        }
+    }
+    let checkout = fetch_cart_checkout(&pool, checkout.id, business_account.id, user_account.id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch cart checkout".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Cart checkout not found".to_string()))?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully send cart checkout select requests",
+        Some(checkout),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/cart/checkout/fetch",
+    tag = "Order",
+    description="This API returns a cart checkout of the user with the current status of each of its orders.",
+    summary= "Cart Checkout Fetch Request",
+    request_body(content = CartCheckoutFetchRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Cart Checkout Fetch Response", body= GenericResponse<CartCheckout>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "cart checkout fetch", skip(pool))]
+pub async fn cart_checkout_fetch(
+    body: CartCheckoutFetchRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<CartCheckout>>, GenericError> {
+    let checkout = fetch_cart_checkout(&pool, body.id, business_account.id, user_account.id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch cart checkout".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Cart checkout not found".to_string()))?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched cart checkout",
+        Some(checkout),
+    )))
+}
+
+fn validate_delivery_location(body: &DeliveryLocationSaveRequest) -> Result<(), GenericError> {
+    if body.name.trim().is_empty() {
+        return Err(GenericError::ValidationError(
+            "Delivery location name is required".to_string(),
+        ));
+    }
+    validate_opening_hours(&body.opening_hours).map_err(GenericError::ValidationError)
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/delivery/location/save",
+    tag = "Order",
+    description="This API creates or updates a saved delivery location of the business, selects can refer to it by id.",
+    summary= "Delivery Location Save Request",
+    request_body(content = DeliveryLocationSaveRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Delivery Location Save Response", body= GenericResponse<DeliveryLocation>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "delivery location save", skip(pool))]
+pub async fn delivery_location_save(
+    body: DeliveryLocationSaveRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<DeliveryLocation>>, GenericError> {
+    validate_delivery_location(&body)?;
+    let delivery_location =
+        save_delivery_location(&pool, business_account.id, &body, user_account.id)
+            .await
+            .map_err(|e| {
+                GenericError::DatabaseError("Failed to save delivery location".to_string(), e)
+            })?
+            .ok_or_else(|| {
+                GenericError::ValidationError("Delivery location not found".to_string())
+            })?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully saved delivery location",
+        Some(delivery_location),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/delivery/location/list",
+    tag = "Order",
+    description="This API returns the saved delivery locations of the business, the default location first.",
+    summary= "Delivery Location List Request",
+    responses(
+        (status=200, description= "Delivery Location List Response", body= GenericResponse<Vec<DeliveryLocation>>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "delivery location list", skip(pool))]
+pub async fn delivery_location_list(
+    pool: web::Data<PgPool>,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<Vec<DeliveryLocation>>>, GenericError> {
+    let delivery_locations = fetch_delivery_locations(&pool, business_account.id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to fetch delivery locations".to_string(), e)
+        })?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched delivery locations",
+        Some(delivery_locations),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/delivery/location/delete",
+    tag = "Order",
+    description="This API deletes a saved delivery location of the business, orders already sent keep their delivery location.",
+    summary= "Delivery Location Delete Request",
+    request_body(content = AddressBookDeleteRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Delivery Location Delete Response", body= GenericResponse<TupleUnit>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "delivery location delete", skip(pool))]
+pub async fn delivery_location_delete(
+    body: AddressBookDeleteRequest,
+    pool: web::Data<PgPool>,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<()>>, GenericError> {
+    let deleted_count = delete_delivery_location(&pool, body.id, business_account.id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to delete delivery location".to_string(), e)
+        })?;
+    if deleted_count == 0 {
+        return Err(GenericError::ValidationError(
+            "Delivery location not found".to_string(),
+        ));
+    }
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully deleted delivery location",
+        Some(()),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/billing/profile/save",
+    tag = "Order",
+    description="This API creates or updates a billing profile of the business, inits can refer to it by id.",
+    summary= "Billing Profile Save Request",
+    request_body(content = BillingProfileSaveRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Billing Profile Save Response", body= GenericResponse<BillingProfile>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "billing profile save", skip(pool))]
+pub async fn billing_profile_save(
+    body: BillingProfileSaveRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<BillingProfile>>, GenericError> {
+    if body.name.trim().is_empty() || body.billing.tax_id.trim().is_empty() {
+        return Err(GenericError::ValidationError(
+            "Billing profile name and tax id are required".to_string(),
+        ));
+    }
+    if body.country_code == CountryCode::IND && !is_valid_gstin(&body.billing.tax_id) {
+        return Err(GenericError::ValidationError(format!(
+            "{} is not a valid GSTIN",
+            &body.billing.tax_id
+        )));
+    }
+    let billing_profile = save_billing_profile(&pool, business_account.id, &body, user_account.id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to save billing profile".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Billing profile not found".to_string()))?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully saved billing profile",
+        Some(billing_profile),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/billing/profile/list",
+    tag = "Order",
+    description="This API returns the billing profiles of the business, the default profile first.",
+    summary= "Billing Profile List Request",
+    responses(
+        (status=200, description= "Billing Profile List Response", body= GenericResponse<Vec<BillingProfile>>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "billing profile list", skip(pool))]
+pub async fn billing_profile_list(
+    pool: web::Data<PgPool>,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<Vec<BillingProfile>>>, GenericError> {
+    let billing_profiles = fetch_billing_profiles(&pool, business_account.id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to fetch billing profiles".to_string(), e)
+        })?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched billing profiles",
+        Some(billing_profiles),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/billing/profile/delete",
+    tag = "Order",
+    description="This API deletes a billing profile of the business, orders already initialized keep their billing.",
+    summary= "Billing Profile Delete Request",
+    request_body(content = AddressBookDeleteRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Billing Profile Delete Response", body= GenericResponse<TupleUnit>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "billing profile delete", skip(pool))]
+pub async fn billing_profile_delete(
+    body: AddressBookDeleteRequest,
+    pool: web::Data<PgPool>,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<()>>, GenericError> {
+    let deleted_count = delete_billing_profile(&pool, body.id, business_account.id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to delete billing profile".to_string(), e)
+        })?;
+    if deleted_count == 0 {
+        return Err(GenericError::ValidationError(
+            "Billing profile not found".to_string(),
+        ));
+    }
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully deleted billing profile",
+        Some(()),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/buyer/terms/template/save",
+    tag = "Order",
+    description="This API creates or updates a buyer terms template of the business, selects use the default template of their domain unless they refer to a template by id.",
+    summary= "Buyer Terms Template Save Request",
+    request_body(content = BuyerTermsTemplateSaveRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Buyer Terms Template Save Response", body= GenericResponse<BuyerTermsTemplate>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "buyer terms template save", skip(pool))]
+pub async fn buyer_terms_template_save(
+    body: BuyerTermsTemplateSaveRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<BuyerTermsTemplate>>, GenericError> {
+    if body.name.trim().is_empty() {
+        return Err(GenericError::ValidationError(
+            "Buyer terms template name is required".to_string(),
+        ));
+    }
+    if body
+        .terms
+        .max_delay_interest
+        .as_ref()
+        .is_some_and(|interest| interest < &BigDecimal::from(0))
+    {
+        return Err(GenericError::ValidationError(
+            "Max delay interest cannot be negative".to_string(),
+        ));
+    }
+    let template = save_buyer_terms_template(&pool, business_account.id, &body, user_account.id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to save buyer terms template".to_string(), e)
+        })?
+        .ok_or_else(|| {
+            GenericError::ValidationError("Buyer terms template not found".to_string())
+        })?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully saved buyer terms template",
+        Some(template),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/buyer/terms/template/list",
+    tag = "Order",
+    description="This API returns the buyer terms templates of the business, the default templates first.",
+    summary= "Buyer Terms Template List Request",
+    responses(
+        (status=200, description= "Buyer Terms Template List Response", body= GenericResponse<Vec<BuyerTermsTemplate>>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "buyer terms template list", skip(pool))]
+pub async fn buyer_terms_template_list(
+    pool: web::Data<PgPool>,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<Vec<BuyerTermsTemplate>>>, GenericError> {
+    let templates = fetch_buyer_terms_templates(&pool, business_account.id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to fetch buyer terms templates".to_string(), e)
+        })?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched buyer terms templates",
+        Some(templates),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/buyer/terms/template/delete",
+    tag = "Order",
+    description="This API deletes a buyer terms template of the business, orders already selected keep a copy of their terms.",
+    summary= "Buyer Terms Template Delete Request",
+    request_body(content = BuyerTermsTemplateDeleteRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Buyer Terms Template Delete Response", body= GenericResponse<TupleUnit>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "buyer terms template delete", skip(pool))]
+pub async fn buyer_terms_template_delete(
+    body: BuyerTermsTemplateDeleteRequest,
+    pool: web::Data<PgPool>,
+    business_account: BusinessAccount,
+) -> Result<web::Json<GenericResponse<()>>, GenericError> {
+    let deleted_count = delete_buyer_terms_template(&pool, body.id, business_account.id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to delete buyer terms template".to_string(), e)
+        })?;
+    if deleted_count == 0 {
+        return Err(GenericError::ValidationError(
+            "Buyer terms template not found".to_string(),
+        ));
+    }
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully deleted buyer terms template",
+        Some(()),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/customs/document/save",
+    tag = "Order",
+    description="This API records or updates a customs document such as the bill of entry or packing list of an import order.",
+    summary= "Customs Document Save Request",
+    request_body(content = CustomsDocumentSaveRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Customs Document Save Response", body= GenericResponse<CustomsDocument>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "customs document save", skip(pool), fields(transaction_id = %body.transaction_id))]
+pub async fn customs_document_save(
+    body: CustomsDocumentSaveRequest,
+    pool: web::Data<PgPool>,
+    user_account: UserAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<CustomsDocument>>, GenericError> {
+    let order = fetch_order_by_id(&pool, body.transaction_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch order".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Order not found".to_string()))?;
+
+    if !allowed_permission.validate_commerce_self(
+        order.created_by,
+        order.buyer_id,
+        PermissionType::UpdateOrderSelf,
+    ) {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "You do not have sufficent preveliege to update the order".to_owned(),
+        ));
+    }
+    if !is_import_order(&order) {
+        return Err(GenericError::ValidationError(
+            "Customs documents can only be recorded on import orders".to_string(),
+        ));
+    }
+    if body.document_no.trim().is_empty() {
+        return Err(GenericError::ValidationError(
+            "Document number is required".to_string(),
+        ));
+    }
+    let document = save_customs_document(&pool, &body, user_account.id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to save customs document".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Customs document not found".to_string()))?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully saved customs document",
+        Some(document),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/customs/document/delete",
+    tag = "Order",
+    description="This API deletes a customs document of an import order.",
+    summary= "Customs Document Delete Request",
+    request_body(content = CustomsDocumentDeleteRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Customs Document Delete Response", body= GenericResponse<TupleUnit>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "customs document delete", skip(pool), fields(transaction_id = %body.transaction_id))]
+pub async fn customs_document_delete(
+    body: CustomsDocumentDeleteRequest,
+    pool: web::Data<PgPool>,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<()>>, GenericError> {
+    let order = fetch_order_by_id(&pool, body.transaction_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch order".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Order not found".to_string()))?;
+
+    if !allowed_permission.validate_commerce_self(
+        order.created_by,
+        order.buyer_id,
+        PermissionType::UpdateOrderSelf,
+    ) {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "You do not have sufficent preveliege to update the order".to_owned(),
+        ));
+    }
+    let deleted_count = delete_customs_document(&pool, body.id, body.transaction_id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to delete customs document".to_string(), e)
+        })?;
+    if deleted_count == 0 {
+        return Err(GenericError::ValidationError(
+            "Customs document not found".to_string(),
+        ));
+    }
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully deleted customs document",
+        Some(()),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/document/list",
+    tag = "Order",
+    description="This API lists the stored versions of the invoices and proforma invoices shared by the seller.",
+    summary= "Order Document List Request",
+    request_body(content = OrderReadRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order Document List Response", body= GenericResponse<Vec<OrderDocumentFile>>),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order document list", skip(pool), fields(transaction_id = %body.transaction_id))]
+pub async fn order_document_list(
+    body: OrderReadRequest,
+    pool: web::Data<PgPool>,
+    business_account: BusinessAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<web::Json<GenericResponse<Vec<OrderDocumentFile>>>, GenericError> {
+    let order = fetch_order_by_id(&pool, body.transaction_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch order".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Order not found".to_string()))?;
+
+    if order.buyer_id != business_account.id
+        || !allowed_permission.validate_commerce_self(
+            order.created_by,
+            order.buyer_id,
+            PermissionType::ReadOrderSelf,
+        )
+    {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "You do not have sufficent preveliege to read the order".to_owned(),
+        ));
+    }
+    let files = fetch_order_document_files(&pool, body.transaction_id)
+        .await
+        .map_err(|e| {
+            GenericError::DatabaseError("Failed to fetch order documents".to_string(), e)
+        })?;
+
+    Ok(web::Json(GenericResponse::success(
+        "Successfully fetched order documents",
+        Some(files.into_iter().map(|file| file.schema()).collect()),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/order/document/download",
+    tag = "Order",
+    description="This API downloads a stored invoice or proforma invoice of the order, the latest version unless a version is given.",
+    summary= "Order Document Download Request",
+    request_body(content = OrderDocumentDownloadRequest, description = "Request Body"),
+    responses(
+        (status=200, description= "Order Document File", body = Vec<u8>, content_type = "application/octet-stream"),
+        (status=400, description= "Invalid Request body", body= GenericResponse<TupleUnit>),
+        (status=401, description= "Invalid Token", body= GenericResponse<TupleUnit>),
+	    (status=403, description= "Insufficient Previlege", body= GenericResponse<TupleUnit>),
+	    (status=410, description= "Data not found", body= GenericResponse<TupleUnit>),
+        (status=500, description= "Internal Server Error", body= GenericResponse<TupleUnit>),
+	    (status=501, description= "Not Implemented", body= GenericResponse<TupleUnit>),
+    )
+)]
+#[tracing::instrument(name = "order document download", skip(pool, document_client), fields(transaction_id = %body.transaction_id))]
+pub async fn order_document_download(
+    body: OrderDocumentDownloadRequest,
+    pool: web::Data<PgPool>,
+    document_client: web::Data<DocumentClient>,
+    business_account: BusinessAccount,
+    allowed_permission: AllowedPermission,
+) -> Result<HttpResponse, GenericError> {
+    let order = fetch_order_by_id(&pool, body.transaction_id)
+        .await
+        .map_err(|e| GenericError::DatabaseError("Failed to fetch order".to_string(), e))?
+        .ok_or_else(|| GenericError::ValidationError("Order not found".to_string()))?;
+
+    if order.buyer_id != business_account.id
+        || !allowed_permission.validate_commerce_self(
+            order.created_by,
+            order.buyer_id,
+            PermissionType::ReadOrderSelf,
+        )
+    {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "You do not have sufficent preveliege to read the order".to_owned(),
+        ));
+    }
+    let file = fetch_order_document_file(
+        &pool,
+        body.transaction_id,
+        &body.document_type,
+        body.version,
+    )
+    .await
+    .map_err(|e| GenericError::DatabaseError("Failed to fetch order document".to_string(), e))?
+    .ok_or_else(|| GenericError::DataNotFound("Order document not found".to_string()))?;
+    let content = document_client.get(&file.storage_key).await?;
+    if get_document_checksum(&content) != file.checksum {
+        tracing::error!("Checksum mismatch for stored document {}", file.storage_key);
+        return Err(GenericError::UnexpectedCustomError(
+            "Stored document is corrupted".to_string(),
+        ));
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type(file.content_type)
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"{}_{}_v{}\"",
+                body.transaction_id, file.document_type, file.version
+            ),
+        ))
+        .body(content))
+}
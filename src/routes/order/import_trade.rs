@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+use bigdecimal::BigDecimal;
+
+use crate::schemas::CountryCode;
+
+use super::schemas::{
+    Commerce, CommerceItem, CommerceStatusType, CustomsDocumentType, IncoTermType,
+    LandedCostEstimate, LandedCostLine, OrderImportCosts, OrderSelectRequest, TradeType,
+};
+
+pub fn is_valid_hs_code(hs_code: &str) -> bool {
+    matches!(hs_code.len(), 6 | 8 | 10) && hs_code.chars().all(|char| char.is_ascii_digit())
+}
+
+pub fn validate_import_select(
+    body: &OrderSelectRequest,
+    seller_country_code: &CountryCode,
+) -> Result<(), String> {
+    let mut import_inco_terms: HashMap<&str, &IncoTermType> = HashMap::new();
+    for fulfillment in &body.fulfillments {
+        if &fulfillment.location.country.code == seller_country_code {
+            continue;
+        }
+        let delivery_terms = fulfillment.delivery_terms.as_ref().ok_or_else(|| {
+            format!(
+                "Incoterms are required for import fulfillment {}",
+                fulfillment.id
+            )
+        })?;
+        if delivery_terms.place_of_delivery.trim().is_empty() {
+            return Err(format!(
+                "Place of delivery is required for import fulfillment {}",
+                fulfillment.id
+            ));
+        }
+        import_inco_terms.insert(fulfillment.id.as_str(), &delivery_terms.inco_terms);
+    }
+
+    for item in &body.items {
+        let inco_terms: Vec<&IncoTermType> = item
+            .fulfillment_ids
+            .iter()
+            .filter_map(|id| import_inco_terms.get(id.as_str()).copied())
+            .collect();
+        if inco_terms.is_empty() {
+            continue;
+        }
+        let import_detail = item.import_detail.as_ref().ok_or_else(|| {
+            format!(
+                "HS code and country of origin are required for imported item {}",
+                item.item_id
+            )
+        })?;
+        if !is_valid_hs_code(&import_detail.hs_code) {
+            return Err(format!(
+                "{} is not a valid HS code for item {}",
+                import_detail.hs_code, item.item_id
+            ));
+        }
+        match &import_detail.duty_rate {
+            Some(duty_rate)
+                if duty_rate < &BigDecimal::from(0) || duty_rate > &BigDecimal::from(100) =>
+            {
+                return Err(format!(
+                    "Duty rate of item {} must be between 0 and 100",
+                    item.item_id
+                ));
+            }
+            None if inco_terms
+                .iter()
+                .any(|inco_terms| **inco_terms != IncoTermType::Ddp) =>
+            {
+                return Err(format!(
+                    "Duty rate is required for imported item {} unless the incoterms are DDP",
+                    item.item_id
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+pub fn get_order_inco_terms(order: &Commerce) -> Option<&IncoTermType> {
+    order
+        .fulfillments
+        .iter()
+        .find(|fulfillment| fulfillment.trade_type == Some(TradeType::Import))
+        .and_then(|fulfillment| fulfillment.delivery_term.as_ref())
+        .map(|delivery_term| &delivery_term.inco_terms)
+}
+
+pub fn is_import_order(order: &Commerce) -> bool {
+    order
+        .fulfillments
+        .iter()
+        .any(|fulfillment| fulfillment.trade_type == Some(TradeType::Import))
+}
+
+pub fn validate_import_init(
+    order: &Commerce,
+    import_costs: Option<&OrderImportCosts>,
+) -> Result<(), String> {
+    let Some(inco_terms) = get_order_inco_terms(order) else {
+        return Ok(());
+    };
+    if let Some(import_costs) = import_costs {
+        if import_costs.freight_amount < BigDecimal::from(0)
+            || import_costs.insurance_amount < BigDecimal::from(0)
+        {
+            return Err("Freight and insurance cannot be negative".to_string());
+        }
+    } else if matches!(inco_terms, IncoTermType::Exw | IncoTermType::Fob) {
+        return Err(format!(
+            "Freight and insurance are required for {} imports",
+            inco_terms
+        ));
+    }
+    Ok(())
+}
+
+pub fn get_landed_cost_lines(
+    items: &[CommerceItem],
+    inco_terms: &IncoTermType,
+    freight_amount: &BigDecimal,
+    insurance_amount: &BigDecimal,
+) -> Vec<LandedCostLine> {
+    let import_items: Vec<&CommerceItem> = items
+        .iter()
+        .filter(|item| item.import_detail.is_some())
+        .collect();
+    let goods_total: BigDecimal = import_items
+        .iter()
+        .map(|item| &item.gross_total - &item.tax_value)
+        .sum();
+    let carriage_total = freight_amount + insurance_amount;
+    let mut carriage_left = carriage_total.clone();
+    let mut lines = vec![];
+    for (idx, item) in import_items.iter().enumerate() {
+        let Some(import_detail) = &item.import_detail else {
+            continue;
+        };
+        let goods_value = &item.gross_total - &item.tax_value;
+        let carriage_share = if idx + 1 == import_items.len() {
+            carriage_left.clone()
+        } else if goods_total == BigDecimal::from(0) {
+            BigDecimal::from(0)
+        } else {
+            (&carriage_total * &goods_value / &goods_total).round(2)
+        };
+        carriage_left -= &carriage_share;
+        let assessable_value = &goods_value + carriage_share;
+        let duty_rate = if inco_terms == &IncoTermType::Ddp {
+            BigDecimal::from(0)
+        } else {
+            import_detail.duty_rate.clone().unwrap_or_default()
+        };
+        let duty_value = (&assessable_value * &duty_rate / BigDecimal::from(100)).round(2);
+        lines.push(LandedCostLine {
+            item_id: item.item_id.clone(),
+            hs_code: import_detail.hs_code.clone(),
+            country_of_origin: import_detail.country_of_origin.clone(),
+            goods_value,
+            assessable_value,
+            duty_rate,
+            duty_value,
+        });
+    }
+    lines
+}
+
+pub fn get_landed_cost_estimate(order: &Commerce) -> Option<LandedCostEstimate> {
+    let inco_terms = get_order_inco_terms(order)?;
+    let (freight_amount, insurance_amount) = match (inco_terms, &order.import_costs) {
+        (IncoTermType::Exw | IncoTermType::Fob, Some(import_costs)) => (
+            import_costs.freight_amount.clone(),
+            import_costs.insurance_amount.clone(),
+        ),
+        _ => (BigDecimal::from(0), BigDecimal::from(0)),
+    };
+    let lines = get_landed_cost_lines(&order.items, inco_terms, &freight_amount, &insurance_amount);
+    let goods_value: BigDecimal = lines.iter().map(|line| &line.goods_value).sum();
+    let duty_value: BigDecimal = lines.iter().map(|line| &line.duty_value).sum();
+    let order_total = order.grand_total.clone().unwrap_or_else(|| {
+        order
+            .items
+            .iter()
+            .map(|item| &item.gross_total)
+            .sum::<BigDecimal>()
+    });
+    Some(LandedCostEstimate {
+        inco_terms: inco_terms.clone(),
+        landed_cost: order_total + &freight_amount + &insurance_amount + &duty_value,
+        goods_value,
+        freight_amount,
+        insurance_amount,
+        duty_value,
+        lines,
+    })
+}
+
+pub fn get_missing_customs_documents(order: &Commerce) -> Vec<CustomsDocumentType> {
+    if !CommerceStatusType::committed_list().contains(&order.record_status) {
+        return vec![];
+    }
+    let Some(inco_terms) = get_order_inco_terms(order) else {
+        return vec![];
+    };
+    let mut required_documents = vec![
+        CustomsDocumentType::CommercialInvoice,
+        CustomsDocumentType::PackingList,
+        CustomsDocumentType::BillOfEntry,
+    ];
+    if inco_terms == &IncoTermType::Cif {
+        required_documents.push(CustomsDocumentType::InsuranceCertificate);
+    }
+    required_documents
+        .into_iter()
+        .filter(|document_type| {
+            !order
+                .customs_documents
+                .iter()
+                .any(|document| &document.document_type == document_type)
+        })
+        .collect()
+}
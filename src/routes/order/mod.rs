@@ -1,7 +1,20 @@
+pub mod address_book;
+pub mod bulk;
+pub mod buyer_terms;
+pub mod cart;
+pub mod documents;
 pub(crate) mod errors;
+pub mod export;
 pub mod handlers;
+pub mod import_trade;
+pub mod quote_validation;
 mod routes;
 pub(crate) mod schemas;
+pub mod scorecard;
+pub mod standing_order;
+pub mod state_machine;
+pub mod tax;
+mod tests;
 pub mod utils;
 pub use routes::order_route;
 pub mod models;
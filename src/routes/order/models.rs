@@ -4,17 +4,28 @@ use crate::routes::product::schemas::{CategoryDomain, FulfillmentType, PaymentTy
 use crate::schemas::DataSource;
 use crate::schemas::{CountryCode, CurrencyType, FeeType};
 use bigdecimal::BigDecimal;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sqlx::FromRow;
 use uuid::Uuid;
 
 use super::schemas::{
-    CancellationFeeType, CommerceList, CommerceSeller, CommerceStatusType, DocumentType,
-    FulfillmentCategoryType, FulfillmentStatusType, IncoTermType, MinimalCommerceData, OrderType,
+    ApprovalStatusType, BillingProfile, BudgetExceedAction, BuyerBudget, BuyerTerms,
+    BuyerTermsSpec, BuyerTermsTemplate, CancellationFeeType, CartCheckoutOrder, CartItem,
+    CommerceApprovalRecord, CommerceBuyerTerms, CommerceList, CommerceSeller, CommerceStatusType,
+    CustomsDocument, CustomsDocumentType, DeliveryLocation, DeliveryLocationOpeningHours,
+    DocumentType, FulfillmentCategoryType, FulfillmentStatusType, IncoTermType,
+    MinimalCommerceData, OrderApprovalPolicy, OrderDocumentFile, OrderExportFormat, OrderExportJob,
+    OrderExportStatus, OrderImportCosts, OrderInitBilling, OrderItemImportDetail,
+    OrderSelectFulfillment, OrderSelectItem, OrderSpendGroup, OrderTimelineEvent, OrderType,
     PaymentCollectedBy, PaymentSettlementCounterparty, PaymentSettlementPhase,
-    PaymentSettlementType, PaymentStatus, ServiceableType, SettlementBasis, TradeType,
+    PaymentSettlementType, PaymentStatus, QuoteIssue, ReturnStatusType, SelectFulfillmentLocation,
+    SellerScorecard, ServiceableType, SettlementBasis, StandingOrder, StandingOrderAutoConfirm,
+    StandingOrderEvent, StandingOrderEventType, StandingOrderFrequency, StandingOrderRun,
+    StandingOrderRunStatus, StandingOrderStatus, TimelineEventType, TradeType,
 };
+use super::state_machine::{StatusActorType, StatusEntityType};
 use crate::domain::EmailObject;
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct OrderCancellationFeeModel {
@@ -36,6 +47,8 @@ pub struct CommerceBppTermsModel {
     pub mandatory_arbitration: bool,
     pub court_jurisdiction: String,
     pub delay_interest: String,
+    #[serde(default)]
+    pub tax_number: Option<String>,
 }
 #[allow(dead_code)]
 #[derive(Deserialize, Debug, FromRow)]
@@ -58,6 +71,7 @@ pub struct CommerceDataModel {
     pub is_deleted: bool,
     pub created_by: Uuid,
     pub grand_total: Option<BigDecimal>,
+    pub refund_grand_total: Option<BigDecimal>,
     pub bpp_id: String,
     pub bpp_uri: String,
     pub bap_id: String,
@@ -71,6 +85,12 @@ pub struct CommerceDataModel {
     pub bpp_terms: Option<sqlx::types::Json<CommerceBppTermsModel>>,
     pub documents: Option<sqlx::types::Json<Vec<CommerceDocumentModel>>>,
     pub buyer_chat_link: Option<String>,
+    pub version: i32,
+    pub quote_issues: Option<sqlx::types::Json<Vec<QuoteIssue>>>,
+    pub buyer_terms: Option<sqlx::types::Json<CommerceBuyerTerms>>,
+    pub approval_status: Option<ApprovalStatusType>,
+    pub approval_levels: Option<Vec<i32>>,
+    pub approval_requested_on: Option<DateTime<Utc>>,
 }
 
 #[allow(dead_code)]
@@ -85,7 +105,10 @@ pub struct CommerceItemModel {
     pub qty: BigDecimal,
     pub item_req: Option<String>,
     pub packaging_req: Option<String>,
+    pub seller_item_req: Option<String>,
+    pub seller_packaging_req: Option<String>,
     pub tax_rate: BigDecimal,
+    pub cess_rate: Option<BigDecimal>,
     pub tax_value: BigDecimal,
     pub unit_price: BigDecimal,
     pub gross_total: BigDecimal,
@@ -93,6 +116,11 @@ pub struct CommerceItemModel {
     pub discount_amount: BigDecimal,
     pub location_ids: Option<sqlx::types::Json<Vec<String>>>,
     pub fulfillment_ids: Option<sqlx::types::Json<Vec<String>>>,
+    pub cancelled_qty: Option<BigDecimal>,
+    pub invoiced_qty: Option<BigDecimal>,
+    pub refunded_tax_value: Option<BigDecimal>,
+    pub refunded_discount_amount: Option<BigDecimal>,
+    pub refunded_gross_total: Option<BigDecimal>,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
@@ -130,6 +158,7 @@ pub struct CommercePaymentModel {
     pub payment_status: Option<PaymentStatus>,
     pub payment_order_id: Option<String>,
     pub payment_id: Option<String>,
+    pub payment_amount: Option<BigDecimal>,
 }
 
 #[allow(dead_code)]
@@ -143,6 +172,7 @@ pub struct CommerceFulfillmentModel {
     pub fulfillment_status: FulfillmentStatusType,
     pub inco_terms: Option<IncoTermType>,
     pub place_of_delivery: Option<String>,
+    pub seller_inco_terms: Option<IncoTermType>,
     pub provider_name: Option<String>,
     pub category: Option<FulfillmentCategoryType>,
     pub servicable_status: Option<ServiceableType>,
@@ -155,6 +185,199 @@ pub struct CommerceFulfillmentModel {
     pub trade_type: Option<TradeType>,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct CommerceReturnModel {
+    pub id: Uuid,
+    pub commerce_data_id: Uuid,
+    pub return_id: String,
+    pub item_id: String,
+    pub qty: BigDecimal,
+    pub reason_id: String,
+    pub reason_desc: Option<String>,
+    pub images: sqlx::types::Json<Vec<String>>,
+    pub fulfillment_id: Option<String>,
+    pub return_status: ReturnStatusType,
+    pub refund_amount: Option<BigDecimal>,
+    pub created_on: DateTime<Utc>,
+    pub updated_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OrderApprovalModel {
+    pub transaction_id: Uuid,
+    pub approval_level: i32,
+    pub approval_status: ApprovalStatusType,
+    pub approval_requested_on: DateTime<Utc>,
+    pub remark: Option<String>,
+    pub created_by: Uuid,
+    pub created_on: DateTime<Utc>,
+}
+
+impl OrderApprovalModel {
+    pub fn schema(self) -> CommerceApprovalRecord {
+        CommerceApprovalRecord {
+            approval_level: self.approval_level,
+            approval_status: self.approval_status,
+            approval_requested_on: self.approval_requested_on,
+            remark: self.remark,
+            created_by: self.created_by,
+            created_on: self.created_on,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OrderApprovalPolicyModel {
+    pub id: Uuid,
+    pub name: String,
+    pub approval_level: i32,
+    pub min_amount: BigDecimal,
+    pub domain_category_code: Option<CategoryDomain>,
+    pub seller_id: Option<String>,
+    pub approver_ids: Vec<Uuid>,
+    pub is_active: bool,
+    pub created_on: DateTime<Utc>,
+    pub created_by: Uuid,
+}
+
+impl OrderApprovalPolicyModel {
+    pub fn schema(self) -> OrderApprovalPolicy {
+        OrderApprovalPolicy {
+            id: self.id,
+            name: self.name,
+            approval_level: self.approval_level,
+            min_amount: self.min_amount,
+            domain_category_code: self.domain_category_code,
+            seller_id: self.seller_id,
+            approver_ids: self.approver_ids,
+            is_active: self.is_active,
+            created_on: self.created_on,
+            created_by: self.created_by,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct OrderSpendGroupModel {
+    pub key: String,
+    pub label: Option<String>,
+    pub order_count: i64,
+    pub qty: Option<BigDecimal>,
+    pub amount: BigDecimal,
+}
+
+impl OrderSpendGroupModel {
+    pub fn schema(self) -> OrderSpendGroup {
+        OrderSpendGroup {
+            key: self.key,
+            label: self.label,
+            order_count: self.order_count,
+            qty: self.qty,
+            amount: self.amount,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct OrderStatusCountModel {
+    pub record_status: CommerceStatusType,
+    pub order_count: i64,
+}
+
+#[derive(Debug, FromRow)]
+pub struct OrderQuoteMetricModel {
+    pub order_count: i64,
+    pub first_quote_total: BigDecimal,
+    pub final_quote_total: BigDecimal,
+    pub avg_quote_to_confirm_secs: Option<f64>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BuyerBudgetModel {
+    pub id: Uuid,
+    pub name: String,
+    pub user_id: Option<Uuid>,
+    pub domain_category_code: Option<CategoryDomain>,
+    pub amount: BigDecimal,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub exceed_action: BudgetExceedAction,
+    pub approval_level: i32,
+    pub is_active: bool,
+    pub consumed_amount: BigDecimal,
+}
+
+impl BuyerBudgetModel {
+    pub fn schema(self) -> BuyerBudget {
+        BuyerBudget {
+            remaining_amount: &self.amount - &self.consumed_amount,
+            id: self.id,
+            name: self.name,
+            user_id: self.user_id,
+            domain_category_code: self.domain_category_code,
+            amount: self.amount,
+            period_start: self.period_start,
+            period_end: self.period_end,
+            exceed_action: self.exceed_action,
+            approval_level: self.approval_level,
+            is_active: self.is_active,
+            consumed_amount: self.consumed_amount,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OrderExportJobModel {
+    pub id: Uuid,
+    pub format: OrderExportFormat,
+    pub status: OrderExportStatus,
+    pub order_count: i64,
+    pub file_name: String,
+    pub error: Option<String>,
+    pub created_on: DateTime<Utc>,
+    pub completed_on: Option<DateTime<Utc>>,
+}
+
+impl OrderExportJobModel {
+    pub fn schema(self) -> OrderExportJob {
+        OrderExportJob {
+            id: self.id,
+            format: self.format,
+            status: self.status,
+            order_count: self.order_count,
+            file_name: self.file_name,
+            error: self.error,
+            created_on: self.created_on,
+            completed_on: self.completed_on,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OrderExportFileModel {
+    pub format: OrderExportFormat,
+    pub status: OrderExportStatus,
+    pub file_name: String,
+    pub content: Option<Vec<u8>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CommerceGoodsReceiptLineModel {
+    pub grn_id: Uuid,
+    pub grn_no: String,
+    pub fulfillment_id: String,
+    pub received_on: DateTime<Utc>,
+    pub grn_remark: Option<String>,
+    pub created_on: DateTime<Utc>,
+    pub created_by: Uuid,
+    pub item_id: String,
+    pub received_qty: BigDecimal,
+    pub accepted_qty: BigDecimal,
+    pub rejected_qty: BigDecimal,
+    pub remark: Option<String>,
+    pub images: sqlx::types::Json<Vec<String>>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OrderBillingModel {
     pub name: String,
@@ -314,3 +537,468 @@ impl MinimalCommerceModel {
         }
     }
 }
+
+#[derive(Deserialize, Debug, FromRow)]
+pub struct StatusPollOrderModel {
+    pub external_urn: Uuid,
+    pub bap_id: String,
+    pub bpp_id: String,
+    pub created_by: Uuid,
+    pub buyer_id: Uuid,
+}
+
+#[derive(Deserialize, Debug, FromRow)]
+pub struct OrderRequestEventModel {
+    pub message_id: Uuid,
+    pub user_id: Uuid,
+    pub action_type: String,
+    pub created_on: DateTime<Utc>,
+}
+
+impl OrderRequestEventModel {
+    pub fn schema(self) -> OrderTimelineEvent {
+        OrderTimelineEvent {
+            event_type: TimelineEventType::Request,
+            entity_type: None,
+            entity_id: None,
+            previous_status: None,
+            status: None,
+            actor_type: StatusActorType::Buyer,
+            actor_id: self.user_id.to_string(),
+            action_type: self.action_type,
+            message_id: Some(self.message_id),
+            is_valid: true,
+            detail: None,
+            created_on: self.created_on,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, FromRow)]
+pub struct OrderStatusHistoryModel {
+    pub entity_type: StatusEntityType,
+    pub entity_id: Option<String>,
+    pub previous_status: Option<String>,
+    pub status: String,
+    pub actor_type: StatusActorType,
+    pub actor_id: String,
+    pub action_type: String,
+    pub message_id: Option<Uuid>,
+    pub is_valid: bool,
+    pub detail: Option<Value>,
+    pub created_on: DateTime<Utc>,
+}
+
+impl OrderStatusHistoryModel {
+    pub fn schema(self) -> OrderTimelineEvent {
+        OrderTimelineEvent {
+            event_type: TimelineEventType::StatusChange,
+            entity_type: Some(self.entity_type),
+            entity_id: self.entity_id,
+            previous_status: self.previous_status,
+            status: Some(self.status),
+            actor_type: self.actor_type,
+            actor_id: self.actor_id,
+            action_type: self.action_type,
+            message_id: self.message_id,
+            is_valid: self.is_valid,
+            detail: self.detail,
+            created_on: self.created_on,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SellerScorecardModel {
+    pub bpp_id: String,
+    pub provider_id: String,
+    pub provider_name: Option<String>,
+    pub quote_response_count: i32,
+    pub quote_response_secs: i64,
+    pub quote_rejected_count: i32,
+    pub order_accepted_count: i32,
+    pub order_rejected_count: i32,
+    pub seller_cancelled_count: i32,
+    pub delivered_count: i32,
+    pub delivery_promised_count: i32,
+    pub on_time_delivery_count: i32,
+    pub updated_on: DateTime<Utc>,
+}
+
+impl SellerScorecardModel {
+    pub fn schema(self) -> SellerScorecard {
+        let get_rate = |count: i32, total: i32| {
+            (total > 0).then(|| (f64::from(count) * 10000.0 / f64::from(total)).round() / 100.0)
+        };
+        let order_count = self.order_accepted_count + self.order_rejected_count;
+        SellerScorecard {
+            avg_quote_response_secs: (self.quote_response_count > 0)
+                .then(|| self.quote_response_secs / i64::from(self.quote_response_count)),
+            quote_rejection_rate: get_rate(self.quote_rejected_count, self.quote_response_count),
+            order_acceptance_rate: get_rate(self.order_accepted_count, order_count),
+            seller_cancellation_rate: get_rate(
+                self.seller_cancelled_count,
+                self.order_accepted_count,
+            ),
+            on_time_delivery_rate: get_rate(
+                self.on_time_delivery_count,
+                self.delivery_promised_count,
+            ),
+            bpp_id: self.bpp_id,
+            provider_id: self.provider_id,
+            provider_name: self.provider_name,
+            quote_response_count: self.quote_response_count,
+            delivered_count: self.delivered_count,
+            updated_on: self.updated_on,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, FromRow)]
+pub struct StandingOrderModel {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    pub name: String,
+    pub bpp_id: String,
+    pub provider_id: String,
+    pub domain_category_code: CategoryDomain,
+    pub payment_types: Vec<PaymentType>,
+    pub order_type: OrderType,
+    pub ttl: String,
+    pub items: sqlx::types::Json<Vec<OrderSelectItem>>,
+    pub fulfillments: sqlx::types::Json<Vec<OrderSelectFulfillment>>,
+    pub frequency: StandingOrderFrequency,
+    pub interval_count: i32,
+    pub next_run_on: DateTime<Utc>,
+    pub end_on: Option<DateTime<Utc>>,
+    pub auto_confirm: Option<sqlx::types::Json<StandingOrderAutoConfirm>>,
+    pub status: StandingOrderStatus,
+    pub created_on: DateTime<Utc>,
+    pub created_by: Uuid,
+    pub updated_on: Option<DateTime<Utc>>,
+    pub updated_by: Option<Uuid>,
+}
+
+impl StandingOrderModel {
+    pub fn schema(self) -> StandingOrder {
+        StandingOrder {
+            id: self.id,
+            business_id: self.business_id,
+            name: self.name,
+            bpp_id: self.bpp_id,
+            provider_id: self.provider_id,
+            domain_category_code: self.domain_category_code,
+            payment_types: self.payment_types,
+            order_type: self.order_type,
+            ttl: self.ttl,
+            items: self.items.0,
+            fulfillments: self.fulfillments.0,
+            frequency: self.frequency,
+            interval_count: self.interval_count,
+            next_run_on: self.next_run_on,
+            end_on: self.end_on,
+            auto_confirm: self.auto_confirm.map(|auto_confirm| auto_confirm.0),
+            status: self.status,
+            created_on: self.created_on,
+            created_by: self.created_by,
+            updated_on: self.updated_on,
+            updated_by: self.updated_by,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, FromRow)]
+pub struct StandingOrderEventModel {
+    pub event_type: StandingOrderEventType,
+    pub detail: Value,
+    pub created_on: DateTime<Utc>,
+    pub created_by: Uuid,
+}
+
+impl StandingOrderEventModel {
+    pub fn schema(self) -> StandingOrderEvent {
+        StandingOrderEvent {
+            event_type: self.event_type,
+            detail: self.detail,
+            created_on: self.created_on,
+            created_by: self.created_by,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, FromRow)]
+pub struct StandingOrderRunModel {
+    pub id: Uuid,
+    pub standing_order_id: Uuid,
+    pub standing_order_name: String,
+    pub transaction_id: Uuid,
+    pub scheduled_on: DateTime<Utc>,
+    pub status: StandingOrderRunStatus,
+    pub quote_total: Option<BigDecimal>,
+    pub message: Option<String>,
+    pub created_on: DateTime<Utc>,
+    pub updated_on: Option<DateTime<Utc>>,
+}
+
+impl StandingOrderRunModel {
+    pub fn schema(self) -> StandingOrderRun {
+        StandingOrderRun {
+            id: self.id,
+            standing_order_id: self.standing_order_id,
+            standing_order_name: self.standing_order_name,
+            transaction_id: self.transaction_id,
+            scheduled_on: self.scheduled_on,
+            status: self.status,
+            quote_total: self.quote_total,
+            message: self.message,
+            created_on: self.created_on,
+            updated_on: self.updated_on,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, FromRow)]
+pub struct CartItemModel {
+    pub id: Uuid,
+    pub domain_category_code: CategoryDomain,
+    pub bpp_id: String,
+    pub provider_id: String,
+    pub item_id: String,
+    pub item_name: String,
+    pub location_id: String,
+    pub qty: i32,
+    pub unit_price: BigDecimal,
+    pub currency_code: CurrencyType,
+    pub buyer_term: Option<sqlx::types::Json<BuyerTerms>>,
+    pub import_detail: Option<sqlx::types::Json<OrderItemImportDetail>>,
+    pub created_on: DateTime<Utc>,
+    pub updated_on: Option<DateTime<Utc>>,
+}
+
+impl CartItemModel {
+    pub fn schema(self) -> CartItem {
+        CartItem {
+            id: self.id,
+            domain_category_code: self.domain_category_code,
+            bpp_id: self.bpp_id,
+            provider_id: self.provider_id,
+            item_id: self.item_id,
+            item_name: self.item_name,
+            location_id: self.location_id,
+            qty: self.qty,
+            unit_price: self.unit_price,
+            currency_code: self.currency_code,
+            buyer_term: self.buyer_term.map(|buyer_term| buyer_term.0),
+            import_detail: self.import_detail.map(|import_detail| import_detail.0),
+            created_on: self.created_on,
+            updated_on: self.updated_on,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, FromRow)]
+pub struct CartCheckoutModel {
+    pub id: Uuid,
+    pub created_on: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug, FromRow)]
+pub struct CartCheckoutOrderModel {
+    pub transaction_id: Uuid,
+    pub bpp_id: String,
+    pub provider_id: String,
+    pub items: sqlx::types::Json<Vec<CartItem>>,
+    pub error: Option<String>,
+    pub record_status: Option<CommerceStatusType>,
+}
+
+impl CartCheckoutOrderModel {
+    pub fn schema(self) -> CartCheckoutOrder {
+        CartCheckoutOrder {
+            transaction_id: self.transaction_id,
+            bpp_id: self.bpp_id,
+            provider_id: self.provider_id,
+            items: self.items.0,
+            error: self.error,
+            record_status: self.record_status,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, FromRow)]
+pub struct DeliveryLocationModel {
+    pub id: Uuid,
+    pub name: String,
+    pub location: sqlx::types::Json<SelectFulfillmentLocation>,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    pub opening_hours: sqlx::types::Json<Vec<DeliveryLocationOpeningHours>>,
+    pub is_default: bool,
+    pub created_on: DateTime<Utc>,
+    pub created_by: Uuid,
+    pub updated_on: Option<DateTime<Utc>>,
+}
+
+impl DeliveryLocationModel {
+    pub fn schema(self) -> DeliveryLocation {
+        DeliveryLocation {
+            id: self.id,
+            name: self.name,
+            location: self.location.0,
+            contact_name: self.contact_name,
+            contact_email: self.contact_email,
+            opening_hours: self.opening_hours.0,
+            is_default: self.is_default,
+            created_on: self.created_on,
+            created_by: self.created_by,
+            updated_on: self.updated_on,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, FromRow)]
+pub struct BillingProfileModel {
+    pub id: Uuid,
+    pub name: String,
+    pub billing: sqlx::types::Json<OrderInitBilling>,
+    pub country_code: CountryCode,
+    pub is_default: bool,
+    pub created_on: DateTime<Utc>,
+    pub created_by: Uuid,
+    pub updated_on: Option<DateTime<Utc>>,
+}
+
+impl BillingProfileModel {
+    pub fn schema(self) -> BillingProfile {
+        BillingProfile {
+            id: self.id,
+            name: self.name,
+            billing: self.billing.0,
+            country_code: self.country_code,
+            is_default: self.is_default,
+            created_on: self.created_on,
+            created_by: self.created_by,
+            updated_on: self.updated_on,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, FromRow)]
+pub struct BuyerTermsTemplateModel {
+    pub id: Uuid,
+    pub name: String,
+    pub domain_category_code: Option<CategoryDomain>,
+    pub terms: sqlx::types::Json<BuyerTermsSpec>,
+    pub is_default: bool,
+    pub created_on: DateTime<Utc>,
+    pub created_by: Uuid,
+    pub updated_on: Option<DateTime<Utc>>,
+}
+
+impl BuyerTermsTemplateModel {
+    pub fn schema(self) -> BuyerTermsTemplate {
+        BuyerTermsTemplate {
+            id: self.id,
+            name: self.name,
+            domain_category_code: self.domain_category_code,
+            terms: self.terms.0,
+            is_default: self.is_default,
+            created_on: self.created_on,
+            created_by: self.created_by,
+            updated_on: self.updated_on,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CommerceImportLineModel {
+    pub transaction_id: Uuid,
+    pub item_id: String,
+    pub hs_code: String,
+    pub country_of_origin: CountryCode,
+    pub duty_rate: Option<BigDecimal>,
+}
+
+impl CommerceImportLineModel {
+    pub fn schema(self) -> OrderItemImportDetail {
+        OrderItemImportDetail {
+            hs_code: self.hs_code,
+            country_of_origin: self.country_of_origin,
+            duty_rate: self.duty_rate,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CommerceImportCostModel {
+    pub transaction_id: Uuid,
+    pub freight_amount: BigDecimal,
+    pub insurance_amount: BigDecimal,
+}
+
+impl CommerceImportCostModel {
+    pub fn schema(self) -> OrderImportCosts {
+        OrderImportCosts {
+            freight_amount: self.freight_amount,
+            insurance_amount: self.insurance_amount,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CustomsDocumentModel {
+    pub id: Uuid,
+    pub transaction_id: Uuid,
+    pub document_type: CustomsDocumentType,
+    pub document_no: String,
+    pub url: Option<String>,
+    pub issued_on: Option<NaiveDate>,
+    pub remark: Option<String>,
+    pub created_on: DateTime<Utc>,
+    pub created_by: Uuid,
+    pub updated_on: Option<DateTime<Utc>>,
+}
+
+impl CustomsDocumentModel {
+    pub fn schema(self) -> CustomsDocument {
+        CustomsDocument {
+            id: self.id,
+            document_type: self.document_type,
+            document_no: self.document_no,
+            url: self.url,
+            issued_on: self.issued_on,
+            remark: self.remark,
+            created_on: self.created_on,
+            created_by: self.created_by,
+            updated_on: self.updated_on,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct CommerceDocumentFileModel {
+    pub id: Uuid,
+    pub document_type: DocumentType,
+    pub version: i32,
+    pub source_url: String,
+    pub storage_key: String,
+    pub checksum: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub created_on: DateTime<Utc>,
+}
+
+impl CommerceDocumentFileModel {
+    pub fn schema(self) -> OrderDocumentFile {
+        OrderDocumentFile {
+            id: self.id,
+            document_type: self.document_type,
+            version: self.version,
+            source_url: self.source_url,
+            checksum: self.checksum,
+            content_type: self.content_type,
+            size_bytes: self.size_bytes,
+            created_on: self.created_on,
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use bigdecimal::BigDecimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use super::errors::QuoteValidationError;
+use super::schemas::{CommerceItem, QuoteIssue, QuoteIssueSeverity};
+use crate::routes::ondc::schemas::{BreakupTitleType, ONDCBreakUp, ONDCQuote};
+
+/// Allowed difference per rounded line, seller amounts are rounded to two decimals.
+const LINE_TOLERANCE: &str = "0.01";
+/// Allowed difference in percentage points between the quoted and the catalog tax rate.
+const TAX_RATE_TOLERANCE: &str = "0.5";
+
+fn parse_amount(value: &str, field: &str, item_id: &str) -> Result<BigDecimal, QuoteIssue> {
+    BigDecimal::from_str(value).map_err(|_| QuoteIssue {
+        severity: QuoteIssueSeverity::Error,
+        item_id: Some(item_id.to_owned()),
+        message: format!("{} '{}' is not a valid amount", field, value),
+    })
+}
+
+fn validate_item_line(breakup: &ONDCBreakUp) -> Option<QuoteIssue> {
+    let (Some(item), Some(quantity)) = (&breakup.item, &breakup.quantity) else {
+        return None;
+    };
+    let unit_price = match parse_amount(&item.price.value, "Unit price", &breakup.item_id) {
+        Ok(unit_price) => unit_price,
+        Err(issue) => return Some(issue),
+    };
+    let line_total = match parse_amount(&breakup.price.value, "Line total", &breakup.item_id) {
+        Ok(line_total) => line_total,
+        Err(issue) => return Some(issue),
+    };
+    let expected_total = &unit_price * BigDecimal::from(quantity.count);
+    let tolerance =
+        BigDecimal::from_str(LINE_TOLERANCE).unwrap() * BigDecimal::from(quantity.count);
+    ((&expected_total - &line_total).abs() > tolerance).then(|| QuoteIssue {
+        severity: QuoteIssueSeverity::Error,
+        item_id: Some(breakup.item_id.clone()),
+        message: format!(
+            "Line total {} does not match unit price {} x quantity {}",
+            line_total, unit_price, quantity.count
+        ),
+    })
+}
+
+fn validate_tax_line(
+    item_line: &ONDCBreakUp,
+    tax_line: &ONDCBreakUp,
+    tax_rate: &BigDecimal,
+) -> Option<QuoteIssue> {
+    let item_total = BigDecimal::from_str(&item_line.price.value).ok()?;
+    let tax_value = match parse_amount(&tax_line.price.value, "Tax", &tax_line.item_id) {
+        Ok(tax_value) => tax_value,
+        Err(issue) => return Some(issue),
+    };
+    if item_total == BigDecimal::from(0) {
+        return None;
+    }
+    let quoted_rate = &tax_value * BigDecimal::from(100) / &item_total;
+    ((&quoted_rate - tax_rate).abs() > BigDecimal::from_str(TAX_RATE_TOLERANCE).unwrap()).then(
+        || QuoteIssue {
+            severity: QuoteIssueSeverity::Warning,
+            item_id: Some(tax_line.item_id.clone()),
+            message: format!(
+                "Quoted tax rate {}% does not match the catalog tax rate {}%",
+                quoted_rate.round(2),
+                tax_rate
+            ),
+        },
+    )
+}
+
+/// Checks that the line totals and the breakup add up and that the quoted tax follows the catalog.
+/// `tax_rates` is keyed by item id, items without a known tax rate skip the tax check.
+pub fn validate_quote(
+    quote: &ONDCQuote,
+    tax_rates: &HashMap<&str, &BigDecimal>,
+) -> Vec<QuoteIssue> {
+    let mut issues = vec![];
+    let quoted_total = match parse_amount(&quote.price.value, "Quote total", "") {
+        Ok(quoted_total) => quoted_total,
+        Err(mut issue) => {
+            issue.item_id = None;
+            return vec![issue];
+        }
+    };
+    let mut breakup_total = BigDecimal::from(0);
+    let mut item_lines: HashMap<&str, &ONDCBreakUp> = HashMap::new();
+    for breakup in &quote.breakup {
+        match parse_amount(&breakup.price.value, "Breakup amount", &breakup.item_id) {
+            Ok(amount) => breakup_total += amount,
+            Err(issue) => issues.push(issue),
+        }
+        if breakup.title_type == BreakupTitleType::Item {
+            item_lines.insert(breakup.item_id.as_str(), breakup);
+            issues.extend(validate_item_line(breakup));
+        }
+    }
+    for tax_line in quote
+        .breakup
+        .iter()
+        .filter(|breakup| breakup.title_type == BreakupTitleType::Tax)
+    {
+        if let (Some(item_line), Some(tax_rate)) = (
+            item_lines.get(tax_line.item_id.as_str()),
+            tax_rates.get(tax_line.item_id.as_str()),
+        ) {
+            issues.extend(validate_tax_line(item_line, tax_line, tax_rate));
+        }
+    }
+    let tolerance = BigDecimal::from_str(LINE_TOLERANCE).unwrap()
+        * BigDecimal::from(quote.breakup.len() as i64);
+    if (&breakup_total - &quoted_total).abs() > tolerance {
+        issues.push(QuoteIssue {
+            severity: QuoteIssueSeverity::Error,
+            item_id: None,
+            message: format!(
+                "Breakup adds up to {} but the quoted total is {}",
+                breakup_total, quoted_total
+            ),
+        });
+    }
+    issues
+}
+
+/// Arithmetic errors reject the callback, warnings are stored on the order for the buyer to review.
+pub fn validate_quote_issues(issues: &[QuoteIssue]) -> Result<(), QuoteValidationError> {
+    let errors: Vec<&str> = issues
+        .iter()
+        .filter(|issue| issue.severity == QuoteIssueSeverity::Error)
+        .map(|issue| issue.message.as_str())
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(QuoteValidationError::InvalidQuote(errors.join(", ")))
+    }
+}
+
+/// Validates a quote against the tax rates stored on the order items.
+pub fn validate_order_quote(
+    quote: &ONDCQuote,
+    items: &[CommerceItem],
+) -> Result<Vec<QuoteIssue>, QuoteValidationError> {
+    let tax_rates: HashMap<&str, &BigDecimal> = items
+        .iter()
+        .map(|item| (item.item_id.as_str(), &item.tax_rate))
+        .collect();
+    let issues = validate_quote(quote, &tax_rates);
+    validate_quote_issues(&issues)?;
+    Ok(issues)
+}
@@ -3,8 +3,17 @@ use crate::user_client::{CustomerType, PermissionType};
 use actix_web::web;
 
 use super::handlers::{
-    order_cancel, order_confirm, order_fetch, order_init, order_list, order_select, order_status,
-    order_update,
+    billing_profile_delete, billing_profile_list, billing_profile_save,
+    buyer_terms_template_delete, buyer_terms_template_list, buyer_terms_template_save,
+    cart_checkout, cart_checkout_fetch, cart_fetch, cart_item_add, cart_item_remove,
+    cart_item_update, customs_document_delete, customs_document_save, delivery_location_delete,
+    delivery_location_list, delivery_location_save, order_analytics, order_approval_policy_list,
+    order_approval_policy_save, order_approve, order_budget_report, order_budget_save,
+    order_bulk_upload, order_cancel, order_cancel_preview, order_confirm, order_document_download,
+    order_document_list, order_export, order_export_download, order_export_status, order_fetch,
+    order_grn_create, order_grn_list, order_grn_match, order_init, order_list, order_list_page,
+    order_reorder, order_select, order_status, order_timeline, order_update, seller_scorecard,
+    standing_order_history, standing_order_list, standing_order_save, standing_order_status,
 };
 pub fn order_route(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -23,6 +32,38 @@ pub fn order_route(cfg: &mut web::ServiceConfig) {
                 .wrap(RequireAuth),
         ),
     );
+    cfg.service(
+        web::resource("/bulk/upload").route(
+            web::post()
+                .to(order_bulk_upload)
+                .wrap(BusinessPermissionValidation {
+                    permission_list: vec![
+                        PermissionType::CreateOrder,
+                        PermissionType::CreateOrderSelf,
+                    ],
+                })
+                .wrap(BusinessAccountValidation {
+                    business_type_list: vec![CustomerType::RetailB2bBuyer],
+                })
+                .wrap(RequireAuth),
+        ),
+    );
+    cfg.service(
+        web::resource("/reorder").route(
+            web::post()
+                .to(order_reorder)
+                .wrap(BusinessPermissionValidation {
+                    permission_list: vec![
+                        PermissionType::CreateOrder,
+                        PermissionType::CreateOrderSelf,
+                    ],
+                })
+                .wrap(BusinessAccountValidation {
+                    business_type_list: vec![CustomerType::RetailB2bBuyer],
+                })
+                .wrap(RequireAuth),
+        ),
+    );
     cfg.service(
         web::resource("/init").route(
             web::post()
@@ -80,6 +121,18 @@ pub fn order_route(cfg: &mut web::ServiceConfig) {
             .wrap(RequireAuth),
     );
 
+    cfg.service(
+        web::resource("/cancel/preview")
+            .route(web::post().to(order_cancel_preview))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::CancelOrder, PermissionType::CancelOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+
     cfg.service(
         web::resource("/update")
             .route(web::post().to(order_update))
@@ -113,4 +166,437 @@ pub fn order_route(cfg: &mut web::ServiceConfig) {
             })
             .wrap(RequireAuth),
     );
+    cfg.service(
+        web::resource("/list/page")
+            .route(web::post().to(order_list_page))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ListOrder, PermissionType::ListOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/analytics")
+            .route(web::post().to(order_analytics))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ListOrder, PermissionType::ListOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/export")
+            .route(web::post().to(order_export))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ListOrder, PermissionType::ListOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/export/status")
+            .route(web::post().to(order_export_status))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ListOrder, PermissionType::ListOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/export/download")
+            .route(web::post().to(order_export_download))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ListOrder, PermissionType::ListOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/timeline")
+            .route(web::post().to(order_timeline))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ReadOrder, PermissionType::ReadOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/grn/create")
+            .route(web::post().to(order_grn_create))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::UpdateOrder, PermissionType::UpdateOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/grn/list")
+            .route(web::post().to(order_grn_list))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ReadOrder, PermissionType::ReadOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/grn/match")
+            .route(web::post().to(order_grn_match))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ReadOrder, PermissionType::ReadOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/approve")
+            .route(web::post().to(order_approve))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ApproveOrder],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/approval/policy/save")
+            .route(web::post().to(order_approval_policy_save))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ManageApprovalPolicy],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/approval/policy/list")
+            .route(web::post().to(order_approval_policy_list))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![
+                    PermissionType::ManageApprovalPolicy,
+                    PermissionType::ApproveOrder,
+                ],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/budget/save")
+            .route(web::post().to(order_budget_save))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ManageBudget],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/budget/report")
+            .route(web::post().to(order_budget_report))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ManageBudget, PermissionType::ReadBudget],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/seller/scorecard")
+            .route(web::post().to(seller_scorecard))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::CreateOrder, PermissionType::CreateOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/standing/save")
+            .route(web::post().to(standing_order_save))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::CreateOrder, PermissionType::CreateOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/standing/list")
+            .route(web::post().to(standing_order_list))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ListOrder, PermissionType::ListOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/standing/status")
+            .route(web::post().to(standing_order_status))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::CreateOrder, PermissionType::CreateOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/standing/history")
+            .route(web::post().to(standing_order_history))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ListOrder, PermissionType::ListOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/cart/add")
+            .route(web::post().to(cart_item_add))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::CreateOrder, PermissionType::CreateOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/cart/update")
+            .route(web::post().to(cart_item_update))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::CreateOrder, PermissionType::CreateOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/cart/remove")
+            .route(web::post().to(cart_item_remove))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::CreateOrder, PermissionType::CreateOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/cart/fetch")
+            .route(web::post().to(cart_fetch))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::CreateOrder, PermissionType::CreateOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/cart/checkout")
+            .route(web::post().to(cart_checkout))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::CreateOrder, PermissionType::CreateOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/cart/checkout/fetch")
+            .route(web::post().to(cart_checkout_fetch))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::CreateOrder, PermissionType::CreateOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/delivery/location/save")
+            .route(web::post().to(delivery_location_save))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ManageAddressBook],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/delivery/location/list")
+            .route(web::post().to(delivery_location_list))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![
+                    PermissionType::ManageAddressBook,
+                    PermissionType::CreateOrder,
+                    PermissionType::CreateOrderSelf,
+                ],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/delivery/location/delete")
+            .route(web::post().to(delivery_location_delete))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ManageAddressBook],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/billing/profile/save")
+            .route(web::post().to(billing_profile_save))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ManageAddressBook],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/billing/profile/list")
+            .route(web::post().to(billing_profile_list))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![
+                    PermissionType::ManageAddressBook,
+                    PermissionType::CreateOrder,
+                    PermissionType::CreateOrderSelf,
+                ],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/billing/profile/delete")
+            .route(web::post().to(billing_profile_delete))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ManageAddressBook],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/buyer/terms/template/save")
+            .route(web::post().to(buyer_terms_template_save))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ManageBuyerTerms],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/buyer/terms/template/list")
+            .route(web::post().to(buyer_terms_template_list))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![
+                    PermissionType::ManageBuyerTerms,
+                    PermissionType::CreateOrder,
+                    PermissionType::CreateOrderSelf,
+                ],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/buyer/terms/template/delete")
+            .route(web::post().to(buyer_terms_template_delete))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ManageBuyerTerms],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/customs/document/save")
+            .route(web::post().to(customs_document_save))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::UpdateOrder, PermissionType::UpdateOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/customs/document/delete")
+            .route(web::post().to(customs_document_delete))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::UpdateOrder, PermissionType::UpdateOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/document/list")
+            .route(web::post().to(order_document_list))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ReadOrder, PermissionType::ReadOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
+    cfg.service(
+        web::resource("/document/download")
+            .route(web::post().to(order_document_download))
+            .wrap(BusinessPermissionValidation {
+                permission_list: vec![PermissionType::ReadOrder, PermissionType::ReadOrderSelf],
+            })
+            .wrap(BusinessAccountValidation {
+                business_type_list: vec![CustomerType::RetailB2bBuyer],
+            })
+            .wrap(RequireAuth),
+    );
 }
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use super::state_machine::{StatusActorType, StatusEntityType};
+use crate::constants::ORDER_EXPORT_PAGE_SIZE;
 use crate::errors::GenericError;
 use crate::routes::ondc::schemas::{
     ONDCFulfillmentStateType, ONDCPaymentSettlementCounterparty, ONDCPaymentSettlementPhase,
@@ -18,20 +20,32 @@ use actix_web::{web, FromRequest, HttpRequest};
 use serde_json::Value;
 
 use crate::domain::EmailObject;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
 use bigdecimal::BigDecimal;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use futures_util::future::LocalBoxFuture;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use utoipa::ToSchema;
 use uuid::Uuid;
-#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct BuyerTerms {
     pub item_req: String,
     pub packaging_req: String,
 }
 
-#[derive(Deserialize, Debug, ToSchema)]
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderItemImportDetail {
+    pub hs_code: String,
+    pub country_of_origin: CountryCode,
+    #[schema(value_type = Option<f64>)]
+    pub duty_rate: Option<BigDecimal>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderSelectItem {
     pub item_id: String,
@@ -39,21 +53,23 @@ pub struct OrderSelectItem {
     pub qty: i32,
     pub buyer_term: Option<BuyerTerms>,
     pub fulfillment_ids: Vec<String>,
+    #[serde(default)]
+    pub import_detail: Option<OrderItemImportDetail>,
 }
 
-#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
 pub struct Country {
     pub code: CountryCode,
     pub name: String,
 }
 
-#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
 pub struct City {
     pub code: String,
     pub name: String,
 }
 
-#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SelectFulfillmentLocation {
     pub gps: String,
@@ -65,7 +81,7 @@ pub struct SelectFulfillmentLocation {
     pub contact_mobile_no: String,
 }
 
-#[derive(Deserialize, Debug, ToSchema, sqlx::Type, Serialize)]
+#[derive(Deserialize, Debug, ToSchema, sqlx::Type, Serialize, Clone, PartialEq)]
 #[sqlx(type_name = "inco_term_type", rename_all = "UPPERCASE")]
 #[serde(rename_all = "UPPERCASE")]
 pub enum IncoTermType {
@@ -96,14 +112,14 @@ impl std::fmt::Display for IncoTermType {
     }
 }
 
-#[derive(Deserialize, Debug, ToSchema)]
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderDeliveyTerm {
     pub inco_terms: IncoTermType,
     pub place_of_delivery: String,
 }
 
-#[derive(Deserialize, Debug, ToSchema)]
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderSelectFulfillment {
     pub id: String,
@@ -113,6 +129,17 @@ pub struct OrderSelectFulfillment {
     pub delivery_terms: Option<OrderDeliveyTerm>,
 }
 
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderSelectSavedFulfillment {
+    pub id: String,
+    pub r#type: FulfillmentType,
+    /// Default delivery location of the business when not given.
+    #[schema(value_type = Option<String>)]
+    pub delivery_location_id: Option<Uuid>,
+    pub delivery_terms: Option<OrderDeliveyTerm>,
+}
+
 // #[derive(Deserialize, Debug, sqlx::Type)]
 // #[sqlx(type_name = "commerce_data_type", rename_all = "snake_case")]
 // #[serde(rename_all = "snake_case")]
@@ -121,7 +148,7 @@ pub struct OrderSelectFulfillment {
 //     PurchaseOrder,
 // }
 
-#[derive(Deserialize, Debug, ToSchema, PartialEq, sqlx::Type, Serialize)]
+#[derive(Deserialize, Debug, ToSchema, PartialEq, sqlx::Type, Serialize, Clone)]
 #[sqlx(type_name = "commerce_data_type", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
 pub enum OrderType {
@@ -181,7 +208,7 @@ pub enum FulfillmentCategoryType {
 //     }
 // }
 
-#[derive(Deserialize, Debug, ToSchema)]
+#[derive(Deserialize, Debug, ToSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderSelectRequest {
     #[schema(value_type = String)]
@@ -196,6 +223,12 @@ pub struct OrderSelectRequest {
     pub fulfillments: Vec<OrderSelectFulfillment>,
     pub order_type: OrderType,
     pub bpp_id: String,
+    /// Fulfillments delivered to a saved delivery location, items refer to them by id like the inline fulfillments.
+    #[serde(default)]
+    pub saved_fulfillments: Vec<OrderSelectSavedFulfillment>,
+    #[schema(value_type = Option<String>)]
+    #[serde(default)]
+    pub buyer_terms_template_id: Option<Uuid>,
 }
 
 impl FromRequest for OrderSelectRequest {
@@ -214,7 +247,105 @@ impl FromRequest for OrderSelectRequest {
     }
 }
 
-#[derive(Deserialize, Debug, sqlx::Type, ToSchema, Serialize, PartialEq)]
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderItem {
+    pub item_id: String,
+    pub qty: i32,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderReorderRequest {
+    #[schema(value_type = String)]
+    pub transaction_id: Uuid,
+    /// Quantity changes of the previous order lines, a quantity of 0 drops the line.
+    #[serde(default)]
+    pub items: Vec<ReorderItem>,
+}
+
+impl FromRequest for OrderReorderRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+/// The billing of the previous order is returned to be reused in the init of the new order.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderReorderResponse {
+    #[schema(value_type = String)]
+    pub transaction_id: Uuid,
+    pub billing: Option<CommerceBilling>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkOrderUploadRequest {
+    pub domain_category_code: CategoryDomain,
+    pub payment_types: Vec<PaymentType>,
+    pub ttl: String,
+    pub order_type: OrderType,
+    /// Referred by the `delivery_location_id` column of the CSV.
+    pub delivery_locations: Vec<OrderSelectFulfillment>,
+    /// CSV with the columns `bpp_id`, `provider_id`, `item_id`, `qty`, `location_id` and `delivery_location_id`.
+    /// Items of a foreign seller also need `hs_code`, `country_of_origin` and `duty_rate`.
+    pub csv: String,
+}
+
+impl FromRequest for BulkOrderUploadRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkOrderRowError {
+    pub row_no: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkOrderSelect {
+    #[schema(value_type = String)]
+    pub transaction_id: Uuid,
+    pub bpp_id: String,
+    pub provider_id: String,
+    pub row_nos: Vec<usize>,
+    pub error: Option<String>,
+}
+
+/// Nothing is sent when any row is invalid, the selects are only listed once every row is valid.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkOrderUploadResponse {
+    pub row_errors: Vec<BulkOrderRowError>,
+    pub orders: Vec<BulkOrderSelect>,
+}
+
+#[derive(Deserialize, Debug, sqlx::Type, ToSchema, Serialize, PartialEq, Clone)]
 #[sqlx(type_name = "commerce_status", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
 pub enum CommerceStatusType {
@@ -230,6 +361,16 @@ pub enum CommerceStatusType {
 }
 
 impl CommerceStatusType {
+    /// Statuses of orders committed with the seller, used for spend and budget consumption.
+    pub fn committed_list() -> Vec<CommerceStatusType> {
+        vec![
+            CommerceStatusType::Created,
+            CommerceStatusType::Accepted,
+            CommerceStatusType::InProgress,
+            CommerceStatusType::Completed,
+        ]
+    }
+
     pub fn get_ondc_order_status(&self) -> ONDCOrderStatus {
         match self {
             CommerceStatusType::QuoteRequested => ONDCOrderStatus::InProgress,
@@ -287,7 +428,7 @@ impl PaymentCollectedBy {
     }
 }
 
-#[derive(Deserialize, Debug, ToSchema)]
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderInitBilling {
     pub name: String,
@@ -306,7 +447,14 @@ pub struct OrderInitRequest {
     pub transaction_id: Uuid,
     #[schema(value_type = String)]
     pub message_id: Uuid,
-    pub billing: OrderInitBilling,
+    /// Billing profile of the business is used when the billing is not given,
+    /// the default billing profile when no profile id is given either.
+    pub billing: Option<OrderInitBilling>,
+    #[schema(value_type = Option<String>)]
+    pub billing_profile_id: Option<Uuid>,
+    #[serde(default)]
+    pub import_costs: Option<OrderImportCosts>,
+    pub expected_version: i32,
 }
 
 impl FromRequest for OrderInitRequest {
@@ -393,6 +541,9 @@ pub struct CommercePayment {
     pub settlement_details: Option<Vec<PaymentSettlementDetail>>,
     pub payment_id: Option<String>,
     pub payment_order_id: Option<String>,
+    pub payment_status: Option<PaymentStatus>,
+    #[schema(value_type = Option<f64>)]
+    pub payment_amount: Option<BigDecimal>,
 }
 
 impl CommercePayment {
@@ -406,7 +557,7 @@ impl CommercePayment {
     }
 }
 
-#[derive(Deserialize, Debug, ToSchema, sqlx::Type, Serialize, Clone)]
+#[derive(Deserialize, Debug, ToSchema, sqlx::Type, Serialize, Clone, PartialEq)]
 #[sqlx(type_name = "commerce_fulfillment_status_type")]
 #[sqlx(rename_all = "snake_case")]
 pub enum FulfillmentStatusType {
@@ -507,6 +658,8 @@ pub struct CommerceFulfillment {
     pub tat: Option<String>,
     pub fulfillment_status: FulfillmentStatusType,
     pub delivery_term: Option<DeliveryTerm>,
+    /// INCOTERMS the seller confirmed in on_init.
+    pub seller_inco_terms: Option<IncoTermType>,
     pub provider_name: Option<String>,
     pub category: Option<FulfillmentCategoryType>,
     pub servicable_status: Option<ServiceableType>,
@@ -553,8 +706,13 @@ pub struct CommerceItem {
     #[schema(value_type = f64)]
     pub qty: BigDecimal,
     pub buyer_terms: Option<BuyerTerm>,
+    /// Item terms the seller returned in the on_select and on_init item tags.
+    pub seller_terms: Option<BuyerTerm>,
     #[schema(value_type = f64)]
     pub tax_rate: BigDecimal,
+    /// Compensation cess included in the tax rate, as reported by the seller.
+    #[schema(value_type = Option<f64>)]
+    pub cess_rate: Option<BigDecimal>,
     #[schema(value_type = f64)]
     pub tax_value: BigDecimal,
     #[schema(value_type = f64)]
@@ -567,6 +725,59 @@ pub struct CommerceItem {
     pub discount_amount: BigDecimal,
     pub location_ids: Vec<String>,
     pub fulfillment_ids: Vec<String>,
+    #[schema(value_type = Option<f64>)]
+    pub cancelled_qty: Option<BigDecimal>,
+    /// Quantity in the item lines of the latest seller quote after confirmation.
+    #[schema(value_type = Option<f64>)]
+    pub invoiced_qty: Option<BigDecimal>,
+    #[schema(value_type = Option<f64>)]
+    pub refunded_tax_value: Option<BigDecimal>,
+    #[schema(value_type = Option<f64>)]
+    pub refunded_discount_amount: Option<BigDecimal>,
+    #[schema(value_type = Option<f64>)]
+    pub refunded_gross_total: Option<BigDecimal>,
+    pub tax_split: Option<CommerceTaxSplit>,
+    pub import_detail: Option<OrderItemImportDetail>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SupplyType {
+    IntraState,
+    InterState,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommerceTaxSplit {
+    #[schema(value_type = f64)]
+    pub cgst_rate: BigDecimal,
+    #[schema(value_type = f64)]
+    pub cgst_value: BigDecimal,
+    #[schema(value_type = f64)]
+    pub sgst_rate: BigDecimal,
+    #[schema(value_type = f64)]
+    pub sgst_value: BigDecimal,
+    #[schema(value_type = f64)]
+    pub igst_rate: BigDecimal,
+    #[schema(value_type = f64)]
+    pub igst_value: BigDecimal,
+    #[schema(value_type = f64)]
+    pub cess_rate: BigDecimal,
+    #[schema(value_type = f64)]
+    pub cess_value: BigDecimal,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommerceTaxSummary {
+    pub supply_type: SupplyType,
+    pub place_of_supply: Option<String>,
+    pub buyer_gstin: String,
+    pub is_buyer_gstin_valid: bool,
+    pub seller_gstin: Option<String>,
+    pub is_seller_gstin_valid: bool,
+    pub total: CommerceTaxSplit,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -593,6 +804,59 @@ pub struct CommerceBPPTerms {
     pub mandatory_arbitration: bool,
     pub court_jurisdiction: String,
     pub delay_interest: String,
+    pub tax_number: Option<String>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, sqlx::Type, Serialize, Clone, PartialEq)]
+#[sqlx(type_name = "return_status_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReturnStatusType {
+    Initiated,
+    Approved,
+    Picked,
+    Delivered,
+    Rejected,
+}
+
+impl std::fmt::Display for ReturnStatusType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", pascal_to_snake_case(&format!("{:?}", self)))
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommerceReturn {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub return_id: String,
+    pub item_id: String,
+    #[schema(value_type = f64)]
+    pub qty: BigDecimal,
+    pub reason_id: String,
+    pub reason_desc: Option<String>,
+    pub images: Vec<String>,
+    pub fulfillment_id: Option<String>,
+    pub return_status: ReturnStatusType,
+    #[schema(value_type = Option<f64>)]
+    pub refund_amount: Option<BigDecimal>,
+    pub created_on: DateTime<Utc>,
+    pub updated_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteIssueSeverity {
+    Warning,
+    Error,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteIssue {
+    pub severity: QuoteIssueSeverity,
+    pub item_id: Option<String>,
+    pub message: String,
 }
 
 #[derive(Deserialize, Debug, ToSchema, Serialize)]
@@ -615,6 +879,8 @@ pub struct Commerce {
     pub created_by: Uuid,
     #[schema(value_type = Option<f64>)]
     pub grand_total: Option<BigDecimal>,
+    #[schema(value_type = Option<f64>)]
+    pub refund_grand_total: Option<BigDecimal>,
     pub bap: BasicNetworkData,
     pub bpp: BasicNetworkData,
     pub quote_ttl: String,
@@ -631,6 +897,17 @@ pub struct Commerce {
     pub chat_link: Option<String>,
     #[schema(value_type = String)]
     pub buyer_id: Uuid,
+    pub version: i32,
+    pub returns: Vec<CommerceReturn>,
+    pub quote_issues: Option<Vec<QuoteIssue>>,
+    pub tax_summary: Option<CommerceTaxSummary>,
+    pub buyer_terms: Option<CommerceBuyerTerms>,
+    pub buyer_terms_deviations: Vec<BuyerTermsDeviation>,
+    pub import_costs: Option<OrderImportCosts>,
+    pub landed_cost: Option<LandedCostEstimate>,
+    pub customs_documents: Vec<CustomsDocument>,
+    pub missing_customs_documents: Vec<CustomsDocumentType>,
+    pub approval: Option<CommerceApproval>,
 }
 
 impl Commerce {
@@ -715,6 +992,7 @@ pub struct OrderConfirmRequest {
     pub transaction_id: Uuid,
     #[schema(value_type = String)]
     pub message_id: Uuid,
+    pub expected_version: i32,
     // pub payment: OrderConfirmPayment,
 }
 
@@ -734,7 +1012,7 @@ impl FromRequest for OrderConfirmRequest {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq, Clone)]
 #[serde(rename_all = "snake_case")]
 #[sqlx(type_name = "payment_status", rename_all = "snake_case")]
 pub enum PaymentStatus {
@@ -774,11 +1052,18 @@ impl FromRequest for OrderStatusRequest {
 }
 
 #[derive(Deserialize, Debug, Serialize, ToSchema, PartialEq, Eq, Hash, Clone, sqlx::Type)]
+#[sqlx(type_name = "commerce_document_type", rename_all = "snake_case")]
 pub enum DocumentType {
     Invoice,
     ProformaInvoice,
 }
 
+impl std::fmt::Display for DocumentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", pascal_to_snake_case(&format!("{:?}", self)))
+    }
+}
+
 #[derive(Deserialize, Debug, Serialize, ToSchema, Clone, sqlx::Type)]
 pub struct CommerceDocument {
     pub r#type: DocumentType,
@@ -793,6 +1078,9 @@ pub struct OrderCancelRequest {
     #[schema(value_type = String)]
     pub message_id: Uuid,
     pub reason_id: String,
+    pub expected_version: i32,
+    #[schema(value_type = Option<f64>)]
+    pub acknowledged_fee: Option<BigDecimal>,
 }
 impl FromRequest for OrderCancelRequest {
     type Error = GenericError;
@@ -827,10 +1115,17 @@ pub struct BulkCancelFulfillmentData {
     pub refunded_packaging_charges: Vec<BigDecimal>,
 }
 
+#[derive(Debug, Default)]
+pub struct QuoteRefund {
+    pub amount: BigDecimal,
+    pub qty: Option<i32>,
+}
+
 #[derive(Debug)]
 pub struct BulkCancelItemData {
     pub commerce_ids: Vec<Uuid>,
     pub item_ids: Vec<String>,
+    pub cancelled_qtys: Vec<Option<BigDecimal>>,
     pub refunded_tax_values: Vec<BigDecimal>,
     pub refunded_discount_amounts: Vec<BigDecimal>,
     pub refunded_gross_totals: Vec<BigDecimal>,
@@ -859,13 +1154,14 @@ pub enum OrderUpdateType {
     Payment,
     Item,
     Fulfillment,
+    Return,
 }
 
 impl OrderUpdateType {
     pub fn get_ondc_type(&self) -> ONDCOrderUpdateTarget {
         match self {
             OrderUpdateType::Payment => ONDCOrderUpdateTarget::Payment,
-            OrderUpdateType::Item => ONDCOrderUpdateTarget::Item,
+            OrderUpdateType::Item | OrderUpdateType::Return => ONDCOrderUpdateTarget::Item,
             OrderUpdateType::Fulfillment => ONDCOrderUpdateTarget::Fulfillment,
         }
     }
@@ -879,6 +1175,7 @@ pub struct UpdateOrderPaymentRequest {
     #[schema(value_type = String)]
     pub message_id: Uuid,
     pub target_type: OrderUpdateType,
+    pub expected_version: i32,
 }
 #[derive(Deserialize, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -888,7 +1185,45 @@ pub struct UpdateOrderItemRequest {
     #[schema(value_type = String)]
     pub message_id: Uuid,
     pub target_type: OrderUpdateType,
-    pub items: Vec<String>,
+    pub expected_version: i32,
+    pub items: Vec<OrderCancelItem>,
+    pub reason_id: String,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderCancelItem {
+    pub item_id: String,
+    pub qty: i32,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderReturnItem {
+    pub item_id: String,
+    pub qty: i32,
+    pub reason_id: String,
+    pub reason_desc: Option<String>,
+    pub images: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateOrderReturnRequest {
+    #[schema(value_type = String)]
+    pub transaction_id: Uuid,
+    #[schema(value_type = String)]
+    pub message_id: Uuid,
+    pub target_type: OrderUpdateType,
+    pub expected_version: i32,
+    pub returns: Vec<OrderReturnItem>,
+}
+
+impl UpdateOrderReturnRequest {
+    /// Return ids are derived from the request so that the ONDC payload and the stored returns agree.
+    pub fn get_return_id(&self, item_id: &str) -> String {
+        format!("{}_{}", self.message_id.simple(), item_id)
+    }
 }
 
 #[derive(Deserialize, Debug, ToSchema)]
@@ -899,6 +1234,7 @@ pub struct UpdateOrderFulfillmentRequest {
     #[schema(value_type = String)]
     pub message_id: Uuid,
     pub target_type: OrderUpdateType,
+    pub expected_version: i32,
     pub fulfillments: Vec<OrderSelectFulfillment>,
 }
 
@@ -906,15 +1242,17 @@ pub struct UpdateOrderFulfillmentRequest {
 #[serde(untagged)]
 #[allow(clippy::enum_variant_names)]
 pub enum OrderUpdateRequest {
-    UpdatePayment(UpdateOrderPaymentRequest),
+    UpdateReturn(UpdateOrderReturnRequest),
     UpdateItem(UpdateOrderItemRequest),
     UpdateFulfillment(UpdateOrderFulfillmentRequest),
+    UpdatePayment(UpdateOrderPaymentRequest),
 }
 
 impl OrderUpdateRequest {
     pub fn transaction_id(&self) -> Uuid {
         match self {
             OrderUpdateRequest::UpdatePayment(request) => request.transaction_id,
+            OrderUpdateRequest::UpdateReturn(request) => request.transaction_id,
             OrderUpdateRequest::UpdateItem(request) => request.transaction_id,
             OrderUpdateRequest::UpdateFulfillment(request) => request.transaction_id,
         }
@@ -922,10 +1260,19 @@ impl OrderUpdateRequest {
     pub fn message_id(&self) -> Uuid {
         match self {
             OrderUpdateRequest::UpdatePayment(request) => request.message_id,
+            OrderUpdateRequest::UpdateReturn(request) => request.message_id,
             OrderUpdateRequest::UpdateItem(request) => request.message_id,
             OrderUpdateRequest::UpdateFulfillment(request) => request.message_id,
         }
     }
+    pub fn expected_version(&self) -> i32 {
+        match self {
+            OrderUpdateRequest::UpdatePayment(request) => request.expected_version,
+            OrderUpdateRequest::UpdateReturn(request) => request.expected_version,
+            OrderUpdateRequest::UpdateItem(request) => request.expected_version,
+            OrderUpdateRequest::UpdateFulfillment(request) => request.expected_version,
+        }
+    }
 }
 
 impl FromRequest for OrderUpdateRequest {
@@ -966,18 +1313,122 @@ impl FromRequest for OrderReadRequest {
     }
 }
 
+#[derive(Deserialize, Debug, ToSchema, sqlx::Type, Serialize, Clone, PartialEq)]
+#[sqlx(type_name = "approval_status_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatusType {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl std::fmt::Display for ApprovalStatusType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", pascal_to_snake_case(&format!("{:?}", self)))
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommerceApprovalRecord {
+    pub approval_level: i32,
+    pub approval_status: ApprovalStatusType,
+    pub approval_requested_on: DateTime<Utc>,
+    pub remark: Option<String>,
+    #[schema(value_type = String)]
+    pub created_by: Uuid,
+    pub created_on: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommerceApproval {
+    pub status: ApprovalStatusType,
+    pub levels: Vec<i32>,
+    pub requested_on: DateTime<Utc>,
+    pub records: Vec<CommerceApprovalRecord>,
+}
+
+impl CommerceApproval {
+    /// Levels approved in the current approval round, a new quote starts a new round.
+    pub fn get_approved_levels(&self) -> Vec<i32> {
+        self.records
+            .iter()
+            .filter(|record| {
+                record.approval_requested_on == self.requested_on
+                    && record.approval_status == ApprovalStatusType::Approved
+            })
+            .map(|record| record.approval_level)
+            .collect()
+    }
+
+    pub fn get_pending_level(&self) -> Option<i32> {
+        let approved_levels = self.get_approved_levels();
+        self.levels
+            .iter()
+            .find(|level| !approved_levels.contains(level))
+            .copied()
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderApprovalPolicy {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub name: String,
+    pub approval_level: i32,
+    #[schema(value_type = f64)]
+    pub min_amount: BigDecimal,
+    pub domain_category_code: Option<CategoryDomain>,
+    pub seller_id: Option<String>,
+    #[schema(value_type = Vec<String>)]
+    pub approver_ids: Vec<Uuid>,
+    pub is_active: bool,
+    pub created_on: DateTime<Utc>,
+    #[schema(value_type = String)]
+    pub created_by: Uuid,
+}
+
+impl OrderApprovalPolicy {
+    /// A policy applies when the quote exceeds its amount and the optional domain and seller match.
+    pub fn is_applicable(
+        &self,
+        order_value: &BigDecimal,
+        domain_category_code: &CategoryDomain,
+        seller_id: &str,
+    ) -> bool {
+        self.is_active
+            && order_value > &self.min_amount
+            && self
+                .domain_category_code
+                .as_ref()
+                .is_none_or(|domain| domain == domain_category_code)
+            && self
+                .seller_id
+                .as_deref()
+                .is_none_or(|policy_seller_id| policy_seller_id == seller_id)
+    }
+}
+
 #[derive(Deserialize, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
-pub struct OrderListRequest {
+pub struct OrderApprovalPolicySaveRequest {
     #[schema(value_type = Option<String>)]
-    pub transaction_id: Option<Vec<Uuid>>,
-    pub start_date: Option<DateTime<Utc>>,
-    pub end_date: Option<DateTime<Utc>>,
-    pub offset: i32,
-    pub limit: i32,
+    pub id: Option<Uuid>,
+    pub name: String,
+    pub approval_level: i32,
+    #[schema(value_type = f64)]
+    pub min_amount: BigDecimal,
+    pub domain_category_code: Option<CategoryDomain>,
+    pub seller_id: Option<String>,
+    /// Users who can approve the level of the policy.
+    #[schema(value_type = Vec<String>)]
+    pub approver_ids: Vec<Uuid>,
+    pub is_active: bool,
 }
 
-impl FromRequest for OrderListRequest {
+impl FromRequest for OrderApprovalPolicySaveRequest {
     type Error = GenericError;
     type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
 
@@ -993,34 +1444,446 @@ impl FromRequest for OrderListRequest {
     }
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderApprovalAction {
+    Approve,
+    Reject,
+}
 
-pub struct OrderListFilter {
-    pub transaction_id_list: Option<Vec<Uuid>>,
-    pub start_date: Option<DateTime<Utc>>,
-    pub end_date: Option<DateTime<Utc>>,
-    pub offset: i32,
-    pub limit: i32,
-    pub user_id: Option<Uuid>,
-    pub business_id: Uuid,
-    // pub permission_list: Vec<PermissionType>,
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderApprovalRequest {
+    #[schema(value_type = String)]
+    pub transaction_id: Uuid,
+    pub action: OrderApprovalAction,
+    pub remark: Option<String>,
 }
 
-impl OrderListFilter {
-    pub fn new(
-        list_request: OrderListRequest,
+impl FromRequest for OrderApprovalRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema, sqlx::Type, Serialize, Clone, PartialEq)]
+#[sqlx(type_name = "budget_exceed_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetExceedAction {
+    Reject,
+    Approval,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BuyerBudgetSaveRequest {
+    #[schema(value_type = Option<String>)]
+    pub id: Option<Uuid>,
+    pub name: String,
+    #[schema(value_type = Option<String>)]
+    pub user_id: Option<Uuid>,
+    pub domain_category_code: Option<CategoryDomain>,
+    #[schema(value_type = f64)]
+    pub amount: BigDecimal,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub exceed_action: BudgetExceedAction,
+    pub approval_level: Option<i32>,
+    pub is_active: bool,
+}
+
+impl FromRequest for BuyerBudgetSaveRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BuyerBudgetReportRequest {
+    pub at: Option<DateTime<Utc>>,
+}
+
+impl FromRequest for BuyerBudgetReportRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuyerBudget {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub name: String,
+    #[schema(value_type = Option<String>)]
+    pub user_id: Option<Uuid>,
+    pub domain_category_code: Option<CategoryDomain>,
+    #[schema(value_type = f64)]
+    pub amount: BigDecimal,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub exceed_action: BudgetExceedAction,
+    pub approval_level: i32,
+    pub is_active: bool,
+    #[schema(value_type = f64)]
+    pub consumed_amount: BigDecimal,
+    #[schema(value_type = f64)]
+    pub remaining_amount: BigDecimal,
+}
+
+impl BuyerBudget {
+    /// Business wide budgets have no user, budgets without a domain cover every domain.
+    pub fn is_applicable(
+        &self,
+        user_id: Uuid,
+        domain_category_code: &CategoryDomain,
+        at: DateTime<Utc>,
+    ) -> bool {
+        self.is_active
+            && self.period_start <= at
+            && at < self.period_end
+            && self
+                .user_id
+                .is_none_or(|budget_user_id| budget_user_id == user_id)
+            && self
+                .domain_category_code
+                .as_ref()
+                .is_none_or(|domain| domain == domain_category_code)
+    }
+
+    /// A budget is exceeded when the committed spend plus the new order value is above the amount.
+    pub fn is_exceeded_by(&self, order_value: &BigDecimal) -> bool {
+        &self.consumed_amount + order_value > self.amount
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GoodsReceiptItemRequest {
+    pub item_id: String,
+    #[schema(value_type = f64)]
+    pub received_qty: BigDecimal,
+    #[schema(value_type = f64)]
+    pub accepted_qty: BigDecimal,
+    #[schema(value_type = f64)]
+    pub rejected_qty: BigDecimal,
+    pub remark: Option<String>,
+    #[serde(default)]
+    pub images: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GoodsReceiptCreateRequest {
+    #[schema(value_type = String)]
+    pub transaction_id: Uuid,
+    pub grn_no: String,
+    pub fulfillment_id: String,
+    pub received_on: DateTime<Utc>,
+    pub remark: Option<String>,
+    pub items: Vec<GoodsReceiptItemRequest>,
+}
+
+impl FromRequest for GoodsReceiptCreateRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommerceGoodsReceiptItem {
+    pub item_id: String,
+    #[schema(value_type = f64)]
+    pub received_qty: BigDecimal,
+    #[schema(value_type = f64)]
+    pub accepted_qty: BigDecimal,
+    #[schema(value_type = f64)]
+    pub rejected_qty: BigDecimal,
+    pub remark: Option<String>,
+    pub images: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommerceGoodsReceipt {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub grn_no: String,
+    pub fulfillment_id: String,
+    pub received_on: DateTime<Utc>,
+    pub remark: Option<String>,
+    pub created_on: DateTime<Utc>,
+    #[schema(value_type = String)]
+    pub created_by: Uuid,
+    pub items: Vec<CommerceGoodsReceiptItem>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GoodsReceiptMatchStatus {
+    Matched,
+    PendingInvoice,
+    PendingReceipt,
+    QuantityMismatch,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoodsReceiptMatchItem {
+    pub item_id: String,
+    pub item_name: String,
+    #[schema(value_type = f64)]
+    pub ordered_qty: BigDecimal,
+    #[schema(value_type = Option<f64>)]
+    pub invoiced_qty: Option<BigDecimal>,
+    #[schema(value_type = f64)]
+    pub received_qty: BigDecimal,
+    #[schema(value_type = f64)]
+    pub accepted_qty: BigDecimal,
+    #[schema(value_type = f64)]
+    pub rejected_qty: BigDecimal,
+    pub status: GoodsReceiptMatchStatus,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoodsReceiptMatchReport {
+    #[schema(value_type = String)]
+    pub transaction_id: Uuid,
+    pub is_matched: bool,
+    pub items: Vec<GoodsReceiptMatchItem>,
+}
+
+/// Order filters shared by the order list, export and analytics requests.
+#[derive(Deserialize, Debug, ToSchema, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListQuery {
+    #[schema(value_type = Option<Vec<String>>)]
+    pub transaction_id: Option<Vec<Uuid>>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub record_status: Option<Vec<CommerceStatusType>>,
+    pub record_type: Option<Vec<OrderType>>,
+    pub bpp_id: Option<Vec<String>>,
+    pub seller_id: Option<Vec<String>>,
+    pub domain_category_code: Option<Vec<CategoryDomain>>,
+    pub city_code: Option<Vec<String>>,
+    #[schema(value_type = Option<f64>)]
+    pub min_amount: Option<BigDecimal>,
+    #[schema(value_type = Option<f64>)]
+    pub max_amount: Option<BigDecimal>,
+    pub payment_status: Option<Vec<PaymentStatus>>,
+    pub fulfillment_status: Option<Vec<FulfillmentStatusType>>,
+    #[schema(value_type = Option<Vec<String>>)]
+    pub created_by: Option<Vec<Uuid>>,
+    /// Matched against the order number, seller name and item names.
+    pub search: Option<String>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderListSortField {
+    #[default]
+    CreatedOn,
+    GrandTotal,
+    Urn,
+}
+
+impl OrderListSortField {
+    pub fn get_column(&self) -> &'static str {
+        match self {
+            OrderListSortField::CreatedOn => "created_on",
+            OrderListSortField::GrandTotal => "COALESCE(grand_total, 0)",
+            OrderListSortField::Urn => "urn",
+        }
+    }
+
+    pub fn get_cursor_value(&self, order: &CommerceList) -> String {
+        match self {
+            OrderListSortField::CreatedOn => order.created_on.to_rfc3339(),
+            OrderListSortField::GrandTotal => order.grand_total.to_string(),
+            OrderListSortField::Urn => order.urn.clone(),
+        }
+    }
+
+    pub fn is_valid_cursor_value(&self, value: &str) -> bool {
+        match self {
+            OrderListSortField::CreatedOn => DateTime::parse_from_rfc3339(value).is_ok(),
+            OrderListSortField::GrandTotal => BigDecimal::from_str(value).is_ok(),
+            OrderListSortField::Urn => true,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    pub fn get_keyword(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+
+    /// Comparison that selects the rows after the cursor in this order.
+    pub fn get_cursor_operator(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => ">",
+            SortOrder::Desc => "<",
+        }
+    }
+}
+
+/// Position of the last row of a page, the id breaks ties between equal sort values.
+#[derive(Deserialize, Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListCursor {
+    pub sort_by: OrderListSortField,
+    pub sort_order: SortOrder,
+    pub value: String,
+    pub id: Uuid,
+}
+
+impl OrderListCursor {
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, GenericError> {
+        URL_SAFE_NO_PAD
+            .decode(cursor)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Self>(&bytes).ok())
+            .filter(|cursor| cursor.sort_by.is_valid_cursor_value(&cursor.value))
+            .ok_or_else(|| GenericError::ValidationError("Invalid cursor".to_string()))
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListRequest {
+    #[serde(flatten)]
+    pub query: OrderListQuery,
+    #[serde(default)]
+    pub sort_by: OrderListSortField,
+    #[serde(default)]
+    pub sort_order: SortOrder,
+    /// `nextCursor` of the previous page from `/order/list/page`, takes precedence over the offset.
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub offset: i32,
+    pub limit: i32,
+}
+
+impl FromRequest for OrderListRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct OrderListFilter {
+    pub query: OrderListQuery,
+    pub sort_by: OrderListSortField,
+    pub sort_order: SortOrder,
+    pub cursor: Option<OrderListCursor>,
+    pub offset: i32,
+    pub limit: i32,
+    pub user_id: Option<Uuid>,
+    pub business_id: Uuid,
+    // pub permission_list: Vec<PermissionType>,
+}
+
+impl OrderListFilter {
+    pub fn new(
+        list_request: OrderListRequest,
         user_id: Option<Uuid>,
         business_id: Uuid,
-    ) -> OrderListFilter {
-        OrderListFilter {
-            transaction_id_list: list_request.transaction_id,
-            start_date: list_request.start_date,
-            end_date: list_request.end_date,
-            offset: list_request.offset,
+    ) -> Result<OrderListFilter, GenericError> {
+        let cursor = list_request
+            .cursor
+            .as_deref()
+            .map(OrderListCursor::decode)
+            .transpose()?;
+        if cursor.as_ref().is_some_and(|cursor| {
+            cursor.sort_by != list_request.sort_by || cursor.sort_order != list_request.sort_order
+        }) {
+            return Err(GenericError::ValidationError(
+                "Cursor does not match the requested sort".to_string(),
+            ));
+        }
+        Ok(OrderListFilter {
+            query: list_request.query,
+            sort_by: list_request.sort_by,
+            sort_order: list_request.sort_order,
+            offset: if cursor.is_some() {
+                0
+            } else {
+                list_request.offset
+            },
+            cursor,
             limit: list_request.limit,
             user_id,
             business_id,
-        }
+        })
     }
 
     pub fn from_transaction_id(
@@ -1029,29 +1892,270 @@ impl OrderListFilter {
         business_id: Uuid,
     ) -> OrderListFilter {
         OrderListFilter {
-            transaction_id_list: Some(transaction_id_list),
-            start_date: None,
-            end_date: None,
+            query: OrderListQuery {
+                transaction_id: Some(transaction_id_list),
+                ..Default::default()
+            },
+            sort_by: OrderListSortField::default(),
+            sort_order: SortOrder::default(),
+            cursor: None,
             offset: 0,
             limit: 1,
             business_id,
             user_id,
         }
     }
+
+    /// Analytics aggregate over every matching order, so no paging is applied.
+    pub fn from_analytics_request(
+        analytics_request: OrderAnalyticsRequest,
+        user_id: Option<Uuid>,
+        business_id: Uuid,
+    ) -> OrderListFilter {
+        OrderListFilter {
+            query: analytics_request.query,
+            sort_by: OrderListSortField::default(),
+            sort_order: SortOrder::default(),
+            cursor: None,
+            offset: 0,
+            limit: 0,
+            business_id,
+            user_id,
+        }
+    }
+
+    pub fn from_export_request(
+        export_request: &OrderExportRequest,
+        user_id: Option<Uuid>,
+        business_id: Uuid,
+    ) -> OrderListFilter {
+        OrderListFilter {
+            query: export_request.query.clone(),
+            sort_by: OrderListSortField::CreatedOn,
+            sort_order: SortOrder::Desc,
+            cursor: None,
+            offset: 0,
+            limit: ORDER_EXPORT_PAGE_SIZE,
+            business_id,
+            user_id,
+        }
+    }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
-pub struct CommerceList {
-    pub id: Uuid,
-    pub external_urn: Uuid,
-    pub urn: String,
-    pub currency_code: CurrencyType,
-    pub grand_total: BigDecimal,
+pub struct CommerceListPage {
+    pub orders: Vec<CommerceList>,
+    pub total_count: i64,
+    /// Cursor of the next page, none on the last page.
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, sqlx::Type, Serialize, Clone, PartialEq)]
+#[sqlx(type_name = "order_export_format", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum OrderExportFormat {
+    Csv,
+    Xlsx,
+}
+
+impl OrderExportFormat {
+    pub fn get_content_type(&self) -> &'static str {
+        match self {
+            OrderExportFormat::Csv => "text/csv; charset=utf-8",
+            OrderExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        }
+    }
+
+    pub fn get_file_name(&self, created_on: DateTime<Utc>) -> String {
+        let extension = match self {
+            OrderExportFormat::Csv => "csv",
+            OrderExportFormat::Xlsx => "xlsx",
+        };
+        format!("orders_{}.{}", created_on.format("%Y%m%d%H%M%S"), extension)
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema, sqlx::Type, Serialize, Clone, PartialEq)]
+#[sqlx(type_name = "order_export_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum OrderExportStatus {
+    Processing,
+    Completed,
+    Failed,
+    Expired,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderExportRequest {
+    #[serde(flatten)]
+    pub query: OrderListQuery,
+    pub format: OrderExportFormat,
+}
+
+impl FromRequest for OrderExportRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderExportJobRequest {
+    #[schema(value_type = String)]
+    pub job_id: Uuid,
+}
+
+impl FromRequest for OrderExportJobRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+/// Export that was too large to stream, the file is downloaded once the status is completed.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderExportJob {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub format: OrderExportFormat,
+    pub status: OrderExportStatus,
+    pub order_count: i64,
+    pub file_name: String,
+    pub error: Option<String>,
+    pub created_on: DateTime<Utc>,
+    pub completed_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAnalyticsRequest {
+    #[serde(flatten)]
+    pub query: OrderListQuery,
+}
+
+impl FromRequest for OrderAnalyticsRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderSpendGroup {
+    pub key: String,
+    pub label: Option<String>,
+    pub order_count: i64,
+    #[schema(value_type = Option<f64>)]
+    pub qty: Option<BigDecimal>,
+    #[schema(value_type = f64)]
+    pub amount: BigDecimal,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderStatusCount {
+    pub record_status: CommerceStatusType,
+    pub order_count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderQuoteSavings {
+    pub order_count: i64,
+    #[schema(value_type = f64)]
+    pub first_quote_total: BigDecimal,
+    #[schema(value_type = f64)]
+    pub final_quote_total: BigDecimal,
+    #[schema(value_type = f64)]
+    pub savings_amount: BigDecimal,
+    #[schema(value_type = Option<f64>)]
+    pub savings_percentage: Option<BigDecimal>,
+}
+
+impl OrderQuoteSavings {
+    pub fn new(
+        order_count: i64,
+        first_quote_total: BigDecimal,
+        final_quote_total: BigDecimal,
+    ) -> Self {
+        let savings_amount = &first_quote_total - &final_quote_total;
+        let savings_percentage = (first_quote_total > BigDecimal::from(0))
+            .then(|| (&savings_amount * BigDecimal::from(100) / &first_quote_total).round(2));
+        Self {
+            order_count,
+            first_quote_total,
+            final_quote_total,
+            savings_amount,
+            savings_percentage,
+        }
+    }
+}
+
+/// Spend only counts orders committed with the seller, net of refunds.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAnalytics {
+    pub spend_by_month: Vec<OrderSpendGroup>,
+    pub spend_by_seller: Vec<OrderSpendGroup>,
+    pub spend_by_domain: Vec<OrderSpendGroup>,
+    pub spend_by_city: Vec<OrderSpendGroup>,
+    pub spend_by_item: Vec<OrderSpendGroup>,
+    pub status_count: Vec<OrderStatusCount>,
+    pub avg_quote_to_confirm_secs: Option<f64>,
+    pub quote_savings: OrderQuoteSavings,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CommerceList {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    #[schema(value_type = String)]
+    pub external_urn: Uuid,
+    pub urn: String,
+    pub currency_code: CurrencyType,
+    #[schema(value_type = f64)]
+    pub grand_total: BigDecimal,
     pub record_status: CommerceStatusType,
     pub created_on: DateTime<Utc>,
     pub seller: CommerceSeller,
+    #[schema(value_type = String)]
     pub buyer_id: Uuid,
+    #[schema(value_type = String)]
     pub created_by: Uuid,
     pub record_type: OrderType,
 }
@@ -1071,3 +2175,875 @@ pub struct MinimalCommerceData {
     pub created_by: Uuid,
     pub record_type: OrderType,
 }
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineEventType {
+    Request,
+    StatusChange,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderTimelineEvent {
+    pub event_type: TimelineEventType,
+    pub entity_type: Option<StatusEntityType>,
+    pub entity_id: Option<String>,
+    pub previous_status: Option<String>,
+    pub status: Option<String>,
+    pub actor_type: StatusActorType,
+    pub actor_id: String,
+    pub action_type: String,
+    #[schema(value_type = Option<String>)]
+    pub message_id: Option<Uuid>,
+    pub is_valid: bool,
+    pub detail: Option<Value>,
+    pub created_on: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderCancellationPreview {
+    pub is_cancellable: bool,
+    pub fulfillment_state: Option<FulfillmentStatusType>,
+    pub reason_required: bool,
+    pub fee_type: Option<CancellationFeeType>,
+    #[schema(value_type = Option<f64>)]
+    pub fee_value: Option<BigDecimal>,
+    #[schema(value_type = f64)]
+    pub cancellation_fee: BigDecimal,
+    #[schema(value_type = f64)]
+    pub expected_refund: BigDecimal,
+    pub currency_type: Option<CurrencyType>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SellerScorecardRequest {
+    pub bpp_id: Option<String>,
+    pub provider_id: Option<Vec<String>>,
+}
+
+impl FromRequest for SellerScorecardRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+/// Rates are `None` until the seller has the underlying activity.
+#[derive(Debug, Serialize, ToSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SellerScorecard {
+    pub bpp_id: String,
+    pub provider_id: String,
+    pub provider_name: Option<String>,
+    pub quote_response_count: i32,
+    pub avg_quote_response_secs: Option<i64>,
+    pub quote_rejection_rate: Option<f64>,
+    pub order_acceptance_rate: Option<f64>,
+    pub seller_cancellation_rate: Option<f64>,
+    pub delivered_count: i32,
+    pub on_time_delivery_rate: Option<f64>,
+    pub updated_on: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, sqlx::Type, Serialize, Clone, PartialEq)]
+#[sqlx(type_name = "standing_order_frequency", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum StandingOrderFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Deserialize, Debug, ToSchema, sqlx::Type, Serialize, Clone, PartialEq)]
+#[sqlx(type_name = "standing_order_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum StandingOrderStatus {
+    Active,
+    Paused,
+}
+
+#[derive(Deserialize, Debug, ToSchema, sqlx::Type, Serialize, Clone, PartialEq)]
+#[sqlx(type_name = "standing_order_event_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum StandingOrderEventType {
+    Created,
+    Updated,
+    Paused,
+    Resumed,
+}
+
+#[derive(Deserialize, Debug, ToSchema, sqlx::Type, Serialize, Clone, PartialEq)]
+#[sqlx(type_name = "standing_order_run_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum StandingOrderRunStatus {
+    SelectSent,
+    InitSent,
+    ConfirmSent,
+    Confirmed,
+    AwaitingBuyer,
+    Failed,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StandingOrderAutoConfirm {
+    pub billing: OrderInitBilling,
+    #[serde(default)]
+    pub import_costs: Option<OrderImportCosts>,
+    #[schema(value_type = f64)]
+    pub reference_total: BigDecimal,
+    #[schema(value_type = f64)]
+    pub price_tolerance: BigDecimal,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StandingOrderSaveRequest {
+    #[schema(value_type = Option<String>)]
+    pub id: Option<Uuid>,
+    pub name: String,
+    pub bpp_id: String,
+    pub provider_id: String,
+    pub domain_category_code: CategoryDomain,
+    pub payment_types: Vec<PaymentType>,
+    pub order_type: OrderType,
+    pub ttl: String,
+    pub items: Vec<OrderSelectItem>,
+    pub fulfillments: Vec<OrderSelectFulfillment>,
+    pub frequency: StandingOrderFrequency,
+    pub interval_count: i32,
+    pub next_run_on: DateTime<Utc>,
+    pub end_on: Option<DateTime<Utc>>,
+    pub auto_confirm: Option<StandingOrderAutoConfirm>,
+}
+
+impl FromRequest for StandingOrderSaveRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StandingOrderStatusRequest {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub status: StandingOrderStatus,
+}
+
+impl FromRequest for StandingOrderStatusRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StandingOrderHistoryRequest {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+}
+
+impl FromRequest for StandingOrderHistoryRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StandingOrder {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    #[schema(value_type = String)]
+    pub business_id: Uuid,
+    pub name: String,
+    pub bpp_id: String,
+    pub provider_id: String,
+    pub domain_category_code: CategoryDomain,
+    pub payment_types: Vec<PaymentType>,
+    pub order_type: OrderType,
+    pub ttl: String,
+    pub items: Vec<OrderSelectItem>,
+    pub fulfillments: Vec<OrderSelectFulfillment>,
+    pub frequency: StandingOrderFrequency,
+    pub interval_count: i32,
+    pub next_run_on: DateTime<Utc>,
+    pub end_on: Option<DateTime<Utc>>,
+    pub auto_confirm: Option<StandingOrderAutoConfirm>,
+    pub status: StandingOrderStatus,
+    pub created_on: DateTime<Utc>,
+    #[schema(value_type = String)]
+    pub created_by: Uuid,
+    pub updated_on: Option<DateTime<Utc>>,
+    #[schema(value_type = Option<String>)]
+    pub updated_by: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StandingOrderEvent {
+    pub event_type: StandingOrderEventType,
+    pub detail: Value,
+    pub created_on: DateTime<Utc>,
+    #[schema(value_type = String)]
+    pub created_by: Uuid,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StandingOrderRun {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    #[schema(value_type = String)]
+    pub standing_order_id: Uuid,
+    pub standing_order_name: String,
+    #[schema(value_type = String)]
+    pub transaction_id: Uuid,
+    pub scheduled_on: DateTime<Utc>,
+    pub status: StandingOrderRunStatus,
+    #[schema(value_type = Option<f64>)]
+    pub quote_total: Option<BigDecimal>,
+    pub message: Option<String>,
+    pub created_on: DateTime<Utc>,
+    pub updated_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StandingOrderHistory {
+    pub events: Vec<StandingOrderEvent>,
+    pub runs: Vec<StandingOrderRun>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CartItemAddRequest {
+    pub domain_category_code: CategoryDomain,
+    pub bpp_id: String,
+    pub provider_id: String,
+    pub item_id: String,
+    pub location_id: String,
+    pub qty: i32,
+    pub buyer_term: Option<BuyerTerms>,
+    pub import_detail: Option<OrderItemImportDetail>,
+}
+
+impl FromRequest for CartItemAddRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CartItemUpdateRequest {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub qty: i32,
+    pub buyer_term: Option<BuyerTerms>,
+    pub import_detail: Option<OrderItemImportDetail>,
+}
+
+impl FromRequest for CartItemUpdateRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CartItemRemoveRequest {
+    #[schema(value_type = Vec<String>)]
+    pub ids: Vec<Uuid>,
+}
+
+impl FromRequest for CartItemRemoveRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CartItem {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub domain_category_code: CategoryDomain,
+    pub bpp_id: String,
+    pub provider_id: String,
+    pub item_id: String,
+    pub item_name: String,
+    pub location_id: String,
+    pub qty: i32,
+    #[schema(value_type = f64)]
+    pub unit_price: BigDecimal,
+    pub currency_code: CurrencyType,
+    pub buyer_term: Option<BuyerTerms>,
+    pub import_detail: Option<OrderItemImportDetail>,
+    pub created_on: DateTime<Utc>,
+    pub updated_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CartCheckoutRequest {
+    pub payment_types: Vec<PaymentType>,
+    pub ttl: String,
+    pub order_type: OrderType,
+    pub fulfillment: OrderSelectFulfillment,
+}
+
+impl FromRequest for CartCheckoutRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CartCheckoutFetchRequest {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+}
+
+impl FromRequest for CartCheckoutFetchRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CartCheckoutOrder {
+    #[schema(value_type = String)]
+    pub transaction_id: Uuid,
+    pub bpp_id: String,
+    pub provider_id: String,
+    pub items: Vec<CartItem>,
+    pub error: Option<String>,
+    pub record_status: Option<CommerceStatusType>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CartCheckout {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub created_on: DateTime<Utc>,
+    pub orders: Vec<CartCheckoutOrder>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WeekDay {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+/// Receiving hours of a delivery location, times are `HH:MM` in the local time of the location.
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryLocationOpeningHours {
+    pub days: Vec<WeekDay>,
+    pub open_time: String,
+    pub close_time: String,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryLocationSaveRequest {
+    #[schema(value_type = Option<String>)]
+    pub id: Option<Uuid>,
+    pub name: String,
+    pub location: SelectFulfillmentLocation,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    pub opening_hours: Vec<DeliveryLocationOpeningHours>,
+    pub is_default: bool,
+}
+
+impl FromRequest for DeliveryLocationSaveRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryLocation {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub name: String,
+    pub location: SelectFulfillmentLocation,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    pub opening_hours: Vec<DeliveryLocationOpeningHours>,
+    pub is_default: bool,
+    pub created_on: DateTime<Utc>,
+    #[schema(value_type = String)]
+    pub created_by: Uuid,
+    pub updated_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingProfileSaveRequest {
+    #[schema(value_type = Option<String>)]
+    pub id: Option<Uuid>,
+    pub name: String,
+    pub billing: OrderInitBilling,
+    pub country_code: CountryCode,
+    pub is_default: bool,
+}
+
+impl FromRequest for BillingProfileSaveRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingProfile {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub name: String,
+    pub billing: OrderInitBilling,
+    pub country_code: CountryCode,
+    pub is_default: bool,
+    pub created_on: DateTime<Utc>,
+    #[schema(value_type = String)]
+    pub created_by: Uuid,
+    pub updated_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBookDeleteRequest {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+}
+
+impl FromRequest for AddressBookDeleteRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BuyerTermsSpec {
+    pub item_req: String,
+    pub packaging_req: String,
+    pub inco_terms: Option<IncoTermType>,
+    pub payment_types: Vec<PaymentType>,
+    #[schema(value_type = Option<f64>)]
+    pub max_delay_interest: Option<BigDecimal>,
+    pub required_certificates: Vec<String>,
+}
+
+impl BuyerTermsSpec {
+    pub fn get_item_buyer_terms(&self) -> BuyerTerms {
+        let item_req = if self.required_certificates.is_empty() {
+            self.item_req.clone()
+        } else {
+            format!(
+                "{}; Certificates required: {}",
+                self.item_req,
+                self.required_certificates.join(", ")
+            )
+        };
+        BuyerTerms {
+            item_req,
+            packaging_req: self.packaging_req.clone(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BuyerTermsTemplateSaveRequest {
+    #[schema(value_type = Option<String>)]
+    pub id: Option<Uuid>,
+    pub name: String,
+    pub domain_category_code: Option<CategoryDomain>,
+    pub terms: BuyerTermsSpec,
+    pub is_default: bool,
+}
+
+impl FromRequest for BuyerTermsTemplateSaveRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BuyerTermsTemplateDeleteRequest {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+}
+
+impl FromRequest for BuyerTermsTemplateDeleteRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BuyerTermsTemplate {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub name: String,
+    pub domain_category_code: Option<CategoryDomain>,
+    pub terms: BuyerTermsSpec,
+    pub is_default: bool,
+    pub created_on: DateTime<Utc>,
+    #[schema(value_type = String)]
+    pub created_by: Uuid,
+    pub updated_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommerceBuyerTerms {
+    #[schema(value_type = String)]
+    pub template_id: Uuid,
+    pub template_name: String,
+    pub terms: BuyerTermsSpec,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BuyerTermsDeviationType {
+    ItemTerms,
+    IncoTerms,
+    PaymentType,
+    DelayInterest,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BuyerTermsDeviation {
+    pub r#type: BuyerTermsDeviationType,
+    pub ref_id: Option<String>,
+    pub expected: String,
+    pub actual: Option<String>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderImportCosts {
+    #[schema(value_type = f64)]
+    pub freight_amount: BigDecimal,
+    #[schema(value_type = f64)]
+    pub insurance_amount: BigDecimal,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LandedCostLine {
+    pub item_id: String,
+    pub hs_code: String,
+    pub country_of_origin: CountryCode,
+    #[schema(value_type = f64)]
+    pub goods_value: BigDecimal,
+    #[schema(value_type = f64)]
+    pub assessable_value: BigDecimal,
+    #[schema(value_type = f64)]
+    pub duty_rate: BigDecimal,
+    #[schema(value_type = f64)]
+    pub duty_value: BigDecimal,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LandedCostEstimate {
+    pub inco_terms: IncoTermType,
+    #[schema(value_type = f64)]
+    pub goods_value: BigDecimal,
+    #[schema(value_type = f64)]
+    pub freight_amount: BigDecimal,
+    #[schema(value_type = f64)]
+    pub insurance_amount: BigDecimal,
+    #[schema(value_type = f64)]
+    pub duty_value: BigDecimal,
+    #[schema(value_type = f64)]
+    pub landed_cost: BigDecimal,
+    pub lines: Vec<LandedCostLine>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, sqlx::Type, Serialize, Clone, PartialEq)]
+#[sqlx(type_name = "customs_document_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CustomsDocumentType {
+    CommercialInvoice,
+    PackingList,
+    BillOfLading,
+    AirwayBill,
+    BillOfEntry,
+    CertificateOfOrigin,
+    InsuranceCertificate,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomsDocumentSaveRequest {
+    #[schema(value_type = Option<String>)]
+    pub id: Option<Uuid>,
+    #[schema(value_type = String)]
+    pub transaction_id: Uuid,
+    pub document_type: CustomsDocumentType,
+    pub document_no: String,
+    pub url: Option<String>,
+    #[schema(value_type = Option<String>)]
+    pub issued_on: Option<NaiveDate>,
+    pub remark: Option<String>,
+}
+
+impl FromRequest for CustomsDocumentSaveRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomsDocumentDeleteRequest {
+    #[schema(value_type = String)]
+    pub transaction_id: Uuid,
+    #[schema(value_type = String)]
+    pub id: Uuid,
+}
+
+impl FromRequest for CustomsDocumentDeleteRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomsDocument {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub document_type: CustomsDocumentType,
+    pub document_no: String,
+    pub url: Option<String>,
+    #[schema(value_type = Option<String>)]
+    pub issued_on: Option<NaiveDate>,
+    pub remark: Option<String>,
+    pub created_on: DateTime<Utc>,
+    #[schema(value_type = String)]
+    pub created_by: Uuid,
+    pub updated_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug, ToSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderDocumentFile {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub document_type: DocumentType,
+    pub version: i32,
+    pub source_url: String,
+    pub checksum: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub created_on: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderDocumentDownloadRequest {
+    #[schema(value_type = String)]
+    pub transaction_id: Uuid,
+    pub document_type: DocumentType,
+    /// Latest stored version when not given.
+    pub version: Option<i32>,
+}
+
+impl FromRequest for OrderDocumentDownloadRequest {
+    type Error = GenericError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let fut = web::Json::<Self>::from_request(req, payload);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(json) => Ok(json.into_inner()),
+                Err(e) => Err(GenericError::ValidationError(e.to_string())),
+            }
+        })
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// Seller side outcomes that feed the seller scorecard, recorded as the callbacks arrive.
+#[derive(Debug, PartialEq)]
+pub enum SellerScorecardEvent {
+    QuoteResponded {
+        message_id: Uuid,
+        response_secs: i64,
+        is_rejected: bool,
+    },
+    OrderConfirmed {
+        is_rejected: bool,
+    },
+    SellerCancelled,
+    Delivered {
+        fulfillment_id: String,
+        is_on_time: Option<bool>,
+    },
+}
+
+impl SellerScorecardEvent {
+    /// Identifies the event within a transaction so that a replayed callback is counted once.
+    pub fn get_key(&self) -> String {
+        match self {
+            SellerScorecardEvent::QuoteResponded { message_id, .. } => {
+                format!("quote_responded:{}", message_id)
+            }
+            SellerScorecardEvent::OrderConfirmed { .. } => "order_confirmed".to_string(),
+            SellerScorecardEvent::SellerCancelled => "seller_cancelled".to_string(),
+            SellerScorecardEvent::Delivered { fulfillment_id, .. } => {
+                format!("delivered:{}", fulfillment_id)
+            }
+        }
+    }
+}
+
+/// Counter increments applied to a scorecard row.
+#[derive(Debug, Default, PartialEq)]
+pub struct SellerScorecardDelta {
+    pub quote_response_count: i32,
+    pub quote_response_secs: i64,
+    pub quote_rejected_count: i32,
+    pub order_accepted_count: i32,
+    pub order_rejected_count: i32,
+    pub seller_cancelled_count: i32,
+    pub delivered_count: i32,
+    pub delivery_promised_count: i32,
+    pub on_time_delivery_count: i32,
+}
+
+pub fn get_scorecard_delta(events: &[SellerScorecardEvent]) -> SellerScorecardDelta {
+    let mut delta = SellerScorecardDelta::default();
+    for event in events {
+        match event {
+            SellerScorecardEvent::QuoteResponded {
+                response_secs,
+                is_rejected,
+                ..
+            } => {
+                delta.quote_response_count += 1;
+                delta.quote_response_secs += (*response_secs).max(0);
+                delta.quote_rejected_count += i32::from(*is_rejected);
+            }
+            SellerScorecardEvent::OrderConfirmed { is_rejected: true } => {
+                delta.order_rejected_count += 1
+            }
+            SellerScorecardEvent::OrderConfirmed { is_rejected: false } => {
+                delta.order_accepted_count += 1
+            }
+            SellerScorecardEvent::SellerCancelled => delta.seller_cancelled_count += 1,
+            SellerScorecardEvent::Delivered { is_on_time, .. } => {
+                delta.delivered_count += 1;
+                if let Some(is_on_time) = is_on_time {
+                    delta.delivery_promised_count += 1;
+                    delta.on_time_delivery_count += i32::from(*is_on_time);
+                }
+            }
+        }
+    }
+    delta
+}
+
+/// Parses the ISO 8601 duration used for the ONDC `TAT`, e.g. `P2D`, `PT48H` or `P1DT12H`.
+/// Years and months are not accepted as their length is ambiguous.
+pub fn parse_tat(tat: &str) -> Option<Duration> {
+    let tat = tat.trim().strip_prefix('P')?;
+    let (date_part, time_part) = match tat.split_once('T') {
+        Some((date_part, time_part)) if !time_part.is_empty() => (date_part, Some(time_part)),
+        Some(_) => return None,
+        None => (tat, None),
+    };
+    let mut total = Duration::zero();
+    let mut is_empty = true;
+    for (part, units) in [
+        (date_part, &[('W', 604800), ('D', 86400)][..]),
+        (
+            time_part.unwrap_or(""),
+            &[('H', 3600), ('M', 60), ('S', 1)][..],
+        ),
+    ] {
+        let mut number = String::new();
+        let mut unit_idx = 0;
+        for char in part.chars() {
+            if char.is_ascii_digit() {
+                number.push(char);
+                continue;
+            }
+            let position = units[unit_idx..]
+                .iter()
+                .position(|(unit, _)| *unit == char)?;
+            let value: i64 = number.parse().ok()?;
+            total += Duration::seconds(value.checked_mul(units[unit_idx + position].1)?);
+            unit_idx += position + 1;
+            number.clear();
+            is_empty = false;
+        }
+        if !number.is_empty() {
+            return None;
+        }
+    }
+    (!is_empty).then_some(total)
+}
+
+/// A delivery is on time when it happens within the promised `TAT` of the confirmation.
+/// Returns `None` when there is no usable promise to compare against.
+pub fn is_delivered_on_time(
+    confirmed_on: Option<DateTime<Utc>>,
+    tat: Option<&str>,
+    delivered_on: DateTime<Utc>,
+) -> Option<bool> {
+    let promised_on = confirmed_on? + parse_tat(tat?)?;
+    Some(delivered_on <= promised_on)
+}
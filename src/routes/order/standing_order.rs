@@ -0,0 +1,513 @@
+use actix_web::web;
+use anyhow::anyhow;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Duration, Months, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::handlers::{send_order_confirm, send_order_init, send_order_select};
+use super::schemas::{
+    OrderConfirmRequest, OrderInitRequest, OrderSelectRequest, StandingOrder,
+    StandingOrderAutoConfirm, StandingOrderFrequency, StandingOrderRun, StandingOrderRunStatus,
+};
+use super::utils::{
+    fetch_order_by_id, fetch_standing_order, fetch_standing_order_run, update_standing_order_run,
+};
+use crate::chat_client::ChatClient;
+use crate::errors::GenericError;
+use crate::schemas::{RequestMetaData, Status, WebSocketParam};
+use crate::user_client::{BusinessAccount, CustomerType, PermissionType, UserAccount, UserClient};
+use crate::utils::validate_business_account_active;
+use crate::websocket_client::{NotificationProcessType, WebSocketActionType, WebSocketClient};
+
+pub fn get_next_run_on(
+    run_on: DateTime<Utc>,
+    frequency: &StandingOrderFrequency,
+    interval_count: i32,
+) -> DateTime<Utc> {
+    let interval_count = interval_count.max(1);
+    match frequency {
+        StandingOrderFrequency::Daily => run_on + Duration::days(interval_count.into()),
+        StandingOrderFrequency::Weekly => run_on + Duration::weeks(interval_count.into()),
+        StandingOrderFrequency::Monthly => run_on
+            .checked_add_months(Months::new(interval_count as u32))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC),
+    }
+}
+
+pub fn get_upcoming_run_on(standing_order: &StandingOrder, now: DateTime<Utc>) -> DateTime<Utc> {
+    let mut run_on = standing_order.next_run_on;
+    while run_on <= now {
+        run_on = get_next_run_on(
+            run_on,
+            &standing_order.frequency,
+            standing_order.interval_count,
+        );
+    }
+    run_on
+}
+
+pub fn is_within_price_tolerance(
+    quote_total: &BigDecimal,
+    auto_confirm: &StandingOrderAutoConfirm,
+) -> bool {
+    let max_total = &auto_confirm.reference_total
+        * (BigDecimal::from(100) + &auto_confirm.price_tolerance)
+        / BigDecimal::from(100);
+    quote_total <= &max_total
+}
+
+/// The seller can still change the total on init, so the tolerance is checked again before confirming.
+pub fn get_standing_order_init_status(
+    error: Option<String>,
+    quote_total: Option<&BigDecimal>,
+    auto_confirm: Option<&StandingOrderAutoConfirm>,
+) -> (StandingOrderRunStatus, Option<String>) {
+    match (error, quote_total, auto_confirm) {
+        (Some(error), _, _) => (
+            StandingOrderRunStatus::AwaitingBuyer,
+            Some(format!("Seller rejected the init: {}", error)),
+        ),
+        (None, Some(quote_total), Some(auto_confirm))
+            if is_within_price_tolerance(quote_total, auto_confirm) =>
+        {
+            (StandingOrderRunStatus::ConfirmSent, None)
+        }
+        (None, Some(quote_total), Some(auto_confirm)) => (
+            StandingOrderRunStatus::AwaitingBuyer,
+            Some(format!(
+                "Quote total {} exceeds the reference total {} by more than {}%",
+                quote_total, auto_confirm.reference_total, auto_confirm.price_tolerance
+            )),
+        ),
+        (None, _, _) => (
+            StandingOrderRunStatus::AwaitingBuyer,
+            Some("Quote received, the order is to be confirmed by the buyer".to_string()),
+        ),
+    }
+}
+
+pub fn get_standing_order_select_request(
+    standing_order: &StandingOrder,
+    transaction_id: Uuid,
+) -> OrderSelectRequest {
+    OrderSelectRequest {
+        transaction_id,
+        message_id: Uuid::new_v4(),
+        domain_category_code: standing_order.domain_category_code.clone(),
+        payment_types: standing_order.payment_types.clone(),
+        provider_id: standing_order.provider_id.clone(),
+        items: standing_order.items.clone(),
+        ttl: standing_order.ttl.clone(),
+        fulfillments: standing_order.fulfillments.clone(),
+        order_type: standing_order.order_type.clone(),
+        bpp_id: standing_order.bpp_id.clone(),
+        saved_fulfillments: vec![],
+        buyer_terms_template_id: None,
+    }
+}
+
+fn get_standing_order_meta_data(transaction_id: Uuid) -> RequestMetaData {
+    RequestMetaData {
+        device_id: "internal".to_string(),
+        request_id: transaction_id.to_string(),
+    }
+}
+
+async fn get_standing_order_accounts(
+    user_client: &UserClient,
+    standing_order: &StandingOrder,
+) -> Result<(UserAccount, BusinessAccount), GenericError> {
+    let user_account = user_client
+        .get_user_account(None, Some(standing_order.created_by))
+        .await?;
+    if user_account.is_active == Status::Inactive || user_account.is_deleted {
+        return Err(GenericError::ValidationError(
+            "User of the standing order is inactive".to_string(),
+        ));
+    }
+    let business_account = user_client
+        .get_business_account(
+            standing_order.created_by,
+            standing_order.business_id,
+            vec![CustomerType::RetailB2bBuyer],
+        )
+        .await?
+        .ok_or_else(|| {
+            GenericError::ValidationError("Business Account doesn't exist".to_string())
+        })?;
+    if let Some(message) = validate_business_account_active(&business_account) {
+        return Err(GenericError::ValidationError(message));
+    }
+    let permission_list = user_client
+        .permission_validation(
+            standing_order.created_by,
+            standing_order.business_id,
+            vec![PermissionType::CreateOrder, PermissionType::CreateOrderSelf],
+        )
+        .await?;
+    if permission_list.is_empty() {
+        return Err(GenericError::InsufficientPrevilegeError(
+            "User of the standing order can no longer create orders".to_string(),
+        ));
+    }
+    Ok((user_account, business_account))
+}
+
+async fn notify_standing_order_run(
+    websocket_client: &WebSocketClient,
+    standing_order: &StandingOrder,
+    run: &StandingOrderRun,
+) {
+    let params = WebSocketParam {
+        user_id: Some(standing_order.created_by),
+        business_id: standing_order.business_id,
+        device_id: None,
+    };
+    let data = match serde_json::to_value(run) {
+        Ok(data) => data,
+        Err(e) => {
+            tracing::error!("Failed to serialize standing order run: {:?}", e);
+            return;
+        }
+    };
+    if let Err(e) = websocket_client
+        .send_msg(
+            params,
+            WebSocketActionType::StandingOrderRun,
+            data,
+            Some(NotificationProcessType::Deferred),
+        )
+        .await
+    {
+        tracing::error!("Failed to notify standing order run: {:?}", e);
+    }
+}
+
+#[tracing::instrument(name = "send standing order run", skip_all, fields(transaction_id=transaction_id.to_string()))]
+pub async fn send_standing_order_run(
+    pool: &PgPool,
+    user_client: &UserClient,
+    chat_client: &ChatClient,
+    websocket_client: &WebSocketClient,
+    standing_order: &StandingOrder,
+    transaction_id: Uuid,
+) -> Result<(), anyhow::Error> {
+    let select_request = get_standing_order_select_request(standing_order, transaction_id);
+    let result = match get_standing_order_accounts(user_client, standing_order).await {
+        Ok((user_account, business_account)) => {
+            send_order_select(
+                &select_request,
+                pool,
+                &user_account,
+                &business_account,
+                &get_standing_order_meta_data(transaction_id),
+                chat_client,
+                user_client,
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
+    let run = match result {
+        Ok(()) => fetch_standing_order_run(pool, transaction_id).await?,
+        Err(e) => {
+            update_standing_order_run(
+                pool,
+                transaction_id,
+                &[StandingOrderRunStatus::SelectSent],
+                &StandingOrderRunStatus::Failed,
+                None,
+                Some(&format!("Failed to send select: {}", e)),
+            )
+            .await?
+        }
+    };
+    if let Some(run) = run {
+        notify_standing_order_run(websocket_client, standing_order, &run).await;
+    }
+    Ok(())
+}
+
+async fn on_standing_order_select(
+    pool: &PgPool,
+    user_client: &UserClient,
+    websocket_client: &WebSocketClient,
+    run: StandingOrderRun,
+    standing_order: &StandingOrder,
+    quote_total: Option<BigDecimal>,
+    error: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let (status, message) = match (&quote_total, &standing_order.auto_confirm) {
+        (None, _) => (
+            StandingOrderRunStatus::Failed,
+            Some(format!(
+                "Seller rejected the select: {}",
+                error.unwrap_or_default()
+            )),
+        ),
+        (Some(_), None) => (
+            StandingOrderRunStatus::AwaitingBuyer,
+            Some("Quote received, the order is to be confirmed by the buyer".to_string()),
+        ),
+        (Some(quote_total), Some(auto_confirm))
+            if !is_within_price_tolerance(quote_total, auto_confirm) =>
+        {
+            (
+                StandingOrderRunStatus::AwaitingBuyer,
+                Some(format!(
+                    "Quote total {} exceeds the reference total {} by more than {}%",
+                    quote_total, auto_confirm.reference_total, auto_confirm.price_tolerance
+                )),
+            )
+        }
+        (Some(_), Some(_)) => (StandingOrderRunStatus::InitSent, None),
+    };
+    let Some(run) = update_standing_order_run(
+        pool,
+        run.transaction_id,
+        &[StandingOrderRunStatus::SelectSent],
+        &status,
+        quote_total.as_ref(),
+        message.as_deref(),
+    )
+    .await?
+    else {
+        return Ok(());
+    };
+    let run = match (&status, &standing_order.auto_confirm) {
+        (StandingOrderRunStatus::InitSent, Some(auto_confirm)) => {
+            let result = async {
+                let (user_account, business_account) =
+                    get_standing_order_accounts(user_client, standing_order).await?;
+                let order = fetch_order_by_id(pool, run.transaction_id)
+                    .await
+                    .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?
+                    .ok_or_else(|| GenericError::ValidationError("Order not found".to_string()))?;
+                let init_request = OrderInitRequest {
+                    transaction_id: run.transaction_id,
+                    message_id: Uuid::new_v4(),
+                    billing: Some(auto_confirm.billing.clone()),
+                    billing_profile_id: None,
+                    import_costs: auto_confirm.import_costs.clone(),
+                    expected_version: order.version,
+                };
+                send_order_init(
+                    &init_request,
+                    &order,
+                    pool,
+                    &user_account,
+                    &business_account,
+                    &get_standing_order_meta_data(run.transaction_id),
+                    user_client,
+                )
+                .await
+            }
+            .await;
+            match result {
+                Ok(()) => Some(run),
+                Err(e) => {
+                    update_standing_order_run(
+                        pool,
+                        run.transaction_id,
+                        &[StandingOrderRunStatus::InitSent],
+                        &StandingOrderRunStatus::AwaitingBuyer,
+                        None,
+                        Some(&format!("Failed to send init: {}", e)),
+                    )
+                    .await?
+                }
+            }
+        }
+        _ => Some(run),
+    };
+    if let Some(run) = run {
+        notify_standing_order_run(websocket_client, standing_order, &run).await;
+    }
+    Ok(())
+}
+
+async fn on_standing_order_init(
+    pool: &PgPool,
+    user_client: &UserClient,
+    websocket_client: &WebSocketClient,
+    run: StandingOrderRun,
+    standing_order: &StandingOrder,
+    quote_total: Option<BigDecimal>,
+    error: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let (status, message) = get_standing_order_init_status(
+        error,
+        quote_total.as_ref(),
+        standing_order.auto_confirm.as_ref(),
+    );
+    let Some(run) = update_standing_order_run(
+        pool,
+        run.transaction_id,
+        &[StandingOrderRunStatus::InitSent],
+        &status,
+        quote_total.as_ref(),
+        message.as_deref(),
+    )
+    .await?
+    else {
+        return Ok(());
+    };
+    let run = if status == StandingOrderRunStatus::ConfirmSent {
+        let result = async {
+            let (user_account, business_account) =
+                get_standing_order_accounts(user_client, standing_order).await?;
+            let order = fetch_order_by_id(pool, run.transaction_id)
+                .await
+                .map_err(|e| GenericError::DatabaseError(e.to_string(), e))?
+                .ok_or_else(|| GenericError::ValidationError("Order not found".to_string()))?;
+            let confirm_request = OrderConfirmRequest {
+                transaction_id: run.transaction_id,
+                message_id: Uuid::new_v4(),
+                expected_version: order.version,
+            };
+            send_order_confirm(
+                &confirm_request,
+                &order,
+                pool,
+                &user_account,
+                &business_account,
+                &get_standing_order_meta_data(run.transaction_id),
+            )
+            .await
+        }
+        .await;
+        match result {
+            Ok(()) => Some(run),
+            Err(e) => {
+                update_standing_order_run(
+                    pool,
+                    run.transaction_id,
+                    &[StandingOrderRunStatus::ConfirmSent],
+                    &StandingOrderRunStatus::AwaitingBuyer,
+                    None,
+                    Some(&format!("Failed to send confirm: {}", e)),
+                )
+                .await?
+            }
+        }
+    } else {
+        Some(run)
+    };
+    if let Some(run) = run {
+        notify_standing_order_run(websocket_client, standing_order, &run).await;
+    }
+    Ok(())
+}
+
+async fn on_standing_order_confirm(
+    pool: &PgPool,
+    websocket_client: &WebSocketClient,
+    run: StandingOrderRun,
+    standing_order: &StandingOrder,
+    error: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let (status, message) = match error {
+        Some(error) => (
+            StandingOrderRunStatus::Failed,
+            Some(format!("Seller rejected the confirm: {}", error)),
+        ),
+        None => (StandingOrderRunStatus::Confirmed, None),
+    };
+    if let Some(run) = update_standing_order_run(
+        pool,
+        run.transaction_id,
+        &[
+            StandingOrderRunStatus::ConfirmSent,
+            StandingOrderRunStatus::AwaitingBuyer,
+        ],
+        &status,
+        None,
+        message.as_deref(),
+    )
+    .await?
+    {
+        notify_standing_order_run(websocket_client, standing_order, &run).await;
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum StandingOrderCallback {
+    Select { quote_total: Option<BigDecimal> },
+    Init { quote_total: Option<BigDecimal> },
+    Confirm,
+}
+
+async fn process_standing_order_callback(
+    pool: &PgPool,
+    user_client: &UserClient,
+    websocket_client: &WebSocketClient,
+    transaction_id: Uuid,
+    callback: StandingOrderCallback,
+    error: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let Some(run) = fetch_standing_order_run(pool, transaction_id).await? else {
+        return Ok(());
+    };
+    let standing_order = fetch_standing_order(pool, run.standing_order_id)
+        .await?
+        .ok_or_else(|| anyhow!("Standing order {} not found", run.standing_order_id))?;
+    match callback {
+        StandingOrderCallback::Select { quote_total } => {
+            on_standing_order_select(
+                pool,
+                user_client,
+                websocket_client,
+                run,
+                &standing_order,
+                quote_total,
+                error,
+            )
+            .await
+        }
+        StandingOrderCallback::Init { quote_total } => {
+            on_standing_order_init(
+                pool,
+                user_client,
+                websocket_client,
+                run,
+                &standing_order,
+                quote_total,
+                error,
+            )
+            .await
+        }
+        StandingOrderCallback::Confirm => {
+            on_standing_order_confirm(pool, websocket_client, run, &standing_order, error).await
+        }
+    }
+}
+
+pub fn spawn_standing_order_callback(
+    pool: web::Data<PgPool>,
+    user_client: web::Data<UserClient>,
+    websocket_client: web::Data<WebSocketClient>,
+    transaction_id: Uuid,
+    callback: StandingOrderCallback,
+    error: Option<String>,
+) {
+    tokio::spawn(async move {
+        if let Err(e) = process_standing_order_callback(
+            &pool,
+            &user_client,
+            &websocket_client,
+            transaction_id,
+            callback,
+            error,
+        )
+        .await
+        {
+            tracing::error!(
+                "Failed to process standing order run of {}: {:?}",
+                transaction_id,
+                e
+            );
+        }
+    });
+}
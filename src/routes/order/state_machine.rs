@@ -0,0 +1,467 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Executor, Postgres, Transaction};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::errors::OrderTransitionError;
+use super::schemas::{
+    CommerceFulfillment, CommercePayment, CommerceReturn, CommerceStatusType,
+    FulfillmentStatusType, PaymentStatus, ReturnStatusType,
+};
+use crate::routes::ondc::ONDCActionType;
+
+#[derive(Deserialize, Debug, sqlx::Type, ToSchema, Serialize, PartialEq, Clone)]
+#[sqlx(type_name = "status_entity_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum StatusEntityType {
+    Order,
+    Fulfillment,
+    Payment,
+    Quote,
+    Return,
+}
+
+#[derive(Deserialize, Debug, sqlx::Type, ToSchema, Serialize, PartialEq)]
+#[sqlx(type_name = "status_actor_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum StatusActorType {
+    Buyer,
+    Seller,
+    System,
+}
+
+#[derive(Debug)]
+pub struct StatusActor {
+    pub actor_type: StatusActorType,
+    pub actor_id: String,
+}
+
+impl StatusActor {
+    pub fn buyer(user_id: Uuid) -> Self {
+        Self {
+            actor_type: StatusActorType::Buyer,
+            actor_id: user_id.to_string(),
+        }
+    }
+    pub fn seller(bpp_id: &str) -> Self {
+        Self {
+            actor_type: StatusActorType::Seller,
+            actor_id: bpp_id.to_owned(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StatusTransition {
+    pub entity_id: Option<String>,
+    pub entity_type: StatusEntityType,
+    pub previous_status: Option<String>,
+    pub status: String,
+    pub is_valid: bool,
+    pub detail: Option<Value>,
+}
+
+impl CommerceStatusType {
+    fn allowed_transitions(&self) -> &'static [CommerceStatusType] {
+        match self {
+            CommerceStatusType::QuoteRequested => &[
+                CommerceStatusType::QuoteAccepted,
+                CommerceStatusType::QuoteRejected,
+                CommerceStatusType::Cancelled,
+            ],
+            CommerceStatusType::QuoteAccepted => &[
+                CommerceStatusType::QuoteRequested,
+                CommerceStatusType::QuoteRejected,
+                CommerceStatusType::Initialized,
+                CommerceStatusType::Cancelled,
+            ],
+            CommerceStatusType::QuoteRejected => &[
+                CommerceStatusType::QuoteRequested,
+                CommerceStatusType::QuoteAccepted,
+                CommerceStatusType::Cancelled,
+            ],
+            CommerceStatusType::Initialized => &[
+                CommerceStatusType::QuoteAccepted,
+                CommerceStatusType::QuoteRejected,
+                CommerceStatusType::Created,
+                CommerceStatusType::Accepted,
+                CommerceStatusType::Cancelled,
+            ],
+            CommerceStatusType::Created => &[
+                CommerceStatusType::Accepted,
+                CommerceStatusType::InProgress,
+                CommerceStatusType::Completed,
+                CommerceStatusType::Cancelled,
+            ],
+            CommerceStatusType::Accepted => &[
+                CommerceStatusType::InProgress,
+                CommerceStatusType::Completed,
+                CommerceStatusType::Cancelled,
+            ],
+            CommerceStatusType::InProgress => {
+                &[CommerceStatusType::Completed, CommerceStatusType::Cancelled]
+            }
+            CommerceStatusType::Completed | CommerceStatusType::Cancelled => &[],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: &CommerceStatusType) -> bool {
+        self == next || self.allowed_transitions().contains(next)
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        self.allowed_transitions().is_empty()
+    }
+}
+
+impl FulfillmentStatusType {
+    fn allowed_transitions(&self) -> &'static [FulfillmentStatusType] {
+        match self {
+            FulfillmentStatusType::Pending => &[
+                FulfillmentStatusType::Packed,
+                FulfillmentStatusType::SearchingForAgent,
+                FulfillmentStatusType::AgentAssigned,
+                FulfillmentStatusType::OrderPickedUp,
+                FulfillmentStatusType::OutForDelivery,
+                FulfillmentStatusType::OrderDelivered,
+                FulfillmentStatusType::Cancelled,
+            ],
+            FulfillmentStatusType::Packed => &[
+                FulfillmentStatusType::SearchingForAgent,
+                FulfillmentStatusType::AgentAssigned,
+                FulfillmentStatusType::OrderPickedUp,
+                FulfillmentStatusType::OutForDelivery,
+                FulfillmentStatusType::OrderDelivered,
+                FulfillmentStatusType::Cancelled,
+            ],
+            FulfillmentStatusType::SearchingForAgent => &[
+                FulfillmentStatusType::Packed,
+                FulfillmentStatusType::AgentAssigned,
+                FulfillmentStatusType::OrderPickedUp,
+                FulfillmentStatusType::OutForDelivery,
+                FulfillmentStatusType::OrderDelivered,
+                FulfillmentStatusType::Cancelled,
+            ],
+            FulfillmentStatusType::AgentAssigned => &[
+                FulfillmentStatusType::Packed,
+                FulfillmentStatusType::OrderPickedUp,
+                FulfillmentStatusType::OutForDelivery,
+                FulfillmentStatusType::OrderDelivered,
+                FulfillmentStatusType::Cancelled,
+            ],
+            FulfillmentStatusType::OrderPickedUp => &[
+                FulfillmentStatusType::OutForDelivery,
+                FulfillmentStatusType::OrderDelivered,
+                FulfillmentStatusType::Cancelled,
+            ],
+            FulfillmentStatusType::OutForDelivery => &[
+                FulfillmentStatusType::OrderDelivered,
+                FulfillmentStatusType::Cancelled,
+            ],
+            FulfillmentStatusType::OrderDelivered | FulfillmentStatusType::Cancelled => &[],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: &FulfillmentStatusType) -> bool {
+        self == next || self.allowed_transitions().contains(next)
+    }
+}
+
+impl ReturnStatusType {
+    fn allowed_transitions(&self) -> &'static [ReturnStatusType] {
+        match self {
+            ReturnStatusType::Initiated => &[
+                ReturnStatusType::Approved,
+                ReturnStatusType::Picked,
+                ReturnStatusType::Delivered,
+                ReturnStatusType::Rejected,
+            ],
+            ReturnStatusType::Approved => &[
+                ReturnStatusType::Picked,
+                ReturnStatusType::Delivered,
+                ReturnStatusType::Rejected,
+            ],
+            ReturnStatusType::Picked => &[ReturnStatusType::Delivered],
+            ReturnStatusType::Delivered | ReturnStatusType::Rejected => &[],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: &ReturnStatusType) -> bool {
+        self == next || self.allowed_transitions().contains(next)
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        self.allowed_transitions().is_empty()
+    }
+}
+
+#[tracing::instrument(name = "validate order transition", skip())]
+pub fn validate_order_transition(
+    current: Option<&CommerceStatusType>,
+    next: &CommerceStatusType,
+) -> Result<(), OrderTransitionError> {
+    let is_valid = match current {
+        Some(current) => current.can_transition_to(next),
+        None => matches!(
+            next,
+            CommerceStatusType::QuoteRequested
+                | CommerceStatusType::QuoteAccepted
+                | CommerceStatusType::QuoteRejected
+        ),
+    };
+    if is_valid {
+        Ok(())
+    } else {
+        Err(OrderTransitionError::InvalidTransition(format!(
+            "Order cannot move from {} to {}",
+            current.map_or("new".to_string(), |status| status.to_string()),
+            next
+        )))
+    }
+}
+
+#[tracing::instrument(name = "validate order action", skip())]
+pub fn validate_order_action(
+    current: &CommerceStatusType,
+    action_type: &ONDCActionType,
+) -> Result<(), OrderTransitionError> {
+    match action_type {
+        ONDCActionType::Init => {
+            validate_order_transition(Some(current), &CommerceStatusType::Initialized)
+        }
+        ONDCActionType::Confirm => {
+            validate_order_transition(Some(current), &CommerceStatusType::Created)
+        }
+        ONDCActionType::Cancel => {
+            validate_order_transition(Some(current), &CommerceStatusType::Cancelled)
+        }
+        ONDCActionType::Update if current.is_terminal() => {
+            Err(OrderTransitionError::InvalidTransition(format!(
+                "Order in {} state cannot be updated",
+                current
+            )))
+        }
+        ONDCActionType::Update => Ok(()),
+        ONDCActionType::Select => {
+            validate_order_transition(Some(current), &CommerceStatusType::QuoteRequested)
+        }
+        ONDCActionType::Search
+        | ONDCActionType::Status
+        | ONDCActionType::Track
+        | ONDCActionType::Issue
+        | ONDCActionType::IssueStatus => Ok(()),
+        ONDCActionType::OnSearch
+        | ONDCActionType::OnSelect
+        | ONDCActionType::OnInit
+        | ONDCActionType::OnConfirm
+        | ONDCActionType::OnCancel
+        | ONDCActionType::OnStatus
+        | ONDCActionType::OnTrack
+        | ONDCActionType::OnUpdate
+        | ONDCActionType::OnIssue
+        | ONDCActionType::OnIssueStatus => Err(OrderTransitionError::InvalidTransition(format!(
+            "{} is not a buyer action",
+            action_type
+        ))),
+    }
+}
+
+pub fn get_order_transition(
+    current: Option<&CommerceStatusType>,
+    next: &CommerceStatusType,
+) -> Option<StatusTransition> {
+    if current == Some(next) {
+        return None;
+    }
+    Some(StatusTransition {
+        entity_id: None,
+        entity_type: StatusEntityType::Order,
+        previous_status: current.map(|status| status.to_string()),
+        status: next.to_string(),
+        is_valid: validate_order_transition(current, next).is_ok(),
+        detail: None,
+    })
+}
+
+/// Returns the transitions for every fulfillment whose state differs from the stored one.
+/// Invalid transitions are kept in the list but flagged so that they are audited and not applied.
+pub fn get_fulfillment_transitions<'a>(
+    order_fulfillments: &[CommerceFulfillment],
+    next_statuses: impl Iterator<Item = (&'a str, FulfillmentStatusType)>,
+) -> Vec<StatusTransition> {
+    let order_fulfillment_map: HashMap<&str, &FulfillmentStatusType> = order_fulfillments
+        .iter()
+        .map(|fulfillment| {
+            (
+                fulfillment.fulfillment_id.as_str(),
+                &fulfillment.fulfillment_status,
+            )
+        })
+        .collect();
+    next_statuses
+        .filter_map(|(fulfillment_id, next)| {
+            let current = order_fulfillment_map.get(fulfillment_id)?;
+            if **current == next {
+                return None;
+            }
+            Some(StatusTransition {
+                entity_id: Some(fulfillment_id.to_owned()),
+                entity_type: StatusEntityType::Fulfillment,
+                previous_status: Some(current.to_string()),
+                is_valid: current.can_transition_to(&next),
+                status: next.to_string(),
+                detail: None,
+            })
+        })
+        .collect()
+}
+
+pub fn get_payment_transitions<'a>(
+    order_payments: &[CommercePayment],
+    next_statuses: impl Iterator<Item = (Option<&'a str>, PaymentStatus)>,
+) -> Vec<StatusTransition> {
+    let order_payment_map: HashMap<&str, Option<&PaymentStatus>> = order_payments
+        .iter()
+        .filter_map(|payment| {
+            payment
+                .payment_id
+                .as_deref()
+                .map(|payment_id| (payment_id, payment.payment_status.as_ref()))
+        })
+        .collect();
+    next_statuses
+        .filter_map(|(payment_id, next)| {
+            let payment_id = payment_id?;
+            let current = order_payment_map.get(payment_id).copied().flatten();
+            if current == Some(&next) {
+                return None;
+            }
+            Some(StatusTransition {
+                entity_id: Some(payment_id.to_owned()),
+                entity_type: StatusEntityType::Payment,
+                previous_status: current.map(|status| status.to_string()),
+                status: next.to_string(),
+                is_valid: true,
+                detail: None,
+            })
+        })
+        .collect()
+}
+
+pub fn get_return_transitions<'a>(
+    order_returns: &[CommerceReturn],
+    next_statuses: impl Iterator<Item = (&'a str, ReturnStatusType)>,
+) -> Vec<StatusTransition> {
+    let order_return_map: HashMap<&str, &ReturnStatusType> = order_returns
+        .iter()
+        .map(|order_return| (order_return.return_id.as_str(), &order_return.return_status))
+        .collect();
+    next_statuses
+        .filter_map(|(return_id, next)| {
+            let current = order_return_map.get(return_id)?;
+            if **current == next {
+                return None;
+            }
+            Some(StatusTransition {
+                entity_id: Some(return_id.to_owned()),
+                entity_type: StatusEntityType::Return,
+                previous_status: Some(current.to_string()),
+                is_valid: current.can_transition_to(&next),
+                status: next.to_string(),
+                detail: None,
+            })
+        })
+        .collect()
+}
+
+pub fn get_quote_event(status: &CommerceStatusType, detail: Option<Value>) -> StatusTransition {
+    StatusTransition {
+        entity_id: None,
+        entity_type: StatusEntityType::Quote,
+        previous_status: None,
+        status: status.to_string(),
+        is_valid: true,
+        detail,
+    }
+}
+
+pub fn resolve_fulfillment_status(
+    current: &FulfillmentStatusType,
+    next: FulfillmentStatusType,
+) -> FulfillmentStatusType {
+    if current.can_transition_to(&next) {
+        next
+    } else {
+        current.clone()
+    }
+}
+
+#[tracing::instrument(name = "save status transitions", skip(transaction))]
+pub async fn save_status_transitions(
+    transaction: &mut Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+    transitions: &[StatusTransition],
+    actor: &StatusActor,
+    action_type: ONDCActionType,
+    message_id: Uuid,
+) -> Result<(), anyhow::Error> {
+    if transitions.is_empty() {
+        return Ok(());
+    }
+    let mut ids = vec![];
+    let mut entity_ids = vec![];
+    let mut entity_types = vec![];
+    let mut previous_statuses = vec![];
+    let mut statuses = vec![];
+    let mut is_valid_list = vec![];
+    let mut details = vec![];
+    for transition in transitions {
+        if !transition.is_valid {
+            tracing::warn!(
+                "Invalid {:?} transition from {:?} to {} flagged for {}",
+                transition.entity_type,
+                transition.previous_status,
+                transition.status,
+                transaction_id
+            );
+        }
+        ids.push(Uuid::new_v4());
+        entity_ids.push(transition.entity_id.clone());
+        entity_types.push(transition.entity_type.clone());
+        previous_statuses.push(transition.previous_status.clone());
+        statuses.push(transition.status.clone());
+        is_valid_list.push(transition.is_valid);
+        details.push(transition.detail.clone());
+    }
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO buyer_order_status_history (id, transaction_id, entity_id, entity_type,
+            previous_status, status, actor_type, actor_id, action_type, is_valid, created_on, message_id, detail)
+        SELECT t.id, $2, t.entity_id, t.entity_type, t.previous_status, t.status, $7, $8, $9, t.is_valid, $10, $12, t.detail
+        FROM UNNEST($1::uuid[], $3::text[], $4::status_entity_type[], $5::text[], $6::text[], $11::bool[], $13::jsonb[])
+        AS t(id, entity_id, entity_type, previous_status, status, is_valid, detail)
+        "#,
+        &ids,
+        transaction_id,
+        &entity_ids as &Vec<Option<String>>,
+        &entity_types as &Vec<StatusEntityType>,
+        &previous_statuses as &Vec<Option<String>>,
+        &statuses,
+        &actor.actor_type as &StatusActorType,
+        &actor.actor_id,
+        &action_type.to_string(),
+        Utc::now(),
+        &is_valid_list,
+        message_id,
+        &details as &Vec<Option<Value>>,
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while saving order status transitions")
+    })?;
+    Ok(())
+}
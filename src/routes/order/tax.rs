@@ -0,0 +1,181 @@
+use bigdecimal::BigDecimal;
+
+use super::schemas::{Commerce, CommerceItem, CommerceTaxSplit, CommerceTaxSummary, SupplyType};
+
+const GSTIN_CHARSET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// GST state codes, older codes follow the current ones so that name lookups resolve to the current code.
+const GST_STATES: &[(&str, &str)] = &[
+    ("01", "Jammu and Kashmir"),
+    ("02", "Himachal Pradesh"),
+    ("03", "Punjab"),
+    ("04", "Chandigarh"),
+    ("05", "Uttarakhand"),
+    ("06", "Haryana"),
+    ("07", "Delhi"),
+    ("08", "Rajasthan"),
+    ("09", "Uttar Pradesh"),
+    ("10", "Bihar"),
+    ("11", "Sikkim"),
+    ("12", "Arunachal Pradesh"),
+    ("13", "Nagaland"),
+    ("14", "Manipur"),
+    ("15", "Mizoram"),
+    ("16", "Tripura"),
+    ("17", "Meghalaya"),
+    ("18", "Assam"),
+    ("19", "West Bengal"),
+    ("20", "Jharkhand"),
+    ("21", "Odisha"),
+    ("22", "Chhattisgarh"),
+    ("23", "Madhya Pradesh"),
+    ("24", "Gujarat"),
+    ("26", "Dadra and Nagar Haveli and Daman and Diu"),
+    ("27", "Maharashtra"),
+    ("29", "Karnataka"),
+    ("30", "Goa"),
+    ("31", "Lakshadweep"),
+    ("32", "Kerala"),
+    ("33", "Tamil Nadu"),
+    ("34", "Puducherry"),
+    ("35", "Andaman and Nicobar Islands"),
+    ("36", "Telangana"),
+    ("37", "Andhra Pradesh"),
+    ("38", "Ladakh"),
+    ("97", "Other Territory"),
+    ("25", "Daman and Diu"),
+    ("28", "Andhra Pradesh"),
+];
+
+/// Resolves a state name or a two digit GST state code to the GST state code.
+pub fn get_gst_state_code(state: &str) -> Option<&'static str> {
+    let state = state.trim().replace('&', "and");
+    GST_STATES
+        .iter()
+        .find(|(code, name)| *code == state || name.eq_ignore_ascii_case(&state))
+        .map(|(code, _)| *code)
+}
+
+fn get_gstin_checksum(gstin: &str) -> Option<char> {
+    let mut sum = 0;
+    for (idx, char) in gstin.chars().take(14).enumerate() {
+        let value = GSTIN_CHARSET.find(char)? as u32;
+        let product = value * if idx % 2 == 0 { 1 } else { 2 };
+        sum += product / 36 + product % 36;
+    }
+    GSTIN_CHARSET.chars().nth(((36 - sum % 36) % 36) as usize)
+}
+
+/// Validates the GSTIN layout (state code, PAN, entity number, `Z`) and its mod 36 check digit.
+pub fn is_valid_gstin(gstin: &str) -> bool {
+    let chars: Vec<char> = gstin.chars().collect();
+    if chars.len() != 15 || !chars.iter().all(|c| GSTIN_CHARSET.contains(*c)) {
+        return false;
+    }
+    let pan_is_valid = chars[2..7].iter().all(|c| c.is_ascii_alphabetic())
+        && chars[7..11].iter().all(|c| c.is_ascii_digit())
+        && chars[11].is_ascii_alphabetic();
+    GST_STATES.iter().any(|(code, _)| *code == &gstin[0..2])
+        && pan_is_valid
+        && chars[12] != '0'
+        && chars[13] == 'Z'
+        && get_gstin_checksum(gstin) == Some(chars[14])
+}
+
+/// Supply is intra-state when the seller location and the buyer billing state match.
+/// The GSTIN state codes are used when a state name cannot be resolved.
+pub fn get_supply_type(
+    seller_state: Option<&str>,
+    buyer_state: &str,
+    seller_gstin: Option<&str>,
+    buyer_gstin: &str,
+) -> Option<SupplyType> {
+    let gstin_state = |gstin: &str| gstin.get(0..2).and_then(get_gst_state_code);
+    let seller_code = seller_state
+        .and_then(get_gst_state_code)
+        .or_else(|| seller_gstin.and_then(gstin_state))?;
+    let buyer_code = get_gst_state_code(buyer_state).or_else(|| gstin_state(buyer_gstin))?;
+    Some(if seller_code == buyer_code {
+        SupplyType::IntraState
+    } else {
+        SupplyType::InterState
+    })
+}
+
+/// Splits the line tax into CGST/SGST or IGST, cess is split out only when the seller reports a cess rate.
+pub fn get_item_tax_split(item: &CommerceItem, supply_type: &SupplyType) -> CommerceTaxSplit {
+    let cess_rate = item
+        .cess_rate
+        .clone()
+        .filter(|cess_rate| *cess_rate > BigDecimal::from(0) && *cess_rate <= item.tax_rate)
+        .unwrap_or_default();
+    let gst_rate = &item.tax_rate - &cess_rate;
+    let gst_value = if item.tax_rate == BigDecimal::from(0) {
+        BigDecimal::from(0)
+    } else {
+        (&item.tax_value * &gst_rate / &item.tax_rate).round(2)
+    };
+    let cess_value = &item.tax_value - &gst_value;
+    match supply_type {
+        SupplyType::IntraState => {
+            let cgst_value = (&gst_value / BigDecimal::from(2)).round(2);
+            CommerceTaxSplit {
+                cgst_rate: &gst_rate / BigDecimal::from(2),
+                sgst_rate: &gst_rate / BigDecimal::from(2),
+                sgst_value: &gst_value - &cgst_value,
+                cgst_value,
+                cess_rate,
+                cess_value,
+                ..Default::default()
+            }
+        }
+        SupplyType::InterState => CommerceTaxSplit {
+            igst_rate: gst_rate,
+            igst_value: gst_value,
+            cess_rate,
+            cess_value,
+            ..Default::default()
+        },
+    }
+}
+
+/// Fills the per line tax split and the order tax summary once the billing detail is known.
+pub fn apply_order_tax_split(order: &mut Commerce) {
+    let Some(billing) = &order.billing else {
+        return;
+    };
+    let seller_gstin = order
+        .bpp_terms
+        .as_ref()
+        .and_then(|terms| terms.tax_number.clone());
+    let seller_state = order
+        .fulfillments
+        .first()
+        .map(|fulfillment| fulfillment.pickup.location.state.as_str());
+    let Some(supply_type) = get_supply_type(
+        seller_state,
+        &billing.state,
+        seller_gstin.as_deref(),
+        &billing.tax_id,
+    ) else {
+        return;
+    };
+    let mut total = CommerceTaxSplit::default();
+    for item in order.items.iter_mut() {
+        let split = get_item_tax_split(item, &supply_type);
+        total.cgst_value += &split.cgst_value;
+        total.sgst_value += &split.sgst_value;
+        total.igst_value += &split.igst_value;
+        total.cess_value += &split.cess_value;
+        item.tax_split = Some(split);
+    }
+    order.tax_summary = Some(CommerceTaxSummary {
+        supply_type,
+        place_of_supply: get_gst_state_code(&billing.state).map(|code| code.to_owned()),
+        is_buyer_gstin_valid: is_valid_gstin(&billing.tax_id),
+        buyer_gstin: billing.tax_id.clone(),
+        is_seller_gstin_valid: seller_gstin.as_deref().is_some_and(is_valid_gstin),
+        seller_gstin,
+        total,
+    });
+}
@@ -0,0 +1,1648 @@
+#[cfg(test)]
+mod tests {
+    use crate::constants::{BULK_ORDER_MAX_BYTES, BULK_ORDER_MAX_ROWS, BULK_ORDER_MAX_SELECTS};
+    use crate::document_client::{get_document_checksum, is_public_ip, DocumentClient};
+    use crate::routes::ondc::{
+        ONDCActionType, ONDCBreakUp, ONDCOnStatusFulfillment, ONDCOnUpdateFulfillment, ONDCQuote,
+        ONDCTag,
+    };
+    use crate::routes::order::address_book::{
+        get_profile_billing, get_saved_select_fulfillments, validate_opening_hours,
+    };
+    use crate::routes::order::bulk::{get_bulk_order_rows, get_bulk_order_selects, parse_csv};
+    use crate::routes::order::buyer_terms::{
+        apply_buyer_terms, get_buyer_terms_deviations, get_select_buyer_terms_template,
+    };
+    use crate::routes::order::cart::get_cart_selects;
+    use crate::routes::order::documents::{get_document_storage_key, get_document_version};
+    use crate::routes::order::export::{
+        get_csv_record, get_order_csv, OrderExportCell, ORDER_EXPORT_HEADER_COLUMNS,
+        ORDER_EXPORT_LINE_COLUMNS,
+    };
+    use crate::routes::order::handlers::validate_expected_order_version;
+    use crate::routes::order::import_trade::{
+        get_landed_cost_lines, is_valid_hs_code, validate_import_select,
+    };
+    use crate::routes::order::models::CommerceDocumentFileModel;
+    use crate::routes::order::quote_validation::{validate_quote, validate_quote_issues};
+    use crate::routes::order::schemas::{
+        ApprovalStatusType, BillingProfile, BudgetExceedAction, BulkOrderUploadRequest,
+        BuyerBudget, BuyerTermsDeviationType, BuyerTermsSpec, BuyerTermsTemplate,
+        CancellationFeeType, CartCheckoutRequest, CartItem, Commerce, CommerceApproval,
+        CommerceApprovalRecord, CommerceBuyerTerms, CommerceCancellationFee, CommerceGoodsReceipt,
+        CommerceGoodsReceiptItem, CommerceItem, CommercePayment, CommerceStatusType,
+        DeliveryLocation, DeliveryLocationOpeningHours, DocumentType, FulfillmentStatusType,
+        IncoTermType, OrderApprovalPolicy, OrderCancelRequest, OrderConfirmRequest,
+        OrderInitBilling, OrderInitRequest, OrderItemImportDetail, OrderListCursor,
+        OrderListFilter, OrderListRequest, OrderListSortField, OrderQuoteSavings,
+        OrderSelectRequest, OrderSelectSavedFulfillment, OrderUpdateRequest, PaymentStatus,
+        QuoteIssueSeverity, ReorderItem, ReturnStatusType, SortOrder, StandingOrderAutoConfirm,
+        StandingOrderFrequency, StandingOrderRunStatus, SupplyType, WeekDay,
+    };
+    use crate::routes::order::scorecard::{
+        get_scorecard_delta, is_delivered_on_time, parse_tat, SellerScorecardEvent,
+    };
+    use crate::routes::order::standing_order::{
+        get_next_run_on, get_standing_order_init_status, is_within_price_tolerance,
+    };
+    use crate::routes::order::state_machine::{
+        get_order_transition, get_payment_transitions, resolve_fulfillment_status,
+        validate_order_action, validate_order_transition, StatusEntityType,
+    };
+    use crate::routes::order::tax::{get_item_tax_split, get_supply_type, is_valid_gstin};
+    use crate::routes::order::utils::{
+        get_cancellation_fee, get_exceeded_budgets, get_expected_refund, get_order_approval_levels,
+        get_quote_item_qty_map, get_received_qty_map, get_refund_bulk_item_data,
+        get_reorder_select_request, get_seller_inco_terms, is_order_level_approver,
+        is_stale_callback,
+    };
+    use crate::routes::product::schemas::{CategoryDomain, PaymentType};
+    use crate::schemas::CountryCode;
+    use actix_web::{http::StatusCode, ResponseError};
+    use bigdecimal::BigDecimal;
+    use std::collections::{HashMap, HashSet};
+
+    #[tokio::test]
+    async fn test_order_transition() {
+        assert!(validate_order_transition(None, &CommerceStatusType::QuoteRequested).is_ok());
+        assert!(validate_order_transition(None, &CommerceStatusType::InProgress).is_err());
+        assert!(validate_order_transition(
+            Some(&CommerceStatusType::Accepted),
+            &CommerceStatusType::InProgress
+        )
+        .is_ok());
+        assert!(validate_order_transition(
+            Some(&CommerceStatusType::Cancelled),
+            &CommerceStatusType::InProgress
+        )
+        .is_err());
+        assert!(validate_order_transition(
+            Some(&CommerceStatusType::Completed),
+            &CommerceStatusType::Completed
+        )
+        .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_order_transition_record() {
+        assert!(get_order_transition(
+            Some(&CommerceStatusType::InProgress),
+            &CommerceStatusType::InProgress
+        )
+        .is_none());
+        let transition = get_order_transition(
+            Some(&CommerceStatusType::Cancelled),
+            &CommerceStatusType::InProgress,
+        )
+        .unwrap();
+        assert!(!transition.is_valid);
+        assert_eq!(transition.previous_status.as_deref(), Some("cancelled"));
+        assert_eq!(transition.status, "in_progress");
+    }
+
+    #[tokio::test]
+    async fn test_fulfillment_transition() {
+        assert!(
+            resolve_fulfillment_status(
+                &FulfillmentStatusType::Pending,
+                FulfillmentStatusType::Packed
+            ) == FulfillmentStatusType::Packed
+        );
+        assert!(
+            resolve_fulfillment_status(
+                &FulfillmentStatusType::OrderDelivered,
+                FulfillmentStatusType::Packed
+            ) == FulfillmentStatusType::OrderDelivered
+        );
+    }
+
+    #[tokio::test]
+    async fn test_order_action() {
+        assert!(
+            validate_order_action(&CommerceStatusType::Accepted, &ONDCActionType::Cancel).is_ok()
+        );
+        assert!(
+            validate_order_action(&CommerceStatusType::Completed, &ONDCActionType::Cancel).is_err()
+        );
+        assert!(
+            validate_order_action(&CommerceStatusType::Cancelled, &ONDCActionType::Update).is_err()
+        );
+        assert!(
+            validate_order_action(&CommerceStatusType::QuoteAccepted, &ONDCActionType::Init)
+                .is_ok()
+        );
+        assert!(
+            validate_order_action(&CommerceStatusType::Accepted, &ONDCActionType::Update).is_ok()
+        );
+        assert!(
+            validate_order_action(&CommerceStatusType::Accepted, &ONDCActionType::OnUpdate)
+                .is_err()
+        );
+        assert!(
+            validate_order_action(&CommerceStatusType::Completed, &ONDCActionType::Status).is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_payment_transition() {
+        let transitions = get_payment_transitions(
+            &[],
+            vec![
+                (Some("PAY-1"), PaymentStatus::Paid),
+                (None, PaymentStatus::NotPaid),
+            ]
+            .into_iter(),
+        );
+        assert_eq!(transitions.len(), 1);
+        assert!(transitions[0].entity_type == StatusEntityType::Payment);
+        assert_eq!(transitions[0].entity_id.as_deref(), Some("PAY-1"));
+        assert!(transitions[0].previous_status.is_none());
+        assert_eq!(transitions[0].status, "paid");
+    }
+
+    #[tokio::test]
+    async fn test_stale_callback() {
+        let applied_on = chrono::Utc::now();
+        assert!(!is_stale_callback(None, applied_on));
+        assert!(!is_stale_callback(Some(applied_on), applied_on));
+        assert!(is_stale_callback(
+            Some(applied_on),
+            applied_on - chrono::Duration::seconds(5)
+        ));
+        assert!(!is_stale_callback(
+            Some(applied_on),
+            applied_on + chrono::Duration::seconds(5)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_item_update_request_deserialization() {
+        let request: OrderUpdateRequest = serde_json::from_value(serde_json::json!({
+            "transactionId": "b1d6f3a4-4c1e-4f7a-9b2d-0c6f3c2e1a11",
+            "messageId": "c2e7a4b5-5d2f-4a8b-8c3e-1d7a4d3f2b22",
+            "targetType": "item",
+            "expectedVersion": 1,
+            "items": [{"itemId": "ITEM-1", "qty": 2}],
+            "reasonId": "002"
+        }))
+        .unwrap();
+        assert!(matches!(request, OrderUpdateRequest::UpdateItem(_)));
+        let request: OrderUpdateRequest = serde_json::from_value(serde_json::json!({
+            "transactionId": "b1d6f3a4-4c1e-4f7a-9b2d-0c6f3c2e1a11",
+            "messageId": "c2e7a4b5-5d2f-4a8b-8c3e-1d7a4d3f2b22",
+            "targetType": "payment",
+            "expectedVersion": 1
+        }))
+        .unwrap();
+        assert!(matches!(request, OrderUpdateRequest::UpdatePayment(_)));
+    }
+
+    #[tokio::test]
+    async fn test_order_version_conflict() {
+        let transaction_id = uuid::Uuid::new_v4();
+        assert!(validate_expected_order_version(transaction_id, 3, 3).is_ok());
+        let error = validate_expected_order_version(transaction_id, 4, 3).unwrap_err();
+        assert_eq!(error.status_code(), StatusCode::CONFLICT);
+
+        let request = serde_json::json!({
+            "transactionId": "b1d6f3a4-4c1e-4f7a-9b2d-0c6f3c2e1a11",
+            "messageId": "c2e7a4b5-5d2f-4a8b-8c3e-1d7a4d3f2b22",
+            "reasonId": "002",
+            "targetType": "payment"
+        });
+        assert!(serde_json::from_value::<OrderCancelRequest>(request.clone()).is_err());
+        assert!(serde_json::from_value::<OrderUpdateRequest>(request.clone()).is_err());
+        assert!(serde_json::from_value::<OrderInitRequest>(request.clone()).is_err());
+        assert!(serde_json::from_value::<OrderConfirmRequest>(request.clone()).is_err());
+        let mut request = request;
+        request["expectedVersion"] = serde_json::json!(3);
+        let request: OrderConfirmRequest = serde_json::from_value(request).unwrap();
+        assert_eq!(request.expected_version, 3);
+    }
+
+    #[tokio::test]
+    async fn test_return_request() {
+        let request: OrderUpdateRequest = serde_json::from_value(serde_json::json!({
+            "transactionId": "b1d6f3a4-4c1e-4f7a-9b2d-0c6f3c2e1a11",
+            "messageId": "c2e7a4b5-5d2f-4a8b-8c3e-1d7a4d3f2b22",
+            "targetType": "return",
+            "expectedVersion": 1,
+            "returns": [{"itemId": "ITEM-1", "qty": 1, "reasonId": "001", "images": []}]
+        }))
+        .unwrap();
+        let OrderUpdateRequest::UpdateReturn(request) = request else {
+            panic!("return request deserialized into another update type");
+        };
+        let return_id = request.get_return_id("ITEM-1");
+        let fulfillment: ONDCOnUpdateFulfillment = serde_json::from_value(serde_json::json!({
+            "id": "RF-1",
+            "type": "Return",
+            "state": {"descriptor": {"code": "Return_Approved"}},
+            "tags": [{
+                "descriptor": {"code": "return_request"},
+                "list": [
+                    {"descriptor": {"code": "id"}, "value": return_id},
+                    {"descriptor": {"code": "item_id"}, "value": "ITEM-1"}
+                ]
+            }]
+        }))
+        .unwrap();
+        assert_eq!(
+            fulfillment.get_return_status(),
+            Some((return_id.as_str(), ReturnStatusType::Approved))
+        );
+        assert!(ReturnStatusType::Initiated.can_transition_to(&ReturnStatusType::Approved));
+        assert!(!ReturnStatusType::Rejected.can_transition_to(&ReturnStatusType::Delivered));
+        let status_fulfillment: ONDCOnStatusFulfillment =
+            serde_json::from_value(serde_json::json!({"id": "RF-1", "type": "Return", "tags": []}))
+                .unwrap();
+        assert!(matches!(
+            status_fulfillment,
+            ONDCOnStatusFulfillment::Return(_)
+        ));
+        let malformed_forward = serde_json::from_value::<ONDCOnStatusFulfillment>(
+            serde_json::json!({"id": "F1", "type": "Delivery"}),
+        );
+        assert!(malformed_forward.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_fee() {
+        let order_value = BigDecimal::from(1000);
+        let percent_fee = CommerceCancellationFee {
+            r#type: CancellationFeeType::Percent,
+            val: BigDecimal::from(15),
+        };
+        assert_eq!(
+            get_cancellation_fee(&percent_fee, &order_value),
+            BigDecimal::from(150)
+        );
+        let amount_fee = CommerceCancellationFee {
+            r#type: CancellationFeeType::Amount,
+            val: BigDecimal::from(1500),
+        };
+        assert_eq!(get_cancellation_fee(&amount_fee, &order_value), order_value);
+
+        let payments: Vec<CommercePayment> = serde_json::from_value(serde_json::json!([
+            {"id": uuid::Uuid::new_v4(), "paymentType": "pre_paid", "paymentStatus": "paid", "paymentAmount": 800},
+            {"id": uuid::Uuid::new_v4(), "paymentType": "pre_paid", "paymentStatus": "not_paid", "paymentAmount": 200}
+        ]))
+        .unwrap();
+        let refunded = BigDecimal::from(100);
+        assert_eq!(
+            get_expected_refund(&payments, Some(&refunded), &BigDecimal::from(150)),
+            BigDecimal::from(550)
+        );
+        assert_eq!(
+            get_expected_refund(&payments[1..], None, &BigDecimal::from(150)),
+            BigDecimal::from(0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quote_validation() {
+        let quote: ONDCQuote = serde_json::from_value(serde_json::json!({
+            "price": {"currency": "INR", "value": "250.00"},
+            "ttl": "P1D",
+            "breakup": [
+                {
+                    "title": "Item",
+                    "@ondc/org/item_id": "I1",
+                    "@ondc/org/title_type": "item",
+                    "price": {"currency": "INR", "value": "200.00"},
+                    "@ondc/org/item_quantity": {"count": 2},
+                    "item": {"price": {"currency": "INR", "value": "100.00"}}
+                },
+                {
+                    "title": "Tax",
+                    "@ondc/org/item_id": "I1",
+                    "@ondc/org/title_type": "tax",
+                    "price": {"currency": "INR", "value": "18.00"}
+                }
+            ]
+        }))
+        .unwrap();
+        let tax_rate = BigDecimal::from(18);
+        let tax_rates = HashMap::from([("I1", &tax_rate)]);
+        let issues = validate_quote(&quote, &tax_rates);
+        assert!(issues
+            .iter()
+            .any(|issue| issue.severity == QuoteIssueSeverity::Warning));
+        assert!(issues
+            .iter()
+            .any(|issue| issue.severity == QuoteIssueSeverity::Error && issue.item_id.is_none()));
+        assert!(validate_quote_issues(&issues).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_gst_tax_split() {
+        assert!(is_valid_gstin("27AAPFU0939F1ZV"));
+        assert!(!is_valid_gstin("27AAPFU0939F1ZA"));
+        assert!(!is_valid_gstin("27AAPFU0939F1Z"));
+        assert_eq!(
+            get_supply_type(Some("Maharashtra"), "27", None, "27AAPFU0939F1ZV"),
+            Some(SupplyType::IntraState)
+        );
+        assert_eq!(
+            get_supply_type(None, "Karnataka", Some("27AAPFU0939F1ZV"), ""),
+            Some(SupplyType::InterState)
+        );
+        let mut item: CommerceItem = serde_json::from_value(serde_json::json!({
+            "id": "6f1a7c4e-52a2-4d1c-9a33-2b6a2f1e9d10",
+            "itemId": "I1",
+            "itemName": "Item",
+            "itemCode": null,
+            "itemImage": "",
+            "qty": 1,
+            "buyerTerms": null,
+            "taxRate": 40,
+            "taxValue": 400,
+            "unitPrice": 1000,
+            "grossTotal": 1400,
+            "availableQty": null,
+            "discountAmount": 0,
+            "locationIds": [],
+            "fulfillmentIds": [],
+            "cancelledQty": null,
+            "refundedTaxValue": null,
+            "refundedDiscountAmount": null,
+            "refundedGrossTotal": null,
+            "taxSplit": null
+        }))
+        .unwrap();
+        let split = get_item_tax_split(&item, &SupplyType::IntraState);
+        assert_eq!(split.cgst_value, BigDecimal::from(200));
+        assert_eq!(split.sgst_value, BigDecimal::from(200));
+        assert_eq!(split.cess_value, BigDecimal::from(0));
+        let split = get_item_tax_split(&item, &SupplyType::InterState);
+        assert_eq!(split.igst_rate, BigDecimal::from(40));
+        assert_eq!(split.igst_value, BigDecimal::from(400));
+        item.cess_rate = Some(BigDecimal::from(12));
+        let split = get_item_tax_split(&item, &SupplyType::IntraState);
+        assert_eq!(split.cgst_value, BigDecimal::from(140));
+        assert_eq!(split.sgst_value, BigDecimal::from(140));
+        assert_eq!(split.cess_value, BigDecimal::from(120));
+        let split = get_item_tax_split(&item, &SupplyType::InterState);
+        assert_eq!(split.igst_value, BigDecimal::from(280));
+    }
+
+    #[tokio::test]
+    async fn test_quote_refund_snapshot() {
+        let item: CommerceItem = serde_json::from_value(serde_json::json!({
+            "id": "6f1a7c4e-52a2-4d1c-9a33-2b6a2f1e9d10",
+            "itemId": "I1",
+            "itemName": "Item",
+            "itemCode": null,
+            "itemImage": "",
+            "qty": 5,
+            "buyerTerms": null,
+            "taxRate": 10,
+            "taxValue": 50,
+            "unitPrice": 100,
+            "grossTotal": 550,
+            "availableQty": null,
+            "discountAmount": 0,
+            "locationIds": [],
+            "fulfillmentIds": [],
+            "cancelledQty": null,
+            "refundedTaxValue": null,
+            "refundedDiscountAmount": null,
+            "refundedGrossTotal": null,
+            "taxSplit": null
+        }))
+        .unwrap();
+        let items = vec![item];
+        let refund_line = |title: &str, value: &str, count: Option<i32>| {
+            serde_json::json!({
+                "title": title,
+                "@ondc/org/item_id": "I1",
+                "@ondc/org/title_type": "refund",
+                "price": {"currency": "INR", "value": value},
+                "@ondc/org/item_quantity": count.map(|count| serde_json::json!({"count": count})),
+                "item": null
+            })
+        };
+        let breakups: Vec<ONDCBreakUp> = serde_json::from_value(serde_json::json!([
+            refund_line("Item", "-200.00", Some(2)),
+            refund_line("Tax", "-20.00", None)
+        ]))
+        .unwrap();
+        let commerce_id = uuid::Uuid::new_v4();
+        // The same on_update applied twice gives the same totals, not twice the quantity.
+        for _ in 0..2 {
+            let data =
+                get_refund_bulk_item_data(commerce_id, &breakups, ["I1"].into_iter(), &items);
+            assert_eq!(data.cancelled_qtys, vec![Some(BigDecimal::from(2))]);
+            assert_eq!(data.refunded_gross_totals, vec![BigDecimal::from(-200)]);
+            assert_eq!(data.refunded_tax_values, vec![BigDecimal::from(-20)]);
+        }
+
+        let breakups: Vec<ONDCBreakUp> = serde_json::from_value(serde_json::json!([
+            refund_line("Item", "-200.00", Some(2)),
+            refund_line("Tax", "-20.00", None),
+            refund_line("Item", "-100.00", Some(1)),
+            refund_line("Tax", "-10.00", None)
+        ]))
+        .unwrap();
+        let data = get_refund_bulk_item_data(commerce_id, &breakups, ["I1"].into_iter(), &items);
+        assert_eq!(data.cancelled_qtys, vec![Some(BigDecimal::from(3))]);
+        assert_eq!(data.refunded_gross_totals, vec![BigDecimal::from(-300)]);
+        assert_eq!(data.refunded_tax_values, vec![BigDecimal::from(-30)]);
+    }
+
+    #[tokio::test]
+    async fn test_goods_receipt_qty() {
+        let get_receipt = |grn_no: &str, received: i32, rejected: i32| CommerceGoodsReceipt {
+            id: uuid::Uuid::new_v4(),
+            grn_no: grn_no.to_string(),
+            fulfillment_id: "F1".to_string(),
+            received_on: chrono::Utc::now(),
+            remark: None,
+            created_on: chrono::Utc::now(),
+            created_by: uuid::Uuid::new_v4(),
+            items: vec![CommerceGoodsReceiptItem {
+                item_id: "I1".to_string(),
+                received_qty: BigDecimal::from(received),
+                accepted_qty: BigDecimal::from(received - rejected),
+                rejected_qty: BigDecimal::from(rejected),
+                remark: None,
+                images: vec![],
+            }],
+        };
+        let receipts = vec![get_receipt("GRN1", 4, 1), get_receipt("GRN2", 6, 0)];
+        let qty_map = get_received_qty_map(&receipts);
+        let (received_qty, accepted_qty, rejected_qty) = qty_map.get("I1").unwrap();
+        assert_eq!(received_qty, &BigDecimal::from(10));
+        assert_eq!(accepted_qty, &BigDecimal::from(9));
+        assert_eq!(rejected_qty, &BigDecimal::from(1));
+    }
+
+    #[tokio::test]
+    async fn test_quote_item_qty() {
+        let quote: ONDCQuote = serde_json::from_value(serde_json::json!({
+            "price": {"currency": "INR", "value": "318.00"},
+            "ttl": "P1D",
+            "breakup": [
+                {
+                    "title": "Item",
+                    "@ondc/org/item_id": "I1",
+                    "@ondc/org/title_type": "item",
+                    "price": {"currency": "INR", "value": "300.00"},
+                    "@ondc/org/item_quantity": {"count": 3}
+                },
+                {
+                    "title": "Tax",
+                    "@ondc/org/item_id": "I1",
+                    "@ondc/org/title_type": "tax",
+                    "price": {"currency": "INR", "value": "18.00"}
+                },
+                {
+                    "title": "Refund",
+                    "@ondc/org/item_id": "I1",
+                    "@ondc/org/title_type": "refund",
+                    "price": {"currency": "INR", "value": "-100.00"},
+                    "@ondc/org/item_quantity": {"count": 1}
+                }
+            ]
+        }))
+        .unwrap();
+        let qty_map = get_quote_item_qty_map(&quote);
+        assert_eq!(qty_map.len(), 1);
+        assert_eq!(qty_map.get("I1"), Some(&BigDecimal::from(3)));
+    }
+
+    #[tokio::test]
+    async fn test_order_approval_levels() {
+        let approver_id = uuid::Uuid::new_v4();
+        let get_policy =
+            |level: i32, min_amount: i32, seller_id: Option<&str>| OrderApprovalPolicy {
+                id: uuid::Uuid::new_v4(),
+                name: format!("Level {}", level),
+                approval_level: level,
+                min_amount: BigDecimal::from(min_amount),
+                domain_category_code: None,
+                seller_id: seller_id.map(|id| id.to_string()),
+                approver_ids: vec![uuid::Uuid::new_v4(), approver_id],
+                is_active: true,
+                created_on: chrono::Utc::now(),
+                created_by: uuid::Uuid::new_v4(),
+            };
+        let mut policies = vec![
+            get_policy(2, 100000, None),
+            get_policy(1, 10000, None),
+            get_policy(1, 5000, Some("S2")),
+        ];
+        let domain = CategoryDomain::Grocery;
+        assert!(
+            get_order_approval_levels(&policies, &BigDecimal::from(8000), &domain, "S1").is_empty()
+        );
+        assert_eq!(
+            get_order_approval_levels(&policies, &BigDecimal::from(200000), &domain, "S2"),
+            vec![1, 2]
+        );
+        policies[2].approver_ids = vec![approver_id];
+        policies[1].approver_ids = vec![];
+        let order_value = BigDecimal::from(20000);
+        assert!(is_order_level_approver(
+            &policies,
+            1,
+            &order_value,
+            &domain,
+            "S2",
+            approver_id
+        ));
+        assert!(!is_order_level_approver(
+            &policies,
+            1,
+            &order_value,
+            &domain,
+            "S1",
+            approver_id
+        ));
+        assert!(!is_order_level_approver(
+            &policies,
+            2,
+            &order_value,
+            &domain,
+            "S2",
+            approver_id
+        ));
+
+        let requested_on = chrono::Utc::now();
+        let approval = CommerceApproval {
+            status: ApprovalStatusType::Pending,
+            levels: vec![1, 2],
+            requested_on,
+            records: vec![CommerceApprovalRecord {
+                approval_level: 1,
+                approval_status: ApprovalStatusType::Approved,
+                approval_requested_on: requested_on,
+                remark: None,
+                created_by: uuid::Uuid::new_v4(),
+                created_on: requested_on,
+            }],
+        };
+        assert_eq!(approval.get_pending_level(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_exceeded_budgets() {
+        let now = chrono::Utc::now();
+        let user_id = uuid::Uuid::new_v4();
+        let get_budget =
+            |amount: i32,
+             consumed_amount: i32,
+             user_id: Option<uuid::Uuid>,
+             domain_category_code: Option<CategoryDomain>| BuyerBudget {
+                id: uuid::Uuid::new_v4(),
+                name: format!("Budget {}", amount),
+                user_id,
+                domain_category_code,
+                amount: BigDecimal::from(amount),
+                period_start: now - chrono::Duration::days(1),
+                period_end: now + chrono::Duration::days(1),
+                exceed_action: BudgetExceedAction::Reject,
+                approval_level: 1,
+                is_active: true,
+                consumed_amount: BigDecimal::from(consumed_amount),
+                remaining_amount: BigDecimal::from(amount - consumed_amount),
+            };
+        let budgets = vec![
+            get_budget(100000, 90000, None, None),
+            get_budget(5000, 1000, Some(user_id), None),
+            get_budget(1000, 0, None, Some(CategoryDomain::Electronics)),
+        ];
+        let domain = CategoryDomain::Grocery;
+        let exceeded =
+            get_exceeded_budgets(&budgets, &BigDecimal::from(4000), user_id, &domain, now);
+        assert!(exceeded.is_empty());
+        let exceeded =
+            get_exceeded_budgets(&budgets, &BigDecimal::from(8000), user_id, &domain, now);
+        assert_eq!(exceeded.len(), 1);
+        assert_eq!(exceeded[0].amount, BigDecimal::from(5000));
+        let exceeded = get_exceeded_budgets(
+            &budgets,
+            &BigDecimal::from(8000),
+            uuid::Uuid::new_v4(),
+            &domain,
+            now,
+        );
+        assert!(exceeded.is_empty());
+        let exceeded = get_exceeded_budgets(
+            &budgets,
+            &BigDecimal::from(20000),
+            uuid::Uuid::new_v4(),
+            &domain,
+            now + chrono::Duration::days(2),
+        );
+        assert!(exceeded.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_order_quote_savings() {
+        let savings = OrderQuoteSavings::new(2, BigDecimal::from(1000), BigDecimal::from(900));
+        assert_eq!(savings.savings_amount, BigDecimal::from(100));
+        assert_eq!(savings.savings_percentage, Some(BigDecimal::from(10)));
+        let savings = OrderQuoteSavings::new(0, BigDecimal::from(0), BigDecimal::from(0));
+        assert_eq!(savings.savings_percentage, None);
+    }
+
+    #[tokio::test]
+    async fn test_seller_scorecard() {
+        assert_eq!(parse_tat("PT48H"), Some(chrono::Duration::hours(48)));
+        assert_eq!(
+            parse_tat("P1DT12H30M"),
+            Some(chrono::Duration::minutes(36 * 60 + 30))
+        );
+        assert_eq!(parse_tat("P1W"), Some(chrono::Duration::days(7)));
+        assert_eq!(parse_tat("P1M"), None);
+        assert_eq!(parse_tat("PT"), None);
+        assert_eq!(parse_tat("PH2"), None);
+
+        let confirmed_on = chrono::Utc::now();
+        let delivered_on = confirmed_on + chrono::Duration::hours(30);
+        assert_eq!(
+            is_delivered_on_time(Some(confirmed_on), Some("P1D"), delivered_on),
+            Some(false)
+        );
+        assert_eq!(
+            is_delivered_on_time(Some(confirmed_on), Some("PT48H"), delivered_on),
+            Some(true)
+        );
+        assert_eq!(
+            is_delivered_on_time(None, Some("PT48H"), delivered_on),
+            None
+        );
+
+        let events = [
+            SellerScorecardEvent::QuoteResponded {
+                message_id: uuid::Uuid::new_v4(),
+                response_secs: 120,
+                is_rejected: false,
+            },
+            SellerScorecardEvent::QuoteResponded {
+                message_id: uuid::Uuid::new_v4(),
+                response_secs: 60,
+                is_rejected: true,
+            },
+            SellerScorecardEvent::OrderConfirmed { is_rejected: false },
+            SellerScorecardEvent::Delivered {
+                fulfillment_id: "F1".to_string(),
+                is_on_time: Some(true),
+            },
+            SellerScorecardEvent::Delivered {
+                fulfillment_id: "F2".to_string(),
+                is_on_time: None,
+            },
+        ];
+        let keys: HashSet<String> = events.iter().map(|event| event.get_key()).collect();
+        assert_eq!(keys.len(), events.len());
+        let delta = get_scorecard_delta(&events);
+        assert_eq!(delta.quote_response_count, 2);
+        assert_eq!(delta.quote_response_secs, 180);
+        assert_eq!(delta.quote_rejected_count, 1);
+        assert_eq!(delta.order_accepted_count, 1);
+        assert_eq!(delta.delivered_count, 2);
+        assert_eq!(delta.delivery_promised_count, 1);
+        assert_eq!(delta.on_time_delivery_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_order_list_cursor() {
+        let cursor = OrderListCursor {
+            sort_by: OrderListSortField::GrandTotal,
+            sort_order: SortOrder::Asc,
+            value: "1250.50".to_string(),
+            id: uuid::Uuid::new_v4(),
+        };
+        let decoded = OrderListCursor::decode(&cursor.encode()).unwrap();
+        assert_eq!(decoded.sort_by, cursor.sort_by);
+        assert_eq!(decoded.value, cursor.value);
+        assert_eq!(decoded.id, cursor.id);
+        assert!(OrderListCursor::decode("not-a-cursor").is_err());
+
+        let invalid_value = OrderListCursor {
+            sort_by: OrderListSortField::CreatedOn,
+            sort_order: SortOrder::Asc,
+            value: "yesterday".to_string(),
+            id: cursor.id,
+        };
+        assert!(OrderListCursor::decode(&invalid_value.encode()).is_err());
+
+        let request_json = |sort_order: &str| {
+            serde_json::json!({
+                "sortBy": "grand_total",
+                "sortOrder": sort_order,
+                "cursor": cursor.encode(),
+                "offset": 20,
+                "limit": 10,
+                "recordStatus": ["created"]
+            })
+        };
+        let request: OrderListRequest = serde_json::from_value(request_json("asc")).unwrap();
+        let filter = OrderListFilter::new(request, None, uuid::Uuid::new_v4()).unwrap();
+        assert_eq!(filter.offset, 0);
+        assert!(filter.cursor.is_some());
+        assert_eq!(
+            filter.query.record_status,
+            Some(vec![CommerceStatusType::Created])
+        );
+
+        let request: OrderListRequest = serde_json::from_value(request_json("desc")).unwrap();
+        assert!(OrderListFilter::new(request, None, uuid::Uuid::new_v4()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_order_export_csv() {
+        let record = get_csv_record(&[
+            OrderExportCell::Text("ACME, Inc".to_string()),
+            OrderExportCell::Text("=SUM(A1)".to_string()),
+            OrderExportCell::Text("12\" pipe".to_string()),
+            OrderExportCell::Amount("12.50".parse::<BigDecimal>().unwrap()),
+        ]);
+        assert_eq!(record, "\"ACME, Inc\",'=SUM(A1),\"12\"\" pipe\",12.50\r\n");
+
+        let csv = get_order_csv(&[], true);
+        assert_eq!(
+            csv.trim_end().split(',').count(),
+            ORDER_EXPORT_HEADER_COLUMNS.len() + ORDER_EXPORT_LINE_COLUMNS.len() - 1
+        );
+        assert_eq!(csv.lines().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_order_csv() {
+        let records = parse_csv("a,\"b, \"\"c\"\"\"\r\nd,\"e\nf\"\n", 2).unwrap();
+        assert_eq!(
+            records,
+            vec![
+                vec!["a".to_string(), "b, \"c\"".to_string()],
+                vec!["d".to_string(), "e\nf".to_string()],
+            ]
+        );
+        assert!(parse_csv("a,\"b", 2).is_err());
+        assert!(parse_csv("a\nb\nc", 2).is_err());
+
+        let location = serde_json::json!({
+            "gps": "12.974002,77.613458",
+            "areaCode": "560001",
+            "address": "Whitefield",
+            "city": {"code": "std:080", "name": "Bangalore"},
+            "country": {"code": "IND", "name": "India"},
+            "state": "Karnataka",
+            "contactMobileNo": "9999999999"
+        });
+        let request: BulkOrderUploadRequest = serde_json::from_value(serde_json::json!({
+            "domainCategoryCode": "RET10",
+            "paymentTypes": ["pre_paid"],
+            "ttl": "PT30S",
+            "orderType": "purchase_order",
+            "deliveryLocations": [
+                {"id": "WH1", "type": "delivery", "location": location},
+                {"id": "WH2", "type": "delivery", "location": location}
+            ],
+            "csv": concat!(
+                "Item_Id,Provider_Id,BPP_Id,Qty,Location_Id,Delivery_Location_Id,HS_Code,Country_Of_Origin,Duty_Rate\n",
+                "I1,P1,bpp.one,5,L1,WH1,,,\n",
+                "I2,P1,bpp.one,0,L1,WH1,,,\n",
+                "I3,P2,bpp.one,2,L2,WH3,,,\n",
+                ",,,,,,,,\n",
+                "I4,P1,bpp.one,3,L1,WH2,,,\n",
+                "I1,P1,bpp.one,1,L1,WH2,,,\n",
+                "I1,P3,bpp.two,1,L9,WH2,85171300,chn,10\n",
+                "I5,P1,,1,L1,WH1,,,\n",
+                "I6,P3,bpp.two,1,L9,WH2,85171300,XYZ,\n"
+            )
+        }))
+        .unwrap();
+        let delivery_location_ids: HashSet<&str> = request
+            .delivery_locations
+            .iter()
+            .map(|location| location.id.as_str())
+            .collect();
+        let (rows, errors) = get_bulk_order_rows(&request.csv, &delivery_location_ids).unwrap();
+        assert_eq!(
+            errors.iter().map(|error| error.row_no).collect::<Vec<_>>(),
+            vec![3, 4, 7, 9, 10]
+        );
+        assert_eq!(errors[3].message, "bpp_id is required");
+        assert_eq!(
+            errors[4].message,
+            "country_of_origin XYZ is not a valid country code"
+        );
+
+        let selects = get_bulk_order_selects(&rows, &request).unwrap();
+        assert_eq!(selects.len(), 2);
+        let (row_nos, select_request) = &selects[0];
+        assert_eq!(row_nos, &vec![2, 6]);
+        assert_eq!(select_request.provider_id, "P1");
+        assert_eq!(select_request.items.len(), 2);
+        assert_eq!(select_request.fulfillments.len(), 2);
+        assert_eq!(selects[1].1.bpp_id, "bpp.two");
+        assert_eq!(selects[1].1.fulfillments[0].id, "WH2");
+        let import_detail = selects[1].1.items[0].import_detail.as_ref().unwrap();
+        assert_eq!(import_detail.country_of_origin, CountryCode::CHN);
+        assert_eq!(import_detail.duty_rate, Some(BigDecimal::from(10)));
+        assert!(selects[0].1.items[0].import_detail.is_none());
+
+        assert!(get_bulk_order_rows("item_id,qty\n", &delivery_location_ids).is_err());
+        let csv = format!(
+            "item_id,provider_id,bpp_id,qty,location_id,delivery_location_id\n{}",
+            "I1,P1,bpp.one,1,L1,WH1\n".repeat(BULK_ORDER_MAX_ROWS + 1)
+        );
+        assert!(get_bulk_order_rows(&csv, &delivery_location_ids).is_err());
+        let csv = format!(
+            "item_id,provider_id,bpp_id,qty,location_id,delivery_location_id\nI1,P1,{},1,L1,WH1\n",
+            "b".repeat(BULK_ORDER_MAX_BYTES)
+        );
+        assert!(get_bulk_order_rows(&csv, &delivery_location_ids).is_err());
+        let csv = format!(
+            "item_id,provider_id,bpp_id,qty,location_id,delivery_location_id\n{}",
+            (0..=BULK_ORDER_MAX_SELECTS)
+                .map(|idx| format!("I1,P{},bpp.one,1,L1,WH1\n", idx))
+                .collect::<String>()
+        );
+        let (rows, _) = get_bulk_order_rows(&csv, &delivery_location_ids).unwrap();
+        assert!(get_bulk_order_selects(&rows, &request).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_standing_order_schedule() {
+        let run_on = "2025-01-31T06:00:00Z"
+            .parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap();
+        assert_eq!(
+            get_next_run_on(run_on, &StandingOrderFrequency::Daily, 3).to_rfc3339(),
+            "2025-02-03T06:00:00+00:00"
+        );
+        assert_eq!(
+            get_next_run_on(run_on, &StandingOrderFrequency::Weekly, 2).to_rfc3339(),
+            "2025-02-14T06:00:00+00:00"
+        );
+        assert_eq!(
+            get_next_run_on(run_on, &StandingOrderFrequency::Monthly, 1).to_rfc3339(),
+            "2025-02-28T06:00:00+00:00"
+        );
+        assert_eq!(
+            get_next_run_on(run_on, &StandingOrderFrequency::Daily, 0).to_rfc3339(),
+            "2025-02-01T06:00:00+00:00"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_standing_order_price_tolerance() {
+        let auto_confirm: StandingOrderAutoConfirm = serde_json::from_value(serde_json::json!({
+            "billing": {
+                "name": "Buyer",
+                "address": "Street 1",
+                "taxId": "29ABCDE1234F1Z5",
+                "mobileNo": "9999999999",
+                "email": "buyer@example.com",
+                "city": {"code": "std:080", "name": "Bengaluru"},
+                "state": "Karnataka"
+            },
+            "referenceTotal": 1000,
+            "priceTolerance": 5
+        }))
+        .unwrap();
+        assert!(is_within_price_tolerance(
+            &BigDecimal::from(1050),
+            &auto_confirm
+        ));
+        assert!(is_within_price_tolerance(
+            &BigDecimal::from(900),
+            &auto_confirm
+        ));
+        assert!(!is_within_price_tolerance(
+            &"1050.01".parse::<BigDecimal>().unwrap(),
+            &auto_confirm
+        ));
+
+        let (status, _) = get_standing_order_init_status(
+            None,
+            Some(&BigDecimal::from(1020)),
+            Some(&auto_confirm),
+        );
+        assert_eq!(status, StandingOrderRunStatus::ConfirmSent);
+        // A total raised by the seller on init above the tolerance waits for the buyer.
+        let (status, message) = get_standing_order_init_status(
+            None,
+            Some(&BigDecimal::from(1100)),
+            Some(&auto_confirm),
+        );
+        assert_eq!(status, StandingOrderRunStatus::AwaitingBuyer);
+        assert!(message.is_some());
+        let (status, _) = get_standing_order_init_status(
+            Some("Item out of stock".to_string()),
+            Some(&BigDecimal::from(1000)),
+            Some(&auto_confirm),
+        );
+        assert_eq!(status, StandingOrderRunStatus::AwaitingBuyer);
+    }
+
+    #[tokio::test]
+    async fn test_cart_checkout_selects() {
+        let location = serde_json::json!({
+            "gps": "12.974002,77.613458",
+            "areaCode": "560001",
+            "address": "Whitefield",
+            "city": {"code": "std:080", "name": "Bangalore"},
+            "country": {"code": "IND", "name": "India"},
+            "state": "Karnataka",
+            "contactMobileNo": "9999999999"
+        });
+        let request: CartCheckoutRequest = serde_json::from_value(serde_json::json!({
+            "paymentTypes": ["pre_paid"],
+            "ttl": "PT30S",
+            "orderType": "purchase_order",
+            "fulfillment": {"id": "WH1", "type": "delivery", "location": location}
+        }))
+        .unwrap();
+        let cart_item = |domain: &str, bpp_id: &str, provider_id: &str, item_id: &str| {
+            serde_json::from_value::<CartItem>(serde_json::json!({
+                "id": uuid::Uuid::new_v4(),
+                "domainCategoryCode": domain,
+                "bppId": bpp_id,
+                "providerId": provider_id,
+                "itemId": item_id,
+                "itemName": item_id,
+                "locationId": "L1",
+                "qty": 2,
+                "unitPrice": "10.5",
+                "currencyCode": "INR",
+                "buyerTerm": null,
+                "createdOn": "2025-01-01T00:00:00Z",
+                "updatedOn": null
+            }))
+            .unwrap()
+        };
+        let mut imported_item = cart_item("RET10", "bpp.two", "P1", "I2");
+        imported_item.import_detail = Some(OrderItemImportDetail {
+            hs_code: "85171300".to_string(),
+            country_of_origin: CountryCode::CHN,
+            duty_rate: Some(BigDecimal::from(10)),
+        });
+        let items = vec![
+            cart_item("RET10", "bpp.one", "P1", "I1"),
+            imported_item,
+            cart_item("RET10", "bpp.one", "P1", "I3"),
+            cart_item("RET12", "bpp.one", "P1", "I4"),
+        ];
+
+        let selects = get_cart_selects(&items, &request);
+        assert_eq!(selects.len(), 3);
+        let (group_items, select_request) = &selects[0];
+        assert_eq!(
+            group_items.iter().map(|item| item.id).collect::<Vec<_>>(),
+            vec![items[0].id, items[2].id]
+        );
+        assert_eq!(select_request.bpp_id, "bpp.one");
+        assert_eq!(select_request.items.len(), 2);
+        assert_eq!(
+            select_request.items[1].fulfillment_ids,
+            vec!["WH1".to_string()]
+        );
+        assert_eq!(selects[1].1.bpp_id, "bpp.two");
+        assert_eq!(selects[1].1.items[0].import_detail, items[1].import_detail);
+        assert_eq!(selects[2].1.domain_category_code, CategoryDomain::Fashion);
+        assert_ne!(selects[0].1.transaction_id, selects[2].1.transaction_id);
+    }
+
+    #[tokio::test]
+    async fn test_reorder_select_request() {
+        let location = serde_json::json!({
+            "gps": "12.974002,77.613458",
+            "area_code": "560001",
+            "address": "Whitefield",
+            "city": "Bangalore",
+            "country": "IND",
+            "state": "Karnataka"
+        });
+        let item = |item_id: &str, qty: i32, buyer_terms: serde_json::Value| {
+            serde_json::json!({
+                "id": uuid::Uuid::new_v4(),
+                "itemId": item_id,
+                "itemName": item_id,
+                "itemCode": null,
+                "itemImage": "",
+                "qty": qty,
+                "buyerTerms": buyer_terms,
+                "taxRate": 18,
+                "taxValue": 18,
+                "unitPrice": 100,
+                "grossTotal": 118,
+                "discountAmount": 0,
+                "locationIds": ["L1"],
+                "fulfillmentIds": ["F1"]
+            })
+        };
+        let order: Commerce = serde_json::from_value(serde_json::json!({
+            "id": uuid::Uuid::new_v4(),
+            "urn": "PO-1",
+            "externalUrn": uuid::Uuid::new_v4(),
+            "recordType": "purchase_order",
+            "recordStatus": "completed",
+            "domainCategoryCode": "RET10",
+            "seller": {"id": "P1", "name": "Seller"},
+            "source": "ondc",
+            "createdOn": "2025-01-01T00:00:00Z",
+            "createdBy": uuid::Uuid::new_v4(),
+            "bap": {"id": "bap.example", "uri": "https://bap.example"},
+            "bpp": {"id": "bpp.example", "uri": "https://bpp.example"},
+            "quoteTtl": "PT30S",
+            "cityCode": "std:080",
+            "countryCode": "IND",
+            "items": [
+                item("I1", 4, serde_json::json!({"itemReq": "Sealed", "packagingReq": "Box"})),
+                item("I2", 2, serde_json::Value::Null)
+            ],
+            "payments": [{"id": uuid::Uuid::new_v4(), "paymentType": "pre_paid"}],
+            "fulfillments": [{
+                "id": "1",
+                "fulfillmentId": "F1",
+                "fulfillmentType": "delivery",
+                "fulfillmentStatus": "Packed",
+                "dropOff": {"location": location, "contact": {"mobile_no": "9999999999"}},
+                "pickup": {
+                    "location": {
+                        "gps": "12.9,77.6",
+                        "area_code": "560002",
+                        "address": "Seller Street",
+                        "city": "Bangalore",
+                        "country": "IND",
+                        "state": "Karnataka"
+                    },
+                    "contact": {"mobile_no": "8888888888"}
+                },
+                "packagingCharge": 0,
+                "deliveryCharge": 0,
+                "convenienceFee": 0
+            }],
+            "buyerId": uuid::Uuid::new_v4(),
+            "version": 3,
+            "returns": [],
+            "buyerTermsDeviations": [],
+            "customsDocuments": [],
+            "missingCustomsDocuments": []
+        }))
+        .unwrap();
+        let reorder_items = vec![ReorderItem {
+            item_id: "I2".to_string(),
+            qty: 0,
+        }];
+        let request = get_reorder_select_request(&order, &reorder_items).unwrap();
+        assert_ne!(request.transaction_id, order.external_urn);
+        assert_eq!(request.provider_id, "P1");
+        assert_eq!(request.bpp_id, "bpp.example");
+        assert_eq!(request.items.len(), 1);
+        assert_eq!(request.items[0].item_id, "I1");
+        assert_eq!(request.items[0].qty, 4);
+        let buyer_term = request.items[0].buyer_term.as_ref().unwrap();
+        assert_eq!(buyer_term.item_req, "Sealed");
+        assert_eq!(buyer_term.packaging_req, "Box");
+        assert_eq!(request.fulfillments.len(), 1);
+        assert_eq!(request.fulfillments[0].location.country.name, "India");
+        assert_eq!(request.fulfillments[0].location.city.code, "std:080");
+        assert_eq!(request.payment_types, vec![PaymentType::PrePaid]);
+
+        let reorder_items = vec![ReorderItem {
+            item_id: "I9".to_string(),
+            qty: 1,
+        }];
+        assert!(get_reorder_select_request(&order, &reorder_items).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_address_book_selection() {
+        let opening_hours = |open_time: &str, close_time: &str| DeliveryLocationOpeningHours {
+            days: vec![WeekDay::Monday, WeekDay::Friday],
+            open_time: open_time.to_string(),
+            close_time: close_time.to_string(),
+        };
+        assert!(validate_opening_hours(&[opening_hours("08:00", "17:30")]).is_ok());
+        assert!(validate_opening_hours(&[opening_hours("18:00", "09:00")]).is_err());
+        assert!(validate_opening_hours(&[opening_hours("8am", "17:30")]).is_err());
+
+        let delivery_location = |address: &str, is_default: bool| DeliveryLocation {
+            id: uuid::Uuid::new_v4(),
+            name: address.to_string(),
+            location: serde_json::from_value(serde_json::json!({
+                "gps": "12.974002,77.613458",
+                "areaCode": "560001",
+                "address": address,
+                "city": {"code": "std:080", "name": "Bangalore"},
+                "country": {"code": "IND", "name": "India"},
+                "state": "Karnataka",
+                "contactMobileNo": "9999999999"
+            }))
+            .unwrap(),
+            contact_name: None,
+            contact_email: None,
+            opening_hours: vec![],
+            is_default,
+            created_on: chrono::Utc::now(),
+            created_by: uuid::Uuid::new_v4(),
+            updated_on: None,
+        };
+        let delivery_locations = vec![
+            delivery_location("Dock 1", false),
+            delivery_location("Dock 2", true),
+        ];
+        let saved_fulfillment = |delivery_location_id: Option<uuid::Uuid>| {
+            serde_json::from_value::<OrderSelectSavedFulfillment>(serde_json::json!({
+                "id": "F1",
+                "type": "delivery",
+                "deliveryLocationId": delivery_location_id,
+                "deliveryTerms": null
+            }))
+            .unwrap()
+        };
+        let fulfillments = get_saved_select_fulfillments(
+            &[
+                saved_fulfillment(Some(delivery_locations[0].id)),
+                saved_fulfillment(None),
+            ],
+            &delivery_locations,
+        )
+        .unwrap();
+        assert_eq!(fulfillments[0].location.address, "Dock 1");
+        assert_eq!(fulfillments[1].location.address, "Dock 2");
+        assert!(get_saved_select_fulfillments(
+            &[saved_fulfillment(Some(uuid::Uuid::new_v4()))],
+            &delivery_locations
+        )
+        .is_err());
+        assert!(get_saved_select_fulfillments(
+            &[saved_fulfillment(None)],
+            &delivery_locations[..1]
+        )
+        .is_err());
+
+        let billing_profile = |tax_id: &str, is_default: bool| BillingProfile {
+            id: uuid::Uuid::new_v4(),
+            name: tax_id.to_string(),
+            billing: serde_json::from_value::<OrderInitBilling>(serde_json::json!({
+                "name": "Buyer",
+                "address": "Street 1",
+                "taxId": tax_id,
+                "mobileNo": "9999999999",
+                "email": "buyer@example.com",
+                "city": {"code": "std:080", "name": "Bengaluru"},
+                "state": "Karnataka"
+            }))
+            .unwrap(),
+            country_code: CountryCode::IND,
+            is_default,
+            created_on: chrono::Utc::now(),
+            created_by: uuid::Uuid::new_v4(),
+            updated_on: None,
+        };
+        let billing_profiles = vec![
+            billing_profile("29ABCDE1234F1Z5", true),
+            billing_profile("27ABCDE1234F1Z5", false),
+        ];
+        assert_eq!(
+            get_profile_billing(None, &billing_profiles).unwrap().tax_id,
+            "29ABCDE1234F1Z5"
+        );
+        assert_eq!(
+            get_profile_billing(Some(billing_profiles[1].id), &billing_profiles)
+                .unwrap()
+                .tax_id,
+            "27ABCDE1234F1Z5"
+        );
+        assert!(get_profile_billing(None, &billing_profiles[1..]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_buyer_terms_template_selection() {
+        let terms: BuyerTermsSpec = serde_json::from_value(serde_json::json!({
+            "itemReq": "Grade A",
+            "packagingReq": "Palletized",
+            "incoTerms": "DAP",
+            "paymentTypes": ["credit"],
+            "maxDelayInterest": "2",
+            "requiredCertificates": ["ISO 9001", "FSSAI"]
+        }))
+        .unwrap();
+        let template =
+            |name: &str, domain: Option<CategoryDomain>, is_default: bool| BuyerTermsTemplate {
+                id: uuid::Uuid::new_v4(),
+                name: name.to_string(),
+                domain_category_code: domain,
+                terms: terms.clone(),
+                is_default,
+                created_on: chrono::Utc::now(),
+                created_by: uuid::Uuid::new_v4(),
+                updated_on: None,
+            };
+        let templates = vec![
+            template("Any", None, true),
+            template("Grocery", Some(CategoryDomain::Grocery), true),
+            template("Fashion", Some(CategoryDomain::Fashion), false),
+        ];
+        let selected =
+            get_select_buyer_terms_template(&templates, None, &CategoryDomain::Grocery).unwrap();
+        assert_eq!(selected.map(|t| t.name.as_str()), Some("Grocery"));
+        let selected =
+            get_select_buyer_terms_template(&templates, None, &CategoryDomain::Fashion).unwrap();
+        assert_eq!(selected.map(|t| t.name.as_str()), Some("Any"));
+        assert!(get_select_buyer_terms_template(
+            &templates,
+            Some(templates[2].id),
+            &CategoryDomain::Grocery
+        )
+        .is_err());
+        assert!(get_select_buyer_terms_template(
+            &templates,
+            Some(uuid::Uuid::new_v4()),
+            &CategoryDomain::Grocery
+        )
+        .is_err());
+        assert!(
+            get_select_buyer_terms_template(&templates[2..], None, &CategoryDomain::Fashion)
+                .unwrap()
+                .is_none()
+        );
+
+        let request: CartCheckoutRequest = serde_json::from_value(serde_json::json!({
+            "paymentTypes": ["credit"],
+            "ttl": "PT30S",
+            "orderType": "purchase_order",
+            "fulfillment": {"id": "WH1", "type": "delivery", "location": {
+                "gps": "12.974002,77.613458",
+                "areaCode": "560001",
+                "address": "Whitefield",
+                "city": {"code": "std:080", "name": "Bangalore"},
+                "country": {"code": "IND", "name": "India"},
+                "state": "Karnataka",
+                "contactMobileNo": "9999999999"
+            }}
+        }))
+        .unwrap();
+        let cart_item = |item_id: &str, buyer_term: serde_json::Value| {
+            serde_json::from_value::<CartItem>(serde_json::json!({
+                "id": uuid::Uuid::new_v4(),
+                "domainCategoryCode": "RET10",
+                "bppId": "bpp.one",
+                "providerId": "P1",
+                "itemId": item_id,
+                "itemName": item_id,
+                "locationId": "L1",
+                "qty": 1,
+                "unitPrice": "10",
+                "currencyCode": "INR",
+                "buyerTerm": buyer_term,
+                "createdOn": "2025-01-01T00:00:00Z",
+                "updatedOn": null
+            }))
+            .unwrap()
+        };
+        let items = vec![
+            cart_item("I1", serde_json::Value::Null),
+            cart_item(
+                "I2",
+                serde_json::json!({"itemReq": "Grade B", "packagingReq": "Loose"}),
+            ),
+        ];
+        let (_, mut select_request) = get_cart_selects(&items, &request).remove(0);
+        apply_buyer_terms(
+            &mut select_request,
+            &CommerceBuyerTerms {
+                template_id: templates[1].id,
+                template_name: templates[1].name.clone(),
+                terms: terms.clone(),
+            },
+        );
+        let item_terms = select_request.items[0].buyer_term.as_ref().unwrap();
+        assert_eq!(
+            item_terms.item_req,
+            "Grade A; Certificates required: ISO 9001, FSSAI"
+        );
+        assert_eq!(item_terms.packaging_req, "Palletized");
+        assert_eq!(
+            select_request.items[1]
+                .buyer_term
+                .as_ref()
+                .unwrap()
+                .item_req,
+            "Grade B"
+        );
+        let delivery_terms = select_request.fulfillments[0]
+            .delivery_terms
+            .as_ref()
+            .unwrap();
+        assert_eq!(delivery_terms.inco_terms, IncoTermType::Dap);
+        assert_eq!(delivery_terms.place_of_delivery, "Bangalore");
+    }
+
+    #[tokio::test]
+    async fn test_buyer_terms_deviations() {
+        let tags: Vec<ONDCTag> = serde_json::from_value(serde_json::json!([{
+            "descriptor": {"code": "DELIVERY_TERMS"},
+            "list": [
+                {"descriptor": {"code": "INCOTERMS"}, "value": "dap"},
+                {"descriptor": {"code": "NAMED_PLACE_OF_DELIVERY"}, "value": "560001"}
+            ]
+        }]))
+        .unwrap();
+        assert_eq!(get_seller_inco_terms(&tags), Some(IncoTermType::Dap));
+
+        let item = |item_id: &str, seller_terms: serde_json::Value| {
+            serde_json::json!({
+                "id": uuid::Uuid::new_v4(),
+                "itemId": item_id,
+                "itemName": item_id,
+                "itemCode": null,
+                "itemImage": "",
+                "qty": 1,
+                "buyerTerms": {"itemReq": "Grade A", "packagingReq": "Palletized"},
+                "sellerTerms": seller_terms,
+                "taxRate": 18,
+                "taxValue": 18,
+                "unitPrice": 100,
+                "grossTotal": 118,
+                "discountAmount": 0,
+                "locationIds": ["L1"],
+                "fulfillmentIds": ["F1"]
+            })
+        };
+        let location = serde_json::json!({
+            "gps": "12.9,77.6",
+            "area_code": "560001",
+            "address": "Street",
+            "city": "Bangalore",
+            "country": "IND",
+            "state": "Karnataka"
+        });
+        let mut order: Commerce = serde_json::from_value(serde_json::json!({
+            "id": uuid::Uuid::new_v4(),
+            "urn": "PO-1",
+            "externalUrn": uuid::Uuid::new_v4(),
+            "recordType": "purchase_order",
+            "recordStatus": "initialized",
+            "domainCategoryCode": "RET10",
+            "seller": {"id": "P1", "name": "Seller"},
+            "source": "ondc",
+            "createdOn": "2025-01-01T00:00:00Z",
+            "createdBy": uuid::Uuid::new_v4(),
+            "bap": {"id": "bap.example", "uri": "https://bap.example"},
+            "bpp": {"id": "bpp.example", "uri": "https://bpp.example"},
+            "quoteTtl": "PT30S",
+            "cityCode": "std:080",
+            "countryCode": "IND",
+            "items": [
+                item("I1", serde_json::json!({"itemReq": "Grade A", "packagingReq": "Palletized"})),
+                item("I2", serde_json::json!({"itemReq": "Grade B", "packagingReq": "Palletized"}))
+            ],
+            "payments": [],
+            "fulfillments": [{
+                "id": "1",
+                "fulfillmentId": "F1",
+                "fulfillmentType": "delivery",
+                "fulfillmentStatus": "Pending",
+                "deliveryTerm": {"inco_terms": "DAP", "place_of_delivery": "560001"},
+                "sellerIncoTerms": "EXW",
+                "pickup": {"location": location, "contact": {"mobile_no": "8888888888"}},
+                "packagingCharge": 0,
+                "deliveryCharge": 0,
+                "convenienceFee": 0
+            }],
+            "buyerTerms": {
+                "templateId": uuid::Uuid::new_v4(),
+                "templateName": "Default",
+                "terms": {
+                    "itemReq": "Grade A",
+                    "packagingReq": "Palletized",
+                    "incoTerms": "DAP",
+                    "paymentTypes": [],
+                    "requiredCertificates": []
+                }
+            },
+            "buyerId": uuid::Uuid::new_v4(),
+            "version": 1,
+            "returns": [],
+            "buyerTermsDeviations": [],
+            "customsDocuments": [],
+            "missingCustomsDocuments": []
+        }))
+        .unwrap();
+        let deviations = get_buyer_terms_deviations(&order);
+        assert_eq!(deviations.len(), 1);
+        assert_eq!(deviations[0].ref_id.as_deref(), Some("I2"));
+
+        order.bpp_terms = serde_json::from_value(serde_json::json!({
+            "maxLiability": "2",
+            "maxLiabilityCap": "10000",
+            "mandatoryArbitration": false,
+            "courtJurisdiction": "Bengaluru",
+            "delayInterest": "1"
+        }))
+        .unwrap();
+        let deviations = get_buyer_terms_deviations(&order);
+        assert_eq!(deviations.len(), 2);
+        assert_eq!(deviations[1].r#type, BuyerTermsDeviationType::IncoTerms);
+        assert_eq!(deviations[1].actual.as_deref(), Some("EXW"));
+    }
+
+    #[tokio::test]
+    async fn test_import_trade_validation() {
+        assert!(is_valid_hs_code("847130"));
+        assert!(is_valid_hs_code("8471300000"));
+        assert!(!is_valid_hs_code("84713"));
+        assert!(!is_valid_hs_code("8471.30"));
+
+        let select_request = |inco_terms: Option<&str>, import_detail: serde_json::Value| {
+            serde_json::from_value::<OrderSelectRequest>(serde_json::json!({
+                "transactionId": uuid::Uuid::new_v4(),
+                "messageId": uuid::Uuid::new_v4(),
+                "domainCategoryCode": "RET10",
+                "paymentTypes": ["pre_paid"],
+                "providerId": "P1",
+                "items": [{
+                    "itemId": "I1",
+                    "locationIds": ["L1"],
+                    "qty": 10,
+                    "buyerTerm": null,
+                    "fulfillmentIds": ["F1"],
+                    "importDetail": import_detail
+                }],
+                "ttl": "P1D",
+                "fulfillments": [{
+                    "id": "F1",
+                    "type": "delivery",
+                    "location": {
+                        "gps": "1.290270,103.851959",
+                        "areaCode": "018956",
+                        "address": "Marina Bay",
+                        "city": {"code": "std:065", "name": "Singapore"},
+                        "country": {"code": "SGP", "name": "Singapore"},
+                        "state": "Singapore",
+                        "contactMobileNo": "9999999999"
+                    },
+                    "deliveryTerms": inco_terms.map(|inco_terms| serde_json::json!({
+                        "incoTerms": inco_terms,
+                        "placeOfDelivery": "Singapore"
+                    }))
+                }],
+                "orderType": "purchase_order",
+                "bppId": "bpp.one"
+            }))
+            .unwrap()
+        };
+        let import_detail = |duty_rate: serde_json::Value| {
+            serde_json::json!({
+                "hsCode": "847130",
+                "countryOfOrigin": "IND",
+                "dutyRate": duty_rate
+            })
+        };
+        assert!(validate_import_select(
+            &select_request(Some("CIF"), import_detail(serde_json::json!("7.5"))),
+            &CountryCode::IND
+        )
+        .is_ok());
+        assert!(validate_import_select(
+            &select_request(None, import_detail(serde_json::json!("7.5"))),
+            &CountryCode::IND
+        )
+        .is_err());
+        assert!(validate_import_select(
+            &select_request(Some("CIF"), serde_json::Value::Null),
+            &CountryCode::IND
+        )
+        .is_err());
+        assert!(validate_import_select(
+            &select_request(Some("CIF"), import_detail(serde_json::Value::Null)),
+            &CountryCode::IND
+        )
+        .is_err());
+        assert!(validate_import_select(
+            &select_request(Some("DDP"), import_detail(serde_json::Value::Null)),
+            &CountryCode::IND
+        )
+        .is_ok());
+        assert!(validate_import_select(
+            &select_request(None, serde_json::Value::Null),
+            &CountryCode::SGP
+        )
+        .is_ok());
+
+        let item = |item_id: &str, gross_total: i32, duty_rate: &str| {
+            serde_json::from_value::<CommerceItem>(serde_json::json!({
+                "id": uuid::Uuid::new_v4(),
+                "itemId": item_id,
+                "itemName": item_id,
+                "itemCode": null,
+                "itemImage": "",
+                "qty": 1,
+                "buyerTerms": null,
+                "taxRate": 0,
+                "taxValue": 0,
+                "unitPrice": gross_total,
+                "grossTotal": gross_total,
+                "availableQty": null,
+                "discountAmount": 0,
+                "locationIds": [],
+                "fulfillmentIds": [],
+                "cancelledQty": null,
+                "refundedTaxValue": null,
+                "refundedDiscountAmount": null,
+                "refundedGrossTotal": null,
+                "taxSplit": null,
+                "importDetail": {"hsCode": "847130", "countryOfOrigin": "IND", "dutyRate": duty_rate}
+            }))
+            .unwrap()
+        };
+        let items = vec![item("I1", 1000, "10"), item("I2", 2000, "5")];
+        let lines = get_landed_cost_lines(
+            &items,
+            &IncoTermType::Fob,
+            &BigDecimal::from(250),
+            &BigDecimal::from(50),
+        );
+        assert_eq!(lines[0].assessable_value, BigDecimal::from(1100));
+        assert_eq!(lines[0].duty_value, BigDecimal::from(110));
+        assert_eq!(lines[1].assessable_value, BigDecimal::from(2200));
+        assert_eq!(lines[1].duty_value, BigDecimal::from(110));
+        let lines = get_landed_cost_lines(
+            &items,
+            &IncoTermType::Ddp,
+            &BigDecimal::from(0),
+            &BigDecimal::from(0),
+        );
+        assert!(lines
+            .iter()
+            .all(|line| line.duty_value == BigDecimal::from(0)));
+    }
+
+    #[tokio::test]
+    async fn test_order_document_versioning() {
+        let transaction_id = uuid::Uuid::new_v4();
+        let checksum = get_document_checksum(b"invoice v1");
+        assert_eq!(
+            get_document_storage_key(transaction_id, &DocumentType::ProformaInvoice, &checksum),
+            format!("orders/{}/proforma_invoice/{}", transaction_id, checksum)
+        );
+        assert_eq!(checksum, get_document_checksum(b"invoice v1"));
+        assert_ne!(checksum, get_document_checksum(b"invoice v2"));
+        assert_eq!(get_document_version(None, &checksum), Some(1));
+        let latest_file = CommerceDocumentFileModel {
+            id: uuid::Uuid::new_v4(),
+            document_type: DocumentType::Invoice,
+            version: 1,
+            source_url: "https://seller.example/invoice.pdf?sig=1".to_string(),
+            storage_key: get_document_storage_key(
+                transaction_id,
+                &DocumentType::Invoice,
+                &checksum,
+            ),
+            checksum: checksum.clone(),
+            content_type: "application/pdf".to_string(),
+            size_bytes: 10,
+            created_on: chrono::Utc::now(),
+        };
+        assert_eq!(get_document_version(Some(&latest_file), &checksum), None);
+        assert_eq!(
+            get_document_version(Some(&latest_file), &get_document_checksum(b"invoice v2")),
+            Some(2)
+        );
+
+        let base_path = std::env::temp_dir().join(format!("order-documents-{}", transaction_id));
+        let document_client = DocumentClient::new_local(
+            base_path.to_string_lossy().to_string(),
+            std::time::Duration::from_secs(5),
+            1024,
+        );
+        document_client
+            .put(
+                &latest_file.storage_key,
+                b"invoice v1".to_vec(),
+                "application/pdf",
+            )
+            .await
+            .unwrap();
+        let content = document_client.get(&latest_file.storage_key).await.unwrap();
+        assert_eq!(get_document_checksum(&content), latest_file.checksum);
+        assert!(document_client.get("../invoice.pdf").await.is_err());
+        assert!(document_client
+            .download("file:///etc/passwd")
+            .await
+            .is_err());
+        for url in [
+            "http://seller.example/invoice.pdf",
+            "https://127.0.0.1/invoice.pdf",
+            "https://169.254.169.254/latest/meta-data",
+            "https://[::1]/invoice.pdf",
+            "https://localhost/invoice.pdf",
+        ] {
+            assert!(document_client.download(url).await.is_err());
+        }
+        assert!(is_public_ip(&"8.8.8.8".parse().unwrap()));
+        assert!(!is_public_ip(&"10.0.0.1".parse().unwrap()));
+        assert!(!is_public_ip(&"100.64.0.1".parse().unwrap()));
+        assert!(!is_public_ip(&"::ffff:192.168.1.1".parse().unwrap()));
+        assert!(!is_public_ip(&"fd00::1".parse().unwrap()));
+        let _ = std::fs::remove_dir_all(base_path);
+    }
+}
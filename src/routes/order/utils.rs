@@ -1,47 +1,91 @@
+use super::bulk::BulkOrderRow;
+use super::buyer_terms::get_buyer_terms_deviations;
 use super::errors::SelectOrderError;
+use super::export::{get_order_csv, get_order_xlsx};
+use super::import_trade::{
+    get_landed_cost_estimate, get_missing_customs_documents, validate_import_select,
+};
 use super::models::{
-    CommerceBppTermsModel, CommerceDataModel, CommerceDocumentModel, CommerceFulfillmentModel,
-    CommerceItemModel, CommerceListModel, CommercePaymentModel, DropOffContactModel,
-    DropOffDataModel, DropOffLocationModel, FulfillmentInstruction, MinimalCommerceModel,
-    OrderBillingModel, OrderCancellationFeeModel, OrderCancellationTermModel,
+    BillingProfileModel, BuyerBudgetModel, BuyerTermsTemplateModel, CartCheckoutModel,
+    CartCheckoutOrderModel, CartItemModel, CommerceBppTermsModel, CommerceDataModel,
+    CommerceDocumentFileModel, CommerceDocumentModel, CommerceFulfillmentModel,
+    CommerceGoodsReceiptLineModel, CommerceImportCostModel, CommerceImportLineModel,
+    CommerceItemModel, CommerceListModel, CommercePaymentModel, CommerceReturnModel,
+    CustomsDocumentModel, DeliveryLocationModel, DropOffContactModel, DropOffDataModel,
+    DropOffLocationModel, FulfillmentInstruction, MinimalCommerceModel, OrderApprovalModel,
+    OrderApprovalPolicyModel, OrderBillingModel, OrderCancellationFeeModel,
+    OrderCancellationTermModel, OrderExportFileModel, OrderExportJobModel, OrderQuoteMetricModel,
+    OrderRequestEventModel, OrderSpendGroupModel, OrderStatusCountModel, OrderStatusHistoryModel,
     PaymentSettlementDetailModel, PickUpContactModel, PickUpDataModel, PickUpLocationModel,
-    SellerPaymentDetailModel, TimeRangeModel,
+    SellerPaymentDetailModel, SellerScorecardModel, StandingOrderEventModel, StandingOrderModel,
+    StandingOrderRunModel, StatusPollOrderModel, TimeRangeModel,
 };
 use super::schemas::{
-    BasicNetworkData, BulkCancelFulfillmentData, BulkCancelItemData, BulkConfirmFulfillmentData,
-    BulkStatusFulfillmentData, BuyerTerm, Commerce, CommerceBPPTerms, CommerceBilling,
-    CommerceCancellationFee, CommerceCancellationTerm, CommerceDocument, CommerceFulfillment,
-    CommerceItem, CommerceList, CommercePayment, CommerceSeller, DocumentType, DropOffData,
-    FulfillmentContact, FulfillmentLocation, MinimalCommerceData, OrderListFilter,
-    OrderSelectFulfillment, OrderSelectRequest, PaymentSettlementDetail, PickUpData,
-    PickUpFulfillmentLocation, SelectFulfillmentLocation, SellerPaymentDetail, TimeRange,
-    TradeType,
+    ApprovalStatusType, BasicNetworkData, BillingProfile, BillingProfileSaveRequest,
+    BudgetExceedAction, BulkCancelFulfillmentData, BulkCancelItemData, BulkConfirmFulfillmentData,
+    BulkOrderRowError, BulkStatusFulfillmentData, BuyerBudget, BuyerBudgetSaveRequest, BuyerTerm,
+    BuyerTerms, BuyerTermsSpec, BuyerTermsTemplate, BuyerTermsTemplateSaveRequest, CartCheckout,
+    CartItem, CartItemAddRequest, CartItemUpdateRequest, City, Commerce, CommerceApproval,
+    CommerceBPPTerms, CommerceBilling, CommerceBuyerTerms, CommerceCancellationFee,
+    CommerceCancellationTerm, CommerceDocument, CommerceFulfillment, CommerceGoodsReceipt,
+    CommerceGoodsReceiptItem, CommerceItem, CommerceList, CommerceListPage, CommercePayment,
+    CommerceReturn, CommerceSeller, Country, CustomsDocument, CustomsDocumentSaveRequest,
+    CustomsDocumentType, DeliveryLocation, DeliveryLocationOpeningHours,
+    DeliveryLocationSaveRequest, DocumentType, DropOffData, FulfillmentContact,
+    FulfillmentLocation, GoodsReceiptCreateRequest, GoodsReceiptMatchItem, GoodsReceiptMatchReport,
+    GoodsReceiptMatchStatus, MinimalCommerceData, OrderAnalytics, OrderApprovalAction,
+    OrderApprovalPolicy, OrderApprovalPolicySaveRequest, OrderCancellationPreview,
+    OrderDeliveyTerm, OrderExportFormat, OrderExportJob, OrderExportStatus, OrderImportCosts,
+    OrderInitBilling, OrderItemImportDetail, OrderListCursor, OrderListFilter, OrderListSortField,
+    OrderQuoteSavings, OrderSelectFulfillment, OrderSelectItem, OrderSelectRequest,
+    OrderSpendGroup, OrderStatusCount, OrderTimelineEvent, PaymentSettlementDetail, PickUpData,
+    PickUpFulfillmentLocation, QuoteIssue, QuoteRefund, ReorderItem, SelectFulfillmentLocation,
+    SellerPaymentDetail, SellerScorecard, StandingOrder, StandingOrderAutoConfirm,
+    StandingOrderEventType, StandingOrderFrequency, StandingOrderHistory, StandingOrderRun,
+    StandingOrderRunStatus, StandingOrderSaveRequest, StandingOrderStatus, TimeRange, TradeType,
+    UpdateOrderReturnRequest,
+};
+use super::scorecard::{get_scorecard_delta, is_delivered_on_time, SellerScorecardEvent};
+use super::standing_order::get_upcoming_run_on;
+use super::state_machine::{
+    get_fulfillment_transitions, get_order_transition, get_payment_transitions, get_quote_event,
+    get_return_transitions, resolve_fulfillment_status, save_status_transitions,
+    validate_order_action, StatusActor, StatusActorType, StatusEntityType, StatusTransition,
 };
+use super::tax::apply_order_tax_split;
 use crate::chat_client::{
     ChatClient, ChatData, ChatMessageType, ChatParticipant, SendMessageDataDescription,
 };
-use crate::constants::ONDC_TTL;
+use crate::configuration::StatusPollConfig;
+use crate::constants::{ONDC_TTL, ORDER_ANALYTICS_ITEM_LIMIT};
 use crate::routes::ondc::schemas::{
     BreakupTitleType, ONDCBilling, ONDCBreakUp, ONDCConfirmFulfillmentStartLocation, ONDCContact,
     ONDCFulfillment, ONDCFulfillmentCategoryType, ONDCFulfillmentStopType, ONDCFulfillmentTime,
-    ONDCOnConfirmFulfillment, ONDCOnConfirmPayment, ONDCOnConfirmRequest, ONDCOnInitPayment,
-    ONDCOnInitRequest, ONDCOnSelectFulfillment, ONDCOnSelectPayment, ONDCOnSelectRequest,
-    ONDCOrderCancellationTerm, ONDCOrderFulfillmentEnd, ONDCPaymentCollectedBy, ONDCSelectRequest,
-    ONDCSellerLocationInfo, ONDCSellerProductInfo, ONDCTag, ONDCTagItemCode, ONDCTagType, TagTrait,
+    ONDCOnConfirmFulfillment, ONDCOnConfirmPayment, ONDCOnConfirmRequest, ONDCOnInitFulfillment,
+    ONDCOnInitPayment, ONDCOnInitRequest, ONDCOnSelectFulfillment, ONDCOnSelectPayment,
+    ONDCOnSelectRequest, ONDCOnUpdateFulfillment, ONDCOrderCancellationTerm,
+    ONDCOrderFulfillmentEnd, ONDCPaymentCollectedBy, ONDCQuote, ONDCSelectRequest,
+    ONDCSelectedItem, ONDCSellerLocationInfo, ONDCSellerProductInfo, ONDCTag, ONDCTagItemCode,
+    ONDCTagType, TagTrait,
+};
+use crate::routes::ondc::utils::{
+    fetch_ondc_seller_location_info, fetch_ondc_seller_product_info, get_lookup_data_from_db,
+    get_ondc_seller_product_mapping_key, get_tag_value_from_list,
 };
-use crate::routes::ondc::utils::{get_ondc_seller_product_mapping_key, get_tag_value_from_list};
 use crate::routes::ondc::{
-    LookupData, ONDCActionType, ONDCConfirmFulfillmentEndLocation, ONDCDocument,
+    LookupData, ONDCActionType, ONDCConfirmFulfillmentEndLocation, ONDCDocument, ONDCDomain,
     ONDCFulfillmentInstruction, ONDCOnCancelRequest, ONDCOnStatusRequest, ONDCOnUpdateRequest,
     ONDCPaymentType, ONDCSellerInfo, ONDCTitleName,
 };
 use crate::routes::order::schemas::{
-    CommerceStatusType, DeliveryTerm, FulfillmentCategoryType, FulfillmentStatusType, IncoTermType,
-    OrderType, PaymentCollectedBy, PaymentStatus, ServiceableType, SettlementBasis,
+    CancellationFeeType, CommerceStatusType, DeliveryTerm, FulfillmentCategoryType,
+    FulfillmentStatusType, IncoTermType, OrderType, PaymentCollectedBy, PaymentStatus,
+    ReturnStatusType, ServiceableType, SettlementBasis,
 };
 use crate::routes::product::schemas::{CategoryDomain, FulfillmentType, PaymentType};
 use crate::schemas::{
-    CountryCode, CurrencyType, FeeType, RegisteredNetworkParticipant, RequestMetaData,
+    CountryCode, CurrencyType, FeeType, ONDCNetworkType, RegisteredNetworkParticipant,
+    RequestMetaData,
 };
 use crate::schemas::{DataSource, SeriesNoType};
 use crate::user_client::{
@@ -50,7 +94,7 @@ use crate::user_client::{
 use crate::utils::{get_gps_string, get_series_no};
 use anyhow::{anyhow, Context};
 use bigdecimal::{BigDecimal, ToPrimitive};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde_json::Value;
 use sqlx::types::Json;
 use sqlx::{Executor, PgPool, Postgres, QueryBuilder, Transaction};
@@ -59,9 +103,9 @@ use std::str::FromStr;
 use uuid::Uuid;
 
 #[allow(clippy::too_many_arguments)]
-#[tracing::instrument(name = "Save Product Search Request", skip(pool))]
+#[tracing::instrument(name = "Save Product Search Request", skip(executor))]
 pub async fn save_ondc_order_request(
-    pool: &PgPool,
+    executor: impl sqlx::PgExecutor<'_>,
     user_account: &UserAccount,
     business_account: &BusinessAccount,
     meta_data: &RequestMetaData,
@@ -69,6 +113,31 @@ pub async fn save_ondc_order_request(
     transaction_id: Uuid,
     message_id: Uuid,
     action_type: ONDCActionType,
+) -> Result<(), anyhow::Error> {
+    save_ondc_order_request_data(
+        executor,
+        user_account.id,
+        business_account.id,
+        Some(&meta_data.device_id),
+        request_payload,
+        transaction_id,
+        message_id,
+        action_type,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(name = "Save ONDC order request data", skip(executor))]
+pub async fn save_ondc_order_request_data(
+    executor: impl sqlx::PgExecutor<'_>,
+    user_id: Uuid,
+    business_id: Uuid,
+    device_id: Option<&str>,
+    request_payload: &Value,
+    transaction_id: Uuid,
+    message_id: Uuid,
+    action_type: ONDCActionType,
 ) -> Result<(), anyhow::Error> {
     sqlx::query!(
         r#"
@@ -77,14 +146,14 @@ pub async fn save_ondc_order_request(
         "#,
         &message_id,
         &transaction_id,
-        &meta_data.device_id,
-        &user_account.id,
-        &business_account.id,
+        device_id,
+        &user_id,
+        &business_id,
         &action_type.to_string(),
         &request_payload
 
     )
-    .execute(pool).await
+    .execute(executor).await
     .map_err(|e| {
         tracing::error!("Failed to execute query: {:?}", e);
         anyhow::Error::new(e).context("A database failure occurred while saving ONDC order request")
@@ -105,6 +174,8 @@ pub async fn save_rfq_order(
     currency_code: &CurrencyType,
     chat_data: &Option<ChatData>,
     order_no: &str,
+    version: i32,
+    buyer_terms: Option<&CommerceBuyerTerms>,
 ) -> Result<Uuid, anyhow::Error> {
     let order_id = Uuid::new_v4();
 
@@ -112,8 +183,8 @@ pub async fn save_rfq_order(
         r#"
         INSERT INTO commerce_data (id, external_urn, urn,  record_type, record_status, 
         domain_category_code, buyer_id, seller_id, seller_name, buyer_name, source, created_on, created_by, bpp_id,
-         bpp_uri, bap_id, bap_uri, quote_ttl, city_code, country_code, currency_code, buyer_chat_link, seller_chat_link)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23)
+         bpp_uri, bap_id, bap_uri, quote_ttl, city_code, country_code, currency_code, buyer_chat_link, seller_chat_link, version, buyer_terms)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25)
         ON CONFLICT (external_urn) 
         DO NOTHING
         "#,
@@ -139,14 +210,29 @@ pub async fn save_rfq_order(
         &select_request.fulfillments[0].location.country.code as &CountryCode,
         &currency_code as &CurrencyType,
         chat_data.as_ref().map(|a| &a.buyer_link),
-        chat_data.as_ref().map(|a| &a.seller_link)
+        chat_data.as_ref().map(|a| &a.seller_link),
+        version,
+        buyer_terms.map(serde_json::to_value).transpose()?
     );
 
-    transaction.execute(query).await.map_err(|e| {
+    let result = transaction.execute(query).await.map_err(|e| {
         tracing::error!("Failed to execute query: {:?}", e);
         anyhow::Error::new(e)
             .context("A database failure occurred while saving RFQ to database request")
     })?;
+    if result.rows_affected() == 1 {
+        if let Some(transition) = get_order_transition(None, &CommerceStatusType::QuoteRequested) {
+            save_status_transitions(
+                transaction,
+                select_request.transaction_id,
+                &[transition],
+                &StatusActor::buyer(user_account.id),
+                ONDCActionType::Select,
+                select_request.message_id,
+            )
+            .await?;
+        }
+    }
     Ok(order_id)
 }
 
@@ -323,6 +409,7 @@ pub async fn save_order_select_items(
     let mut mrp_list = vec![];
     let mut unit_price_list = vec![];
     let mut tax_rate_list = vec![];
+    let mut cess_rate_list = vec![];
     let mut item_req_list = vec![];
     let mut packagin_req_list = vec![];
     for item in &select_request.items {
@@ -349,6 +436,7 @@ pub async fn save_order_select_items(
                     .clone(),
             );
             tax_rate_list.push(seller_item_obj.tax_rate.clone());
+            cess_rate_list.push(seller_item_obj.cess_rate.clone());
         } else {
             item_code_list.push(None);
             item_name_list.push("");
@@ -356,6 +444,7 @@ pub async fn save_order_select_items(
             mrp_list.push(BigDecimal::from(0));
             unit_price_list.push(BigDecimal::from(0));
             tax_rate_list.push(BigDecimal::from(0));
+            cess_rate_list.push(None);
         }
         // let item_name = '';
         // let item_image = ''.as_str();
@@ -371,9 +460,10 @@ pub async fn save_order_select_items(
     let query = sqlx::query!(
         r#"
         INSERT INTO commerce_data_line (id, commerce_data_id, item_id, item_name, item_code, item_image, 
-            qty, location_ids, fulfillment_ids, tax_rate, mrp, unit_price, item_req, packaging_req)
+            qty, location_ids, fulfillment_ids, tax_rate, mrp, unit_price, item_req, packaging_req, cess_rate)
             SELECT * FROM UNNEST($1::uuid[], $2::uuid[], $3::text[], $4::text[], $5::text[], $6::text[],
-             $7::decimal[], $8::jsonb[], $9::jsonb[], $10::decimal[], $11::decimal[], $12::decimal[], $13::text[], $14::text[])
+             $7::decimal[], $8::jsonb[], $9::jsonb[], $10::decimal[], $11::decimal[], $12::decimal[], $13::text[], $14::text[],
+             $15::decimal[])
         ON CONFLICT (commerce_data_id, item_code) 
         DO NOTHING
         "#,
@@ -391,6 +481,7 @@ pub async fn save_order_select_items(
         &unit_price_list[..] as &[BigDecimal],
         &item_req_list[..] as &[Option<&str>],
         &packagin_req_list[..] as &[Option<&str>],
+        &cess_rate_list[..] as &[Option<BigDecimal>],
     );
 
     transaction.execute(query).await.map_err(|e| {
@@ -405,12 +496,12 @@ pub async fn save_order_select_items(
 pub async fn delete_order(
     transaction: &mut Transaction<'_, Postgres>,
     id: Uuid,
-) -> Result<Option<String>, anyhow::Error> {
+) -> Result<Option<(String, i32, Option<CommerceBuyerTerms>)>, anyhow::Error> {
     let query = sqlx::query!(
         r#"
         DELETE FROM commerce_data
         WHERE external_urn = $1
-        RETURNING urn;
+        RETURNING urn, version, buyer_terms as "buyer_terms?: Json<CommerceBuyerTerms>";
         "#,
         id
     );
@@ -423,7 +514,13 @@ pub async fn delete_order(
             anyhow::Error::new(e)
                 .context("A database failure occurred while saving RFQ to database request")
         })?;
-    Ok(result.map(|record| record.urn))
+    Ok(result.map(|record| {
+        (
+            record.urn,
+            record.version,
+            record.buyer_terms.map(|terms| terms.0),
+        )
+    }))
 }
 
 #[tracing::instrument(name = "save select payments", skip(transaction))]
@@ -475,6 +572,7 @@ pub async fn initialize_order_select(
     seller_product_map: &HashMap<String, ONDCSellerProductInfo>,
     chat_data: &Option<ChatData>,
     setting: &SettingData,
+    buyer_terms: Option<&CommerceBuyerTerms>,
 ) -> Result<(), anyhow::Error> {
     let currency_code = seller_product_map
         .iter()
@@ -495,19 +593,20 @@ pub async fn initialize_order_select(
         .begin()
         .await
         .context("Failed to acquire a Postgres connection from the pool")?;
-    let order_no = if let Some(order_no) =
-        delete_order(&mut transaction, select_request.transaction_id).await?
-    {
-        order_no
-    } else {
-        get_series_no(
-            &mut transaction,
-            setting,
-            &bap_detail.subscriber_id,
-            SeriesNoType::Order,
-        )
-        .await?
-    };
+    let (order_no, version) =
+        match delete_order(&mut transaction, select_request.transaction_id).await? {
+            Some((order_no, version, _)) => (order_no, version + 1),
+            None => (
+                get_series_no(
+                    &mut transaction,
+                    setting,
+                    &bap_detail.subscriber_id,
+                    SeriesNoType::Order,
+                )
+                .await?,
+                1,
+            ),
+        };
 
     let order_id = save_rfq_order(
         &mut transaction,
@@ -520,6 +619,8 @@ pub async fn initialize_order_select(
         currency_code,
         chat_data,
         &order_no,
+        version,
+        buyer_terms,
     )
     .await?;
     save_rfq_fulfillment(
@@ -757,6 +858,8 @@ pub async fn save_buyer_order_data_on_select(
     provider_name: &str,
     chat_data: &Option<ChatData>,
     order_no: &str,
+    version: i32,
+    buyer_terms: Option<&CommerceBuyerTerms>,
 ) -> Result<Uuid, anyhow::Error> {
     let grand_total =
         BigDecimal::from_str(&ondc_on_select_req.message.order.quote.price.value).unwrap();
@@ -778,10 +881,11 @@ pub async fn save_buyer_order_data_on_select(
         r#"
         INSERT INTO commerce_data (id, external_urn, urn, record_type, record_status,
         domain_category_code, buyer_id, seller_id, seller_name, buyer_name, source, created_on, created_by, bpp_id, bpp_uri,
-        bap_id, bap_uri, quote_ttl, updated_on, updated_by, currency_code, grand_total, city_code, country_code, seller_chat_link, buyer_chat_link)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26)
+        bap_id, bap_uri, quote_ttl, updated_on, updated_by, currency_code, grand_total, city_code, country_code, seller_chat_link, buyer_chat_link, version, buyer_terms)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28)
         ON CONFLICT (external_urn)
         DO UPDATE SET
+        version = commerce_data.version + 1,
         record_status = EXCLUDED.record_status,
         updated_on = EXCLUDED.updated_on,
         updated_by =  EXCLUDED.updated_by,
@@ -814,7 +918,9 @@ pub async fn save_buyer_order_data_on_select(
         &ondc_select_req.context.location.city.code,
         &ondc_select_req.context.location.country.code as &CountryCode,
         chat_data.as_ref().map(|a| &a.buyer_link),
-        chat_data.as_ref().map(|a| &a.seller_link)
+        chat_data.as_ref().map(|a| &a.seller_link),
+        version,
+        buyer_terms.map(serde_json::to_value).transpose()?
     );
 
     let result = query.fetch_one(&mut **transaction).await.map_err(|e| {
@@ -838,6 +944,7 @@ pub async fn initialize_order_on_select(
     seller_location_map: &HashMap<String, ONDCSellerLocationInfo>,
     seller_info_map: &ONDCSellerInfo,
     setting: &SettingData,
+    previous_status: Option<&CommerceStatusType>,
 ) -> Result<(), anyhow::Error> {
     let pick_up_location = seller_location_map
         .values()
@@ -862,19 +969,21 @@ pub async fn initialize_order_on_select(
         None
     };
 
-    let order_no = if let Some(order_no) =
-        delete_order(transaction, on_select_request.context.transaction_id).await?
-    {
-        order_no
-    } else {
-        get_series_no(
-            transaction,
-            setting,
-            &business_account.subscriber_id,
-            SeriesNoType::Order,
-        )
-        .await?
-    };
+    let (order_no, version, buyer_terms) =
+        match delete_order(transaction, on_select_request.context.transaction_id).await? {
+            Some((order_no, version, buyer_terms)) => (order_no, version + 1, buyer_terms),
+            None => (
+                get_series_no(
+                    transaction,
+                    setting,
+                    &business_account.subscriber_id,
+                    SeriesNoType::Order,
+                )
+                .await?,
+                1,
+                None,
+            ),
+        };
     let order_id = save_buyer_order_data_on_select(
         transaction,
         ondc_select_req,
@@ -884,6 +993,35 @@ pub async fn initialize_order_on_select(
         provider_name,
         &chat_data,
         &order_no,
+        version,
+        buyer_terms.as_ref(),
+    )
+    .await?;
+    let order_status = if on_select_request.error.is_none() {
+        CommerceStatusType::QuoteAccepted
+    } else {
+        CommerceStatusType::QuoteRejected
+    };
+    let quote = &on_select_request.message.order.quote;
+    let mut transitions: Vec<StatusTransition> =
+        get_order_transition(previous_status, &order_status)
+            .into_iter()
+            .collect();
+    transitions.push(get_quote_event(
+        &order_status,
+        Some(serde_json::json!({
+            "grand_total": quote.price.value,
+            "currency": quote.price.currency,
+            "ttl": quote.ttl,
+        })),
+    ));
+    save_status_transitions(
+        transaction,
+        on_select_request.context.transaction_id,
+        &transitions,
+        &StatusActor::seller(on_select_request.context.bpp_id.as_deref().unwrap_or("")),
+        ONDCActionType::OnSelect,
+        on_select_request.context.message_id,
     )
     .await?;
 
@@ -938,13 +1076,20 @@ pub fn get_quote_item_breakup_mapping<'a>(
     header_map
 }
 
+/// Quotes are cumulative, so the refund lines of the latest quote sum up to the total refund so far.
 pub fn get_quote_item_breakup_mapping_for_refund(
     breakups: &Vec<ONDCBreakUp>,
-) -> HashMap<String, &ONDCBreakUp> {
-    let mut header_map = HashMap::new();
+) -> HashMap<String, QuoteRefund> {
+    let mut header_map: HashMap<String, QuoteRefund> = HashMap::new();
     for breakup in breakups {
         if breakup.title_type == BreakupTitleType::Refund {
-            header_map.insert(format!("{}_{}", breakup.title, breakup.item_id), breakup);
+            let refund = header_map
+                .entry(format!("{}_{}", breakup.title, breakup.item_id))
+                .or_default();
+            refund.amount += BigDecimal::from_str(&breakup.price.value).unwrap_or_default();
+            if let Some(quantity) = &breakup.quantity {
+                refund.qty = Some(refund.qty.unwrap_or(0) + quantity.count);
+            }
         }
     }
     header_map
@@ -1034,6 +1179,7 @@ pub async fn save_order_on_select_items(
     let mut mrp_list = vec![];
     let mut unit_price_list = vec![];
     let mut tax_rate_list = vec![];
+    let mut cess_rate_list = vec![];
     let mut tax_amount_list = vec![];
     let mut discount_amount_list = vec![];
     let mut gross_amount_list = vec![];
@@ -1139,6 +1285,7 @@ pub async fn save_order_on_select_items(
             mrp_list.push(seller_item_obj.mrp.clone());
             // unit_price_list.push(seller_item_obj.unit_price.clone());
             tax_rate_list.push(seller_item_obj.tax_rate.clone());
+            cess_rate_list.push(seller_item_obj.cess_rate.clone());
         } else {
             item_code_list.push(None);
             item_name_list.push("");
@@ -1146,6 +1293,7 @@ pub async fn save_order_on_select_items(
             mrp_list.push(BigDecimal::from(0));
 
             tax_rate_list.push(BigDecimal::from(0));
+            cess_rate_list.push(None);
         }
         item_id_list.push(item.id.as_str());
 
@@ -1158,10 +1306,10 @@ pub async fn save_order_on_select_items(
         r#"
         INSERT INTO commerce_data_line (id, commerce_data_id, item_id, item_name, item_code, item_image, 
             qty, location_ids, fulfillment_ids, tax_rate, mrp, unit_price, discount_amount, tax_value, gross_total,
-            available_qty,item_req, packaging_req)
+            available_qty,item_req, packaging_req, cess_rate, seller_item_req, seller_packaging_req)
             SELECT * FROM UNNEST($1::uuid[], $2::uuid[], $3::text[], $4::text[], $5::text[], $6::text[],
              $7::decimal[], $8::jsonb[], $9::jsonb[], $10::decimal[], $11::decimal[], $12::decimal[], $13::decimal[],
-            $14::decimal[], $15::decimal[], $16::decimal[], $17::text[], $18::text[])
+            $14::decimal[], $15::decimal[], $16::decimal[], $17::text[], $18::text[], $19::decimal[], $17::text[], $18::text[])
         ON CONFLICT (commerce_data_id, item_code) 
         DO UPDATE SET 
         fulfillment_ids = EXCLUDED.fulfillment_ids,
@@ -1169,7 +1317,9 @@ pub async fn save_order_on_select_items(
         discount_amount = EXCLUDED.discount_amount,
         tax_value = EXCLUDED.tax_value,
         gross_total = EXCLUDED.gross_total,
-        available_qty = EXCLUDED.available_qty
+        available_qty = EXCLUDED.available_qty,
+        seller_item_req = EXCLUDED.seller_item_req,
+        seller_packaging_req = EXCLUDED.seller_packaging_req
         "#,
         &line_id_list[..] as &[Uuid],
         &order_id_list[..] as &[Uuid],
@@ -1189,6 +1339,7 @@ pub async fn save_order_on_select_items(
         &available_qty_list[..] as &[BigDecimal],
         &item_req_list[..] as &[Option<&str>],
         &packaging_req_list[..] as &[Option<&str>],
+        &cess_rate_list[..] as &[Option<BigDecimal>],
     );
 
     transaction.execute(query).await.map_err(|e| {
@@ -1202,10 +1353,10 @@ pub async fn save_order_on_select_items(
 #[tracing::instrument(name = "fetch buyer commerce data", skip(pool))]
 async fn get_commerce_data(
     pool: &PgPool,
-    transaction_id: Uuid,
-) -> Result<Option<CommerceDataModel>, anyhow::Error> {
+    transaction_ids: &[Uuid],
+) -> Result<Vec<CommerceDataModel>, anyhow::Error> {
     //vectors:sqlx::types::Json<Vec<UserVector>>
-    let record = sqlx::query_as!(
+    let records = sqlx::query_as!(
         CommerceDataModel,
         r#"
         
@@ -1214,16 +1365,20 @@ async fn get_commerce_data(
            domain_category_code as "domain_category_code:CategoryDomain", 
            buyer_id, seller_id, buyer_name, seller_name, source as "source:DataSource", 
            created_on, updated_on, updated_by, deleted_on, is_deleted, created_by, grand_total, 
-           bpp_id, bpp_uri, bap_id, bap_uri, quote_ttl,
+           refund_grand_total, bpp_id, bpp_uri, bap_id, bap_uri, quote_ttl,
            currency_code as "currency_code?:CurrencyType", city_code, buyer_chat_link,
            country_code as "country_code:CountryCode",
            billing as "billing?:  Json<OrderBillingModel>",
            cancellation_terms as "cancellation_terms?: Json<Vec<OrderCancellationTermModel>>",
-           bpp_terms as "bpp_terms?: Json<CommerceBppTermsModel>", documents as "documents?: Json<Vec<CommerceDocumentModel>>"
-        FROM commerce_data where external_urn= $1;"#,
-        transaction_id
+           bpp_terms as "bpp_terms?: Json<CommerceBppTermsModel>", documents as "documents?: Json<Vec<CommerceDocumentModel>>",
+           version, quote_issues as "quote_issues?: Json<Vec<QuoteIssue>>",
+           buyer_terms as "buyer_terms?: Json<CommerceBuyerTerms>",
+           approval_status as "approval_status?: ApprovalStatusType", approval_levels,
+           approval_requested_on
+        FROM commerce_data where external_urn = ANY($1);"#,
+        transaction_ids
     )
-    .fetch_optional(pool)
+    .fetch_all(pool)
     .await
     .map_err(|e| {
         tracing::error!("Failed to execute query: {:?}", e);
@@ -1232,13 +1387,13 @@ async fn get_commerce_data(
         )
     })?;
 
-    Ok(record)
+    Ok(records)
 }
 
 #[tracing::instrument(name = "fetch buyer commerce data line", skip(pool))]
 async fn get_commerce_data_line(
     pool: &PgPool,
-    order_id: Uuid,
+    order_ids: &[Uuid],
 ) -> Result<Vec<CommerceItemModel>, anyhow::Error> {
     let records = sqlx::query_as!(
         CommerceItemModel,
@@ -1253,18 +1408,26 @@ async fn get_commerce_data_line(
             qty, 
             packaging_req, 
             item_req,
+            seller_item_req,
+            seller_packaging_req,
             tax_rate, 
+            cess_rate,
             tax_value, 
             unit_price, 
             gross_total, 
             available_qty, 
             discount_amount, 
             location_ids as "location_ids?: Json<Vec<String>>", 
-            fulfillment_ids as "fulfillment_ids?: Json<Vec<String>>"
+            fulfillment_ids as "fulfillment_ids?: Json<Vec<String>>",
+            cancelled_qty,
+            invoiced_qty,
+            refunded_tax_value,
+            refunded_discount_amount,
+            refunded_gross_total
         FROM commerce_data_line 
-        WHERE commerce_data_id = $1
+        WHERE commerce_data_id = ANY($1)
         "#,
-        order_id
+        order_ids
     )
     .fetch_all(pool)
     .await
@@ -1280,7 +1443,7 @@ async fn get_commerce_data_line(
 #[tracing::instrument(name = "fetch buyer commerce payments", skip(pool))]
 async fn get_commerce_payments(
     pool: &PgPool,
-    order_id: Uuid,
+    order_ids: &[Uuid],
 ) -> Result<Vec<CommercePaymentModel>, anyhow::Error> {
     let records = sqlx::query_as!(
         CommercePaymentModel,
@@ -1299,11 +1462,12 @@ async fn get_commerce_payments(
             settlement_details as "settlement_details?: Json<Vec<PaymentSettlementDetailModel>>",
             payment_status as "payment_status?: PaymentStatus",
             payment_id,
-            payment_order_id
+            payment_order_id,
+            payment_amount
         FROM commerce_payment_data 
-        WHERE commerce_data_id = $1
+        WHERE commerce_data_id = ANY($1)
         "#,
-        order_id
+        order_ids
     )
     .fetch_all(pool)
     .await
@@ -1320,10 +1484,53 @@ async fn get_commerce_payments(
     Ok(records)
 }
 
+#[tracing::instrument(name = "fetch buyer commerce returns", skip(pool))]
+async fn get_commerce_returns(
+    pool: &PgPool,
+    order_ids: &[Uuid],
+) -> Result<Vec<CommerceReturnModel>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        CommerceReturnModel,
+        r#"
+        SELECT
+            id,
+            commerce_data_id,
+            return_id,
+            item_id,
+            qty,
+            reason_id,
+            reason_desc,
+            images as "images: Json<Vec<String>>",
+            fulfillment_id,
+            return_status as "return_status: ReturnStatusType",
+            refund_amount,
+            created_on,
+            updated_on
+        FROM commerce_return_data
+        WHERE commerce_data_id = ANY($1)
+        ORDER BY created_on
+        "#,
+        order_ids
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "Failed to execute query while fetching commerce data returns: {:?}",
+            e
+        );
+        anyhow::Error::new(e).context(
+            "A database failure occurred while fetching commerce data returns from database",
+        )
+    })?;
+
+    Ok(records)
+}
+
 #[tracing::instrument(name = "fetch buyer commerce fulfillments", skip(pool))]
 async fn get_commerce_fulfillments(
     pool: &PgPool,
-    order_id: Uuid,
+    order_ids: &[Uuid],
 ) -> Result<Vec<CommerceFulfillmentModel>, anyhow::Error> {
     let records = sqlx::query_as!(
         CommerceFulfillmentModel,
@@ -1337,6 +1544,7 @@ async fn get_commerce_fulfillments(
             fulfillment_status as "fulfillment_status: FulfillmentStatusType",
             inco_terms as "inco_terms?: IncoTermType",
             place_of_delivery,
+            seller_inco_terms as "seller_inco_terms?: IncoTermType",
             provider_name,
             category as "category?: FulfillmentCategoryType",
             servicable_status as "servicable_status?: ServiceableType", 
@@ -1348,9 +1556,9 @@ async fn get_commerce_fulfillments(
             convenience_fee,
             trade_type as "trade_type?: TradeType"
         FROM commerce_fulfillment_data 
-        WHERE commerce_data_id = $1
+        WHERE commerce_data_id = ANY($1)
         "#,
-        order_id
+        order_ids
     )
     .fetch_all(pool)
     .await
@@ -1367,6 +1575,26 @@ async fn get_commerce_fulfillments(
     Ok(records)
 }
 
+fn get_order_returns_from_model(returns: Vec<CommerceReturnModel>) -> Vec<CommerceReturn> {
+    returns
+        .into_iter()
+        .map(|order_return| CommerceReturn {
+            id: order_return.id,
+            return_id: order_return.return_id,
+            item_id: order_return.item_id,
+            qty: order_return.qty,
+            reason_id: order_return.reason_id,
+            reason_desc: order_return.reason_desc,
+            images: order_return.images.0,
+            fulfillment_id: order_return.fulfillment_id,
+            return_status: order_return.return_status,
+            refund_amount: order_return.refund_amount,
+            created_on: order_return.created_on,
+            updated_on: order_return.updated_on,
+        })
+        .collect()
+}
+
 fn get_order_payment_from_model(payments: Vec<CommercePaymentModel>) -> Vec<CommercePayment> {
     let mut payment_obj = vec![];
     for payment in payments {
@@ -1409,6 +1637,8 @@ fn get_order_payment_from_model(payments: Vec<CommercePaymentModel>) -> Vec<Comm
             settlement_details: Some(settlement_details_list),
             payment_id: payment.payment_id,
             payment_order_id: payment.payment_order_id,
+            payment_status: payment.payment_status,
+            payment_amount: payment.payment_amount,
         })
     }
     payment_obj
@@ -1425,6 +1655,13 @@ fn get_order_items_from_model(items: Vec<CommerceItemModel>) -> Vec<CommerceItem
         } else {
             None
         };
+        let seller_terms =
+            item.seller_item_req
+                .zip(item.seller_packaging_req)
+                .map(|(item_req, packaging_req)| BuyerTerm {
+                    item_req,
+                    packaging_req,
+                });
         let location_ids = item
             .location_ids
             .map(|json| json.0)
@@ -1441,7 +1678,9 @@ fn get_order_items_from_model(items: Vec<CommerceItemModel>) -> Vec<CommerceItem
             item_image: item.item_image,
             qty: item.qty,
             buyer_terms: buyer_term,
+            seller_terms,
             tax_rate: item.tax_rate,
+            cess_rate: item.cess_rate,
             tax_value: item.tax_value,
             unit_price: item.unit_price,
             gross_total: item.gross_total,
@@ -1449,6 +1688,13 @@ fn get_order_items_from_model(items: Vec<CommerceItemModel>) -> Vec<CommerceItem
             discount_amount: item.discount_amount,
             location_ids,
             fulfillment_ids,
+            cancelled_qty: item.cancelled_qty,
+            invoiced_qty: item.invoiced_qty,
+            refunded_tax_value: item.refunded_tax_value,
+            refunded_discount_amount: item.refunded_discount_amount,
+            refunded_gross_total: item.refunded_gross_total,
+            tax_split: None,
+            import_detail: None,
         })
     }
     item_obj
@@ -1514,6 +1760,7 @@ fn get_order_fulfillment_from_model(
             tat: fulfillment.tat,
             fulfillment_status: fulfillment.fulfillment_status,
             delivery_term,
+            seller_inco_terms: fulfillment.seller_inco_terms,
             provider_name: fulfillment.provider_name,
             category: fulfillment.category,
             servicable_status: fulfillment.servicable_status,
@@ -1574,6 +1821,7 @@ fn get_bpp_terms_from_model(bpp_model: CommerceBppTermsModel) -> CommerceBPPTerm
         mandatory_arbitration: bpp_model.mandatory_arbitration,
         court_jurisdiction: bpp_model.court_jurisdiction,
         delay_interest: bpp_model.delay_interest,
+        tax_number: bpp_model.tax_number,
     }
 }
 
@@ -1590,17 +1838,37 @@ fn get_document_from_document_model(
     document_list
 }
 
+/// Import details of an order, kept by transaction id as the order rows are recreated on every quote.
+#[derive(Debug)]
+struct OrderImportModels {
+    lines: Vec<CommerceImportLineModel>,
+    costs: Option<CommerceImportCostModel>,
+    customs_documents: Vec<CustomsDocumentModel>,
+}
+
 #[tracing::instrument(name = "model to struct", skip())]
 fn get_order_from_model(
     order: CommerceDataModel,
     lines: Vec<CommerceItemModel>,
     payments: Vec<CommercePaymentModel>,
     fulfillments: Vec<CommerceFulfillmentModel>,
+    returns: Vec<CommerceReturnModel>,
+    approvals: Vec<OrderApprovalModel>,
+    import_models: OrderImportModels,
 ) -> Commerce {
+    let mut import_lines: HashMap<String, OrderItemImportDetail> = import_models
+        .lines
+        .into_iter()
+        .map(|line| (line.item_id.clone(), line.schema()))
+        .collect();
+    let mut items = get_order_items_from_model(lines);
+    for item in items.iter_mut() {
+        item.import_detail = import_lines.remove(&item.item_id);
+    }
     let cancelletion_model_obj = order
         .cancellation_terms
         .map(|e| get_cancelletion_term_from_model(e.0));
-    Commerce {
+    let mut commerce = Commerce {
         id: order.id,
         urn: order.urn,
         external_urn: order.external_urn,
@@ -1617,6 +1885,7 @@ fn get_order_from_model(
         updated_by: order.updated_by,
         created_by: order.created_by,
         grand_total: order.grand_total,
+        refund_grand_total: order.refund_grand_total,
         bap: BasicNetworkData {
             id: order.bap_id,
             uri: order.bap_uri,
@@ -1629,7 +1898,7 @@ fn get_order_from_model(
         city_code: order.city_code,
         country_code: order.country_code,
         payments: get_order_payment_from_model(payments),
-        items: get_order_items_from_model(lines),
+        items,
         fulfillments: get_order_fulfillment_from_model(fulfillments),
         billing: order
             .billing
@@ -1645,7 +1914,111 @@ fn get_order_from_model(
             .map(|f| get_document_from_document_model(f.0)),
         buyer_id: order.buyer_id,
         chat_link: order.buyer_chat_link,
+        version: order.version,
+        returns: get_order_returns_from_model(returns),
+        quote_issues: order.quote_issues.map(|issues| issues.0),
+        tax_summary: None,
+        buyer_terms: order.buyer_terms.map(|terms| terms.0),
+        buyer_terms_deviations: vec![],
+        import_costs: import_models.costs.map(|costs| costs.schema()),
+        landed_cost: None,
+        customs_documents: import_models
+            .customs_documents
+            .into_iter()
+            .map(|document| document.schema())
+            .collect(),
+        missing_customs_documents: vec![],
+        approval: get_order_approval_from_model(
+            order.approval_status,
+            order.approval_levels,
+            order.approval_requested_on,
+            approvals,
+        ),
+    };
+    apply_order_tax_split(&mut commerce);
+    commerce.buyer_terms_deviations = get_buyer_terms_deviations(&commerce);
+    commerce.landed_cost = get_landed_cost_estimate(&commerce);
+    commerce.missing_customs_documents = get_missing_customs_documents(&commerce);
+    commerce
+}
+
+fn group_by_key<K: std::hash::Hash + Eq, T>(
+    records: Vec<T>,
+    key: impl Fn(&T) -> K,
+) -> HashMap<K, Vec<T>> {
+    let mut groups: HashMap<K, Vec<T>> = HashMap::new();
+    for record in records {
+        groups.entry(key(&record)).or_default().push(record);
+    }
+    groups
+}
+
+#[tracing::instrument(name = "fetch orders", skip(pool))]
+pub async fn fetch_orders_by_ids(
+    pool: &PgPool,
+    transaction_ids: &[Uuid],
+) -> Result<Vec<Commerce>, anyhow::Error> {
+    let order_data_list = get_commerce_data(pool, transaction_ids).await?;
+    if order_data_list.is_empty() {
+        return Ok(vec![]);
     }
+    let order_ids: Vec<Uuid> = order_data_list.iter().map(|order| order.id).collect();
+    let (
+        lines,
+        payments,
+        fulfillments,
+        returns,
+        approvals,
+        import_lines,
+        import_costs,
+        customs_documents,
+    ) = tokio::try_join!(
+        get_commerce_data_line(pool, &order_ids),
+        get_commerce_payments(pool, &order_ids),
+        get_commerce_fulfillments(pool, &order_ids),
+        get_commerce_returns(pool, &order_ids),
+        fetch_order_approvals(pool, transaction_ids),
+        fetch_order_import_lines(pool, transaction_ids),
+        fetch_order_import_costs(pool, transaction_ids),
+        fetch_customs_documents(pool, transaction_ids)
+    )?;
+    let mut lines = group_by_key(lines, |line| line.commerce_data_id);
+    let mut payments = group_by_key(payments, |payment| payment.commerce_data_id);
+    let mut fulfillments = group_by_key(fulfillments, |fulfillment| fulfillment.commerce_data_id);
+    let mut returns = group_by_key(returns, |order_return| order_return.commerce_data_id);
+    let mut approvals = group_by_key(approvals, |approval| approval.transaction_id);
+    let mut import_lines = group_by_key(import_lines, |line| line.transaction_id);
+    let mut import_costs: HashMap<Uuid, CommerceImportCostModel> = import_costs
+        .into_iter()
+        .map(|costs| (costs.transaction_id, costs))
+        .collect();
+    let mut customs_documents = group_by_key(customs_documents, |document| document.transaction_id);
+    let mut orders: HashMap<Uuid, Commerce> = order_data_list
+        .into_iter()
+        .map(|order_data| {
+            let (id, transaction_id) = (order_data.id, order_data.external_urn);
+            let order = get_order_from_model(
+                order_data,
+                lines.remove(&id).unwrap_or_default(),
+                payments.remove(&id).unwrap_or_default(),
+                fulfillments.remove(&id).unwrap_or_default(),
+                returns.remove(&id).unwrap_or_default(),
+                approvals.remove(&transaction_id).unwrap_or_default(),
+                OrderImportModels {
+                    lines: import_lines.remove(&transaction_id).unwrap_or_default(),
+                    costs: import_costs.remove(&transaction_id),
+                    customs_documents: customs_documents
+                        .remove(&transaction_id)
+                        .unwrap_or_default(),
+                },
+            );
+            (transaction_id, order)
+        })
+        .collect();
+    Ok(transaction_ids
+        .iter()
+        .filter_map(|transaction_id| orders.remove(transaction_id))
+        .collect())
 }
 
 #[tracing::instrument(name = "fetch order", skip(pool))]
@@ -1653,22 +2026,76 @@ pub async fn fetch_order_by_id(
     pool: &PgPool,
     transaction_id: Uuid,
 ) -> Result<Option<Commerce>, anyhow::Error> {
-    if let Some(order_data) = get_commerce_data(pool, transaction_id).await? {
-        let task1 = get_commerce_data_line(pool, order_data.id);
-
-        let task2 = get_commerce_payments(pool, order_data.id);
-
-        let task3 = get_commerce_fulfillments(pool, order_data.id);
-        let (lines, payments, fulfillmets) = tokio::try_join!(task1, task2, task3)?;
-        Ok(Some(get_order_from_model(
-            order_data,
-            lines,
-            payments,
-            fulfillmets,
-        )))
-    } else {
-        Ok(None)
-    }
+    Ok(fetch_orders_by_ids(pool, &[transaction_id]).await?.pop())
+}
+
+#[tracing::instrument(name = "fetch order status for update", skip(transaction))]
+pub async fn fetch_commerce_status_for_update(
+    transaction: &mut Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+) -> Result<Option<CommerceStatusType>, anyhow::Error> {
+    let row = sqlx::query!(
+        r#"SELECT record_status as "record_status: CommerceStatusType" FROM commerce_data
+        WHERE external_urn = $1 FOR UPDATE"#,
+        transaction_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while fetching order status")
+    })?;
+    Ok(row.map(|row| row.record_status))
+}
+
+/// Locks the order row for the rest of the transaction so that callbacks on the same order are
+/// applied one after another, and returns the `context.timestamp` of the last applied callback.
+#[tracing::instrument(name = "fetch order callback timestamp for update", skip(transaction))]
+pub async fn fetch_order_callback_on_for_update(
+    transaction: &mut Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+) -> Result<Option<Option<DateTime<Utc>>>, anyhow::Error> {
+    let row = sqlx::query!(
+        r#"SELECT last_callback_on FROM commerce_data
+        WHERE external_urn = $1 FOR UPDATE"#,
+        transaction_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching order callback timestamp")
+    })?;
+    Ok(row.map(|row| row.last_callback_on))
+}
+
+#[tracing::instrument(name = "update order callback timestamp", skip(transaction))]
+pub async fn update_order_callback_on(
+    transaction: &mut Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+    callback_on: DateTime<Utc>,
+) -> Result<(), anyhow::Error> {
+    let query = sqlx::query!(
+        r#"
+        UPDATE commerce_data SET last_callback_on = GREATEST(last_callback_on, $1) WHERE external_urn = $2
+        "#,
+        callback_on,
+        transaction_id
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while updating order callback timestamp")
+    })?;
+    Ok(())
+}
+
+pub fn is_stale_callback(
+    last_callback_on: Option<DateTime<Utc>>,
+    callback_on: DateTime<Utc>,
+) -> bool {
+    last_callback_on.is_some_and(|last_callback_on| callback_on < last_callback_on)
 }
 
 #[tracing::instrument(name = "delete payment", skip(transaction))]
@@ -1866,12 +2293,19 @@ pub fn get_bpp_term_model_from_tag(tags: &[ONDCTag]) -> CommerceBppTermsModel {
     )
     .unwrap_or_default()
     .to_owned();
+    let tax_number = get_tag_value_from_list(
+        tags,
+        ONDCTagType::BppTerms,
+        &ONDCTagItemCode::TaxNumber.to_string(),
+    )
+    .map(|tax_number| tax_number.to_owned());
     CommerceBppTermsModel {
         max_liability,
         max_liability_cap,
         mandatory_arbitration,
         court_jurisdiction,
         delay_interest,
+        tax_number,
     }
 }
 
@@ -1910,7 +2344,7 @@ async fn update_commerce_in_on_init(
 
     let query = sqlx::query!(
         r#"
-        UPDATE commerce_data SET billing=$1, bpp_terms=$2, record_status=$3, cancellation_terms=$4, updated_on=$5, updated_by=$6 WHERE external_urn=$7
+        UPDATE commerce_data SET billing=$1, bpp_terms=$2, record_status=$3, cancellation_terms=$4, updated_on=$5, updated_by=$6, version=version + 1 WHERE external_urn=$7
         "#,
         serde_json::to_value(billing).unwrap(),
         serde_json::to_value(bpp_terms).unwrap(),
@@ -1933,6 +2367,7 @@ async fn update_commerce_in_on_init(
 pub async fn initialize_order_on_init(
     transaction: &mut Transaction<'_, Postgres>,
     on_init_request: &ONDCOnInitRequest,
+    order: &Commerce,
     business_id: Uuid,
 ) -> Result<(), anyhow::Error> {
     let commerce_id =
@@ -1944,6 +2379,123 @@ pub async fn initialize_order_on_init(
     )
     .await?;
     update_commerce_in_on_init(transaction, on_init_request, business_id).await?;
+    update_seller_item_terms(
+        transaction,
+        commerce_id,
+        &on_init_request.message.order.items,
+    )
+    .await?;
+    update_seller_delivery_terms(
+        transaction,
+        commerce_id,
+        &on_init_request.message.order.fulfillments,
+    )
+    .await?;
+    if let Some(transition) =
+        get_order_transition(Some(&order.record_status), &CommerceStatusType::Initialized)
+    {
+        save_status_transitions(
+            transaction,
+            order.external_urn,
+            &[transition],
+            &StatusActor::seller(&order.bpp.id),
+            ONDCActionType::OnInit,
+            on_init_request.context.message_id,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Item terms of the on_init item tags, items the seller sent without terms keep the on_select terms.
+#[tracing::instrument(name = "update seller item terms", skip(transaction, items))]
+async fn update_seller_item_terms(
+    transaction: &mut Transaction<'_, Postgres>,
+    commerce_id: Uuid,
+    items: &[ONDCSelectedItem],
+) -> Result<(), anyhow::Error> {
+    let mut item_id_list = vec![];
+    let mut item_req_list = vec![];
+    let mut packaging_req_list = vec![];
+    for item in items {
+        let Some(tags) = &item.tags else {
+            continue;
+        };
+        item_id_list.push(item.id.as_str());
+        item_req_list.push(get_tag_value_from_list(
+            tags,
+            ONDCTagType::BuyerTerms,
+            &ONDCTagItemCode::ItemReq.to_string(),
+        ));
+        packaging_req_list.push(get_tag_value_from_list(
+            tags,
+            ONDCTagType::BuyerTerms,
+            &ONDCTagItemCode::PackagingsReq.to_string(),
+        ));
+    }
+    if item_id_list.is_empty() {
+        return Ok(());
+    }
+    let query = sqlx::query!(
+        r#"
+        UPDATE commerce_data_line SET seller_item_req = t.item_req, seller_packaging_req = t.packaging_req
+        FROM UNNEST($2::text[], $3::text[], $4::text[]) AS t(item_id, item_req, packaging_req)
+        WHERE commerce_data_id = $1 AND commerce_data_line.item_id = t.item_id
+        "#,
+        commerce_id,
+        &item_id_list[..] as &[&str],
+        &item_req_list[..] as &[Option<&str>],
+        &packaging_req_list[..] as &[Option<&str>]
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while saving seller item terms to database")
+    })?;
+    Ok(())
+}
+
+/// INCOTERMS of the delivery terms tag of a seller fulfillment.
+pub fn get_seller_inco_terms(tags: &[ONDCTag]) -> Option<IncoTermType> {
+    get_tag_value_from_list(
+        tags,
+        ONDCTagType::DeliveyTerms,
+        &ONDCTagItemCode::IncoTerms.to_string(),
+    )
+    .and_then(|inco_terms| {
+        serde_json::from_value(Value::String(inco_terms.trim().to_uppercase())).ok()
+    })
+}
+
+#[tracing::instrument(name = "update seller delivery terms", skip(transaction, fulfillments))]
+async fn update_seller_delivery_terms(
+    transaction: &mut Transaction<'_, Postgres>,
+    commerce_id: Uuid,
+    fulfillments: &[ONDCOnInitFulfillment],
+) -> Result<(), anyhow::Error> {
+    let fulfillment_id_list: Vec<&str> = fulfillments
+        .iter()
+        .map(|fulfillment| fulfillment.id.as_str())
+        .collect();
+    let inco_terms_list: Vec<Option<IncoTermType>> = fulfillments
+        .iter()
+        .map(|fulfillment| fulfillment.tags.as_deref().and_then(get_seller_inco_terms))
+        .collect();
+    let query = sqlx::query!(
+        r#"
+        UPDATE commerce_fulfillment_data SET seller_inco_terms = t.inco_terms
+        FROM UNNEST($2::text[], $3::inco_term_type[]) AS t(fulfillment_id, inco_terms)
+        WHERE commerce_data_id = $1 AND commerce_fulfillment_data.fulfillment_id = t.fulfillment_id
+        "#,
+        commerce_id,
+        &fulfillment_id_list[..] as &[&str],
+        &inco_terms_list[..] as &[Option<IncoTermType>]
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while saving seller delivery terms to database")
+    })?;
     Ok(())
 }
 
@@ -1956,7 +2508,7 @@ async fn update_commerce_in_on_confirm(
 ) -> Result<(), anyhow::Error> {
     let query = sqlx::query!(
         r#"
-        UPDATE commerce_data SET record_status=$1, updated_on=$2, updated_by=$3 WHERE external_urn=$4
+        UPDATE commerce_data SET record_status=$1, updated_on=$2, updated_by=$3, version=version + 1 WHERE external_urn=$4
         "#,
         confirm_req
             .message
@@ -2041,13 +2593,14 @@ fn get_bulk_update_on_confirm_data(
                     confirm_fulfillment.get_fulfillment_time(ONDCFulfillmentStopType::Start),
                     confirm_fulfillment.get_fulfillment_instruction(ONDCFulfillmentStopType::Start),
                 );
-                fulfillment_statuses.push(
+                fulfillment_statuses.push(resolve_fulfillment_status(
+                    &order_fulfillment.fulfillment_status,
                     confirm_fulfillment
                         .state
                         .descriptor
                         .code
                         .get_fulfillment_state(),
-                );
+                ));
 
                 pickup_datas.push(serde_json::to_value(pick_up_data).unwrap());
                 fulfillment_ids.push(confirm_fulfillment.id.to_owned());
@@ -2243,6 +2796,18 @@ pub async fn initialize_order_on_confirm(
     business_id: Uuid,
 ) -> Result<(), anyhow::Error> {
     update_commerce_in_on_confirm(transaction, order, on_confirm_request, business_id).await?;
+    update_invoiced_qtys(
+        transaction,
+        order.id,
+        &on_confirm_request.message.order.quote,
+    )
+    .await?;
+    update_order_callback_on(
+        transaction,
+        on_confirm_request.context.transaction_id,
+        on_confirm_request.context.timestamp,
+    )
+    .await?;
     update_commerce_fulfillment_in_on_confirm(
         transaction,
         order.id,
@@ -2250,6 +2815,47 @@ pub async fn initialize_order_on_confirm(
         &order.fulfillments,
     )
     .await?;
+    let record_status = on_confirm_request
+        .message
+        .order
+        .state
+        .get_commerce_status(&order.record_type, None);
+    let mut transitions: Vec<StatusTransition> =
+        get_order_transition(Some(&order.record_status), &record_status)
+            .into_iter()
+            .collect();
+    transitions.extend(get_fulfillment_transitions(
+        &order.fulfillments,
+        on_confirm_request
+            .message
+            .order
+            .fulfillments
+            .iter()
+            .map(|f| {
+                (
+                    f.id.as_str(),
+                    f.state.descriptor.code.get_fulfillment_state(),
+                )
+            }),
+    ));
+    transitions.extend(get_payment_transitions(
+        &order.payments,
+        on_confirm_request
+            .message
+            .order
+            .payments
+            .iter()
+            .map(|p| (p.id.as_deref(), p.status.get_payment_status())),
+    ));
+    save_status_transitions(
+        transaction,
+        order.external_urn,
+        &transitions,
+        &StatusActor::seller(&order.bpp.id),
+        ONDCActionType::OnConfirm,
+        on_confirm_request.context.message_id,
+    )
+    .await?;
     let _ =
         delete_payment_in_commerce(transaction, on_confirm_request.context.transaction_id).await?;
 
@@ -2302,6 +2908,38 @@ fn get_ondc_document(
 
     final_documents
 }
+fn is_order_status_changed(
+    order: &Commerce,
+    record_status: &CommerceStatusType,
+    status_req: &ONDCOnStatusRequest,
+) -> bool {
+    if &order.record_status != record_status {
+        return true;
+    }
+    let fulfillment_status_map: HashMap<&str, &FulfillmentStatusType> = order
+        .fulfillments
+        .iter()
+        .map(|fulfillment| {
+            (
+                fulfillment.fulfillment_id.as_str(),
+                &fulfillment.fulfillment_status,
+            )
+        })
+        .collect();
+    status_req
+        .message
+        .order
+        .get_forward_fulfillments()
+        .into_iter()
+        .any(|fulfillment| {
+            fulfillment_status_map
+                .get(fulfillment.id.as_str())
+                .is_none_or(|status| {
+                    **status != fulfillment.state.descriptor.code.get_fulfillment_state()
+                })
+        })
+}
+
 #[tracing::instrument(name = "save buyer commerce on on_status", skip(transaction))]
 async fn update_commerce_in_on_status(
     transaction: &mut Transaction<'_, Postgres>,
@@ -2309,16 +2947,19 @@ async fn update_commerce_in_on_status(
     status_req: &ONDCOnStatusRequest,
 ) -> Result<(), anyhow::Error> {
     let document_type = status_req.message.order.documents.is_some();
+    let record_status = status_req
+        .message
+        .order
+        .state
+        .get_commerce_status(&order.record_type, Some(document_type));
+    let is_changed = is_order_status_changed(order, &record_status, status_req);
     let query = sqlx::query!(
         r#"
-        UPDATE commerce_data SET record_status=$1, updated_on=$2, documents=$3 WHERE external_urn=$4
+        UPDATE commerce_data SET record_status=$1, updated_on=$2, documents=$3, version=version + 1,
+        status_poll_count = CASE WHEN $4 THEN 0 ELSE status_poll_count END, last_callback_on=$6
+        WHERE external_urn=$5
         "#,
-        status_req
-            .message
-            .order
-            .state
-            .get_commerce_status(&order.record_type, Some(document_type))
-            as CommerceStatusType,
+        record_status as CommerceStatusType,
         status_req.message.order.updated_at,
         status_req
             .message
@@ -2326,7 +2967,9 @@ async fn update_commerce_in_on_status(
             .documents
             .as_ref()
             .map(|docs| serde_json::to_value(get_ondc_document(&order.documents, docs)).unwrap()),
+        is_changed,
         status_req.context.transaction_id,
+        status_req.context.timestamp,
     );
 
     transaction.execute(query).await.map_err(|e| {
@@ -2414,7 +3057,7 @@ pub fn create_pickup_off_from_on_status_fulfullment(
 
 fn get_bulk_update_on_status_data(
     commerce_id: Uuid,
-    status_fulfillments: &Vec<ONDCOnConfirmFulfillment>,
+    status_fulfillments: &[&ONDCOnConfirmFulfillment],
     order_fulfillments: &[CommerceFulfillment],
 ) -> BulkStatusFulfillmentData {
     let mut fulfillment_statuses = vec![];
@@ -2456,13 +3099,14 @@ fn get_bulk_update_on_status_data(
                 } else {
                     None
                 };
-                fulfillment_statuses.push(
+                fulfillment_statuses.push(resolve_fulfillment_status(
+                    &order_fulfillment.fulfillment_status,
                     status_fulfillment
                         .state
                         .descriptor
                         .code
                         .get_fulfillment_state(),
-                );
+                ));
 
                 pickup_datas.push(serde_json::to_value(pick_up_data).unwrap());
                 drop_off_datas.push(serde_json::to_value(drop_off_data).unwrap());
@@ -2486,8 +3130,9 @@ fn get_bulk_update_on_status_data(
 async fn update_commerce_fulfillment_in_on_status(
     transaction: &mut Transaction<'_, Postgres>,
     commerce_id: Uuid,
-    status_fulfillments: &Vec<ONDCOnConfirmFulfillment>,
+    status_fulfillments: &[&ONDCOnConfirmFulfillment],
     order_fulfillments: &Vec<CommerceFulfillment>,
+    callback_on: DateTime<Utc>,
 ) -> Result<(), anyhow::Error> {
     let data = get_bulk_update_on_status_data(commerce_id, status_fulfillments, order_fulfillments);
     println!("{:?}", data.pickup_datas);
@@ -2498,17 +3143,20 @@ async fn update_commerce_fulfillment_in_on_status(
         SET
         fulfillment_status = t.fulfillment_status,
         pickup_data  = t.pickup_data,
-        drop_off_data = t.drop_off_data
+        drop_off_data = t.drop_off_data,
+        last_callback_on = $6
         FROM UNNEST($1::uuid[],  $2::commerce_fulfillment_status_type[], $3::jsonb[], $4::jsonb[], $5::text[])
         AS t(commerce_data_id, fulfillment_status, pickup_data, drop_off_data, fulfillment_id)
         WHERE commerce_fulfillment_data.commerce_data_id = t.commerce_data_id and 
-        commerce_fulfillment_data.fulfillment_id=t.fulfillment_id;
+        commerce_fulfillment_data.fulfillment_id=t.fulfillment_id and
+        (commerce_fulfillment_data.last_callback_on IS NULL OR commerce_fulfillment_data.last_callback_on <= $6);
         "#,
         &data.commerce_data_ids,
         &data.fulfillment_statuses as &Vec<FulfillmentStatusType>,
         &data.pickup_datas,
         &data.drop_off_datas,
         &data.fulfillment_ids,
+        callback_on
     );
     transaction.execute(query).await.map_err(|e| {
         tracing::error!("Failed to execute query: {:?}", e);
@@ -2526,18 +3174,112 @@ pub async fn initialize_order_on_status(
     order: &Commerce,
 ) -> Result<(), anyhow::Error> {
     update_commerce_in_on_status(transaction, order, on_status_request).await?;
+    update_invoiced_qtys(
+        transaction,
+        order.id,
+        &on_status_request.message.order.quote,
+    )
+    .await?;
 
     update_commerce_fulfillment_in_on_status(
         transaction,
         order.id,
-        &on_status_request.message.order.fulfillments,
+        &on_status_request.message.order.get_forward_fulfillments(),
         &order.fulfillments,
+        on_status_request.context.timestamp,
     )
     .await?;
+    let proforma_present = on_status_request.message.order.documents.is_some();
+    let record_status = on_status_request
+        .message
+        .order
+        .state
+        .get_commerce_status(&order.record_type, Some(proforma_present));
+    let mut transitions: Vec<StatusTransition> =
+        get_order_transition(Some(&order.record_status), &record_status)
+            .into_iter()
+            .collect();
+    transitions.extend(get_fulfillment_transitions(
+        &order.fulfillments,
+        on_status_request
+            .message
+            .order
+            .get_forward_fulfillments()
+            .into_iter()
+            .map(|f| {
+                (
+                    f.id.as_str(),
+                    f.state.descriptor.code.get_fulfillment_state(),
+                )
+            }),
+    ));
+    transitions.extend(
+        update_order_returns(
+            transaction,
+            order,
+            &on_status_request.message.order.get_return_fulfillments(),
+            Some(&on_status_request.message.order.quote),
+        )
+        .await?,
+    );
+    transitions.extend(get_payment_transitions(
+        &order.payments,
+        on_status_request
+            .message
+            .order
+            .payments
+            .iter()
+            .map(|p| (p.id.as_deref(), p.status.get_payment_status())),
+    ));
+    save_status_transitions(
+        transaction,
+        order.external_urn,
+        &transitions,
+        &StatusActor::seller(&order.bpp.id),
+        ONDCActionType::OnStatus,
+        on_status_request.context.message_id,
+    )
+    .await?;
+    let delivered_status = FulfillmentStatusType::OrderDelivered.to_string();
+    let delivered_fulfillment_ids: Vec<&str> = transitions
+        .iter()
+        .filter(|transition| {
+            transition.entity_type == StatusEntityType::Fulfillment
+                && transition.is_valid
+                && transition.status == delivered_status
+        })
+        .filter_map(|transition| transition.entity_id.as_deref())
+        .collect();
+    if !delivered_fulfillment_ids.is_empty() {
+        let confirmed_on = fetch_order_confirmed_on(transaction, order.external_urn).await?;
+        let events: Vec<SellerScorecardEvent> = delivered_fulfillment_ids
+            .into_iter()
+            .map(|fulfillment_id| SellerScorecardEvent::Delivered {
+                fulfillment_id: fulfillment_id.to_string(),
+                is_on_time: is_delivered_on_time(
+                    confirmed_on,
+                    order
+                        .fulfillments
+                        .iter()
+                        .find(|fulfillment| fulfillment.fulfillment_id == fulfillment_id)
+                        .and_then(|fulfillment| fulfillment.tat.as_deref()),
+                    on_status_request.context.timestamp,
+                ),
+            })
+            .collect();
+        save_seller_scorecard_events(
+            transaction,
+            order.external_urn,
+            &order.bpp.id,
+            &order.seller.id,
+            events,
+        )
+        .await?;
+    }
+
+    let _ =
+        delete_payment_in_commerce(transaction, on_status_request.context.transaction_id).await?;
 
-    let _ =
-        delete_payment_in_commerce(transaction, on_status_request.context.transaction_id).await?;
-
     initialize_payment_on_confirm(
         transaction,
         order,
@@ -2556,7 +3298,7 @@ async fn update_commerce_in_on_cancel(
 ) -> Result<(), anyhow::Error> {
     let query = sqlx::query!(
         r#"
-        UPDATE commerce_data SET record_status=$1, updated_on=$2, updated_by=$3, refund_grand_total=$4 WHERE external_urn=$5
+        UPDATE commerce_data SET record_status=$1, updated_on=$2, updated_by=$3, refund_grand_total=$4, version=version + 1 WHERE external_urn=$5
         "#,
         cancel_req
             .message
@@ -2631,7 +3373,7 @@ fn get_on_cancel_bulk_fulfillment_data(
                 ONDCTitleName::ConvenienceFee,
                 fulfillment.id,
             ))
-            .map(|e| BigDecimal::from_str(&e.price.value).unwrap_or(BigDecimal::from(0)))
+            .map(|e| e.amount.clone())
             .unwrap_or(BigDecimal::from(0));
         let delivery_charge = item_breakup_mapping
             .get(&format!(
@@ -2639,11 +3381,11 @@ fn get_on_cancel_bulk_fulfillment_data(
                 ONDCTitleName::DeliveryCharge,
                 fulfillment.id,
             ))
-            .map(|e| BigDecimal::from_str(&e.price.value).unwrap_or(BigDecimal::from(0)))
+            .map(|e| e.amount.clone())
             .unwrap_or(BigDecimal::from(0));
         let packaging_charge = item_breakup_mapping
             .get(&format!("{}_{}", ONDCTitleName::Packing, fulfillment.id,))
-            .map(|e| BigDecimal::from_str(&e.price.value).unwrap_or(BigDecimal::from(0)))
+            .map(|e| e.amount.clone())
             .unwrap_or(BigDecimal::from(0));
         refunded_convenience_fees.push(convenience_fee);
         refunded_delivery_charges.push(delivery_charge);
@@ -2668,6 +3410,40 @@ pub async fn initialize_order_on_cancel(
     updated_by: &str,
 ) -> Result<(), anyhow::Error> {
     update_commerce_in_on_cancel(transaction, order, on_cancel_request, updated_by).await?;
+    update_order_callback_on(
+        transaction,
+        on_cancel_request.context.transaction_id,
+        on_cancel_request.context.timestamp,
+    )
+    .await?;
+    let mut transitions: Vec<StatusTransition> = get_order_transition(
+        Some(&order.record_status),
+        &on_cancel_request
+            .message
+            .order
+            .state
+            .get_commerce_status(&order.record_type, None),
+    )
+    .into_iter()
+    .collect();
+    transitions.extend(get_payment_transitions(
+        &order.payments,
+        on_cancel_request
+            .message
+            .order
+            .payments
+            .iter()
+            .map(|p| (p.id.as_deref(), p.status.get_payment_status())),
+    ));
+    save_status_transitions(
+        transaction,
+        order.external_urn,
+        &transitions,
+        &StatusActor::seller(&order.bpp.id),
+        ONDCActionType::OnCancel,
+        on_cancel_request.context.message_id,
+    )
+    .await?;
 
     let _ =
         delete_payment_in_commerce(transaction, on_cancel_request.context.transaction_id).await?;
@@ -2681,80 +3457,87 @@ pub async fn initialize_order_on_cancel(
 
     update_on_cancel_fulfillments(transaction, order.id, on_cancel_request).await?;
     update_on_cancel_items(transaction, order.id, on_cancel_request, order).await?;
+    update_invoiced_qtys(
+        transaction,
+        order.id,
+        &on_cancel_request.message.order.quote,
+    )
+    .await?;
 
     Ok(())
 }
 
-fn get_on_cancel_bulk_item_data(
+pub fn get_refund_bulk_item_data<'a>(
     commerce_id: Uuid,
-    request: &ONDCOnCancelRequest,
-    order: &Commerce,
+    breakups: &Vec<ONDCBreakUp>,
+    item_ids: impl Iterator<Item = &'a str>,
+    items: &[CommerceItem],
 ) -> BulkCancelItemData {
     let mut commerce_ids = vec![];
     let mut refunded_tax_values = vec![];
-    let mut item_ids = vec![];
+    let mut item_id_list = vec![];
+    let mut cancelled_qtys = vec![];
     let mut refunded_discount_amounts = vec![];
     let mut refunded_gross_totals = vec![];
-    let item_breakup_mapping =
-        get_quote_item_breakup_mapping_for_refund(&request.message.order.quote.breakup);
-    let item_mapping: HashMap<&str, &CommerceItem> = order
-        .items
+    let item_breakup_mapping = get_quote_item_breakup_mapping_for_refund(breakups);
+    let item_mapping: HashMap<&str, &CommerceItem> = items
         .iter()
         .map(|product| (product.item_id.as_str(), product))
         .collect();
-    for item in &request.message.order.items {
+    for item_id in item_ids {
+        let item_name = item_mapping
+            .get(item_id)
+            .map(|e| e.item_name.as_str())
+            .unwrap_or(item_id);
+        let item_breakup = item_breakup_mapping.get(&format!("{}_{}", item_name, item_id));
         commerce_ids.push(commerce_id);
-        item_ids.push(item.id.clone());
+        item_id_list.push(item_id.to_owned());
 
         let refunded_discount_amount = item_breakup_mapping
-            .get(&format!("{}_{}", ONDCTitleName::Discount, item.id,))
-            .map(|e| BigDecimal::from_str(&e.price.value).unwrap_or(BigDecimal::from(0)))
+            .get(&format!("{}_{}", ONDCTitleName::Discount, item_id,))
+            .map(|e| e.amount.clone())
             .unwrap_or(BigDecimal::from(0));
         refunded_discount_amounts.push(refunded_discount_amount);
 
         let refunded_tax_value = item_breakup_mapping
-            .get(&format!("{}_{}", ONDCTitleName::Tax, item.id))
-            .map(|e| BigDecimal::from_str(&e.price.value).unwrap_or(BigDecimal::from(0)))
+            .get(&format!("{}_{}", ONDCTitleName::Tax, item_id))
+            .map(|e| e.amount.clone())
             .unwrap_or(BigDecimal::from(0));
         refunded_tax_values.push(refunded_tax_value);
-        let item_name = item_mapping
-            .get(item.id.as_str())
-            .map(|e| &e.item_name)
-            .unwrap_or(&item.id);
 
-        let refund_gross_total = item_breakup_mapping
-            .get(&format!("{}_{}", item_name, item.id,))
-            .map(|e| BigDecimal::from_str(&e.price.value).unwrap_or(BigDecimal::from(0)))
+        let refund_gross_total = item_breakup
+            .map(|e| e.amount.clone())
             .unwrap_or(BigDecimal::from(0));
         refunded_gross_totals.push(refund_gross_total);
+        cancelled_qtys.push(item_breakup.and_then(|e| e.qty).map(BigDecimal::from));
     }
 
     BulkCancelItemData {
         commerce_ids,
-        item_ids,
+        item_ids: item_id_list,
+        cancelled_qtys,
         refunded_discount_amounts,
         refunded_gross_totals,
         refunded_tax_values,
     }
 }
-#[tracing::instrument(name = "bulk_update_on_cancel_items", skip(transaction))]
-async fn update_on_cancel_items(
+
+#[tracing::instrument(name = "bulk_update_refund_items", skip(transaction))]
+async fn update_refund_items(
     transaction: &mut Transaction<'_, Postgres>,
-    commerce_id: Uuid,
-    request: &ONDCOnCancelRequest,
-    order: &Commerce,
+    data: BulkCancelItemData,
 ) -> Result<(), anyhow::Error> {
-    let data = get_on_cancel_bulk_item_data(commerce_id, request, order);
     let query = sqlx::query!(
         r#"
         UPDATE commerce_data_line
         SET
             refunded_tax_value = t.refunded_tax_value,
             refunded_discount_amount  = t.refunded_discount_amount,
-            refunded_gross_total = t.refunded_gross_total
-    
-        FROM UNNEST($1::uuid[], $2::text[], $3::decimal[], $4::decimal[], $5::decimal[]) AS t(id, item_id,
-             refunded_discount_amount, refunded_gross_total, refunded_tax_value)
+            refunded_gross_total = t.refunded_gross_total,
+            cancelled_qty = CASE WHEN t.cancelled_qty IS NULL THEN commerce_data_line.cancelled_qty
+                ELSE LEAST(commerce_data_line.qty, t.cancelled_qty) END
+        FROM UNNEST($1::uuid[], $2::text[], $3::decimal[], $4::decimal[], $5::decimal[], $6::decimal[]) AS t(id, item_id,
+             refunded_discount_amount, refunded_gross_total, refunded_tax_value, cancelled_qty)
         WHERE commerce_data_id = t.id and commerce_data_line.item_id=t.item_id;
         "#,
         &data.commerce_ids,
@@ -2762,15 +3545,242 @@ async fn update_on_cancel_items(
         &data.refunded_discount_amounts,
         &data.refunded_gross_totals,
         &data.refunded_tax_values,
+        &data.cancelled_qtys as &Vec<Option<BigDecimal>>,
     );
     transaction.execute(query).await.map_err(|e| {
         tracing::error!("Failed to execute query: {:?}", e);
         anyhow::Error::new(e)
-            .context("A database failure occurred while saving RFQ to database request")
+            .context("A database failure occurred while saving item refunds to database")
+    })?;
+    Ok(())
+}
+
+/// Quantity per item of the item lines of a seller quote.
+pub fn get_quote_item_qty_map(quote: &ONDCQuote) -> HashMap<&str, BigDecimal> {
+    let mut qty_map: HashMap<&str, BigDecimal> = HashMap::new();
+    for breakup in &quote.breakup {
+        if let (BreakupTitleType::Item, Some(quantity)) = (&breakup.title_type, &breakup.quantity) {
+            *qty_map.entry(breakup.item_id.as_str()).or_default() +=
+                BigDecimal::from(quantity.count);
+        }
+    }
+    qty_map
+}
+
+#[tracing::instrument(name = "update invoiced quantity", skip(transaction, quote))]
+async fn update_invoiced_qtys(
+    transaction: &mut Transaction<'_, Postgres>,
+    commerce_id: Uuid,
+    quote: &ONDCQuote,
+) -> Result<(), anyhow::Error> {
+    let qty_map = get_quote_item_qty_map(quote);
+    if qty_map.is_empty() {
+        return Ok(());
+    }
+    let (item_ids, invoiced_qtys): (Vec<String>, Vec<BigDecimal>) = qty_map
+        .into_iter()
+        .map(|(item_id, qty)| (item_id.to_owned(), qty))
+        .unzip();
+    let query = sqlx::query!(
+        r#"
+        UPDATE commerce_data_line SET invoiced_qty = t.invoiced_qty
+        FROM UNNEST($2::text[], $3::decimal[]) AS t(item_id, invoiced_qty)
+        WHERE commerce_data_id = $1 AND commerce_data_line.item_id = t.item_id
+        "#,
+        commerce_id,
+        &item_ids,
+        &invoiced_qtys
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while saving invoiced quantity to database")
+    })?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "bulk_update_on_cancel_items", skip(transaction))]
+async fn update_on_cancel_items(
+    transaction: &mut Transaction<'_, Postgres>,
+    commerce_id: Uuid,
+    request: &ONDCOnCancelRequest,
+    order: &Commerce,
+) -> Result<(), anyhow::Error> {
+    let data = get_refund_bulk_item_data(
+        commerce_id,
+        &request.message.order.quote.breakup,
+        request
+            .message
+            .order
+            .items
+            .iter()
+            .map(|item| item.id.as_str()),
+        &order.items,
+    );
+    update_refund_items(transaction, data).await
+}
+
+/// Items whose quote carries a refund line have been (partially) cancelled by the update.
+/// Refunds of returned items are settled on the return itself and are skipped here.
+#[tracing::instrument(name = "bulk_update_on_update_items", skip(transaction))]
+async fn update_on_update_items(
+    transaction: &mut Transaction<'_, Postgres>,
+    order: &Commerce,
+    quote: &ONDCQuote,
+    returned_item_ids: &HashSet<&str>,
+) -> Result<(), anyhow::Error> {
+    let refunded_item_ids: HashSet<&str> = quote
+        .breakup
+        .iter()
+        .filter(|breakup| breakup.title_type == BreakupTitleType::Refund)
+        .map(|breakup| breakup.item_id.as_str())
+        .filter(|item_id| !returned_item_ids.contains(item_id))
+        .collect();
+    if refunded_item_ids.is_empty() {
+        return Ok(());
+    }
+    let data = get_refund_bulk_item_data(
+        order.id,
+        &quote.breakup,
+        order
+            .items
+            .iter()
+            .map(|item| item.item_id.as_str())
+            .filter(|item_id| refunded_item_ids.contains(item_id)),
+        &order.items,
+    );
+    update_refund_items(transaction, data).await
+}
+
+#[tracing::instrument(name = "save buyer commerce on on_update", skip(transaction))]
+async fn update_commerce_in_on_update(
+    transaction: &mut Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+    quote: &ONDCQuote,
+) -> Result<(), anyhow::Error> {
+    let grand_total = BigDecimal::from_str(&quote.price.value).map_err(|e| anyhow!(e))?;
+    let query = sqlx::query!(
+        r#"
+        UPDATE commerce_data SET
+        refund_grand_total = COALESCE(refund_grand_total, 0) + GREATEST(COALESCE(grand_total, 0) - $1, 0),
+        grand_total = $1
+        WHERE external_urn = $2
+        "#,
+        grand_total,
+        transaction_id
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context(
+            "A database failure occurred while saving on_update buyer commerce to database",
+        )
     })?;
     Ok(())
 }
 
+/// Uses the refund lines of the seller quote for the item and falls back to the pro-rata line total.
+fn get_return_refund_amount(
+    order: &Commerce,
+    order_return: &CommerceReturn,
+    quote: Option<&ONDCQuote>,
+) -> BigDecimal {
+    let refund_lines: Vec<BigDecimal> = quote
+        .map(|quote| {
+            quote
+                .breakup
+                .iter()
+                .filter(|breakup| {
+                    breakup.title_type == BreakupTitleType::Refund
+                        && breakup.item_id == order_return.item_id
+                })
+                .filter_map(|breakup| BigDecimal::from_str(&breakup.price.value).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    if !refund_lines.is_empty() {
+        return refund_lines
+            .into_iter()
+            .fold(BigDecimal::from(0), |total, value| total + value)
+            .abs();
+    }
+    order
+        .items
+        .iter()
+        .find(|item| item.item_id == order_return.item_id)
+        .filter(|item| item.qty > BigDecimal::from(0))
+        .map(|item| &item.gross_total * &order_return.qty / &item.qty)
+        .unwrap_or_else(|| BigDecimal::from(0))
+}
+
+/// Applies the return states reported by the seller and returns the audited transitions.
+/// Transitions that the return state machine does not allow are recorded but not applied.
+#[tracing::instrument(name = "update order returns", skip(transaction))]
+pub async fn update_order_returns(
+    transaction: &mut Transaction<'_, Postgres>,
+    order: &Commerce,
+    fulfillments: &[&ONDCOnUpdateFulfillment],
+    quote: Option<&ONDCQuote>,
+) -> Result<Vec<StatusTransition>, anyhow::Error> {
+    let order_return_map: HashMap<&str, &CommerceReturn> = order
+        .returns
+        .iter()
+        .map(|order_return| (order_return.return_id.as_str(), order_return))
+        .collect();
+    let mut return_ids = vec![];
+    let mut return_statuses = vec![];
+    let mut fulfillment_ids = vec![];
+    let mut refund_amounts = vec![];
+    let mut next_statuses = vec![];
+    for fulfillment in fulfillments {
+        let Some((return_id, status)) = fulfillment.get_return_status() else {
+            continue;
+        };
+        let Some(order_return) = order_return_map.get(return_id) else {
+            tracing::warn!("Unknown return {} reported for {}", return_id, order.urn);
+            continue;
+        };
+        next_statuses.push((return_id, status.clone()));
+        if !order_return.return_status.can_transition_to(&status) {
+            continue;
+        }
+        refund_amounts.push(
+            (status == ReturnStatusType::Delivered)
+                .then(|| get_return_refund_amount(order, order_return, quote)),
+        );
+        return_ids.push(return_id.to_owned());
+        return_statuses.push(status);
+        fulfillment_ids.push(fulfillment.id.clone());
+    }
+    let transitions = get_return_transitions(&order.returns, next_statuses.into_iter());
+    if return_ids.is_empty() {
+        return Ok(transitions);
+    }
+    let query = sqlx::query!(
+        r#"
+        UPDATE commerce_return_data
+        SET
+            return_status = t.return_status,
+            fulfillment_id = t.fulfillment_id,
+            refund_amount = COALESCE(t.refund_amount, commerce_return_data.refund_amount),
+            updated_on = $6
+        FROM UNNEST($2::text[], $3::return_status_type[], $4::text[], $5::decimal[])
+            AS t(return_id, return_status, fulfillment_id, refund_amount)
+        WHERE commerce_data_id = $1 AND commerce_return_data.return_id = t.return_id
+        "#,
+        order.id,
+        &return_ids,
+        &return_statuses as &Vec<ReturnStatusType>,
+        &fulfillment_ids,
+        &refund_amounts as &Vec<Option<BigDecimal>>,
+        Utc::now()
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while updating order returns")
+    })?;
+    Ok(transitions)
+}
+
 #[tracing::instrument(name = "save order on on_update", skip(transaction))]
 pub async fn initialize_order_on_update(
     transaction: &mut Transaction<'_, Postgres>,
@@ -2781,6 +3791,40 @@ pub async fn initialize_order_on_update(
     let _ =
         delete_payment_in_commerce(transaction, on_cancel_request.context.transaction_id).await?;
 
+    let mut transitions = get_payment_transitions(
+        &order.payments,
+        on_cancel_request
+            .message
+            .order
+            .payments
+            .iter()
+            .map(|p| (p.id.as_deref(), p.status.get_payment_status())),
+    );
+    let return_fulfillments: Vec<&ONDCOnUpdateFulfillment> = on_cancel_request
+        .message
+        .order
+        .fulfillments
+        .iter()
+        .flatten()
+        .collect();
+    transitions.extend(
+        update_order_returns(
+            transaction,
+            order,
+            &return_fulfillments,
+            on_cancel_request.message.order.quote.as_ref(),
+        )
+        .await?,
+    );
+    save_status_transitions(
+        transaction,
+        order.external_urn,
+        &transitions,
+        &StatusActor::seller(&order.bpp.id),
+        ONDCActionType::OnUpdate,
+        on_cancel_request.context.message_id,
+    )
+    .await?;
     initialize_payment_on_confirm(
         transaction,
         order,
@@ -2793,6 +3837,30 @@ pub async fn initialize_order_on_update(
         updated_by,
     )
     .await?;
+    update_order_callback_on(
+        transaction,
+        on_cancel_request.context.transaction_id,
+        on_cancel_request.context.timestamp,
+    )
+    .await?;
+    if let Some(quote) = &on_cancel_request.message.order.quote {
+        update_commerce_in_on_update(transaction, on_cancel_request.context.transaction_id, quote)
+            .await?;
+        let returned_item_ids: HashSet<&str> = order
+            .returns
+            .iter()
+            .filter(|order_return| {
+                return_fulfillments.iter().any(|fulfillment| {
+                    fulfillment
+                        .get_return_status()
+                        .is_some_and(|(return_id, _)| return_id == order_return.return_id)
+                })
+            })
+            .map(|order_return| order_return.item_id.as_str())
+            .collect();
+        update_on_update_items(transaction, order, quote, &returned_item_ids).await?;
+        update_invoiced_qtys(transaction, order.id, quote).await?;
+    }
 
     Ok(())
 }
@@ -2820,10 +3888,141 @@ pub fn validate_select_request(
             }
         }
     }
+    validate_import_select(body, &seller_location_obj.1.country_code)
+        .map_err(SelectOrderError::ValidationError)?;
 
     Ok(())
 }
 
+/// Builds the select of a new order from the lines, fulfillments and payment types of a previous order.
+pub fn get_reorder_select_request(
+    order: &Commerce,
+    reorder_items: &[ReorderItem],
+) -> Result<OrderSelectRequest, SelectOrderError> {
+    if let Some(reorder_item) = reorder_items.iter().find(|reorder_item| {
+        !order
+            .items
+            .iter()
+            .any(|item| item.item_id == reorder_item.item_id)
+    }) {
+        return Err(SelectOrderError::ValidationError(format!(
+            "Item {} is not in the order",
+            reorder_item.item_id
+        )));
+    }
+    let mut items = vec![];
+    for item in order.items.iter() {
+        let qty = match reorder_items
+            .iter()
+            .find(|reorder_item| reorder_item.item_id == item.item_id)
+        {
+            Some(reorder_item) if reorder_item.qty < 0 => {
+                return Err(SelectOrderError::ValidationError(format!(
+                    "Quantity of item {} cannot be negative",
+                    item.item_id
+                )))
+            }
+            Some(reorder_item) => reorder_item.qty,
+            None => item
+                .qty
+                .to_i32()
+                .filter(|_| item.qty.is_integer())
+                .ok_or_else(|| {
+                    SelectOrderError::ValidationError(format!(
+                        "Quantity of item {} is not a whole number",
+                        item.item_id
+                    ))
+                })?,
+        };
+        if qty == 0 {
+            continue;
+        }
+        items.push(OrderSelectItem {
+            item_id: item.item_id.clone(),
+            location_ids: item.location_ids.clone(),
+            qty,
+            buyer_term: item.buyer_terms.as_ref().map(|buyer_term| BuyerTerms {
+                item_req: buyer_term.item_req.clone(),
+                packaging_req: buyer_term.packaging_req.clone(),
+            }),
+            fulfillment_ids: item.fulfillment_ids.clone(),
+            import_detail: item.import_detail.clone(),
+        });
+    }
+    if items.is_empty() {
+        return Err(SelectOrderError::ValidationError(
+            "Reorder does not have any item".to_string(),
+        ));
+    }
+
+    let mut fulfillments = vec![];
+    for fulfillment in order.fulfillments.iter().filter(|fulfillment| {
+        items
+            .iter()
+            .any(|item| item.fulfillment_ids.contains(&fulfillment.fulfillment_id))
+    }) {
+        let drop_off = fulfillment.drop_off.as_ref().ok_or_else(|| {
+            SelectOrderError::ValidationError(format!(
+                "Fulfillment {} does not have a delivery location",
+                fulfillment.fulfillment_id
+            ))
+        })?;
+        fulfillments.push(OrderSelectFulfillment {
+            id: fulfillment.fulfillment_id.clone(),
+            r#type: fulfillment.fulfillment_type.clone(),
+            location: SelectFulfillmentLocation {
+                gps: drop_off.location.gps.clone(),
+                area_code: drop_off.location.area_code.clone(),
+                address: drop_off.location.address.clone().unwrap_or_default(),
+                city: City {
+                    code: order.city_code.clone(),
+                    name: drop_off.location.city.clone(),
+                },
+                country: Country {
+                    code: drop_off.location.country.clone(),
+                    name: drop_off.location.country.get_name().to_owned(),
+                },
+                state: drop_off.location.state.clone(),
+                contact_mobile_no: drop_off.contact.mobile_no.clone(),
+            },
+            delivery_terms: fulfillment
+                .delivery_term
+                .as_ref()
+                .map(|term| OrderDeliveyTerm {
+                    inco_terms: term.inco_terms.clone(),
+                    place_of_delivery: term.place_of_delivery.clone(),
+                }),
+        });
+    }
+    if fulfillments.is_empty() {
+        return Err(SelectOrderError::ValidationError(
+            "Order does not have any fulfillment to reorder".to_string(),
+        ));
+    }
+
+    let mut payment_types: Vec<PaymentType> = vec![];
+    for payment in order.payments.iter() {
+        if !payment_types.contains(&payment.payment_type) {
+            payment_types.push(payment.payment_type.clone());
+        }
+    }
+
+    Ok(OrderSelectRequest {
+        transaction_id: Uuid::new_v4(),
+        message_id: Uuid::new_v4(),
+        domain_category_code: order.domain_category_code.clone(),
+        payment_types,
+        provider_id: order.seller.id.clone(),
+        items,
+        ttl: order.quote_ttl.clone(),
+        fulfillments,
+        order_type: order.record_type.clone(),
+        bpp_id: order.bpp.id.clone(),
+        saved_fulfillments: vec![],
+        buyer_terms_template_id: None,
+    })
+}
+
 pub async fn get_chat_links(
     client: &ChatClient,
     transaction_id: Uuid,
@@ -3136,7 +4335,7 @@ pub async fn send_rfq_update_chat(
 }
 
 fn get_status_fulfillments(
-    fulfillments: &Vec<ONDCOnConfirmFulfillment>,
+    fulfillments: &[&ONDCOnConfirmFulfillment],
     error_message: Option<String>,
 ) -> Vec<SendMessageDataDescription> {
     let mut descriptions = vec![];
@@ -3192,7 +4391,7 @@ pub async fn send_rfq_status_chat(
     chat_client: &ChatClient,
     transaction_id: Uuid,
     order_status: CommerceStatusType,
-    fulfillments: &Vec<ONDCOnConfirmFulfillment>,
+    fulfillments: &[&ONDCOnConfirmFulfillment],
     error_message: Option<String>,
     order: &Commerce,
 ) -> Result<(), anyhow::Error> {
@@ -3214,13 +4413,13 @@ pub async fn send_rfq_status_chat(
 #[tracing::instrument(name = "fetch_order_list_data_model", skip(pool), fields())]
 async fn fetch_order_list_data_model(
     pool: &PgPool,
-    filter: OrderListFilter,
+    filter: &OrderListFilter,
 ) -> Result<Vec<CommerceListModel>, anyhow::Error> {
     let mut query = QueryBuilder::new(
         r#"
-        SELECT 
+        SELECT
             id,
-            external_urn, 
+            external_urn,
             urn,
             currency_code,
             grand_total,
@@ -3231,78 +4430,440 @@ async fn fetch_order_list_data_model(
             created_by,
             seller_id,
             seller_name
-        FROM 
+        FROM
             commerce_data
         WHERE is_deleted = false
         "#,
     );
+    push_order_list_filter(&mut query, filter);
+    let sort_column = filter.sort_by.get_column();
+    if let Some(cursor) = &filter.cursor {
+        query.push(format!(
+            " AND ({}, id) {} (",
+            sort_column,
+            filter.sort_order.get_cursor_operator()
+        ));
+        match filter.sort_by {
+            OrderListSortField::CreatedOn => {
+                query.push_bind(
+                    DateTime::parse_from_rfc3339(&cursor.value)
+                        .map(|value| value.with_timezone(&Utc))
+                        .map_err(|e| anyhow!("Invalid cursor value: {}", e))?,
+                );
+            }
+            OrderListSortField::GrandTotal => {
+                query.push_bind(
+                    BigDecimal::from_str(&cursor.value)
+                        .map_err(|e| anyhow!("Invalid cursor value: {}", e))?,
+                );
+            }
+            OrderListSortField::Urn => {
+                query.push_bind(cursor.value.clone());
+            }
+        }
+        query.push(", ");
+        query.push_bind(cursor.id);
+        query.push(")");
+    }
+    let sort_keyword = filter.sort_order.get_keyword();
+    query.push(format!(
+        " ORDER BY {} {}, id {}",
+        sort_column, sort_keyword, sort_keyword
+    ));
+
+    query.push(" OFFSET ");
+    query.push_bind(filter.offset);
+
+    query.push(" LIMIT ");
+    query.push_bind(filter.limit);
+    let rows: Vec<CommerceListModel> =
+        query.build_query_as().fetch_all(pool).await.map_err(|e| {
+            tracing::error!("Failed to execute query: {:?}", e);
+            anyhow::Error::new(e).context("Failed to fetch order list from the database")
+        })?;
+
+    Ok(rows)
+}
+
+#[tracing::instrument(name = "fetch order list count", skip(pool))]
+pub async fn fetch_order_list_count(
+    pool: &PgPool,
+    filter: &OrderListFilter,
+) -> Result<i64, anyhow::Error> {
+    let mut query =
+        QueryBuilder::new("SELECT COUNT(*) FROM commerce_data WHERE is_deleted = false");
+    push_order_list_filter(&mut query, filter);
+    let (count,): (i64,) = query.build_query_as().fetch_one(pool).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("Failed to fetch order count from the database")
+    })?;
+    Ok(count)
+}
+
+/// Escapes the `LIKE` wildcards of user input and wraps it for a substring match.
+fn get_like_pattern(value: &str) -> String {
+    format!(
+        "%{}%",
+        value
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    )
+}
 
+/// Appends the `commerce_data` conditions of the filter, paging is left to the caller.
+fn push_order_list_filter(query: &mut QueryBuilder<'_, Postgres>, filter: &OrderListFilter) {
     query.push(" AND buyer_id = ");
     query.push_bind(filter.business_id);
     if let Some(user_id) = filter.user_id {
         query.push(" AND created_by = ");
         query.push_bind(user_id);
     }
+    let order_query = &filter.query;
 
-    if let Some(transaction_ids) = filter.transaction_id_list {
+    if let Some(transaction_ids) = order_query.transaction_id.clone() {
         if !transaction_ids.is_empty() {
-            query.push(" AND external_urn IN (");
-            let mut first = true;
-            for id in transaction_ids {
-                if !first {
-                    query.push(", ");
-                }
-                query.push_bind(id);
-                first = false;
-            }
+            query.push(" AND external_urn = ANY(");
+            query.push_bind(transaction_ids);
             query.push(")");
         }
     }
 
-    if let Some(from_date) = filter.start_date {
+    if let Some(from_date) = order_query.start_date {
         query.push(" AND created_on >= ");
         query.push_bind(from_date);
     }
 
-    if let Some(to_date) = filter.end_date {
+    if let Some(to_date) = order_query.end_date {
         query.push(" AND created_on <= ");
         query.push_bind(to_date);
     }
 
-    query.push(" OFFSET ");
-    query.push_bind(filter.offset);
+    if let Some(record_status) = order_query.record_status.clone() {
+        query.push(" AND record_status = ANY(");
+        query.push_bind(record_status);
+        query.push(")");
+    }
 
-    query.push(" LIMIT ");
-    query.push_bind(filter.limit);
-    let query_string = query.sql();
-    println!("Generated SQL query: {}", query_string);
-    let rows: Vec<CommerceListModel> =
-        query.build_query_as().fetch_all(pool).await.map_err(|e| {
-            tracing::error!("Failed to execute query: {:?}", e);
-            anyhow::Error::new(e).context("Failed to fetch order list from the database")
-        })?;
+    if let Some(record_type) = order_query.record_type.clone() {
+        query.push(" AND record_type = ANY(");
+        query.push_bind(record_type);
+        query.push(")");
+    }
 
-    Ok(rows)
-}
+    if let Some(bpp_id) = order_query.bpp_id.clone() {
+        query.push(" AND bpp_id = ANY(");
+        query.push_bind(bpp_id);
+        query.push(")");
+    }
 
-#[tracing::instrument(name = "get_order_list", skip(pool), fields())]
-pub async fn get_order_list(
-    pool: &PgPool,
-    filter: OrderListFilter,
-) -> Result<Vec<CommerceList>, anyhow::Error> {
-    let models = fetch_order_list_data_model(pool, filter).await?;
-    Ok(models.into_iter().map(|a| a.schema()).collect())
-}
+    if let Some(seller_id) = order_query.seller_id.clone() {
+        query.push(" AND seller_id = ANY(");
+        query.push_bind(seller_id);
+        query.push(")");
+    }
 
-#[tracing::instrument(name = "update_order_update_field", skip(transaction), fields())]
-pub async fn update_order_update_field(
-    transaction: &mut Transaction<'_, Postgres>,
-    transaction_id: Uuid,
-    updated_by: &str,
-) -> Result<(), anyhow::Error> {
-    let query = sqlx::query!(
-        r#"
-        UPDATE commerce_data SET updated_on=$1, updated_by=$2 WHERE external_urn=$3
+    if let Some(domain_category_code) = order_query.domain_category_code.clone() {
+        query.push(" AND domain_category_code = ANY(");
+        query.push_bind(domain_category_code);
+        query.push(")");
+    }
+
+    if let Some(city_code) = order_query.city_code.clone() {
+        query.push(" AND city_code = ANY(");
+        query.push_bind(city_code);
+        query.push(")");
+    }
+
+    if let Some(min_amount) = order_query.min_amount.clone() {
+        query.push(" AND grand_total >= ");
+        query.push_bind(min_amount);
+    }
+
+    if let Some(max_amount) = order_query.max_amount.clone() {
+        query.push(" AND grand_total <= ");
+        query.push_bind(max_amount);
+    }
+
+    if let Some(created_by) = order_query.created_by.clone() {
+        query.push(" AND created_by = ANY(");
+        query.push_bind(created_by);
+        query.push(")");
+    }
+
+    if let Some(payment_status) = order_query.payment_status.clone() {
+        query.push(
+            " AND EXISTS (SELECT 1 FROM commerce_payment_data p WHERE p.commerce_data_id = commerce_data.id AND p.payment_status = ANY(",
+        );
+        query.push_bind(payment_status);
+        query.push("))");
+    }
+
+    if let Some(fulfillment_status) = order_query.fulfillment_status.clone() {
+        query.push(
+            " AND EXISTS (SELECT 1 FROM commerce_fulfillment_data f WHERE f.commerce_data_id = commerce_data.id AND f.fulfillment_status = ANY(",
+        );
+        query.push_bind(fulfillment_status);
+        query.push("))");
+    }
+
+    if let Some(search) = order_query
+        .search
+        .as_deref()
+        .map(str::trim)
+        .filter(|search| !search.is_empty())
+    {
+        let pattern = get_like_pattern(search);
+        query.push(" AND (urn ILIKE ");
+        query.push_bind(pattern.clone());
+        query.push(" OR seller_name ILIKE ");
+        query.push_bind(pattern.clone());
+        query.push(
+            " OR EXISTS (SELECT 1 FROM commerce_data_line l WHERE l.commerce_data_id = commerce_data.id AND l.item_name ILIKE ",
+        );
+        query.push_bind(pattern);
+        query.push("))");
+    }
+}
+
+/// Starts an analytics query with a `filtered_order` CTE holding the orders matching the filter.
+fn get_order_analytics_query<'a>(
+    filter: &OrderListFilter,
+    sql: &str,
+) -> QueryBuilder<'a, Postgres> {
+    let mut query = QueryBuilder::new(
+        "WITH filtered_order AS (SELECT * FROM commerce_data WHERE is_deleted = false",
+    );
+    push_order_list_filter(&mut query, filter);
+    query.push(") ");
+    query.push(sql);
+    query
+}
+
+#[tracing::instrument(name = "fetch order spend group", skip(pool))]
+async fn fetch_order_spend_group(
+    pool: &PgPool,
+    filter: &OrderListFilter,
+    key: &str,
+    label: &str,
+    order_by: &str,
+) -> Result<Vec<OrderSpendGroup>, anyhow::Error> {
+    let mut query = get_order_analytics_query(
+        filter,
+        &format!(
+            r#"
+            SELECT {key} AS key, {label} AS label, COUNT(*) AS order_count, NULL::DECIMAL AS qty,
+                COALESCE(SUM(grand_total - COALESCE(refund_grand_total, 0)), 0) AS amount
+            FROM filtered_order
+            WHERE record_status = ANY("#
+        ),
+    );
+    query.push_bind(CommerceStatusType::committed_list());
+    query.push(format!(") GROUP BY 1 ORDER BY {order_by}"));
+    let rows: Vec<OrderSpendGroupModel> =
+        query.build_query_as().fetch_all(pool).await.map_err(|e| {
+            tracing::error!("Failed to execute query: {:?}", e);
+            anyhow::Error::new(e).context("Failed to fetch order spend from the database")
+        })?;
+    Ok(rows.into_iter().map(|row| row.schema()).collect())
+}
+
+#[tracing::instrument(name = "fetch order item spend", skip(pool))]
+async fn fetch_order_item_spend(
+    pool: &PgPool,
+    filter: &OrderListFilter,
+) -> Result<Vec<OrderSpendGroup>, anyhow::Error> {
+    let mut query = get_order_analytics_query(
+        filter,
+        r#"
+        SELECT l.item_id AS key, MAX(l.item_name) AS label, COUNT(DISTINCT o.id) AS order_count,
+            SUM(l.qty - COALESCE(l.cancelled_qty, 0)) AS qty,
+            COALESCE(SUM(l.gross_total - COALESCE(l.refunded_gross_total, 0)), 0) AS amount
+        FROM filtered_order o
+        INNER JOIN commerce_data_line l ON l.commerce_data_id = o.id
+        WHERE o.record_status = ANY("#,
+    );
+    query.push_bind(CommerceStatusType::committed_list());
+    query.push(") GROUP BY l.item_id ORDER BY amount DESC LIMIT ");
+    query.push_bind(ORDER_ANALYTICS_ITEM_LIMIT);
+    let rows: Vec<OrderSpendGroupModel> =
+        query.build_query_as().fetch_all(pool).await.map_err(|e| {
+            tracing::error!("Failed to execute query: {:?}", e);
+            anyhow::Error::new(e).context("Failed to fetch order item spend from the database")
+        })?;
+    Ok(rows.into_iter().map(|row| row.schema()).collect())
+}
+
+#[tracing::instrument(name = "fetch order status count", skip(pool))]
+async fn fetch_order_status_count(
+    pool: &PgPool,
+    filter: &OrderListFilter,
+) -> Result<Vec<OrderStatusCount>, anyhow::Error> {
+    let mut query = get_order_analytics_query(
+        filter,
+        r#"
+        SELECT record_status, COUNT(*) AS order_count
+        FROM filtered_order
+        GROUP BY record_status ORDER BY record_status
+        "#,
+    );
+    let rows: Vec<OrderStatusCountModel> =
+        query.build_query_as().fetch_all(pool).await.map_err(|e| {
+            tracing::error!("Failed to execute query: {:?}", e);
+            anyhow::Error::new(e).context("Failed to fetch order status count from the database")
+        })?;
+    Ok(rows
+        .into_iter()
+        .map(|row| OrderStatusCount {
+            record_status: row.record_status,
+            order_count: row.order_count,
+        })
+        .collect())
+}
+
+/// The first quote and the confirmation time are taken from the status history, as the order
+/// itself only keeps the latest quote.
+#[tracing::instrument(name = "fetch order quote metric", skip(pool))]
+async fn fetch_order_quote_metric(
+    pool: &PgPool,
+    filter: &OrderListFilter,
+) -> Result<OrderQuoteMetricModel, anyhow::Error> {
+    let mut query = get_order_analytics_query(
+        filter,
+        r#"
+        SELECT COUNT(fq.grand_total) AS order_count,
+            COALESCE(SUM(fq.grand_total), 0) AS first_quote_total,
+            COALESCE(SUM(o.grand_total) FILTER (WHERE fq.grand_total IS NOT NULL), 0) AS final_quote_total,
+            AVG(EXTRACT(EPOCH FROM (oc.created_on - fq.created_on)))::FLOAT8 AS avg_quote_to_confirm_secs
+        FROM filtered_order o
+        LEFT JOIN LATERAL (
+            SELECT CASE WHEN h.detail->>'grand_total' ~ '^[0-9]+(\.[0-9]+)?$'
+                THEN (h.detail->>'grand_total')::DECIMAL END AS grand_total, h.created_on
+            FROM buyer_order_status_history h
+            WHERE h.transaction_id = o.external_urn AND h.entity_type = 'quote'
+                AND h.status = 'quote_accepted'
+            ORDER BY h.created_on LIMIT 1
+        ) fq ON true
+        LEFT JOIN LATERAL (
+            SELECT h.created_on
+            FROM buyer_order_status_history h
+            WHERE h.transaction_id = o.external_urn AND h.entity_type = 'order'
+                AND h.status = 'created' AND h.is_valid
+            ORDER BY h.created_on LIMIT 1
+        ) oc ON true
+        WHERE o.record_status = ANY("#,
+    );
+    query.push_bind(CommerceStatusType::committed_list());
+    query.push(")");
+    query.build_query_as().fetch_one(pool).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("Failed to fetch order quote metric from the database")
+    })
+}
+
+#[tracing::instrument(name = "get order analytics", skip(pool))]
+pub async fn get_order_analytics(
+    pool: &PgPool,
+    filter: OrderListFilter,
+) -> Result<OrderAnalytics, anyhow::Error> {
+    let (
+        spend_by_month,
+        spend_by_seller,
+        spend_by_domain,
+        spend_by_city,
+        spend_by_item,
+        status_count,
+        quote_metric,
+    ) = tokio::try_join!(
+        fetch_order_spend_group(
+            pool,
+            &filter,
+            "to_char(date_trunc('month', created_on), 'YYYY-MM')",
+            "NULL::TEXT",
+            "key"
+        ),
+        fetch_order_spend_group(
+            pool,
+            &filter,
+            "seller_id",
+            "MAX(seller_name)",
+            "amount DESC"
+        ),
+        fetch_order_spend_group(
+            pool,
+            &filter,
+            "domain_category_code::TEXT",
+            "NULL::TEXT",
+            "amount DESC"
+        ),
+        fetch_order_spend_group(pool, &filter, "city_code", "NULL::TEXT", "amount DESC"),
+        fetch_order_item_spend(pool, &filter),
+        fetch_order_status_count(pool, &filter),
+        fetch_order_quote_metric(pool, &filter),
+    )?;
+    Ok(OrderAnalytics {
+        spend_by_month,
+        spend_by_seller,
+        spend_by_domain,
+        spend_by_city,
+        spend_by_item,
+        status_count,
+        avg_quote_to_confirm_secs: quote_metric.avg_quote_to_confirm_secs,
+        quote_savings: OrderQuoteSavings::new(
+            quote_metric.order_count,
+            quote_metric.first_quote_total,
+            quote_metric.final_quote_total,
+        ),
+    })
+}
+
+#[tracing::instrument(name = "get_order_list", skip(pool), fields())]
+pub async fn get_order_list(
+    pool: &PgPool,
+    filter: OrderListFilter,
+) -> Result<Vec<CommerceList>, anyhow::Error> {
+    let data = fetch_order_list_data_model(pool, &filter).await?;
+    Ok(data.into_iter().map(|a| a.schema()).collect())
+}
+
+#[tracing::instrument(name = "get_order_list_page", skip(pool), fields())]
+pub async fn get_order_list_page(
+    pool: &PgPool,
+    filter: OrderListFilter,
+) -> Result<CommerceListPage, anyhow::Error> {
+    let (order_models, total_count) = tokio::try_join!(
+        fetch_order_list_data_model(pool, &filter),
+        fetch_order_list_count(pool, &filter)
+    )?;
+    let orders: Vec<CommerceList> = order_models.into_iter().map(|a| a.schema()).collect();
+    let next_cursor = orders
+        .last()
+        .filter(|_| orders.len() as i64 == i64::from(filter.limit))
+        .map(|order| {
+            OrderListCursor {
+                sort_by: filter.sort_by.clone(),
+                sort_order: filter.sort_order.clone(),
+                value: filter.sort_by.get_cursor_value(order),
+                id: order.id,
+            }
+            .encode()
+        });
+    Ok(CommerceListPage {
+        orders,
+        total_count,
+        next_cursor,
+    })
+}
+
+#[tracing::instrument(name = "update_order_update_field", skip(transaction), fields())]
+pub async fn update_order_update_field(
+    transaction: &mut Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+    updated_by: &str,
+) -> Result<(), anyhow::Error> {
+    let query = sqlx::query!(
+        r#"
+        UPDATE commerce_data SET updated_on=$1, updated_by=$2, version=version + 1 WHERE external_urn=$3
         "#,
         Utc::now(),
         updated_by,
@@ -3317,6 +4878,236 @@ pub async fn update_order_update_field(
     Ok(())
 }
 
+/// Compare-and-set on the order version: bumps it only if nobody else has written the order since
+/// `expected_version` was read. Returns `false` when the version is stale.
+#[tracing::instrument(name = "update order version", skip(transaction))]
+pub async fn update_order_version(
+    transaction: &mut Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+    expected_version: i32,
+    updated_by: &str,
+) -> Result<bool, anyhow::Error> {
+    let query = sqlx::query!(
+        r#"
+        UPDATE commerce_data SET version = version + 1, updated_on = $1, updated_by = $2
+        WHERE external_urn = $3 AND version = $4
+        "#,
+        Utc::now(),
+        updated_by,
+        transaction_id,
+        expected_version
+    );
+    let result = transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while updating order version")
+    })?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Undoes the logged request and the version bump of a request that could not be sent to the seller,
+/// so that the buyer can retry it with the same version.
+#[tracing::instrument(name = "revert order request", skip(pool))]
+pub async fn revert_order_request(
+    pool: &PgPool,
+    transaction_id: Uuid,
+    message_id: Uuid,
+    action_type: ONDCActionType,
+    expected_version: i32,
+) -> Result<(), anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let query = sqlx::query!(
+        r#"
+        DELETE FROM ondc_buyer_order_req
+        WHERE transaction_id = $1 AND message_id = $2 AND action_type = $3
+        "#,
+        transaction_id,
+        message_id,
+        &action_type.to_string()
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while deleting ONDC order request")
+    })?;
+    let query = sqlx::query!(
+        r#"
+        UPDATE commerce_data SET version = $2
+        WHERE external_urn = $1 AND version = $2 + 1
+        "#,
+        transaction_id,
+        expected_version
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while reverting order version")
+    })?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to revert order request")?;
+    Ok(())
+}
+
+/// Stores the warnings of the latest validated quote, an empty list clears the flag.
+#[tracing::instrument(name = "update order quote issues", skip(transaction))]
+pub async fn update_order_quote_issues(
+    transaction: &mut Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+    issues: &[QuoteIssue],
+) -> Result<(), anyhow::Error> {
+    let quote_issues = (!issues.is_empty())
+        .then(|| serde_json::to_value(issues))
+        .transpose()?;
+    let query = sqlx::query!(
+        r#"
+        UPDATE commerce_data SET quote_issues = $1 WHERE external_urn = $2
+        "#,
+        quote_issues,
+        transaction_id
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while updating order quote issues")
+    })?;
+    Ok(())
+}
+
+/// Removes the returns of an update request that could not be sent to the seller.
+#[tracing::instrument(name = "delete order returns", skip(pool))]
+pub async fn delete_order_returns(
+    pool: &PgPool,
+    order: &Commerce,
+    body: &UpdateOrderReturnRequest,
+) -> Result<(), anyhow::Error> {
+    let return_ids: Vec<String> = body
+        .returns
+        .iter()
+        .map(|return_item| body.get_return_id(&return_item.item_id))
+        .collect();
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let query = sqlx::query!(
+        r#"
+        DELETE FROM commerce_return_data
+        WHERE commerce_data_id = $1 AND return_id = ANY($2) AND return_status = $3
+        "#,
+        order.id,
+        &return_ids,
+        &ReturnStatusType::Initiated as &ReturnStatusType
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while deleting order returns")
+    })?;
+    let query = sqlx::query!(
+        r#"
+        UPDATE buyer_order_status_history SET is_valid = false
+        WHERE transaction_id = $1 AND entity_type = $2 AND entity_id = ANY($3) AND message_id = $4
+        "#,
+        order.external_urn,
+        StatusEntityType::Return as StatusEntityType,
+        &return_ids,
+        body.message_id
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while invalidating order return history")
+    })?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to delete order returns")?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "save order returns", skip(pool))]
+pub async fn save_order_returns(
+    pool: &PgPool,
+    order: &Commerce,
+    body: &UpdateOrderReturnRequest,
+    created_by: Uuid,
+) -> Result<(), anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let mut ids = vec![];
+    let mut return_ids = vec![];
+    let mut item_ids = vec![];
+    let mut qtys = vec![];
+    let mut reason_ids = vec![];
+    let mut reason_descs = vec![];
+    let mut images_list = vec![];
+    let mut transitions = vec![];
+    for return_item in &body.returns {
+        let return_id = body.get_return_id(&return_item.item_id);
+        ids.push(Uuid::new_v4());
+        item_ids.push(return_item.item_id.as_str());
+        qtys.push(BigDecimal::from(return_item.qty));
+        reason_ids.push(return_item.reason_id.as_str());
+        reason_descs.push(return_item.reason_desc.as_deref());
+        images_list.push(serde_json::to_value(&return_item.images)?);
+        transitions.push(StatusTransition {
+            entity_id: Some(return_id.clone()),
+            entity_type: StatusEntityType::Return,
+            previous_status: None,
+            status: ReturnStatusType::Initiated.to_string(),
+            is_valid: true,
+            detail: Some(serde_json::json!({
+                "item_id": return_item.item_id,
+                "qty": return_item.qty,
+                "reason_id": return_item.reason_id,
+            })),
+        });
+        return_ids.push(return_id);
+    }
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO commerce_return_data (id, commerce_data_id, return_id, item_id, qty, reason_id,
+            reason_desc, images, return_status, created_on, created_by)
+        SELECT t.id, $2, t.return_id, t.item_id, t.qty, t.reason_id, t.reason_desc, t.images, $9, $10, $11
+        FROM UNNEST($1::uuid[], $3::text[], $4::text[], $5::decimal[], $6::text[], $7::text[], $8::jsonb[])
+        AS t(id, return_id, item_id, qty, reason_id, reason_desc, images)
+        "#,
+        &ids,
+        order.id,
+        &return_ids,
+        &item_ids as &Vec<&str>,
+        &qtys,
+        &reason_ids as &Vec<&str>,
+        &reason_descs as &Vec<Option<&str>>,
+        &images_list,
+        &ReturnStatusType::Initiated as &ReturnStatusType,
+        Utc::now(),
+        created_by
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving order returns")
+    })?;
+    save_status_transitions(
+        &mut transaction,
+        order.external_urn,
+        &transitions,
+        &StatusActor::buyer(created_by),
+        ONDCActionType::Update,
+        body.message_id,
+    )
+    .await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store order returns")?;
+    Ok(())
+}
+
 #[tracing::instrument(name = "fetch_minimal_commerce_data_model", skip(pool), fields())]
 pub async fn fetch_minimal_commerce_data_model(
     pool: &PgPool,
@@ -3355,3 +5146,2843 @@ pub async fn fetch_minimal_commerce_data_model(
 
     Ok(rows.schema())
 }
+
+#[tracing::instrument(name = "fetch orders due for status poll", skip(pool))]
+pub async fn fetch_status_poll_orders(
+    pool: &PgPool,
+    status_poll_config: &StatusPollConfig,
+) -> Result<Vec<StatusPollOrderModel>, anyhow::Error> {
+    let record_status_list = vec![CommerceStatusType::Accepted, CommerceStatusType::InProgress];
+    let rows = sqlx::query_as!(
+        StatusPollOrderModel,
+        r#"
+        SELECT
+            external_urn as "external_urn!",
+            bap_id as "bap_id!",
+            bpp_id as "bpp_id!",
+            created_by as "created_by!",
+            buyer_id as "buyer_id!"
+        FROM (
+            SELECT
+                external_urn, bap_id, bpp_id, created_by, buyer_id, last_status_polled_on, created_on,
+                ROW_NUMBER() OVER (
+                    PARTITION BY bpp_id ORDER BY last_status_polled_on ASC NULLS FIRST, created_on ASC
+                ) AS seller_rank
+            FROM commerce_data
+            WHERE
+                is_deleted = false
+                AND record_status = ANY($1::commerce_status[])
+                AND created_on <= NOW() - make_interval(secs => $2::double precision)
+                AND (
+                    last_status_polled_on IS NULL
+                    OR last_status_polled_on + make_interval(
+                        secs => LEAST($3::double precision * POWER(2, LEAST(status_poll_count, 16)), $4::double precision)
+                    ) <= NOW()
+                )
+        ) AS due_order
+        WHERE seller_rank <= $5
+        ORDER BY last_status_polled_on ASC NULLS FIRST, created_on ASC
+        LIMIT $6
+        "#,
+        &record_status_list as &Vec<CommerceStatusType>,
+        status_poll_config.min_order_age_seconds as f64,
+        status_poll_config.interval_seconds as f64,
+        status_poll_config.max_backoff_seconds as f64,
+        status_poll_config.per_seller_limit,
+        status_poll_config.batch_size,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("Failed to fetch orders due for status poll from the database")
+    })?;
+
+    Ok(rows)
+}
+
+#[tracing::instrument(name = "update order status poll", skip(pool))]
+pub async fn update_order_status_poll(
+    pool: &PgPool,
+    transaction_id: Uuid,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE commerce_data SET last_status_polled_on=$1, status_poll_count=status_poll_count + 1
+        WHERE external_urn=$2
+        "#,
+        Utc::now(),
+        transaction_id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while updating order status poll")
+    })?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "fetch order request events", skip(pool))]
+async fn fetch_order_request_events(
+    pool: &PgPool,
+    transaction_id: Uuid,
+) -> Result<Vec<OrderRequestEventModel>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        OrderRequestEventModel,
+        r#"SELECT message_id, user_id, action_type, created_on
+        FROM ondc_buyer_order_req WHERE transaction_id = $1
+        ORDER BY created_on"#,
+        transaction_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching order request events")
+    })?;
+    Ok(records)
+}
+
+#[tracing::instrument(name = "fetch order status history", skip(pool))]
+async fn fetch_order_status_history(
+    pool: &PgPool,
+    transaction_id: Uuid,
+) -> Result<Vec<OrderStatusHistoryModel>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        OrderStatusHistoryModel,
+        r#"SELECT entity_type as "entity_type: StatusEntityType", entity_id, previous_status, status,
+        actor_type as "actor_type: StatusActorType", actor_id, action_type, message_id, is_valid,
+        detail, created_on
+        FROM buyer_order_status_history WHERE transaction_id = $1
+        ORDER BY created_on"#,
+        transaction_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching order status history")
+    })?;
+    Ok(records)
+}
+
+pub fn get_cancellation_fee(fee: &CommerceCancellationFee, order_value: &BigDecimal) -> BigDecimal {
+    let cancellation_fee = match fee.r#type {
+        CancellationFeeType::Percent => order_value * &fee.val / BigDecimal::from(100),
+        CancellationFeeType::Amount => fee.val.clone(),
+    };
+    cancellation_fee.min(order_value.clone()).round(2)
+}
+
+/// Refund expected on cancelling, the paid amount less the refunds so far and the cancellation fee.
+pub fn get_expected_refund(
+    payments: &[CommercePayment],
+    refund_grand_total: Option<&BigDecimal>,
+    cancellation_fee: &BigDecimal,
+) -> BigDecimal {
+    let paid_amount: BigDecimal = payments
+        .iter()
+        .filter(|payment| payment.payment_status == Some(PaymentStatus::Paid))
+        .filter_map(|payment| payment.payment_amount.as_ref())
+        .sum();
+    (paid_amount - refund_grand_total.cloned().unwrap_or_default() - cancellation_fee)
+        .max(BigDecimal::from(0))
+}
+
+/// Evaluates the stored cancellation terms against the current fulfillment states.
+/// When several fulfillments match a term the costliest one applies, no matching term means no fee.
+pub fn get_cancellation_preview(order: &Commerce) -> OrderCancellationPreview {
+    let order_value = order
+        .grand_total
+        .clone()
+        .unwrap_or_else(|| BigDecimal::from(0));
+    let applicable_term = order
+        .cancellation_terms
+        .iter()
+        .flatten()
+        .filter(|term| {
+            order
+                .fulfillments
+                .iter()
+                .any(|fulfillment| fulfillment.fulfillment_status == term.fulfillment_state)
+        })
+        .map(|term| {
+            (
+                term,
+                get_cancellation_fee(&term.cancellation_fee, &order_value),
+            )
+        })
+        .max_by(|(_, fee_a), (_, fee_b)| fee_a.cmp(fee_b));
+    let cancellation_fee = applicable_term
+        .as_ref()
+        .map(|(_, fee)| fee.clone())
+        .unwrap_or_else(|| BigDecimal::from(0));
+    OrderCancellationPreview {
+        is_cancellable: validate_order_action(&order.record_status, &ONDCActionType::Cancel)
+            .is_ok(),
+        fulfillment_state: applicable_term
+            .as_ref()
+            .map(|(term, _)| term.fulfillment_state.clone()),
+        reason_required: applicable_term
+            .as_ref()
+            .is_some_and(|(term, _)| term.reason_required),
+        fee_type: applicable_term
+            .as_ref()
+            .map(|(term, _)| term.cancellation_fee.r#type.clone()),
+        fee_value: applicable_term
+            .as_ref()
+            .map(|(term, _)| term.cancellation_fee.val.clone()),
+        expected_refund: get_expected_refund(
+            &order.payments,
+            order.refund_grand_total.as_ref(),
+            &cancellation_fee,
+        ),
+        cancellation_fee,
+        currency_type: order.currency_type.clone(),
+    }
+}
+
+#[tracing::instrument(name = "fetch order timeline", skip(pool))]
+pub async fn fetch_order_timeline(
+    pool: &PgPool,
+    transaction_id: Uuid,
+) -> Result<Vec<OrderTimelineEvent>, anyhow::Error> {
+    let (requests, history) = tokio::try_join!(
+        fetch_order_request_events(pool, transaction_id),
+        fetch_order_status_history(pool, transaction_id)
+    )?;
+    let mut timeline: Vec<OrderTimelineEvent> = requests
+        .into_iter()
+        .map(|request| request.schema())
+        .chain(history.into_iter().map(|status| status.schema()))
+        .collect();
+    timeline.sort_by_key(|event| event.created_on);
+    Ok(timeline)
+}
+
+#[tracing::instrument(name = "fetch goods receipts", skip(executor))]
+pub async fn fetch_goods_receipts(
+    executor: impl sqlx::PgExecutor<'_>,
+    order_id: Uuid,
+) -> Result<Vec<CommerceGoodsReceipt>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        CommerceGoodsReceiptLineModel,
+        r#"
+        SELECT
+            g.id as grn_id,
+            g.grn_no,
+            g.fulfillment_id,
+            g.received_on,
+            g.remark as grn_remark,
+            g.created_on,
+            g.created_by,
+            l.item_id,
+            gl.received_qty,
+            gl.accepted_qty,
+            gl.rejected_qty,
+            gl.remark,
+            gl.images as "images: Json<Vec<String>>"
+        FROM commerce_grn g
+        INNER JOIN commerce_grn_line gl ON gl.grn_id = g.id
+        INNER JOIN commerce_data_line l ON l.id = gl.commerce_data_line_id
+        WHERE g.commerce_data_id = $1
+        ORDER BY g.received_on, g.created_on, g.id
+        "#,
+        order_id
+    )
+    .fetch_all(executor)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching goods receipts from database")
+    })?;
+    Ok(get_goods_receipts_from_model(records))
+}
+
+fn get_goods_receipts_from_model(
+    lines: Vec<CommerceGoodsReceiptLineModel>,
+) -> Vec<CommerceGoodsReceipt> {
+    let mut receipts: Vec<CommerceGoodsReceipt> = vec![];
+    for line in lines {
+        let item = CommerceGoodsReceiptItem {
+            item_id: line.item_id,
+            received_qty: line.received_qty,
+            accepted_qty: line.accepted_qty,
+            rejected_qty: line.rejected_qty,
+            remark: line.remark,
+            images: line.images.0,
+        };
+        match receipts.last_mut() {
+            Some(receipt) if receipt.id == line.grn_id => receipt.items.push(item),
+            _ => receipts.push(CommerceGoodsReceipt {
+                id: line.grn_id,
+                grn_no: line.grn_no,
+                fulfillment_id: line.fulfillment_id,
+                received_on: line.received_on,
+                remark: line.grn_remark,
+                created_on: line.created_on,
+                created_by: line.created_by,
+                items: vec![item],
+            }),
+        }
+    }
+    receipts
+}
+
+#[tracing::instrument(name = "save goods receipt", skip(transaction))]
+pub async fn save_goods_receipt(
+    transaction: &mut Transaction<'_, Postgres>,
+    order: &Commerce,
+    body: &GoodsReceiptCreateRequest,
+    created_by: Uuid,
+) -> Result<(), anyhow::Error> {
+    let line_map: HashMap<&str, Uuid> = order
+        .items
+        .iter()
+        .map(|item| (item.item_id.as_str(), item.id))
+        .collect();
+    let grn_id = Uuid::new_v4();
+    let mut ids = vec![];
+    let mut line_ids = vec![];
+    let mut received_qtys = vec![];
+    let mut accepted_qtys = vec![];
+    let mut rejected_qtys = vec![];
+    let mut remarks = vec![];
+    let mut images_list = vec![];
+    for item in &body.items {
+        let line_id = line_map
+            .get(item.item_id.as_str())
+            .ok_or_else(|| anyhow!("{} is not part of the order", item.item_id))?;
+        ids.push(Uuid::new_v4());
+        line_ids.push(*line_id);
+        received_qtys.push(item.received_qty.clone());
+        accepted_qtys.push(item.accepted_qty.clone());
+        rejected_qtys.push(item.rejected_qty.clone());
+        remarks.push(item.remark.as_deref());
+        images_list.push(serde_json::to_value(&item.images)?);
+    }
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO commerce_grn (id, commerce_data_id, grn_no, fulfillment_id, received_on, remark,
+            created_on, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+        grn_id,
+        order.id,
+        body.grn_no,
+        body.fulfillment_id,
+        body.received_on,
+        body.remark,
+        Utc::now(),
+        created_by
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving goods receipt")
+    })?;
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO commerce_grn_line (id, grn_id, commerce_data_line_id, received_qty, accepted_qty,
+            rejected_qty, remark, images)
+        SELECT t.id, $2, t.line_id, t.received_qty, t.accepted_qty, t.rejected_qty, t.remark, t.images
+        FROM UNNEST($1::uuid[], $3::uuid[], $4::decimal[], $5::decimal[], $6::decimal[], $7::text[],
+            $8::jsonb[])
+        AS t(id, line_id, received_qty, accepted_qty, rejected_qty, remark, images)
+        "#,
+        &ids,
+        grn_id,
+        &line_ids,
+        &received_qtys,
+        &accepted_qtys,
+        &rejected_qtys,
+        &remarks as &Vec<Option<&str>>,
+        &images_list,
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while saving goods receipt lines")
+    })?;
+    Ok(())
+}
+
+/// Received, accepted and rejected quantity per item across all receipts.
+pub fn get_received_qty_map(
+    receipts: &[CommerceGoodsReceipt],
+) -> HashMap<&str, (BigDecimal, BigDecimal, BigDecimal)> {
+    let mut qty_map: HashMap<&str, (BigDecimal, BigDecimal, BigDecimal)> = HashMap::new();
+    for item in receipts.iter().flat_map(|receipt| &receipt.items) {
+        let entry = qty_map.entry(item.item_id.as_str()).or_default();
+        entry.0 += &item.received_qty;
+        entry.1 += &item.accepted_qty;
+        entry.2 += &item.rejected_qty;
+    }
+    qty_map
+}
+
+/// Three-way match of ordered, seller invoiced and received quantity per item.
+/// Items the seller has not quoted since the confirmation are pending the invoice.
+pub fn get_goods_receipt_match_report(
+    order: &Commerce,
+    receipts: &[CommerceGoodsReceipt],
+) -> GoodsReceiptMatchReport {
+    let qty_map = get_received_qty_map(receipts);
+    let items: Vec<GoodsReceiptMatchItem> = order
+        .items
+        .iter()
+        .map(|item| {
+            let (received_qty, accepted_qty, rejected_qty) = qty_map
+                .get(item.item_id.as_str())
+                .cloned()
+                .unwrap_or_default();
+            let invoiced_qty = item.invoiced_qty.clone();
+            let status = match &invoiced_qty {
+                None => GoodsReceiptMatchStatus::PendingInvoice,
+                Some(invoiced_qty) if &received_qty < invoiced_qty => {
+                    GoodsReceiptMatchStatus::PendingReceipt
+                }
+                Some(invoiced_qty) if &accepted_qty == invoiced_qty => {
+                    GoodsReceiptMatchStatus::Matched
+                }
+                Some(_) => GoodsReceiptMatchStatus::QuantityMismatch,
+            };
+            GoodsReceiptMatchItem {
+                item_id: item.item_id.clone(),
+                item_name: item.item_name.clone(),
+                ordered_qty: item.qty.clone(),
+                invoiced_qty,
+                received_qty,
+                accepted_qty,
+                rejected_qty,
+                status,
+            }
+        })
+        .collect();
+    GoodsReceiptMatchReport {
+        transaction_id: order.external_urn,
+        is_matched: items
+            .iter()
+            .all(|item| item.status == GoodsReceiptMatchStatus::Matched),
+        items,
+    }
+}
+
+fn get_order_approval_from_model(
+    approval_status: Option<ApprovalStatusType>,
+    approval_levels: Option<Vec<i32>>,
+    approval_requested_on: Option<DateTime<Utc>>,
+    approvals: Vec<OrderApprovalModel>,
+) -> Option<CommerceApproval> {
+    Some(CommerceApproval {
+        status: approval_status?,
+        levels: approval_levels?,
+        requested_on: approval_requested_on?,
+        records: approvals
+            .into_iter()
+            .map(|approval| approval.schema())
+            .collect(),
+    })
+}
+
+#[tracing::instrument(name = "fetch order approvals", skip(pool))]
+async fn fetch_order_approvals(
+    pool: &PgPool,
+    transaction_ids: &[Uuid],
+) -> Result<Vec<OrderApprovalModel>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        OrderApprovalModel,
+        r#"
+        SELECT transaction_id, approval_level, approval_status as "approval_status: ApprovalStatusType",
+            approval_requested_on, remark, created_by, created_on
+        FROM buyer_order_approval
+        WHERE transaction_id = ANY($1)
+        ORDER BY created_on
+        "#,
+        transaction_ids
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching order approvals from database")
+    })?;
+    Ok(records)
+}
+
+#[tracing::instrument(name = "fetch approval policies", skip(pool))]
+pub async fn fetch_approval_policies(
+    pool: &PgPool,
+    business_id: Uuid,
+) -> Result<Vec<OrderApprovalPolicy>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        OrderApprovalPolicyModel,
+        r#"
+        SELECT id, name, approval_level, min_amount,
+            domain_category_code as "domain_category_code?: CategoryDomain", seller_id, approver_ids,
+            is_active, created_on, created_by
+        FROM order_approval_policy
+        WHERE business_id = $1
+        ORDER BY approval_level, min_amount
+        "#,
+        business_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching approval policies from database")
+    })?;
+    Ok(records.into_iter().map(|policy| policy.schema()).collect())
+}
+
+/// Creates the policy, or updates it when the id belongs to the same business.
+/// Returns `false` when the policy to update does not exist.
+#[tracing::instrument(name = "save approval policy", skip(pool))]
+pub async fn save_approval_policy(
+    pool: &PgPool,
+    business_id: Uuid,
+    body: &OrderApprovalPolicySaveRequest,
+    user_id: Uuid,
+) -> Result<bool, anyhow::Error> {
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO order_approval_policy (id, business_id, name, approval_level, min_amount,
+            domain_category_code, seller_id, approver_ids, is_active, created_on, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        ON CONFLICT (id) DO UPDATE SET
+            name = EXCLUDED.name,
+            approval_level = EXCLUDED.approval_level,
+            min_amount = EXCLUDED.min_amount,
+            domain_category_code = EXCLUDED.domain_category_code,
+            seller_id = EXCLUDED.seller_id,
+            approver_ids = EXCLUDED.approver_ids,
+            is_active = EXCLUDED.is_active,
+            updated_on = EXCLUDED.created_on,
+            updated_by = EXCLUDED.created_by
+        WHERE order_approval_policy.business_id = EXCLUDED.business_id
+        "#,
+        body.id.unwrap_or_else(Uuid::new_v4),
+        business_id,
+        body.name,
+        body.approval_level,
+        body.min_amount,
+        body.domain_category_code.as_ref() as Option<&CategoryDomain>,
+        body.seller_id,
+        &body.approver_ids,
+        body.is_active,
+        Utc::now(),
+        user_id
+    );
+    let result = pool.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving approval policy")
+    })?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Approval levels required for a quote, one approval per distinct level in ascending order.
+pub fn get_order_approval_levels(
+    policies: &[OrderApprovalPolicy],
+    order_value: &BigDecimal,
+    domain_category_code: &CategoryDomain,
+    seller_id: &str,
+) -> Vec<i32> {
+    let mut levels: Vec<i32> = policies
+        .iter()
+        .filter(|policy| policy.is_applicable(order_value, domain_category_code, seller_id))
+        .map(|policy| policy.approval_level)
+        .collect();
+    levels.sort();
+    levels.dedup();
+    levels
+}
+
+/// A level is approved by an approver of one of the applicable policies of that level.
+pub fn is_order_level_approver(
+    policies: &[OrderApprovalPolicy],
+    level: i32,
+    order_value: &BigDecimal,
+    domain_category_code: &CategoryDomain,
+    seller_id: &str,
+    user_id: Uuid,
+) -> bool {
+    policies.iter().any(|policy| {
+        policy.approval_level == level
+            && policy.is_applicable(order_value, domain_category_code, seller_id)
+            && policy.approver_ids.contains(&user_id)
+    })
+}
+
+/// Every accepted quote starts a new approval round, an order without matching policies needs no approval.
+/// A replayed callback keeps the current round, and changed levels on the same callback start a new one.
+#[tracing::instrument(name = "update order approval requirement", skip(transaction))]
+pub async fn update_order_approval_requirement(
+    transaction: &mut Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+    levels: &[i32],
+    requested_on: DateTime<Utc>,
+) -> Result<(), anyhow::Error> {
+    let is_required = !levels.is_empty();
+    let query = sqlx::query!(
+        r#"
+        UPDATE commerce_data SET approval_status = $1, approval_levels = $2,
+            approval_requested_on = CASE WHEN approval_requested_on = $3 THEN $5 ELSE $3 END
+        WHERE external_urn = $4
+            AND (approval_requested_on IS DISTINCT FROM $3 OR approval_levels IS DISTINCT FROM $2)
+        "#,
+        is_required.then_some(ApprovalStatusType::Pending) as Option<ApprovalStatusType>,
+        is_required.then_some(levels) as Option<&[i32]>,
+        is_required.then_some(requested_on),
+        transaction_id,
+        Utc::now()
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while updating order approval requirement")
+    })?;
+    Ok(())
+}
+
+/// Starts a new approval round when the final quote needs a level outside the current round.
+#[tracing::instrument(name = "extend order approval requirement", skip(transaction))]
+pub async fn extend_order_approval_requirement(
+    transaction: &mut Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+    levels: &[i32],
+    requested_on: DateTime<Utc>,
+) -> Result<(), anyhow::Error> {
+    if levels.is_empty() {
+        return Ok(());
+    }
+    let query = sqlx::query!(
+        r#"
+        UPDATE commerce_data SET approval_status = $1, approval_levels = $2, approval_requested_on = $3
+        WHERE external_urn = $4 AND NOT ($2 <@ COALESCE(approval_levels, '{}'))
+        "#,
+        ApprovalStatusType::Pending as ApprovalStatusType,
+        levels,
+        requested_on,
+        transaction_id
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while extending order approval requirement")
+    })?;
+    Ok(())
+}
+
+/// Records the decision for `level` and returns the resulting approval status of the order.
+/// Returns `None` when the approval round changed in the meantime.
+#[tracing::instrument(name = "save order approval", skip(pool, approval))]
+pub async fn save_order_approval(
+    pool: &PgPool,
+    transaction_id: Uuid,
+    approval: &CommerceApproval,
+    level: i32,
+    action: &OrderApprovalAction,
+    remark: Option<&str>,
+    user_id: Uuid,
+) -> Result<Option<ApprovalStatusType>, anyhow::Error> {
+    let (level_status, order_status) = match action {
+        OrderApprovalAction::Reject => (ApprovalStatusType::Rejected, ApprovalStatusType::Rejected),
+        OrderApprovalAction::Approve
+            if approval.levels.iter().all(|approval_level| {
+                *approval_level == level || approval.get_approved_levels().contains(approval_level)
+            }) =>
+        {
+            (ApprovalStatusType::Approved, ApprovalStatusType::Approved)
+        }
+        OrderApprovalAction::Approve => (ApprovalStatusType::Approved, ApprovalStatusType::Pending),
+    };
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let result = sqlx::query!(
+        r#"
+        UPDATE commerce_data SET approval_status = $1
+        WHERE external_urn = $2 AND approval_status = $3 AND approval_requested_on = $4
+        "#,
+        &order_status as &ApprovalStatusType,
+        transaction_id,
+        ApprovalStatusType::Pending as ApprovalStatusType,
+        approval.requested_on
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while updating order approval")
+    })?;
+    if result.rows_affected() == 0 {
+        return Ok(None);
+    }
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO buyer_order_approval (id, transaction_id, approval_level, approval_status,
+            approval_requested_on, remark, created_on, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (transaction_id, approval_requested_on, approval_level) DO NOTHING
+        "#,
+        Uuid::new_v4(),
+        transaction_id,
+        level,
+        &level_status as &ApprovalStatusType,
+        approval.requested_on,
+        remark,
+        Utc::now(),
+        user_id
+    );
+    let result = transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving order approval")
+    })?;
+    if result.rows_affected() == 0 {
+        return Ok(None);
+    }
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store order approval")?;
+    Ok(Some(order_status))
+}
+
+/// Locks the budgets of the business, so that confirms checked against the same budgets run one at a time.
+#[tracing::instrument(name = "lock buyer budgets", skip(transaction))]
+pub async fn lock_buyer_budgets(
+    transaction: &mut Transaction<'_, Postgres>,
+    business_id: Uuid,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        SELECT id FROM buyer_budget WHERE business_id = $1 ORDER BY id FOR UPDATE
+        "#,
+        business_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while locking buyer budgets")
+    })?;
+    Ok(())
+}
+
+/// Budgets of the business with the spend committed in each budget period, including the orders
+/// whose confirm is sent and awaiting the seller, other than `exclude_transaction_id`.
+/// Only budgets covering `at` are returned when it is given.
+#[tracing::instrument(name = "fetch buyer budgets", skip(executor))]
+pub async fn fetch_buyer_budgets(
+    executor: impl sqlx::PgExecutor<'_>,
+    business_id: Uuid,
+    at: Option<DateTime<Utc>>,
+    exclude_transaction_id: Option<Uuid>,
+) -> Result<Vec<BuyerBudget>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        BuyerBudgetModel,
+        r#"
+        SELECT b.id, b.name, b.user_id,
+            b.domain_category_code as "domain_category_code?: CategoryDomain", b.amount,
+            b.period_start, b.period_end, b.exceed_action as "exceed_action: BudgetExceedAction",
+            b.approval_level, b.is_active, c.consumed_amount as "consumed_amount!"
+        FROM buyer_budget b
+        CROSS JOIN LATERAL (
+            SELECT COALESCE(SUM(cd.grand_total - COALESCE(cd.refund_grand_total, 0)), 0) as consumed_amount
+            FROM commerce_data cd
+            WHERE cd.buyer_id = b.business_id AND cd.is_deleted = false
+                AND (cd.record_status = ANY($2::commerce_status[])
+                    OR (cd.record_status = 'initialized' AND EXISTS (
+                        SELECT 1 FROM ondc_buyer_order_req r
+                        WHERE r.transaction_id = cd.external_urn AND r.action_type = $4
+                    )))
+                AND ($5::UUID IS NULL OR cd.external_urn != $5)
+                AND cd.created_on >= b.period_start AND cd.created_on < b.period_end
+                AND (b.user_id IS NULL OR cd.created_by = b.user_id)
+                AND (b.domain_category_code IS NULL OR cd.domain_category_code = b.domain_category_code)
+        ) c
+        WHERE b.business_id = $1
+            AND ($3::TIMESTAMPTZ IS NULL OR (b.is_active AND b.period_start <= $3 AND b.period_end > $3))
+        ORDER BY b.period_start, b.name
+        "#,
+        business_id,
+        &CommerceStatusType::committed_list() as &[CommerceStatusType],
+        at,
+        ONDCActionType::Confirm.to_string(),
+        exclude_transaction_id
+    )
+    .fetch_all(executor)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching buyer budgets from database")
+    })?;
+    Ok(records.into_iter().map(|budget| budget.schema()).collect())
+}
+
+/// Creates the budget, or updates it when the id belongs to the same business.
+/// Returns `false` when the budget to update does not exist.
+#[tracing::instrument(name = "save buyer budget", skip(pool))]
+pub async fn save_buyer_budget(
+    pool: &PgPool,
+    business_id: Uuid,
+    body: &BuyerBudgetSaveRequest,
+    user_id: Uuid,
+) -> Result<bool, anyhow::Error> {
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO buyer_budget (id, business_id, name, user_id, domain_category_code, amount,
+            period_start, period_end, exceed_action, approval_level, is_active, created_on, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        ON CONFLICT (id) DO UPDATE SET
+            name = EXCLUDED.name,
+            user_id = EXCLUDED.user_id,
+            domain_category_code = EXCLUDED.domain_category_code,
+            amount = EXCLUDED.amount,
+            period_start = EXCLUDED.period_start,
+            period_end = EXCLUDED.period_end,
+            exceed_action = EXCLUDED.exceed_action,
+            approval_level = EXCLUDED.approval_level,
+            is_active = EXCLUDED.is_active,
+            updated_on = EXCLUDED.created_on,
+            updated_by = EXCLUDED.created_by
+        WHERE buyer_budget.business_id = EXCLUDED.business_id
+        "#,
+        body.id.unwrap_or_else(Uuid::new_v4),
+        business_id,
+        body.name,
+        body.user_id,
+        body.domain_category_code.as_ref() as Option<&CategoryDomain>,
+        body.amount,
+        body.period_start,
+        body.period_end,
+        &body.exceed_action as &BudgetExceedAction,
+        body.approval_level.unwrap_or(1),
+        body.is_active,
+        Utc::now(),
+        user_id
+    );
+    let result = pool.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving buyer budget")
+    })?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Budgets covering the order which the order value would take over the budgeted amount.
+pub fn get_exceeded_budgets<'a>(
+    budgets: &'a [BuyerBudget],
+    order_value: &BigDecimal,
+    user_id: Uuid,
+    domain_category_code: &CategoryDomain,
+    at: DateTime<Utc>,
+) -> Vec<&'a BuyerBudget> {
+    budgets
+        .iter()
+        .filter(|budget| {
+            budget.is_applicable(user_id, domain_category_code, at)
+                && budget.is_exceeded_by(order_value)
+        })
+        .collect()
+}
+
+/// Starts a new approval round outside of a seller callback, e.g. when an order exceeds a budget.
+#[tracing::instrument(name = "request order approval", skip(pool))]
+pub async fn request_order_approval(
+    pool: &PgPool,
+    transaction_id: Uuid,
+    levels: &[i32],
+) -> Result<(), anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    update_order_approval_requirement(&mut transaction, transaction_id, levels, Utc::now()).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to request order approval")?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "fetch order confirmed on", skip(transaction))]
+async fn fetch_order_confirmed_on(
+    transaction: &mut Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+) -> Result<Option<DateTime<Utc>>, anyhow::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT MIN(created_on) as confirmed_on
+        FROM buyer_order_status_history
+        WHERE transaction_id = $1 AND entity_type = $2 AND status = $3 AND is_valid
+        "#,
+        transaction_id,
+        StatusEntityType::Order as StatusEntityType,
+        CommerceStatusType::Created.to_string()
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching order confirmation")
+    })?;
+    Ok(row.confirmed_on)
+}
+
+/// Applies the events to the running counters of the seller, creating the scorecard on first use.
+/// Events already recorded for the transaction are skipped.
+#[tracing::instrument(name = "save seller scorecard events", skip(transaction))]
+pub async fn save_seller_scorecard_events(
+    transaction: &mut Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+    bpp_id: &str,
+    provider_id: &str,
+    events: Vec<SellerScorecardEvent>,
+) -> Result<(), anyhow::Error> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let event_keys: Vec<String> = events.iter().map(|event| event.get_key()).collect();
+    let new_event_keys: HashSet<String> = sqlx::query!(
+        r#"
+        INSERT INTO seller_scorecard_event (transaction_id, event_key, created_on)
+        SELECT $1, UNNEST($2::text[]), $3
+        ON CONFLICT DO NOTHING
+        RETURNING event_key
+        "#,
+        transaction_id,
+        &event_keys,
+        Utc::now()
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while saving seller scorecard events")
+    })?
+    .into_iter()
+    .map(|row| row.event_key)
+    .collect();
+    let events: Vec<SellerScorecardEvent> = events
+        .into_iter()
+        .filter(|event| new_event_keys.contains(&event.get_key()))
+        .collect();
+    if events.is_empty() {
+        return Ok(());
+    }
+    let delta = get_scorecard_delta(&events);
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO seller_scorecard (bpp_id, provider_id, quote_response_count, quote_response_secs,
+            quote_rejected_count, order_accepted_count, order_rejected_count, seller_cancelled_count,
+            delivered_count, delivery_promised_count, on_time_delivery_count, created_on, updated_on)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $12)
+        ON CONFLICT (bpp_id, provider_id) DO UPDATE SET
+            quote_response_count = seller_scorecard.quote_response_count + EXCLUDED.quote_response_count,
+            quote_response_secs = seller_scorecard.quote_response_secs + EXCLUDED.quote_response_secs,
+            quote_rejected_count = seller_scorecard.quote_rejected_count + EXCLUDED.quote_rejected_count,
+            order_accepted_count = seller_scorecard.order_accepted_count + EXCLUDED.order_accepted_count,
+            order_rejected_count = seller_scorecard.order_rejected_count + EXCLUDED.order_rejected_count,
+            seller_cancelled_count = seller_scorecard.seller_cancelled_count + EXCLUDED.seller_cancelled_count,
+            delivered_count = seller_scorecard.delivered_count + EXCLUDED.delivered_count,
+            delivery_promised_count = seller_scorecard.delivery_promised_count + EXCLUDED.delivery_promised_count,
+            on_time_delivery_count = seller_scorecard.on_time_delivery_count + EXCLUDED.on_time_delivery_count,
+            updated_on = EXCLUDED.updated_on
+        "#,
+        bpp_id,
+        provider_id,
+        delta.quote_response_count,
+        delta.quote_response_secs,
+        delta.quote_rejected_count,
+        delta.order_accepted_count,
+        delta.order_rejected_count,
+        delta.seller_cancelled_count,
+        delta.delivered_count,
+        delta.delivery_promised_count,
+        delta.on_time_delivery_count,
+        Utc::now()
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving seller scorecard")
+    })?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "fetch seller scorecards", skip(pool))]
+pub async fn fetch_seller_scorecards(
+    pool: &PgPool,
+    bpp_id: Option<&str>,
+    provider_ids: Option<&[String]>,
+) -> Result<Vec<SellerScorecard>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        SellerScorecardModel,
+        r#"
+        SELECT s.bpp_id, s.provider_id, i.provider_name as "provider_name?", s.quote_response_count,
+            s.quote_response_secs, s.quote_rejected_count, s.order_accepted_count, s.order_rejected_count,
+            s.seller_cancelled_count, s.delivered_count, s.delivery_promised_count,
+            s.on_time_delivery_count, s.updated_on
+        FROM seller_scorecard s
+        LEFT JOIN ondc_seller_info i
+            ON i.seller_subscriber_id = s.bpp_id AND i.provider_id = s.provider_id
+        WHERE ($1::TEXT IS NULL OR s.bpp_id = $1)
+            AND ($2::TEXT[] IS NULL OR s.provider_id = ANY($2))
+        ORDER BY s.bpp_id, s.provider_id
+        "#,
+        bpp_id,
+        provider_ids
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching seller scorecards from database")
+    })?;
+    Ok(records.into_iter().map(|record| record.schema()).collect())
+}
+
+/// Loads the full orders of the next export page and moves the filter cursor past them.
+/// Returns `None` once every order matching the filter is exported.
+#[tracing::instrument(name = "fetch order export page", skip(pool))]
+pub async fn fetch_order_export_page(
+    pool: &PgPool,
+    filter: &mut OrderListFilter,
+) -> Result<Option<Vec<Commerce>>, anyhow::Error> {
+    let orders: Vec<CommerceList> = fetch_order_list_data_model(pool, filter)
+        .await?
+        .into_iter()
+        .map(|a| a.schema())
+        .collect();
+    let Some(last_order) = orders.last() else {
+        return Ok(None);
+    };
+    filter.cursor = Some(OrderListCursor {
+        sort_by: filter.sort_by.clone(),
+        sort_order: filter.sort_order.clone(),
+        value: filter.sort_by.get_cursor_value(last_order),
+        id: last_order.id,
+    });
+    let transaction_ids: Vec<Uuid> = orders.iter().map(|order| order.external_urn).collect();
+    Ok(Some(fetch_orders_by_ids(pool, &transaction_ids).await?))
+}
+
+#[tracing::instrument(name = "get order export file", skip(pool))]
+pub async fn get_order_export_file(
+    pool: &PgPool,
+    mut filter: OrderListFilter,
+    format: &OrderExportFormat,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let mut csv = get_order_csv(&[], true);
+    let mut orders = vec![];
+    while let Some(page) = fetch_order_export_page(pool, &mut filter).await? {
+        match format {
+            OrderExportFormat::Csv => csv.push_str(&get_order_csv(&page, false)),
+            OrderExportFormat::Xlsx => orders.extend(page),
+        }
+    }
+    match format {
+        OrderExportFormat::Csv => Ok(csv.into_bytes()),
+        OrderExportFormat::Xlsx => get_order_xlsx(&orders),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(name = "create order export job", skip(pool))]
+pub async fn create_order_export_job(
+    pool: &PgPool,
+    job_id: Uuid,
+    business_id: Uuid,
+    user_id: Uuid,
+    format: &OrderExportFormat,
+    order_count: i64,
+    file_name: &str,
+    created_on: DateTime<Utc>,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO order_export_job (id, business_id, format, status, order_count, file_name,
+            created_on, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+        job_id,
+        business_id,
+        format as &OrderExportFormat,
+        &OrderExportStatus::Processing as &OrderExportStatus,
+        order_count,
+        file_name,
+        created_on,
+        user_id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving order export job")
+    })?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "update order export job", skip(pool, result))]
+async fn update_order_export_job(
+    pool: &PgPool,
+    job_id: Uuid,
+    result: Result<Vec<u8>, anyhow::Error>,
+) -> Result<(), anyhow::Error> {
+    let (status, content, error) = match result {
+        Ok(content) => (OrderExportStatus::Completed, Some(content), None),
+        Err(e) => (OrderExportStatus::Failed, None, Some(e.to_string())),
+    };
+    sqlx::query!(
+        r#"
+        UPDATE order_export_job
+        SET status = $2, content = $3, error = $4, completed_on = $5
+        WHERE id = $1
+        "#,
+        job_id,
+        &status as &OrderExportStatus,
+        content,
+        error,
+        Utc::now()
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while updating order export job")
+    })?;
+    Ok(())
+}
+
+/// Builds the export file in the background and stores it on the job for download.
+#[tracing::instrument(name = "run order export job", skip(pool))]
+pub async fn run_order_export_job(
+    pool: PgPool,
+    job_id: Uuid,
+    filter: OrderListFilter,
+    format: OrderExportFormat,
+) {
+    let result = get_order_export_file(&pool, filter, &format).await;
+    if let Err(e) = &result {
+        tracing::error!("Failed to export orders for job {}: {:?}", job_id, e);
+    }
+    if let Err(e) = update_order_export_job(&pool, job_id, result).await {
+        tracing::error!("Failed to update order export job {}: {:?}", job_id, e);
+    }
+}
+
+#[tracing::instrument(name = "expire order export jobs", skip(pool))]
+pub async fn expire_order_export_jobs(
+    pool: &PgPool,
+    stale_before: DateTime<Utc>,
+    expire_before: DateTime<Utc>,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE order_export_job
+        SET status = $2, error = $3, completed_on = $4
+        WHERE status = $1 AND created_on < $5
+        "#,
+        &OrderExportStatus::Processing as &OrderExportStatus,
+        &OrderExportStatus::Failed as &OrderExportStatus,
+        "Order export was interrupted",
+        Utc::now(),
+        stale_before
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while failing stale order export jobs")
+    })?;
+    sqlx::query!(
+        r#"
+        UPDATE order_export_job
+        SET status = $2, content = NULL
+        WHERE status = $1 AND completed_on < $3
+        "#,
+        &OrderExportStatus::Completed as &OrderExportStatus,
+        &OrderExportStatus::Expired as &OrderExportStatus,
+        expire_before
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while expiring order export jobs")
+    })?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "fetch order export job", skip(pool))]
+pub async fn fetch_order_export_job(
+    pool: &PgPool,
+    job_id: Uuid,
+    business_id: Uuid,
+    user_id: Option<Uuid>,
+) -> Result<Option<OrderExportJob>, anyhow::Error> {
+    let record = sqlx::query_as!(
+        OrderExportJobModel,
+        r#"
+        SELECT id, format as "format: OrderExportFormat", status as "status: OrderExportStatus",
+            order_count, file_name, error, created_on, completed_on
+        FROM order_export_job
+        WHERE id = $1 AND business_id = $2 AND ($3::uuid IS NULL OR created_by = $3)
+        "#,
+        job_id,
+        business_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while fetching order export job")
+    })?;
+    Ok(record.map(|record| record.schema()))
+}
+
+#[tracing::instrument(name = "fetch order export file", skip(pool))]
+pub async fn fetch_order_export_file(
+    pool: &PgPool,
+    job_id: Uuid,
+    business_id: Uuid,
+    user_id: Option<Uuid>,
+) -> Result<Option<OrderExportFileModel>, anyhow::Error> {
+    let record = sqlx::query_as!(
+        OrderExportFileModel,
+        r#"
+        SELECT format as "format: OrderExportFormat", status as "status: OrderExportStatus",
+            file_name, content
+        FROM order_export_job
+        WHERE id = $1 AND business_id = $2 AND ($3::uuid IS NULL OR created_by = $3)
+        "#,
+        job_id,
+        business_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching order export file")
+    })?;
+    Ok(record)
+}
+
+/// Checks the bulk order rows against the registered BPPs and the cached seller locations and catalog.
+#[tracing::instrument(name = "validate bulk order catalog", skip(pool, rows))]
+pub async fn validate_bulk_order_catalog(
+    pool: &PgPool,
+    rows: &[BulkOrderRow],
+    domain_category_code: &CategoryDomain,
+) -> Result<Vec<BulkOrderRowError>, anyhow::Error> {
+    let ondc_domain = ONDCDomain::get_ondc_domain(domain_category_code);
+    let mut provider_rows: HashMap<(&str, &str), Vec<&BulkOrderRow>> = HashMap::new();
+    for row in rows {
+        provider_rows
+            .entry((row.bpp_id.as_str(), row.provider_id.as_str()))
+            .or_default()
+            .push(row);
+    }
+
+    let mut errors = vec![];
+    for ((bpp_id, provider_id), rows) in provider_rows {
+        let bpp_detail =
+            get_lookup_data_from_db(pool, bpp_id, &ONDCNetworkType::Bpp, &ondc_domain).await?;
+        if bpp_detail.is_none() {
+            errors.extend(rows.iter().map(|row| BulkOrderRowError {
+                row_no: row.row_no,
+                message: format!("{} is not a valid BPP id", bpp_id),
+            }));
+            continue;
+        }
+        let location_ids: Vec<String> = rows
+            .iter()
+            .map(|row| row.location_id.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let locations =
+            fetch_ondc_seller_location_info(pool, bpp_id, provider_id, &location_ids).await?;
+        let products = match locations.first() {
+            Some(location) => {
+                let item_ids: Vec<&str> = rows.iter().map(|row| row.item_id.as_str()).collect();
+                fetch_ondc_seller_product_info(
+                    pool,
+                    bpp_id,
+                    provider_id,
+                    &item_ids,
+                    &location.country_code,
+                )
+                .await?
+            }
+            None => vec![],
+        };
+        for row in rows {
+            let message = if !locations
+                .iter()
+                .any(|location| location.location_id == row.location_id)
+            {
+                format!(
+                    "Location {} is not available for provider {}",
+                    row.location_id, provider_id
+                )
+            } else if !products
+                .iter()
+                .any(|product| product.item_id == row.item_id)
+            {
+                format!(
+                    "Item {} is not in the catalog of provider {}",
+                    row.item_id, provider_id
+                )
+            } else {
+                continue;
+            };
+            errors.push(BulkOrderRowError {
+                row_no: row.row_no,
+                message,
+            });
+        }
+    }
+    errors.sort_by_key(|error| error.row_no);
+    Ok(errors)
+}
+
+#[tracing::instrument(name = "save standing order", skip(pool, body))]
+pub async fn save_standing_order(
+    pool: &PgPool,
+    business_id: Uuid,
+    body: &StandingOrderSaveRequest,
+    user_id: Uuid,
+) -> Result<Option<StandingOrder>, anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let record = sqlx::query_as!(
+        StandingOrderModel,
+        r#"
+        INSERT INTO standing_order (id, business_id, name, bpp_id, provider_id, domain_category_code,
+            payment_types, order_type, ttl, items, fulfillments, frequency, interval_count,
+            next_run_on, end_on, auto_confirm, status, created_on, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)
+        ON CONFLICT (id) DO UPDATE SET
+            name = EXCLUDED.name,
+            bpp_id = EXCLUDED.bpp_id,
+            provider_id = EXCLUDED.provider_id,
+            domain_category_code = EXCLUDED.domain_category_code,
+            payment_types = EXCLUDED.payment_types,
+            order_type = EXCLUDED.order_type,
+            ttl = EXCLUDED.ttl,
+            items = EXCLUDED.items,
+            fulfillments = EXCLUDED.fulfillments,
+            frequency = EXCLUDED.frequency,
+            interval_count = EXCLUDED.interval_count,
+            next_run_on = EXCLUDED.next_run_on,
+            end_on = EXCLUDED.end_on,
+            auto_confirm = EXCLUDED.auto_confirm,
+            updated_on = EXCLUDED.created_on,
+            updated_by = EXCLUDED.created_by
+        WHERE standing_order.business_id = EXCLUDED.business_id
+        RETURNING id, business_id, name, bpp_id, provider_id,
+            domain_category_code as "domain_category_code: CategoryDomain",
+            payment_types as "payment_types: Vec<PaymentType>", order_type as "order_type: OrderType",
+            ttl, items as "items: Json<Vec<OrderSelectItem>>",
+            fulfillments as "fulfillments: Json<Vec<OrderSelectFulfillment>>",
+            frequency as "frequency: StandingOrderFrequency", interval_count, next_run_on, end_on,
+            auto_confirm as "auto_confirm?: Json<StandingOrderAutoConfirm>",
+            status as "status: StandingOrderStatus", created_on, created_by, updated_on, updated_by
+        "#,
+        body.id.unwrap_or_else(Uuid::new_v4),
+        business_id,
+        body.name,
+        body.bpp_id,
+        body.provider_id,
+        &body.domain_category_code as &CategoryDomain,
+        &body.payment_types[..] as &[PaymentType],
+        &body.order_type as &OrderType,
+        body.ttl,
+        serde_json::to_value(&body.items)?,
+        serde_json::to_value(&body.fulfillments)?,
+        &body.frequency as &StandingOrderFrequency,
+        body.interval_count,
+        body.next_run_on,
+        body.end_on,
+        body.auto_confirm
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?,
+        &StandingOrderStatus::Active as &StandingOrderStatus,
+        Utc::now(),
+        user_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving standing order")
+    })?;
+    let Some(standing_order) = record.map(|record| record.schema()) else {
+        return Ok(None);
+    };
+    let event_type = if body.id.is_some() {
+        StandingOrderEventType::Updated
+    } else {
+        StandingOrderEventType::Created
+    };
+    save_standing_order_event(&mut transaction, &standing_order, event_type, user_id).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store a standing order")?;
+    Ok(Some(standing_order))
+}
+
+#[tracing::instrument(name = "save standing order event", skip(transaction, standing_order))]
+async fn save_standing_order_event(
+    transaction: &mut Transaction<'_, Postgres>,
+    standing_order: &StandingOrder,
+    event_type: StandingOrderEventType,
+    user_id: Uuid,
+) -> Result<(), anyhow::Error> {
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO standing_order_event (id, standing_order_id, event_type, detail, created_on, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        Uuid::new_v4(),
+        standing_order.id,
+        &event_type as &StandingOrderEventType,
+        serde_json::to_value(standing_order)?,
+        Utc::now(),
+        user_id
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while saving standing order event")
+    })?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "update standing order status", skip(pool))]
+pub async fn update_standing_order_status(
+    pool: &PgPool,
+    id: Uuid,
+    business_id: Uuid,
+    status: &StandingOrderStatus,
+    next_run_on: DateTime<Utc>,
+    user_id: Uuid,
+) -> Result<Option<StandingOrder>, anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let record = sqlx::query_as!(
+        StandingOrderModel,
+        r#"
+        UPDATE standing_order SET status = $1, next_run_on = $2, updated_on = $3, updated_by = $4
+        WHERE id = $5 AND business_id = $6
+        RETURNING id, business_id, name, bpp_id, provider_id,
+            domain_category_code as "domain_category_code: CategoryDomain",
+            payment_types as "payment_types: Vec<PaymentType>", order_type as "order_type: OrderType",
+            ttl, items as "items: Json<Vec<OrderSelectItem>>",
+            fulfillments as "fulfillments: Json<Vec<OrderSelectFulfillment>>",
+            frequency as "frequency: StandingOrderFrequency", interval_count, next_run_on, end_on,
+            auto_confirm as "auto_confirm?: Json<StandingOrderAutoConfirm>",
+            status as "status: StandingOrderStatus", created_on, created_by, updated_on, updated_by
+        "#,
+        status as &StandingOrderStatus,
+        next_run_on,
+        Utc::now(),
+        user_id,
+        id,
+        business_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while updating standing order status")
+    })?;
+    let Some(standing_order) = record.map(|record| record.schema()) else {
+        return Ok(None);
+    };
+    let event_type = match status {
+        StandingOrderStatus::Active => StandingOrderEventType::Resumed,
+        StandingOrderStatus::Paused => StandingOrderEventType::Paused,
+    };
+    save_standing_order_event(&mut transaction, &standing_order, event_type, user_id).await?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to update a standing order")?;
+    Ok(Some(standing_order))
+}
+
+#[tracing::instrument(name = "fetch standing order", skip(pool))]
+pub async fn fetch_standing_order(
+    pool: &PgPool,
+    id: Uuid,
+) -> Result<Option<StandingOrder>, anyhow::Error> {
+    let record = sqlx::query_as!(
+        StandingOrderModel,
+        r#"
+        SELECT id, business_id, name, bpp_id, provider_id,
+            domain_category_code as "domain_category_code: CategoryDomain",
+            payment_types as "payment_types: Vec<PaymentType>", order_type as "order_type: OrderType",
+            ttl, items as "items: Json<Vec<OrderSelectItem>>",
+            fulfillments as "fulfillments: Json<Vec<OrderSelectFulfillment>>",
+            frequency as "frequency: StandingOrderFrequency", interval_count, next_run_on, end_on,
+            auto_confirm as "auto_confirm?: Json<StandingOrderAutoConfirm>",
+            status as "status: StandingOrderStatus", created_on, created_by, updated_on, updated_by
+        FROM standing_order
+        WHERE id = $1
+        "#,
+        id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while fetching standing order")
+    })?;
+    Ok(record.map(|record| record.schema()))
+}
+
+#[tracing::instrument(name = "fetch standing orders", skip(pool))]
+pub async fn fetch_standing_orders(
+    pool: &PgPool,
+    business_id: Uuid,
+    user_id: Option<Uuid>,
+) -> Result<Vec<StandingOrder>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        StandingOrderModel,
+        r#"
+        SELECT id, business_id, name, bpp_id, provider_id,
+            domain_category_code as "domain_category_code: CategoryDomain",
+            payment_types as "payment_types: Vec<PaymentType>", order_type as "order_type: OrderType",
+            ttl, items as "items: Json<Vec<OrderSelectItem>>",
+            fulfillments as "fulfillments: Json<Vec<OrderSelectFulfillment>>",
+            frequency as "frequency: StandingOrderFrequency", interval_count, next_run_on, end_on,
+            auto_confirm as "auto_confirm?: Json<StandingOrderAutoConfirm>",
+            status as "status: StandingOrderStatus", created_on, created_by, updated_on, updated_by
+        FROM standing_order
+        WHERE business_id = $1 AND ($2::uuid IS NULL OR created_by = $2)
+        ORDER BY created_on DESC
+        "#,
+        business_id,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while fetching standing orders")
+    })?;
+    Ok(records.into_iter().map(|record| record.schema()).collect())
+}
+
+#[tracing::instrument(name = "fetch standing order history", skip(pool))]
+pub async fn fetch_standing_order_history(
+    pool: &PgPool,
+    id: Uuid,
+) -> Result<StandingOrderHistory, anyhow::Error> {
+    let task1 = sqlx::query_as!(
+        StandingOrderEventModel,
+        r#"
+        SELECT event_type as "event_type: StandingOrderEventType", detail, created_on, created_by
+        FROM standing_order_event
+        WHERE standing_order_id = $1
+        ORDER BY created_on DESC
+        "#,
+        id
+    )
+    .fetch_all(pool);
+    let task2 = sqlx::query_as!(
+        StandingOrderRunModel,
+        r#"
+        SELECT run.id, run.standing_order_id, so.name as standing_order_name, run.transaction_id,
+            run.scheduled_on, run.status as "status: StandingOrderRunStatus", run.quote_total,
+            run.message, run.created_on, run.updated_on
+        FROM standing_order_run run
+        INNER JOIN standing_order so ON so.id = run.standing_order_id
+        WHERE run.standing_order_id = $1
+        ORDER BY run.scheduled_on DESC
+        "#,
+        id
+    )
+    .fetch_all(pool);
+    let (events, runs) = tokio::try_join!(task1, task2).map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching standing order history")
+    })?;
+    Ok(StandingOrderHistory {
+        events: events.into_iter().map(|event| event.schema()).collect(),
+        runs: runs.into_iter().map(|run| run.schema()).collect(),
+    })
+}
+
+#[tracing::instrument(name = "fetch standing order run", skip(pool))]
+pub async fn fetch_standing_order_run(
+    pool: &PgPool,
+    transaction_id: Uuid,
+) -> Result<Option<StandingOrderRun>, anyhow::Error> {
+    let record = sqlx::query_as!(
+        StandingOrderRunModel,
+        r#"
+        SELECT run.id, run.standing_order_id, so.name as standing_order_name, run.transaction_id,
+            run.scheduled_on, run.status as "status: StandingOrderRunStatus", run.quote_total,
+            run.message, run.created_on, run.updated_on
+        FROM standing_order_run run
+        INNER JOIN standing_order so ON so.id = run.standing_order_id
+        WHERE run.transaction_id = $1
+        "#,
+        transaction_id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching standing order run")
+    })?;
+    Ok(record.map(|record| record.schema()))
+}
+
+/// Moves a run to `status` only from one of `from_status_list`, so a repeated callback cannot send a step twice.
+#[tracing::instrument(name = "update standing order run", skip(pool))]
+pub async fn update_standing_order_run(
+    pool: &PgPool,
+    transaction_id: Uuid,
+    from_status_list: &[StandingOrderRunStatus],
+    status: &StandingOrderRunStatus,
+    quote_total: Option<&BigDecimal>,
+    message: Option<&str>,
+) -> Result<Option<StandingOrderRun>, anyhow::Error> {
+    let record = sqlx::query_as!(
+        StandingOrderRunModel,
+        r#"
+        WITH run AS (
+            UPDATE standing_order_run SET status = $1, quote_total = COALESCE($2, quote_total),
+                message = $3, updated_on = $4
+            WHERE transaction_id = $5 AND status = ANY($6)
+            RETURNING id, standing_order_id, transaction_id, scheduled_on, status, quote_total,
+                message, created_on, updated_on
+        )
+        SELECT run.id, run.standing_order_id, so.name as standing_order_name, run.transaction_id,
+            run.scheduled_on, run.status as "status: StandingOrderRunStatus", run.quote_total,
+            run.message, run.created_on, run.updated_on
+        FROM run
+        INNER JOIN standing_order so ON so.id = run.standing_order_id
+        "#,
+        status as &StandingOrderRunStatus,
+        quote_total,
+        message,
+        Utc::now(),
+        transaction_id,
+        from_status_list as &[StandingOrderRunStatus]
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while updating standing order run")
+    })?;
+    Ok(record.map(|record| record.schema()))
+}
+
+/// Claims the due templates with `SKIP LOCKED` so that several instances never send the same occurrence.
+#[tracing::instrument(name = "claim due standing orders", skip(pool))]
+pub async fn claim_due_standing_orders(
+    pool: &PgPool,
+    now: DateTime<Utc>,
+    batch_size: i64,
+) -> Result<Vec<(StandingOrder, Uuid)>, anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let records = sqlx::query_as!(
+        StandingOrderModel,
+        r#"
+        SELECT id, business_id, name, bpp_id, provider_id,
+            domain_category_code as "domain_category_code: CategoryDomain",
+            payment_types as "payment_types: Vec<PaymentType>", order_type as "order_type: OrderType",
+            ttl, items as "items: Json<Vec<OrderSelectItem>>",
+            fulfillments as "fulfillments: Json<Vec<OrderSelectFulfillment>>",
+            frequency as "frequency: StandingOrderFrequency", interval_count, next_run_on, end_on,
+            auto_confirm as "auto_confirm?: Json<StandingOrderAutoConfirm>",
+            status as "status: StandingOrderStatus", created_on, created_by, updated_on, updated_by
+        FROM standing_order
+        WHERE status = $1 AND next_run_on <= $2 AND (end_on IS NULL OR next_run_on <= end_on)
+        ORDER BY next_run_on
+        LIMIT $3
+        FOR UPDATE SKIP LOCKED
+        "#,
+        &StandingOrderStatus::Active as &StandingOrderStatus,
+        now,
+        batch_size
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching due standing orders")
+    })?;
+    if records.is_empty() {
+        return Ok(vec![]);
+    }
+    let standing_orders: Vec<StandingOrder> =
+        records.into_iter().map(|record| record.schema()).collect();
+    let mut id_list = vec![];
+    let mut next_run_on_list = vec![];
+    let mut run_id_list = vec![];
+    let mut transaction_id_list = vec![];
+    let mut scheduled_on_list = vec![];
+    for standing_order in &standing_orders {
+        id_list.push(standing_order.id);
+        next_run_on_list.push(get_upcoming_run_on(standing_order, now));
+        run_id_list.push(Uuid::new_v4());
+        transaction_id_list.push(Uuid::new_v4());
+        scheduled_on_list.push(standing_order.next_run_on);
+    }
+    let query = sqlx::query!(
+        r#"
+        UPDATE standing_order SET next_run_on = data.next_run_on
+        FROM UNNEST($1::uuid[], $2::timestamptz[]) AS data(id, next_run_on)
+        WHERE standing_order.id = data.id
+        "#,
+        &id_list[..] as &[Uuid],
+        &next_run_on_list[..] as &[DateTime<Utc>]
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while updating standing order schedule")
+    })?;
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO standing_order_run (id, standing_order_id, transaction_id, scheduled_on, status,
+            created_on)
+        SELECT data.*, $5, $6
+        FROM UNNEST($1::uuid[], $2::uuid[], $3::uuid[], $4::timestamptz[])
+            AS data(id, standing_order_id, transaction_id, scheduled_on)
+        "#,
+        &run_id_list[..] as &[Uuid],
+        &id_list[..] as &[Uuid],
+        &transaction_id_list[..] as &[Uuid],
+        &scheduled_on_list[..] as &[DateTime<Utc>],
+        &StandingOrderRunStatus::SelectSent as &StandingOrderRunStatus,
+        now
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving standing order run")
+    })?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to claim standing orders")?;
+    Ok(standing_orders
+        .into_iter()
+        .zip(transaction_id_list)
+        .collect())
+}
+
+/// Adding an item already in the cart adds to its quantity and refreshes its catalog values.
+#[tracing::instrument(name = "save cart item", skip(pool, product))]
+pub async fn save_cart_item(
+    pool: &PgPool,
+    business_id: Uuid,
+    user_id: Uuid,
+    body: &CartItemAddRequest,
+    product: &ONDCSellerProductInfo,
+) -> Result<CartItem, anyhow::Error> {
+    let record = sqlx::query_as!(
+        CartItemModel,
+        r#"
+        INSERT INTO cart_item (id, business_id, user_id, domain_category_code, bpp_id, provider_id,
+            item_id, item_name, location_id, qty, unit_price, currency_code, buyer_term, created_on,
+            import_detail)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+        ON CONFLICT (business_id, user_id, bpp_id, provider_id, item_id) DO UPDATE SET
+            domain_category_code = EXCLUDED.domain_category_code,
+            item_name = EXCLUDED.item_name,
+            location_id = EXCLUDED.location_id,
+            qty = cart_item.qty + EXCLUDED.qty,
+            unit_price = EXCLUDED.unit_price,
+            currency_code = EXCLUDED.currency_code,
+            buyer_term = COALESCE(EXCLUDED.buyer_term, cart_item.buyer_term),
+            import_detail = COALESCE(EXCLUDED.import_detail, cart_item.import_detail),
+            updated_on = EXCLUDED.created_on
+        RETURNING id, domain_category_code as "domain_category_code: CategoryDomain", bpp_id,
+            provider_id, item_id, item_name, location_id, qty, unit_price,
+            currency_code as "currency_code: CurrencyType",
+            buyer_term as "buyer_term?: Json<BuyerTerms>",
+            import_detail as "import_detail?: Json<OrderItemImportDetail>", created_on, updated_on
+        "#,
+        Uuid::new_v4(),
+        business_id,
+        user_id,
+        &body.domain_category_code as &CategoryDomain,
+        body.bpp_id,
+        body.provider_id,
+        body.item_id,
+        product.item_name,
+        body.location_id,
+        body.qty,
+        product.unit_price_with_tax,
+        &product.currency_code as &CurrencyType,
+        body.buyer_term
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?,
+        Utc::now(),
+        body.import_detail
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving cart item")
+    })?;
+    Ok(record.schema())
+}
+
+#[tracing::instrument(name = "update cart item", skip(pool))]
+pub async fn update_cart_item(
+    pool: &PgPool,
+    business_id: Uuid,
+    user_id: Uuid,
+    body: &CartItemUpdateRequest,
+) -> Result<Option<CartItem>, anyhow::Error> {
+    let record = sqlx::query_as!(
+        CartItemModel,
+        r#"
+        UPDATE cart_item SET qty = $1, buyer_term = $2, updated_on = $3, import_detail = $7
+        WHERE id = $4 AND business_id = $5 AND user_id = $6
+        RETURNING id, domain_category_code as "domain_category_code: CategoryDomain", bpp_id,
+            provider_id, item_id, item_name, location_id, qty, unit_price,
+            currency_code as "currency_code: CurrencyType",
+            buyer_term as "buyer_term?: Json<BuyerTerms>",
+            import_detail as "import_detail?: Json<OrderItemImportDetail>", created_on, updated_on
+        "#,
+        body.qty,
+        body.buyer_term
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?,
+        Utc::now(),
+        body.id,
+        business_id,
+        user_id,
+        body.import_detail
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while updating cart item")
+    })?;
+    Ok(record.map(|record| record.schema()))
+}
+
+#[tracing::instrument(name = "delete cart items", skip(pool))]
+pub async fn delete_cart_items(
+    pool: &PgPool,
+    business_id: Uuid,
+    user_id: Uuid,
+    id_list: &[Uuid],
+) -> Result<u64, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM cart_item
+        WHERE business_id = $1 AND user_id = $2 AND id = ANY($3)
+        "#,
+        business_id,
+        user_id,
+        id_list
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while deleting cart items")
+    })?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(name = "fetch cart items", skip(pool))]
+pub async fn fetch_cart_items(
+    pool: &PgPool,
+    business_id: Uuid,
+    user_id: Uuid,
+) -> Result<Vec<CartItem>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        CartItemModel,
+        r#"
+        SELECT id, domain_category_code as "domain_category_code: CategoryDomain", bpp_id,
+            provider_id, item_id, item_name, location_id, qty, unit_price,
+            currency_code as "currency_code: CurrencyType",
+            buyer_term as "buyer_term?: Json<BuyerTerms>",
+            import_detail as "import_detail?: Json<OrderItemImportDetail>", created_on, updated_on
+        FROM cart_item
+        WHERE business_id = $1 AND user_id = $2
+        ORDER BY created_on
+        "#,
+        business_id,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while fetching cart items")
+    })?;
+    Ok(records.into_iter().map(|record| record.schema()).collect())
+}
+
+/// Removes the cart items in the same transaction that records the checkout, so that a concurrent
+/// checkout of the same cart finds it empty.
+#[tracing::instrument(name = "claim cart items", skip(transaction))]
+pub async fn claim_cart_items(
+    transaction: &mut Transaction<'_, Postgres>,
+    business_id: Uuid,
+    user_id: Uuid,
+) -> Result<Vec<CartItem>, anyhow::Error> {
+    let mut records = sqlx::query_as!(
+        CartItemModel,
+        r#"
+        DELETE FROM cart_item
+        WHERE business_id = $1 AND user_id = $2
+        RETURNING id, domain_category_code as "domain_category_code: CategoryDomain", bpp_id,
+            provider_id, item_id, item_name, location_id, qty, unit_price,
+            currency_code as "currency_code: CurrencyType",
+            buyer_term as "buyer_term?: Json<BuyerTerms>",
+            import_detail as "import_detail?: Json<OrderItemImportDetail>", created_on, updated_on
+        "#,
+        business_id,
+        user_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while claiming cart items")
+    })?;
+    records.sort_by_key(|record| record.created_on);
+    Ok(records.into_iter().map(|record| record.schema()).collect())
+}
+
+#[tracing::instrument(name = "save cart checkout", skip(transaction, checkout))]
+pub async fn save_cart_checkout(
+    transaction: &mut Transaction<'_, Postgres>,
+    business_id: Uuid,
+    user_id: Uuid,
+    checkout: &CartCheckout,
+) -> Result<(), anyhow::Error> {
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO cart_checkout (id, business_id, user_id, created_on)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        checkout.id,
+        business_id,
+        user_id,
+        checkout.created_on
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving cart checkout")
+    })?;
+
+    for order in &checkout.orders {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO cart_checkout_order (checkout_id, transaction_id, bpp_id, provider_id, items, error)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            checkout.id,
+            order.transaction_id,
+            order.bpp_id,
+            order.provider_id,
+            serde_json::to_value(&order.items)?,
+            order.error
+        );
+        transaction.execute(query).await.map_err(|e| {
+            tracing::error!("Failed to execute query: {:?}", e);
+            anyhow::Error::new(e)
+                .context("A database failure occurred while saving cart checkout order")
+        })?;
+    }
+    Ok(())
+}
+
+/// Records the error of a select that could not be sent and puts its items back in the cart.
+#[tracing::instrument(name = "fail cart checkout order", skip(pool, items))]
+pub async fn fail_cart_checkout_order(
+    pool: &PgPool,
+    business_id: Uuid,
+    user_id: Uuid,
+    checkout_id: Uuid,
+    transaction_id: Uuid,
+    error: &str,
+    items: &[CartItem],
+) -> Result<(), anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let query = sqlx::query!(
+        r#"
+        UPDATE cart_checkout_order SET error = $3
+        WHERE checkout_id = $1 AND transaction_id = $2
+        "#,
+        checkout_id,
+        transaction_id,
+        error
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while updating cart checkout order")
+    })?;
+    for item in items {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO cart_item (id, business_id, user_id, domain_category_code, bpp_id,
+                provider_id, item_id, item_name, location_id, qty, unit_price, currency_code,
+                buyer_term, created_on, updated_on, import_detail)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+            ON CONFLICT DO NOTHING
+            "#,
+            item.id,
+            business_id,
+            user_id,
+            &item.domain_category_code as &CategoryDomain,
+            item.bpp_id,
+            item.provider_id,
+            item.item_id,
+            item.item_name,
+            item.location_id,
+            item.qty,
+            item.unit_price,
+            &item.currency_code as &CurrencyType,
+            item.buyer_term
+                .as_ref()
+                .map(serde_json::to_value)
+                .transpose()?,
+            item.created_on,
+            item.updated_on,
+            item.import_detail
+                .as_ref()
+                .map(serde_json::to_value)
+                .transpose()?
+        );
+        transaction.execute(query).await.map_err(|e| {
+            tracing::error!("Failed to execute query: {:?}", e);
+            anyhow::Error::new(e).context("A database failure occurred while restoring cart item")
+        })?;
+    }
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store a failed cart checkout order")?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "fetch cart checkout", skip(pool))]
+pub async fn fetch_cart_checkout(
+    pool: &PgPool,
+    id: Uuid,
+    business_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<CartCheckout>, anyhow::Error> {
+    let checkout = sqlx::query_as!(
+        CartCheckoutModel,
+        r#"
+        SELECT id, created_on FROM cart_checkout
+        WHERE id = $1 AND business_id = $2 AND user_id = $3
+        "#,
+        id,
+        business_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while fetching cart checkout")
+    })?;
+    let Some(checkout) = checkout else {
+        return Ok(None);
+    };
+    let orders = sqlx::query_as!(
+        CartCheckoutOrderModel,
+        r#"
+        SELECT co.transaction_id, co.bpp_id, co.provider_id, co.items as "items: Json<Vec<CartItem>>",
+            co.error, cd.record_status as "record_status?: CommerceStatusType"
+        FROM cart_checkout_order co
+        LEFT JOIN commerce_data cd ON cd.external_urn = co.transaction_id AND cd.buyer_id = $2
+        WHERE co.checkout_id = $1
+        ORDER BY co.provider_id
+        "#,
+        id,
+        business_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching cart checkout orders")
+    })?;
+    Ok(Some(CartCheckout {
+        id: checkout.id,
+        created_on: checkout.created_on,
+        orders: orders.into_iter().map(|order| order.schema()).collect(),
+    }))
+}
+
+/// A default delivery location replaces the previous default of the business.
+#[tracing::instrument(name = "save delivery location", skip(pool))]
+pub async fn save_delivery_location(
+    pool: &PgPool,
+    business_id: Uuid,
+    body: &DeliveryLocationSaveRequest,
+    user_id: Uuid,
+) -> Result<Option<DeliveryLocation>, anyhow::Error> {
+    let id = body.id.unwrap_or_else(Uuid::new_v4);
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    if body.is_default {
+        let query = sqlx::query!(
+            r#"
+            UPDATE buyer_delivery_location SET is_default = false
+            WHERE business_id = $1 AND is_default AND id != $2
+            "#,
+            business_id,
+            id
+        );
+        transaction.execute(query).await.map_err(|e| {
+            tracing::error!("Failed to execute query: {:?}", e);
+            anyhow::Error::new(e)
+                .context("A database failure occurred while resetting default delivery location")
+        })?;
+    }
+    let record = sqlx::query_as!(
+        DeliveryLocationModel,
+        r#"
+        INSERT INTO buyer_delivery_location (id, business_id, name, location, contact_name,
+            contact_email, opening_hours, is_default, created_on, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT (id) DO UPDATE SET
+            name = EXCLUDED.name,
+            location = EXCLUDED.location,
+            contact_name = EXCLUDED.contact_name,
+            contact_email = EXCLUDED.contact_email,
+            opening_hours = EXCLUDED.opening_hours,
+            is_default = EXCLUDED.is_default,
+            updated_on = EXCLUDED.created_on,
+            updated_by = EXCLUDED.created_by
+        WHERE buyer_delivery_location.business_id = EXCLUDED.business_id
+        RETURNING id, name, location as "location: Json<SelectFulfillmentLocation>", contact_name,
+            contact_email, opening_hours as "opening_hours: Json<Vec<DeliveryLocationOpeningHours>>",
+            is_default, created_on, created_by, updated_on
+        "#,
+        id,
+        business_id,
+        body.name,
+        serde_json::to_value(&body.location)?,
+        body.contact_name,
+        body.contact_email,
+        serde_json::to_value(&body.opening_hours)?,
+        body.is_default,
+        Utc::now(),
+        user_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving delivery location")
+    })?;
+    let Some(record) = record else {
+        return Ok(None);
+    };
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store a delivery location")?;
+    Ok(Some(record.schema()))
+}
+
+#[tracing::instrument(name = "fetch delivery locations", skip(pool))]
+pub async fn fetch_delivery_locations(
+    pool: &PgPool,
+    business_id: Uuid,
+) -> Result<Vec<DeliveryLocation>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        DeliveryLocationModel,
+        r#"
+        SELECT id, name, location as "location: Json<SelectFulfillmentLocation>", contact_name,
+            contact_email, opening_hours as "opening_hours: Json<Vec<DeliveryLocationOpeningHours>>",
+            is_default, created_on, created_by, updated_on
+        FROM buyer_delivery_location
+        WHERE business_id = $1
+        ORDER BY is_default DESC, name
+        "#,
+        business_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching delivery locations")
+    })?;
+    Ok(records.into_iter().map(|record| record.schema()).collect())
+}
+
+#[tracing::instrument(name = "delete delivery location", skip(pool))]
+pub async fn delete_delivery_location(
+    pool: &PgPool,
+    id: Uuid,
+    business_id: Uuid,
+) -> Result<u64, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM buyer_delivery_location WHERE id = $1 AND business_id = $2
+        "#,
+        id,
+        business_id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while deleting delivery location")
+    })?;
+    Ok(result.rows_affected())
+}
+
+/// A default billing profile replaces the previous default of the business.
+#[tracing::instrument(name = "save billing profile", skip(pool))]
+pub async fn save_billing_profile(
+    pool: &PgPool,
+    business_id: Uuid,
+    body: &BillingProfileSaveRequest,
+    user_id: Uuid,
+) -> Result<Option<BillingProfile>, anyhow::Error> {
+    let id = body.id.unwrap_or_else(Uuid::new_v4);
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    if body.is_default {
+        let query = sqlx::query!(
+            r#"
+            UPDATE buyer_billing_profile SET is_default = false
+            WHERE business_id = $1 AND is_default AND id != $2
+            "#,
+            business_id,
+            id
+        );
+        transaction.execute(query).await.map_err(|e| {
+            tracing::error!("Failed to execute query: {:?}", e);
+            anyhow::Error::new(e)
+                .context("A database failure occurred while resetting default billing profile")
+        })?;
+    }
+    let record = sqlx::query_as!(
+        BillingProfileModel,
+        r#"
+        INSERT INTO buyer_billing_profile (id, business_id, name, billing, country_code, is_default,
+            created_on, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (id) DO UPDATE SET
+            name = EXCLUDED.name,
+            billing = EXCLUDED.billing,
+            country_code = EXCLUDED.country_code,
+            is_default = EXCLUDED.is_default,
+            updated_on = EXCLUDED.created_on,
+            updated_by = EXCLUDED.created_by
+        WHERE buyer_billing_profile.business_id = EXCLUDED.business_id
+        RETURNING id, name, billing as "billing: Json<OrderInitBilling>",
+            country_code as "country_code: CountryCode", is_default, created_on, created_by, updated_on
+        "#,
+        id,
+        business_id,
+        body.name,
+        serde_json::to_value(&body.billing)?,
+        &body.country_code as &CountryCode,
+        body.is_default,
+        Utc::now(),
+        user_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving billing profile")
+    })?;
+    let Some(record) = record else {
+        return Ok(None);
+    };
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store a billing profile")?;
+    Ok(Some(record.schema()))
+}
+
+#[tracing::instrument(name = "fetch billing profiles", skip(pool))]
+pub async fn fetch_billing_profiles(
+    pool: &PgPool,
+    business_id: Uuid,
+) -> Result<Vec<BillingProfile>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        BillingProfileModel,
+        r#"
+        SELECT id, name, billing as "billing: Json<OrderInitBilling>",
+            country_code as "country_code: CountryCode", is_default, created_on, created_by, updated_on
+        FROM buyer_billing_profile
+        WHERE business_id = $1
+        ORDER BY is_default DESC, name
+        "#,
+        business_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while fetching billing profiles")
+    })?;
+    Ok(records.into_iter().map(|record| record.schema()).collect())
+}
+
+#[tracing::instrument(name = "delete billing profile", skip(pool))]
+pub async fn delete_billing_profile(
+    pool: &PgPool,
+    id: Uuid,
+    business_id: Uuid,
+) -> Result<u64, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM buyer_billing_profile WHERE id = $1 AND business_id = $2
+        "#,
+        id,
+        business_id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while deleting billing profile")
+    })?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(name = "save buyer terms template", skip(pool))]
+pub async fn save_buyer_terms_template(
+    pool: &PgPool,
+    business_id: Uuid,
+    body: &BuyerTermsTemplateSaveRequest,
+    user_id: Uuid,
+) -> Result<Option<BuyerTermsTemplate>, anyhow::Error> {
+    let id = body.id.unwrap_or_else(Uuid::new_v4);
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    if body.is_default {
+        let query = sqlx::query!(
+            r#"
+            UPDATE buyer_terms_template SET is_default = false
+            WHERE business_id = $1 AND is_default AND id != $2
+                AND domain_category_code IS NOT DISTINCT FROM $3
+            "#,
+            business_id,
+            id,
+            body.domain_category_code.as_ref() as Option<&CategoryDomain>
+        );
+        transaction.execute(query).await.map_err(|e| {
+            tracing::error!("Failed to execute query: {:?}", e);
+            anyhow::Error::new(e)
+                .context("A database failure occurred while resetting default buyer terms template")
+        })?;
+    }
+    let record = sqlx::query_as!(
+        BuyerTermsTemplateModel,
+        r#"
+        INSERT INTO buyer_terms_template (id, business_id, name, domain_category_code, terms,
+            is_default, created_on, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (id) DO UPDATE SET
+            name = EXCLUDED.name,
+            domain_category_code = EXCLUDED.domain_category_code,
+            terms = EXCLUDED.terms,
+            is_default = EXCLUDED.is_default,
+            updated_on = EXCLUDED.created_on,
+            updated_by = EXCLUDED.created_by
+        WHERE buyer_terms_template.business_id = EXCLUDED.business_id
+        RETURNING id, name, domain_category_code as "domain_category_code?: CategoryDomain",
+            terms as "terms: Json<BuyerTermsSpec>", is_default, created_on, created_by, updated_on
+        "#,
+        id,
+        business_id,
+        body.name,
+        body.domain_category_code.as_ref() as Option<&CategoryDomain>,
+        serde_json::to_value(&body.terms)?,
+        body.is_default,
+        Utc::now(),
+        user_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while saving buyer terms template")
+    })?;
+    let Some(record) = record else {
+        return Ok(None);
+    };
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store a buyer terms template")?;
+    Ok(Some(record.schema()))
+}
+
+#[tracing::instrument(name = "fetch buyer terms templates", skip(pool))]
+pub async fn fetch_buyer_terms_templates(
+    pool: &PgPool,
+    business_id: Uuid,
+) -> Result<Vec<BuyerTermsTemplate>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        BuyerTermsTemplateModel,
+        r#"
+        SELECT id, name, domain_category_code as "domain_category_code?: CategoryDomain",
+            terms as "terms: Json<BuyerTermsSpec>", is_default, created_on, created_by, updated_on
+        FROM buyer_terms_template
+        WHERE business_id = $1
+        ORDER BY is_default DESC, name
+        "#,
+        business_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching buyer terms templates")
+    })?;
+    Ok(records.into_iter().map(|record| record.schema()).collect())
+}
+
+#[tracing::instrument(name = "delete buyer terms template", skip(pool))]
+pub async fn delete_buyer_terms_template(
+    pool: &PgPool,
+    id: Uuid,
+    business_id: Uuid,
+) -> Result<u64, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM buyer_terms_template WHERE id = $1 AND business_id = $2
+        "#,
+        id,
+        business_id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while deleting buyer terms template")
+    })?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(name = "save order import lines", skip(pool))]
+pub async fn save_order_import_lines(
+    pool: &PgPool,
+    select_request: &OrderSelectRequest,
+) -> Result<(), anyhow::Error> {
+    let mut item_id_list = vec![];
+    let mut hs_code_list = vec![];
+    let mut country_of_origin_list = vec![];
+    let mut duty_rate_list = vec![];
+    for item in select_request.items.iter() {
+        if let Some(import_detail) = &item.import_detail {
+            item_id_list.push(item.item_id.as_str());
+            hs_code_list.push(import_detail.hs_code.as_str());
+            country_of_origin_list.push(&import_detail.country_of_origin);
+            duty_rate_list.push(import_detail.duty_rate.clone());
+        }
+    }
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let query = sqlx::query!(
+        r#"
+        DELETE FROM commerce_import_line WHERE transaction_id = $1
+        "#,
+        select_request.transaction_id
+    );
+    transaction.execute(query).await.map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while deleting import lines")
+    })?;
+    if !item_id_list.is_empty() {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO commerce_import_line (transaction_id, item_id, hs_code, country_of_origin,
+                duty_rate)
+            SELECT $1, * FROM UNNEST($2::text[], $3::text[], $4::country_code[], $5::decimal[])
+            "#,
+            select_request.transaction_id,
+            &item_id_list[..] as &[&str],
+            &hs_code_list[..] as &[&str],
+            &country_of_origin_list[..] as &[&CountryCode],
+            &duty_rate_list[..] as &[Option<BigDecimal>]
+        );
+        transaction.execute(query).await.map_err(|e| {
+            tracing::error!("Failed to execute query: {:?}", e);
+            anyhow::Error::new(e).context("A database failure occurred while saving import lines")
+        })?;
+    }
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store import lines")?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "fetch order import lines", skip(pool))]
+async fn fetch_order_import_lines(
+    pool: &PgPool,
+    transaction_ids: &[Uuid],
+) -> Result<Vec<CommerceImportLineModel>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        CommerceImportLineModel,
+        r#"
+        SELECT transaction_id, item_id, hs_code,
+            country_of_origin as "country_of_origin: CountryCode", duty_rate
+        FROM commerce_import_line
+        WHERE transaction_id = ANY($1)
+        "#,
+        transaction_ids
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while fetching import lines")
+    })?;
+    Ok(records)
+}
+
+#[tracing::instrument(name = "save order import costs", skip(pool))]
+pub async fn save_order_import_costs(
+    pool: &PgPool,
+    transaction_id: Uuid,
+    import_costs: &OrderImportCosts,
+    user_id: Uuid,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO commerce_import_cost (transaction_id, freight_amount, insurance_amount,
+            created_on, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (transaction_id) DO UPDATE SET
+            freight_amount = EXCLUDED.freight_amount,
+            insurance_amount = EXCLUDED.insurance_amount,
+            created_on = EXCLUDED.created_on,
+            created_by = EXCLUDED.created_by
+        "#,
+        transaction_id,
+        import_costs.freight_amount,
+        import_costs.insurance_amount,
+        Utc::now(),
+        user_id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving import costs")
+    })?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "fetch order import costs", skip(pool))]
+async fn fetch_order_import_costs(
+    pool: &PgPool,
+    transaction_ids: &[Uuid],
+) -> Result<Vec<CommerceImportCostModel>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        CommerceImportCostModel,
+        r#"
+        SELECT transaction_id, freight_amount, insurance_amount
+        FROM commerce_import_cost
+        WHERE transaction_id = ANY($1)
+        "#,
+        transaction_ids
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while fetching import costs")
+    })?;
+    Ok(records)
+}
+
+#[tracing::instrument(name = "save customs document", skip(pool))]
+pub async fn save_customs_document(
+    pool: &PgPool,
+    body: &CustomsDocumentSaveRequest,
+    user_id: Uuid,
+) -> Result<Option<CustomsDocument>, anyhow::Error> {
+    let record = sqlx::query_as!(
+        CustomsDocumentModel,
+        r#"
+        INSERT INTO commerce_customs_document (id, transaction_id, document_type, document_no, url,
+            issued_on, remark, created_on, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        ON CONFLICT (id) DO UPDATE SET
+            document_type = EXCLUDED.document_type,
+            document_no = EXCLUDED.document_no,
+            url = EXCLUDED.url,
+            issued_on = EXCLUDED.issued_on,
+            remark = EXCLUDED.remark,
+            updated_on = EXCLUDED.created_on,
+            updated_by = EXCLUDED.created_by
+        WHERE commerce_customs_document.transaction_id = EXCLUDED.transaction_id
+        RETURNING id, transaction_id, document_type as "document_type: CustomsDocumentType",
+            document_no, url,
+            issued_on, remark, created_on, created_by, updated_on
+        "#,
+        body.id.unwrap_or_else(Uuid::new_v4),
+        body.transaction_id,
+        &body.document_type as &CustomsDocumentType,
+        body.document_no.trim(),
+        body.url,
+        body.issued_on,
+        body.remark,
+        Utc::now(),
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while saving customs document")
+    })?;
+    Ok(record.map(|record| record.schema()))
+}
+
+#[tracing::instrument(name = "fetch customs documents", skip(pool))]
+async fn fetch_customs_documents(
+    pool: &PgPool,
+    transaction_ids: &[Uuid],
+) -> Result<Vec<CustomsDocumentModel>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        CustomsDocumentModel,
+        r#"
+        SELECT id, transaction_id, document_type as "document_type: CustomsDocumentType",
+            document_no, url, issued_on, remark, created_on, created_by, updated_on
+        FROM commerce_customs_document
+        WHERE transaction_id = ANY($1)
+        ORDER BY created_on
+        "#,
+        transaction_ids
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching customs documents")
+    })?;
+    Ok(records)
+}
+
+#[tracing::instrument(name = "delete customs document", skip(pool))]
+pub async fn delete_customs_document(
+    pool: &PgPool,
+    id: Uuid,
+    transaction_id: Uuid,
+) -> Result<u64, anyhow::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM commerce_customs_document WHERE id = $1 AND transaction_id = $2
+        "#,
+        id,
+        transaction_id
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e).context("A database failure occurred while deleting customs document")
+    })?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(name = "fetch order document files", skip(pool))]
+pub async fn fetch_order_document_files(
+    pool: &PgPool,
+    transaction_id: Uuid,
+) -> Result<Vec<CommerceDocumentFileModel>, anyhow::Error> {
+    let records = sqlx::query_as!(
+        CommerceDocumentFileModel,
+        r#"
+        SELECT id, document_type as "document_type: DocumentType", version, source_url, storage_key,
+            checksum, content_type, size_bytes, created_on
+        FROM commerce_document_file
+        WHERE transaction_id = $1
+        ORDER BY document_type, version
+        "#,
+        transaction_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching order document files")
+    })?;
+    Ok(records)
+}
+
+#[tracing::instrument(name = "fetch order document file", skip(pool))]
+pub async fn fetch_order_document_file(
+    pool: &PgPool,
+    transaction_id: Uuid,
+    document_type: &DocumentType,
+    version: Option<i32>,
+) -> Result<Option<CommerceDocumentFileModel>, anyhow::Error> {
+    let record = sqlx::query_as!(
+        CommerceDocumentFileModel,
+        r#"
+        SELECT id, document_type as "document_type: DocumentType", version, source_url, storage_key,
+            checksum, content_type, size_bytes, created_on
+        FROM commerce_document_file
+        WHERE transaction_id = $1 AND document_type = $2 AND ($3::INTEGER IS NULL OR version = $3)
+        ORDER BY version DESC
+        LIMIT 1
+        "#,
+        transaction_id,
+        document_type as &DocumentType,
+        version
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching order document file")
+    })?;
+    Ok(record)
+}
+
+#[tracing::instrument(name = "fetch latest order document file", skip(transaction))]
+pub async fn fetch_latest_order_document_file(
+    transaction: &mut Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+    document_type: &DocumentType,
+) -> Result<Option<CommerceDocumentFileModel>, anyhow::Error> {
+    let record = sqlx::query_as!(
+        CommerceDocumentFileModel,
+        r#"
+        SELECT id, document_type as "document_type: DocumentType", version, source_url, storage_key,
+            checksum, content_type, size_bytes, created_on
+        FROM commerce_document_file
+        WHERE transaction_id = $1 AND document_type = $2
+        ORDER BY version DESC
+        LIMIT 1
+        "#,
+        transaction_id,
+        document_type as &DocumentType
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while fetching latest order document file")
+    })?;
+    Ok(record)
+}
+
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(name = "save order document file", skip(transaction))]
+pub async fn save_order_document_file(
+    transaction: &mut Transaction<'_, Postgres>,
+    transaction_id: Uuid,
+    document_type: &DocumentType,
+    version: i32,
+    source_url: &str,
+    storage_key: &str,
+    checksum: &str,
+    content_type: &str,
+    size_bytes: i64,
+) -> Result<(), anyhow::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO commerce_document_file (id, transaction_id, document_type, version, source_url,
+            storage_key, checksum, content_type, size_bytes, created_on)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        "#,
+        Uuid::new_v4(),
+        transaction_id,
+        document_type as &DocumentType,
+        version,
+        source_url,
+        storage_key,
+        checksum,
+        content_type,
+        size_bytes,
+        Utc::now()
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to execute query: {:?}", e);
+        anyhow::Error::new(e)
+            .context("A database failure occurred while saving order document file")
+    })?;
+    Ok(())
+}
@@ -210,7 +210,7 @@ pub async fn update_payment_status(
         r#"
         WITH updated_order AS (
             UPDATE commerce_data
-            SET updated_on = $1, updated_by = $2
+            SET updated_on = $1, updated_by = $2, version = version + 1
             WHERE external_urn = $3
             RETURNING id
         )
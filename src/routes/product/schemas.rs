@@ -3,6 +3,7 @@ use std::fmt::{Display, Formatter};
 
 use crate::routes::ondc::schemas::{ONDCFulfillmentType, ONDCPaymentType};
 use crate::routes::ondc::ONDCItemUOM;
+use crate::routes::order::schemas::SellerScorecard;
 use crate::schemas::{CurrencyType, ONDCNetworkType};
 use crate::{errors::GenericError, schemas::CountryCode};
 use actix_web::{dev::Payload, web, FromRequest, HttpRequest};
@@ -27,6 +28,20 @@ pub enum PaymentType {
 //     }
 // }
 
+impl Display for PaymentType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                PaymentType::PrePaid => "pre_paid",
+                PaymentType::CashOnDelivery => "cash_on_delivery",
+                PaymentType::Credit => "credit",
+            }
+        )
+    }
+}
+
 impl PaymentType {
     pub fn get_ondc_payment(&self) -> ONDCPaymentType {
         match self {
@@ -37,7 +52,7 @@ impl PaymentType {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, ToSchema, sqlx::Type, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, ToSchema, sqlx::Type, PartialEq, Clone)]
 #[serde(rename_all = "snake_case")]
 #[sqlx(type_name = "fulfillment_type", rename_all = "snake_case")]
 
@@ -95,6 +110,8 @@ pub struct ProductSearchRequest {
     pub fulfillment_locations: Option<Vec<ProductFulFillmentLocations>>,
     pub city_code: String,
     pub update_cache: bool,
+    #[serde(default)]
+    pub include_seller_scorecard: bool,
 }
 
 impl FromRequest for ProductSearchRequest {
@@ -113,7 +130,7 @@ impl FromRequest for ProductSearchRequest {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, ToSchema, Clone, PartialEq)]
 #[sqlx(type_name = "domain_category")]
 pub enum CategoryDomain {
     #[serde(rename = "RET10")]
@@ -172,6 +189,8 @@ pub struct SearchRequestModel {
     pub user_id: Uuid,
     pub business_id: Uuid,
     pub device_id: String,
+    #[serde(default)]
+    pub include_seller_scorecard: bool,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -315,6 +334,8 @@ pub struct WSSearchItem {
     pub categories: Vec<WSProductCategory>,
     #[schema(value_type = f64)]
     pub tax_rate: BigDecimal,
+    #[schema(value_type = Option<f64>)]
+    pub cess_rate: Option<BigDecimal>,
     // pub country_of_origin: CountryCode,
     pub images: Vec<String>,
     pub price_slabs: Option<Vec<WSPriceSlab>>,
@@ -358,6 +379,8 @@ pub struct WSSearchProvider {
     pub items: Vec<WSSearchItem>,
     pub provider_detail: WSSearchProductProvider,
     pub locations: HashMap<String, WSSearchProviderLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seller_scorecard: Option<SellerScorecard>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
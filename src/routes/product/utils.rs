@@ -14,8 +14,8 @@ pub async fn save_search_request(
 ) -> Result<(), anyhow::Error> {
     sqlx::query!(
         r#"
-        INSERT INTO search_request (message_id, transaction_id, device_id, business_id,  user_id, created_on, update_cache, query, payment_type, domain_category_code, search_type, fulfillment_type)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        INSERT INTO search_request (message_id, transaction_id, device_id, business_id,  user_id, created_on, update_cache, query, payment_type, domain_category_code, search_type, fulfillment_type, include_seller_scorecard)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
         "#,
         &search_request.message_id,
         &search_request.transaction_id,
@@ -28,7 +28,8 @@ pub async fn save_search_request(
         &search_request.payment_type as &Option<PaymentType>, 
         &search_request.domain_category_code.to_string(),
         &search_request.search_type as &ProductSearchType,
-        &search_request.fulfillment_type as &Option<FulfillmentType>
+        &search_request.fulfillment_type as &Option<FulfillmentType>,
+        &search_request.include_seller_scorecard
 
     )
     .execute(pool).await
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+use actix_web::web::Data;
+use anyhow::anyhow;
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use tokio::time::MissedTickBehavior;
+use uuid::Uuid;
+
+use crate::{
+    chat_client::ChatClient,
+    configuration::{StandingOrderConfig, StatusPollConfig},
+    constants::{
+        ORDER_EXPORT_CLEANUP_INTERVAL_SECONDS, ORDER_EXPORT_RETENTION_HOURS,
+        ORDER_EXPORT_STALE_MINUTES,
+    },
+    routes::{
+        ondc::{
+            utils::{get_ondc_status_payload, send_ondc_payload},
+            ONDCActionType,
+        },
+        order::{
+            models::StatusPollOrderModel,
+            schemas::OrderStatusRequest,
+            standing_order::send_standing_order_run,
+            utils::{
+                claim_due_standing_orders, expire_order_export_jobs, fetch_order_by_id,
+                fetch_status_poll_orders, save_ondc_order_request_data, update_order_status_poll,
+            },
+        },
+    },
+    schemas::{ONDCNetworkType, RegisteredNetworkParticipant},
+    user_client::UserClient,
+    utils::{create_authorization_header, get_np_detail},
+    websocket_client::WebSocketClient,
+};
+
+#[tracing::instrument(name = "send order status poll", skip(pool, bap_detail))]
+async fn send_order_status_poll(
+    pool: &PgPool,
+    poll_order: &StatusPollOrderModel,
+    bap_detail: &RegisteredNetworkParticipant,
+) -> Result<(), anyhow::Error> {
+    let order = fetch_order_by_id(pool, poll_order.external_urn)
+        .await?
+        .ok_or_else(|| anyhow!("{} is not found in datbase", poll_order.external_urn))?;
+    let status_request = OrderStatusRequest {
+        transaction_id: poll_order.external_urn,
+        message_id: Uuid::new_v4(),
+    };
+    let ondc_status_payload = get_ondc_status_payload(&order, &status_request)?;
+    let status_json_obj = serde_json::to_value(&ondc_status_payload)?;
+    let ondc_status_payload_str = serde_json::to_string(&ondc_status_payload)?;
+    let header = create_authorization_header(&ondc_status_payload_str, bap_detail, None, None)?;
+    save_ondc_order_request_data(
+        pool,
+        poll_order.created_by,
+        poll_order.buyer_id,
+        None,
+        &status_json_obj,
+        status_request.transaction_id,
+        status_request.message_id,
+        ONDCActionType::Status,
+    )
+    .await?;
+    send_ondc_payload(
+        &order.bpp.uri,
+        &ondc_status_payload_str,
+        &header,
+        ONDCActionType::Status,
+    )
+    .await?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "poll order status", skip(pool))]
+async fn poll_order_status(
+    pool: &PgPool,
+    status_poll_config: &StatusPollConfig,
+) -> Result<(), anyhow::Error> {
+    let poll_orders = fetch_status_poll_orders(pool, status_poll_config).await?;
+    let mut bap_detail_map: HashMap<String, Option<RegisteredNetworkParticipant>> = HashMap::new();
+    for poll_order in poll_orders {
+        if !bap_detail_map.contains_key(&poll_order.bap_id) {
+            let bap_detail = get_np_detail(pool, &poll_order.bap_id, &ONDCNetworkType::Bap).await?;
+            bap_detail_map.insert(poll_order.bap_id.clone(), bap_detail);
+        }
+        match bap_detail_map
+            .get(&poll_order.bap_id)
+            .and_then(|d| d.as_ref())
+        {
+            Some(bap_detail) => {
+                if let Err(e) = send_order_status_poll(pool, &poll_order, bap_detail).await {
+                    tracing::error!(
+                        "Failed to poll status of {} from {}: {:?}",
+                        poll_order.external_urn,
+                        poll_order.bpp_id,
+                        e
+                    );
+                }
+            }
+            None => {
+                tracing::warn!(
+                    "{} is not a registered network participant, skipping status poll",
+                    poll_order.bap_id
+                );
+            }
+        }
+        // Failed polls are also counted so that an unresponsive seller is backed off as well.
+        update_order_status_poll(pool, poll_order.external_urn).await?;
+    }
+    Ok(())
+}
+
+pub fn start_order_status_poller(pool: Data<PgPool>, status_poll_config: StatusPollConfig) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(status_poll_config.interval());
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            if let Err(e) = poll_order_status(&pool, &status_poll_config).await {
+                tracing::error!("Order status poll failed: {:?}", e);
+            }
+        }
+    });
+}
+
+#[tracing::instrument(name = "run standing orders", skip_all)]
+async fn run_standing_orders(
+    pool: &PgPool,
+    user_client: &UserClient,
+    chat_client: &ChatClient,
+    websocket_client: &WebSocketClient,
+    standing_order_config: &StandingOrderConfig,
+) -> Result<(), anyhow::Error> {
+    let due_runs =
+        claim_due_standing_orders(pool, Utc::now(), standing_order_config.batch_size).await?;
+    for (standing_order, transaction_id) in due_runs {
+        if let Err(e) = send_standing_order_run(
+            pool,
+            user_client,
+            chat_client,
+            websocket_client,
+            &standing_order,
+            transaction_id,
+        )
+        .await
+        {
+            tracing::error!(
+                "Failed to run standing order {}: {:?}",
+                standing_order.id,
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+// Export jobs run in-process, so a job left processing by a restart is failed here.
+pub fn start_order_export_cleanup(pool: Data<PgPool>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+            ORDER_EXPORT_CLEANUP_INTERVAL_SECONDS,
+        ));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            let now = Utc::now();
+            if let Err(e) = expire_order_export_jobs(
+                &pool,
+                now - Duration::minutes(ORDER_EXPORT_STALE_MINUTES),
+                now - Duration::hours(ORDER_EXPORT_RETENTION_HOURS),
+            )
+            .await
+            {
+                tracing::error!("Order export cleanup failed: {:?}", e);
+            }
+        }
+    });
+}
+
+pub fn start_standing_order_scheduler(
+    pool: Data<PgPool>,
+    user_client: Data<UserClient>,
+    chat_client: Data<ChatClient>,
+    websocket_client: Data<WebSocketClient>,
+    standing_order_config: StandingOrderConfig,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(standing_order_config.interval());
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            if let Err(e) = run_standing_orders(
+                &pool,
+                &user_client,
+                &chat_client,
+                &websocket_client,
+                &standing_order_config,
+            )
+            .await
+            {
+                tracing::error!("Standing order run failed: {:?}", e);
+            }
+        }
+    });
+}
@@ -5,6 +5,9 @@ use crate::middleware::SaveRequestResponse;
 // use crate::middleware::tracing_middleware;
 
 use crate::routes::main_route;
+use crate::scheduler::{
+    start_order_export_cleanup, start_order_status_poller, start_standing_order_scheduler,
+};
 // use actix_session::storage::RedisSessionStore;
 // use actix_session::SessionMiddleware;
 // use actix_web::cookie::Key;
@@ -72,6 +75,7 @@ async fn run(
     let redis_app = web::Data::new(configuration.redis.client());
     let es_client = web::Data::new(configuration.elastic_search.client());
     let payment_client = web::Data::new(configuration.payment.client());
+    let document_client = web::Data::new(configuration.document_store.client()?);
     // es_client.send().await;
     // let kafka_producer = kafka_client.create_producer().await;
     let workers = configuration.application.workers;
@@ -80,6 +84,15 @@ async fn run(
     let _ = kafka_client
         .kafka_client_search_consumer(ws_client.clone(), db_pool.clone())
         .await;
+    start_order_status_poller(db_pool.clone(), configuration.status_poll);
+    start_order_export_cleanup(db_pool.clone());
+    start_standing_order_scheduler(
+        db_pool.clone(),
+        user_client.clone(),
+        chat_client.clone(),
+        ws_client.clone(),
+        configuration.standing_order,
+    );
     let kafka_client = web::Data::new(kafka_client);
     let server = HttpServer::new(move || {
         App::new()
@@ -96,6 +109,7 @@ async fn run(
             .app_data(kafka_client.clone())
             .app_data(es_client.clone())
             .app_data(payment_client.clone())
+            .app_data(document_client.clone())
             .app_data(secret_obj.clone())
             .app_data(application_obj.clone())
             .configure(main_route)
@@ -74,6 +74,18 @@ pub enum PermissionType {
     ReadOrder,
     #[serde(rename = "list:order")]
     ListOrder,
+    #[serde(rename = "approve:order")]
+    ApproveOrder,
+    #[serde(rename = "manage:approval_policy")]
+    ManageApprovalPolicy,
+    #[serde(rename = "manage:budget")]
+    ManageBudget,
+    #[serde(rename = "read:budget")]
+    ReadBudget,
+    #[serde(rename = "manage:address_book")]
+    ManageAddressBook,
+    #[serde(rename = "manage:buyer_terms")]
+    ManageBuyerTerms,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -261,6 +273,8 @@ impl FromRequest for BusinessAccount {
 pub enum SettingKey {
     OrderNoPrefix,
     PaymentServiceId,
+    CancellationFeeAcknowledgement,
+    GstinValidation,
 }
 
 #[derive(Serialize, Debug)]
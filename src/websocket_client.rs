@@ -18,6 +18,7 @@ pub enum WebSocketActionType {
     OrderCancel,
     OrderUpdate,
     OrderPayment,
+    StandingOrderRun,
 }
 
 #[derive(Debug, Serialize, PartialEq)]